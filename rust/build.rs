@@ -0,0 +1,662 @@
+//! Generates the `proboscis_linter_rust.pyi` type stub from the pyo3 items in `src/`
+//!
+//! The stub is written to `$OUT_DIR`; the `stubs` unit test checks that the copy shipped
+//! in the Python package matches it (`UPDATE_STUBS=1 cargo test` refreshes that copy).
+//!
+//! Classes and functions are found via `#[pyclass]`, `#[pymethods]` and `#[pyfunction]`.
+//! Module membership is read from the `add_class::<..>` / `wrap_pyfunction!(..)` calls of
+//! each file: the `#[pymodule]` file registers the top-level module, and a file calling
+//! `PyModule::new(py, "<name>")` registers the `<name>` submodule.
+//!
+//! Items behind a `#[cfg(...)]` that the build leaves out (e.g. `lint_project_async`
+//! without the `asyncio` feature) are left out of the stub too; features are read from
+//! the `CARGO_FEATURE_*` variables Cargo sets for build scripts.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, FnArg, Item, Lit, Meta, Pat, ReturnType, Token, Type};
+
+const MODULE_NAME: &str = "proboscis_linter_rust";
+
+/// Methods returning a Python awaitable (typed `Bound<PyAny>` in Rust), and what it resolves to
+const AWAITABLE_RETURNS: &[(&str, &str)] = &[("lint_project_async", "list[LintViolation]")];
+
+struct Class {
+    py_name: String,
+    doc: Vec<String>,
+    /// (name, type, doc) of `#[pyo3(get)]` fields
+    properties: Vec<(String, String, Vec<String>)>,
+    /// (name, type, doc) of `#[getter]` methods
+    getters: Vec<(String, String, Vec<String>)>,
+    methods: Vec<Function>,
+}
+
+struct Function {
+    py_name: String,
+    doc: Vec<String>,
+    params: Vec<String>,
+    returns: String,
+    kind: FunctionKind,
+}
+
+#[derive(PartialEq)]
+enum FunctionKind {
+    Function,
+    Method,
+    Constructor,
+    Static,
+}
+
+/// Registrations found in one module-building file
+#[derive(Default)]
+struct Module {
+    constants: Vec<String>,
+    classes: Vec<String>,
+    functions: Vec<String>,
+}
+
+#[derive(Default)]
+struct Items {
+    /// Rust struct name → class
+    classes: HashMap<String, Class>,
+    /// Rust fn name → function
+    functions: HashMap<String, Function>,
+    /// `FromPyObject` enums, as the union of their variant types
+    unions: HashMap<String, String>,
+    /// Python module name (`""` for the top level) → registrations, in file order
+    modules: Vec<(String, Module)>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut files = Vec::new();
+    collect_rust_files(Path::new("src"), &mut files);
+    files.sort();
+
+    let sources: Vec<(String, syn::File)> = files
+        .iter()
+        .map(|path| {
+            let source = fs::read_to_string(path).unwrap();
+            let file = syn::parse_file(&source)
+                .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e));
+            (source, file)
+        })
+        .collect();
+
+    let mut items = Items::default();
+    // Struct names first, so method and field types can refer to any class
+    for (_, file) in &sources {
+        for item in &file.items {
+            match item {
+                Item::Struct(s) if has_attr(&s.attrs, "pyclass") && cfg_enabled(&s.attrs) => {
+                    let py_name = pyo3_name(&s.attrs).unwrap_or_else(|| s.ident.to_string());
+                    items.classes.insert(
+                        s.ident.to_string(),
+                        Class {
+                            py_name,
+                            doc: doc_lines(&s.attrs),
+                            properties: Vec::new(),
+                            getters: Vec::new(),
+                            methods: Vec::new(),
+                        },
+                    );
+                }
+                Item::Enum(e) if derives(&e.attrs, "FromPyObject") => {
+                    let variants: Vec<&Type> = e
+                        .variants
+                        .iter()
+                        .filter_map(|v| v.fields.iter().next().map(|f| &f.ty))
+                        .collect();
+                    items.unions.insert(e.ident.to_string(), {
+                        let empty = Items::default();
+                        let types: Vec<String> =
+                            variants.iter().map(|ty| py_type(ty, &empty, "")).collect();
+                        types.join(" | ")
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (source, file) in &sources {
+        for item in &file.items {
+            match item {
+                Item::Struct(s) if has_attr(&s.attrs, "pyclass") && cfg_enabled(&s.attrs) => {
+                    let properties = s
+                        .fields
+                        .iter()
+                        .filter(|field| pyo3_flag(&field.attrs, "get") && cfg_enabled(&field.attrs))
+                        .map(|field| {
+                            (
+                                field.ident.as_ref().unwrap().to_string(),
+                                py_type(&field.ty, &items, ""),
+                                doc_lines(&field.attrs),
+                            )
+                        })
+                        .collect();
+                    items
+                        .classes
+                        .get_mut(&s.ident.to_string())
+                        .unwrap()
+                        .properties = properties;
+                }
+                Item::Impl(imp) if has_attr(&imp.attrs, "pymethods") && cfg_enabled(&imp.attrs) => {
+                    let Type::Path(self_ty) = imp.self_ty.as_ref() else {
+                        continue;
+                    };
+                    let rust_name = self_ty.path.segments.last().unwrap().ident.to_string();
+                    let class_name = items.classes[&rust_name].py_name.clone();
+                    let mut getters = Vec::new();
+                    let mut methods = Vec::new();
+                    for item in &imp.items {
+                        let syn::ImplItem::Fn(f) = item else {
+                            continue;
+                        };
+                        if !cfg_enabled(&f.attrs) {
+                            continue;
+                        }
+                        let function = function(&f.attrs, &f.sig, &items, &class_name);
+                        if has_attr(&f.attrs, "getter") {
+                            getters.push((function.py_name, function.returns, function.doc));
+                        } else {
+                            methods.push(function);
+                        }
+                    }
+                    let class = items.classes.get_mut(&rust_name).unwrap();
+                    class.getters.extend(getters);
+                    class.methods.extend(methods);
+                }
+                Item::Fn(f) if has_attr(&f.attrs, "pyfunction") && cfg_enabled(&f.attrs) => {
+                    let function = function(&f.attrs, &f.sig, &items, "");
+                    items.functions.insert(f.sig.ident.to_string(), function);
+                }
+                _ => {}
+            }
+        }
+
+        let is_top_level = source.contains("#[pymodule]");
+        let submodule = after(source, "PyModule::new(py, \"").next();
+        if is_top_level || submodule.is_some() {
+            let name = if is_top_level { "" } else { submodule.unwrap() };
+            let module = Module {
+                constants: after(source, ".add(\"").map(str::to_string).collect(),
+                classes: after(source, "add_class::<")
+                    .map(|path| path.rsplit("::").next().unwrap().to_string())
+                    .collect(),
+                functions: after(source, "wrap_pyfunction!(")
+                    .map(str::to_string)
+                    .collect(),
+            };
+            items.modules.push((name.to_string(), module));
+        }
+    }
+    items.modules.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let stub = render(&items);
+    let out = PathBuf::from(std::env::var("OUT_DIR").unwrap()).join(format!("{MODULE_NAME}.pyi"));
+    fs::write(out, stub).unwrap();
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_rust_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// The identifier or string following each occurrence of `marker` in `source`
+fn after<'a>(source: &'a str, marker: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    source.match_indices(marker).map(move |(idx, _)| {
+        let rest = &source[idx + marker.len()..];
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(rest.len());
+        &rest[..end]
+    })
+}
+
+/// Whether the `#[cfg(...)]` attributes of an item keep it in this build
+fn cfg_enabled(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .all(|attr| attr.parse_args::<Meta>().is_ok_and(|meta| cfg_holds(&meta)))
+}
+
+/// Evaluate a cfg predicate; only features are known, other options do not hold
+fn cfg_holds(meta: &Meta) -> bool {
+    match meta {
+        Meta::NameValue(nv) if nv.path.is_ident("feature") => match &nv.value {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(feature),
+                ..
+            }) => {
+                let name = feature.value().to_uppercase().replace('-', "_");
+                std::env::var_os(format!("CARGO_FEATURE_{name}")).is_some()
+            }
+            _ => false,
+        },
+        Meta::List(list) => {
+            let predicates: Vec<Meta> = list
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .map(|predicates| predicates.into_iter().collect())
+                .unwrap_or_default();
+            if list.path.is_ident("all") {
+                predicates.iter().all(cfg_holds)
+            } else if list.path.is_ident("any") {
+                predicates.iter().any(cfg_holds)
+            } else if list.path.is_ident("not") {
+                predicates
+                    .first()
+                    .is_some_and(|predicate| !cfg_holds(predicate))
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("derive")
+            && attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .is_ok_and(|paths| paths.iter().any(|path| path.is_ident(name)))
+    })
+}
+
+/// Arguments of the `#[pyo3(...)]`, `#[pyclass(...)]` and `#[pyfunction(...)]` attributes
+fn pyo3_args(attrs: &[Attribute]) -> Vec<Meta> {
+    attrs
+        .iter()
+        .filter(|attr| {
+            ["pyo3", "pyclass", "pyfunction"]
+                .iter()
+                .any(|name| attr.path().is_ident(name))
+        })
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .collect()
+}
+
+fn pyo3_flag(attrs: &[Attribute], flag: &str) -> bool {
+    pyo3_args(attrs)
+        .iter()
+        .any(|meta| matches!(meta, Meta::Path(path) if path.is_ident(flag)))
+}
+
+fn pyo3_value(attrs: &[Attribute], key: &str) -> Option<Expr> {
+    pyo3_args(attrs).into_iter().find_map(|meta| match meta {
+        Meta::NameValue(nv) if nv.path.is_ident(key) => Some(nv.value),
+        _ => None,
+    })
+}
+
+/// `name = "..."` override of the Python name
+fn pyo3_name(attrs: &[Attribute]) -> Option<String> {
+    match pyo3_value(attrs, "name")? {
+        Expr::Lit(lit) => match lit.lit {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(s) => Some(
+                        s.value()
+                            .strip_prefix(' ')
+                            .unwrap_or(&s.value())
+                            .to_string(),
+                    ),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parameter name → default value rendered as Python, from `#[pyo3(signature = (...))]`
+fn signature_defaults(attrs: &[Attribute]) -> HashMap<String, String> {
+    let elems: Vec<Expr> = match pyo3_value(attrs, "signature") {
+        Some(Expr::Tuple(tuple)) => tuple.elems.into_iter().collect(),
+        Some(Expr::Paren(paren)) => vec![*paren.expr],
+        _ => Vec::new(),
+    };
+    elems
+        .iter()
+        .filter_map(|elem| match elem {
+            Expr::Assign(assign) => {
+                let Expr::Path(name) = assign.left.as_ref() else {
+                    return None;
+                };
+                let name = name.path.get_ident()?.to_string();
+                Some((name, py_literal(&assign.right)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn py_literal(expr: &Expr) -> String {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => format!("{:?}", s.value()),
+            Lit::Bool(b) => if b.value { "True" } else { "False" }.to_string(),
+            Lit::Int(i) => i.base10_digits().to_string(),
+            Lit::Float(f) => f.base10_digits().to_string(),
+            _ => "...".to_string(),
+        },
+        Expr::Path(path) if path.path.is_ident("None") => "None".to_string(),
+        _ => "...".to_string(),
+    }
+}
+
+fn function(
+    attrs: &[Attribute],
+    sig: &syn::Signature,
+    items: &Items,
+    class_name: &str,
+) -> Function {
+    let kind = if class_name.is_empty() {
+        FunctionKind::Function
+    } else if has_attr(attrs, "new") {
+        FunctionKind::Constructor
+    } else if has_attr(attrs, "staticmethod") {
+        FunctionKind::Static
+    } else {
+        FunctionKind::Method
+    };
+    let defaults = signature_defaults(attrs);
+
+    let mut params = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(_) => params.push("self".to_string()),
+            FnArg::Typed(typed) => {
+                let Pat::Ident(pat) = typed.pat.as_ref() else {
+                    continue;
+                };
+                if last_ident(&typed.ty).is_some_and(|ident| ident == "Python") {
+                    continue;
+                }
+                let name = pat.ident.to_string();
+                if is_kwargs(&typed.ty) {
+                    params.push(format!("**{name}: Any"));
+                    continue;
+                }
+                let ty = py_type(&typed.ty, items, class_name);
+                match defaults.get(&name) {
+                    Some(default) => params.push(format!("{name}: {ty} = {default}")),
+                    None => params.push(format!("{name}: {ty}")),
+                }
+            }
+        }
+    }
+    if kind == FunctionKind::Constructor {
+        params.insert(0, "self".to_string());
+    }
+
+    Function {
+        py_name: if kind == FunctionKind::Constructor {
+            "__init__".to_string()
+        } else {
+            pyo3_name(attrs).unwrap_or_else(|| sig.ident.to_string())
+        },
+        doc: doc_lines(attrs),
+        params,
+        returns: match (&sig.output, &kind) {
+            (_, FunctionKind::Constructor) | (ReturnType::Default, _) => "None".to_string(),
+            (ReturnType::Type(_, ty), _) => {
+                match AWAITABLE_RETURNS.iter().find(|(name, _)| sig.ident == name) {
+                    Some((_, resolved)) => format!("Awaitable[{resolved}]"),
+                    None => py_type(ty, items, class_name),
+                }
+            }
+        },
+        kind,
+    }
+}
+
+fn last_ident(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => Some(path.path.segments.last()?.ident.to_string()),
+        Type::Reference(reference) => last_ident(&reference.elem),
+        _ => None,
+    }
+}
+
+/// Generic arguments of the last path segment of `ty`
+fn type_args(ty: &Type) -> Vec<&Type> {
+    let path = match ty {
+        Type::Path(path) => path,
+        Type::Reference(reference) => return type_args(&reference.elem),
+        _ => return Vec::new(),
+    };
+    match &path.path.segments.last().unwrap().arguments {
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `Option<&Bound<PyDict>>` parameters collect `**kwargs`
+fn is_kwargs(ty: &Type) -> bool {
+    last_ident(ty).is_some_and(|ident| ident == "Option")
+        && type_args(ty)
+            .first()
+            .is_some_and(|inner| inner_ident(inner).is_some_and(|ident| ident == "PyDict"))
+}
+
+/// Innermost type name through references and `Bound<..>`
+fn inner_ident(ty: &Type) -> Option<String> {
+    match last_ident(ty)?.as_str() {
+        "Bound" | "Py" => inner_ident(type_args(ty).first()?),
+        ident => Some(ident.to_string()),
+    }
+}
+
+fn py_type(ty: &Type, items: &Items, class_name: &str) -> String {
+    match ty {
+        Type::Reference(reference) => return py_type(&reference.elem, items, class_name),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => return "None".to_string(),
+        _ => {}
+    }
+    let Some(ident) = last_ident(ty) else {
+        return "Any".to_string();
+    };
+    let args: Vec<String> = type_args(ty)
+        .iter()
+        .map(|arg| py_type(arg, items, class_name))
+        .collect();
+    match ident.as_str() {
+        "String" | "str" | "PyString" | "PathBuf" | "Path" => "str".to_string(),
+        "bool" => "bool".to_string(),
+        "usize" | "isize" | "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" => {
+            "int".to_string()
+        }
+        "f32" | "f64" => "float".to_string(),
+        "Vec" => format!("list[{}]", args[0]),
+        "HashSet" => format!("set[{}]", args[0]),
+        "HashMap" => format!("dict[{}, {}]", args[0], args[1]),
+        "Option" => format!("{} | None", args[0]),
+        "PyResult" | "Result" => args[0].clone(),
+        "Bound" | "Py" => args.first().cloned().unwrap_or_else(|| "Any".to_string()),
+        "PyDict" => "dict[str, Any]".to_string(),
+        "PyAny" => "Any".to_string(),
+        "Self" => class_name.to_string(),
+        other => match (items.classes.get(other), items.unions.get(other)) {
+            (Some(class), _) => class.py_name.clone(),
+            (_, Some(union)) => union.clone(),
+            _ => "Any".to_string(),
+        },
+    }
+}
+
+fn render(items: &Items) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# Generated by rust/build.rs from the pyo3 bindings; do not edit."
+    )
+    .unwrap();
+    writeln!(out, "# Refresh with `UPDATE_STUBS=1 cargo test` in rust/.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "from collections.abc import Awaitable").unwrap();
+    writeln!(out, "from typing import Any, final").unwrap();
+
+    for (name, module) in &items.modules {
+        let indent = if name.is_empty() { "" } else { "    " };
+        writeln!(out).unwrap();
+        if !name.is_empty() {
+            writeln!(out).unwrap();
+            writeln!(out, "@final").unwrap();
+            writeln!(out, "class {name}:").unwrap();
+            writeln!(out, "    \"\"\"The `{MODULE_NAME}.{name}` submodule\"\"\"").unwrap();
+        }
+        for constant in &module.constants {
+            writeln!(out, "{indent}{constant}: str").unwrap();
+        }
+        // Registrations of items the build leaves out are behind a `cfg` as well
+        for class in module
+            .classes
+            .iter()
+            .filter_map(|name| items.classes.get(name))
+        {
+            if name.is_empty() {
+                writeln!(out).unwrap();
+            }
+            render_class(&mut out, class, indent);
+        }
+        for function in module
+            .functions
+            .iter()
+            .filter_map(|name| items.functions.get(name))
+        {
+            writeln!(out).unwrap();
+            if !name.is_empty() {
+                writeln!(out, "{indent}@staticmethod").unwrap();
+            }
+            render_function(&mut out, function, indent);
+        }
+    }
+    out
+}
+
+fn render_class(out: &mut String, class: &Class, indent: &str) {
+    writeln!(out).unwrap();
+    writeln!(out, "{indent}class {}:", class.py_name).unwrap();
+    let inner = format!("{indent}    ");
+    let mut empty = true;
+    if !class.doc.is_empty() {
+        render_doc(out, &class.doc, &inner);
+        empty = false;
+    }
+    for (name, ty, doc) in class.properties.iter().chain(&class.getters) {
+        if !empty {
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "{inner}@property").unwrap();
+        if doc.is_empty() {
+            writeln!(out, "{inner}def {name}(self) -> {ty}: ...").unwrap();
+        } else {
+            writeln!(out, "{inner}def {name}(self) -> {ty}:").unwrap();
+            render_doc(out, doc, &format!("{inner}    "));
+        }
+        empty = false;
+    }
+    for method in &class.methods {
+        if !empty {
+            writeln!(out).unwrap();
+        }
+        if method.kind == FunctionKind::Static {
+            writeln!(out, "{inner}@staticmethod").unwrap();
+        }
+        render_function(out, method, &inner);
+        empty = false;
+    }
+    if empty {
+        writeln!(out, "{inner}...").unwrap();
+    }
+}
+
+fn render_function(out: &mut String, function: &Function, indent: &str) {
+    let params = function.params.join(", ");
+    let head = format!(
+        "{indent}def {}({params}) -> {}:",
+        function.py_name, function.returns
+    );
+    let head = if head.len() > 100 {
+        let inner = format!("{indent}    ");
+        let params: String = function
+            .params
+            .iter()
+            .map(|param| format!("{inner}{param},\n"))
+            .collect();
+        format!(
+            "{indent}def {}(\n{params}{indent}) -> {}:",
+            function.py_name, function.returns
+        )
+    } else {
+        head
+    };
+    if function.doc.is_empty() {
+        writeln!(out, "{head} ...").unwrap();
+    } else {
+        writeln!(out, "{head}").unwrap();
+        render_doc(out, &function.doc, &format!("{indent}    "));
+    }
+}
+
+fn render_doc(out: &mut String, doc: &[String], indent: &str) {
+    // A quote right before the closing `\"\"\"` would end the docstring early
+    let escape = |line: &str| match line.strip_suffix('"') {
+        Some(rest) => format!("{rest}\\\""),
+        None => line.to_string(),
+    };
+    if doc.len() == 1 {
+        writeln!(out, "{indent}\"\"\"{}\"\"\"", escape(&doc[0])).unwrap();
+        return;
+    }
+    writeln!(out, "{indent}\"\"\"{}", doc[0]).unwrap();
+    for line in &doc[1..] {
+        if line.is_empty() {
+            writeln!(out).unwrap();
+        } else {
+            writeln!(out, "{indent}{line}").unwrap();
+        }
+    }
+    writeln!(out, "{indent}\"\"\"").unwrap();
+}
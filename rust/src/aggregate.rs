@@ -0,0 +1,161 @@
+//! Collapsing the missing-test violations of a function (`aggregate_missing_tests`)
+//!
+//! A codebase adopting the linter gets one PL001, PL002 and PL003 violation per untested
+//! function, which buries reviewers. In aggregation mode the missing-test violations of
+//! each function are merged into a single `PL00X:missing-tests` violation listing the
+//! missing test types; every other violation is left as it is.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::fixes::TODO_MARKER;
+use crate::models::LintViolation;
+
+pub const RULE_NAME: &str = "PL00X:missing-tests";
+
+/// Rules whose violations are merged, with the test type each one requires
+const MISSING_TEST_RULES: &[(&str, &str)] = &[
+    ("PL001", "unit"),
+    ("PL002", "integration"),
+    ("PL003", "e2e"),
+];
+
+fn test_type(violation: &LintViolation) -> Option<&'static str> {
+    MISSING_TEST_RULES
+        .iter()
+        .find(|(rule_id, _)| *rule_id == violation.rule_id())
+        .map(|(_, test_type)| *test_type)
+}
+
+/// Merge the PL001–PL003 violations of each function into one `PL00X` violation, in place
+/// of the first of them
+pub fn collapse_missing_tests(violations: Vec<LintViolation>) -> Vec<LintViolation> {
+    let mut groups: HashMap<(Arc<str>, usize), Vec<LintViolation>> = HashMap::new();
+    let mut order: Vec<Result<(Arc<str>, usize), LintViolation>> = Vec::new();
+    for violation in violations {
+        if test_type(&violation).is_none() {
+            order.push(Err(violation));
+            continue;
+        }
+        let key = (violation.file_path.clone(), violation.line_number);
+        let group = groups.entry(key.clone()).or_default();
+        if group.is_empty() {
+            order.push(Ok(key));
+        }
+        group.push(violation);
+    }
+
+    order
+        .into_iter()
+        .map(|entry| match entry {
+            Ok(key) => merge(groups.remove(&key).unwrap_or_default()),
+            Err(violation) => violation,
+        })
+        .collect()
+}
+
+/// Merge the missing-test violations of one function
+fn merge(mut group: Vec<LintViolation>) -> LintViolation {
+    group.sort_by(|a, b| a.rule_name.cmp(&b.rule_name));
+    let test_types: Vec<&str> = group.iter().filter_map(test_type).collect();
+    let mut merged = group[0].clone();
+
+    let subject = match &merged.class_name {
+        Some(class) => format!("Method '{}' of class '{}'", merged.function_name, class),
+        None => format!("Function '{}'", merged.function_name),
+    };
+    let details: Vec<String> = group
+        .iter()
+        .map(|violation| {
+            format!(
+                "{} ({}): Expected test function: {}; In test file: {}",
+                violation.rule_id(),
+                test_type(violation).unwrap_or_default(),
+                violation.expected_test_name.as_deref().unwrap_or_default(),
+                violation.expected_test_file.as_deref().unwrap_or_default()
+            )
+        })
+        .collect();
+    merged.rule_name = RULE_NAME.to_string();
+    merged.message = format!(
+        "[PL00X] {} has no {} test found.\n{}",
+        subject,
+        join_types(&test_types),
+        details.join("\n")
+    );
+
+    // One TODO comment naming every missing test
+    let todos: Vec<&str> = group
+        .iter()
+        .filter_map(|violation| violation.fix_content.as_deref())
+        .map(|todo| todo.trim_start_matches(TODO_MARKER).trim())
+        .collect();
+    if !todos.is_empty() {
+        merged.fix_content = Some(format!("{} {}", TODO_MARKER, todos.join("; ")));
+    }
+    merged
+}
+
+/// "unit", "unit or e2e", "unit, integration or e2e"
+fn join_types(test_types: &[&str]) -> String {
+    match test_types {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing(rule: &str, test_type: &str, line_number: usize) -> LintViolation {
+        LintViolation {
+            rule_name: format!("{}:require-{}-test", rule, test_type),
+            file_path: "src/m.py".into(),
+            line_number,
+            function_name: "get".to_string(),
+            class_name: Some("Cache".to_string()),
+            expected_test_name: Some("test_Cache_get".to_string()),
+            expected_test_file: Some(format!("test/{}/test_m.py", test_type)),
+            severity: "error".to_string(),
+            fix_type: Some("add_todo_comment".to_string()),
+            fix_content: Some(format!(
+                "{} add {} test test_Cache_get in test/{}/test_m.py",
+                TODO_MARKER, test_type, test_type
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collapse_missing_tests() {
+        let other = LintViolation {
+            rule_name: "PL022:skipped-test-only".to_string(),
+            ..Default::default()
+        };
+        let collapsed = collapse_missing_tests(vec![
+            missing("PL003", "e2e", 3),
+            other,
+            missing("PL001", "unit", 3),
+            missing("PL002", "integration", 9),
+        ]);
+
+        let rule_names: Vec<&str> = collapsed.iter().map(|v| v.rule_name.as_str()).collect();
+        assert_eq!(
+            rule_names,
+            vec![RULE_NAME, "PL022:skipped-test-only", RULE_NAME]
+        );
+        assert_eq!(
+            collapsed[0].message(),
+            "[PL00X] Method 'get' of class 'Cache' has no unit or e2e test found.\nPL001 (unit): Expected test function: test_Cache_get; In test file: test/unit/test_m.py\nPL003 (e2e): Expected test function: test_Cache_get; In test file: test/e2e/test_m.py"
+        );
+        assert_eq!(
+            collapsed[0].fix_content.as_deref(),
+            Some("# TODO(proboscis): add unit test test_Cache_get in test/unit/test_m.py; add e2e test test_Cache_get in test/e2e/test_m.py")
+        );
+        assert!(collapsed[2]
+            .message()
+            .contains("has no integration test found"));
+    }
+}
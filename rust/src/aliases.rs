@@ -0,0 +1,194 @@
+//! Resolving the names decorators are imported or bound under
+//!
+//! Decorators are matched by their dotted name (`pytest.mark.unit`, `pytest.mark.skip`),
+//! which misses markers used under another name:
+//! - `import pytest as pt`, `from pytest import mark as m`, `from pytest.mark import unit`
+//! - module-level assignments such as `unit = pytest.mark.unit`, including those made in a
+//!   helper module of the project and imported with `from tests.helpers import unit`
+//!
+//! `Aliases` maps each such name to the dotted name it stands for, so decorators can be
+//! rewritten to their canonical form before they are matched.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::parser;
+use crate::patterns::PATTERNS;
+use crate::public_api;
+
+/// How many helper modules deep imports are followed
+const MAX_IMPORT_DEPTH: usize = 3;
+
+/// Names bound in a module, mapped to the dotted name they stand for
+#[derive(Debug, Default)]
+pub struct Aliases {
+    names: HashMap<String, String>,
+    /// Project modules read to resolve the imports, at any depth
+    modules: Vec<PathBuf>,
+}
+
+impl Aliases {
+    /// Aliases of the module at `file_path`, following imports from modules of the project
+    pub fn of_file(file_path: &Path, project_root: &Path) -> Self {
+        fs::read_to_string(file_path)
+            .map(|content| Self::from_source(file_path, &content, project_root))
+            .unwrap_or_default()
+    }
+
+    /// Aliases of `content`, the source of `file_path`
+    pub fn from_source(file_path: &Path, content: &str, project_root: &Path) -> Self {
+        Self::parse(file_path, content, project_root, MAX_IMPORT_DEPTH)
+    }
+
+    fn parse(file_path: &Path, content: &str, project_root: &Path, depth: usize) -> Self {
+        let package = package_of(file_path, project_root);
+        let mut aliases = Self::default();
+
+        for import in public_api::parse_imports(content, &package) {
+            // Names a project module binds itself resolve to what they stand for there
+            let module = match depth {
+                0 => None,
+                _ => find_module(&import.module, file_path, project_root),
+            }
+            .map(|path| {
+                let module = Self::of_module(&path, project_root, depth - 1);
+                aliases.modules.push(path);
+                aliases.modules.extend(module.modules.iter().cloned());
+                module
+            });
+            for (name, alias) in import.names {
+                if name == "*" {
+                    continue;
+                }
+                let target = module
+                    .as_ref()
+                    .and_then(|module| module.names.get(&name).cloned())
+                    .unwrap_or_else(|| format!("{}.{}", import.module, name));
+                aliases.names.insert(alias.unwrap_or(name), target);
+            }
+        }
+
+        // Only module-level statements bind names other modules can import
+        let lines: Vec<&str> = content.lines().collect();
+        for code in parser::code_lines(&lines) {
+            if code.starts_with(char::is_whitespace) {
+                continue;
+            }
+            if let Some(captures) = PATTERNS.import.captures(code.trim_end()) {
+                for part in captures[1].split(',') {
+                    if let [module, "as", alias] = part.split_whitespace().collect::<Vec<_>>()[..] {
+                        aliases.names.insert(alias.to_string(), module.to_string());
+                    }
+                }
+            } else if let Some(captures) = PATTERNS.assignment.captures(code.trim_end()) {
+                let target = aliases.resolve(&captures[2]);
+                // `x = y` with a bare name is not a marker or decorator alias
+                if target
+                    .split('(')
+                    .next()
+                    .is_some_and(|name| name.contains('.'))
+                {
+                    aliases.names.insert(captures[1].to_string(), target);
+                }
+            }
+        }
+        aliases.modules.sort();
+        aliases.modules.dedup();
+        aliases
+    }
+
+    fn of_module(path: &Path, project_root: &Path, depth: usize) -> Self {
+        fs::read_to_string(path)
+            .map(|content| Self::parse(path, &content, project_root, depth))
+            .unwrap_or_default()
+    }
+
+    /// The project modules the aliases were resolved from, which change them when edited
+    pub fn modules(&self) -> &[PathBuf] {
+        &self.modules
+    }
+
+    /// Rewrite a decorator (with or without its `@` and arguments) to the dotted name its
+    /// leading name stands for, without whitespace
+    ///
+    /// `m.unit` becomes `pytest.mark.unit` after `from pytest import mark as m`; decorators
+    /// that use no alias are returned as they are.
+    pub fn resolve(&self, decorator: &str) -> String {
+        let decorator: String = decorator
+            .trim_start_matches('@')
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let head_end = decorator
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(decorator.len());
+        match self.names.get(&decorator[..head_end]) {
+            Some(target) => format!("{}{}", target, &decorator[head_end..]),
+            None => decorator,
+        }
+    }
+}
+
+/// Dotted package of the module at `file_path`, for resolving relative imports
+fn package_of(file_path: &Path, project_root: &Path) -> String {
+    let directory = file_path.parent().unwrap_or(Path::new(""));
+    let relative = directory.strip_prefix(project_root).unwrap_or(directory);
+    relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// File of the project module `module`, looked up from the project root and from the
+/// directory of the importing file (which pytest puts on `sys.path` in `prepend` mode)
+fn find_module(module: &str, importing_file: &Path, project_root: &Path) -> Option<PathBuf> {
+    let relative: PathBuf = module.split('.').collect();
+    let roots = [Some(project_root), importing_file.parent()];
+    roots.into_iter().flatten().find_map(|root| {
+        let base = root.join(&relative);
+        [base.with_extension("py"), base.join("__init__.py")]
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_aliases() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("tests/unit");
+        fs::create_dir_all(&unit).unwrap();
+        fs::write(
+            root.join("tests/helpers.py"),
+            "import pytest\n\nunit = pytest.mark.unit\nflaky = pytest.mark.skip(reason=\"flaky\")\n",
+        )
+        .unwrap();
+        let test_file = unit.join("test_m.py");
+        let content = "import pytest as pt\nfrom pytest import mark as m\nfrom tests.helpers import unit, flaky as known_flaky\n\nslow = m.slow\nvalue = other\n";
+
+        let aliases = Aliases::from_source(&test_file, content, root);
+        assert_eq!(aliases.resolve("@pt.mark.e2e"), "pytest.mark.e2e");
+        assert_eq!(
+            aliases.resolve("m.integration()"),
+            "pytest.mark.integration()"
+        );
+        assert_eq!(aliases.resolve("unit"), "pytest.mark.unit");
+        assert_eq!(
+            aliases.resolve("known_flaky"),
+            "pytest.mark.skip(reason=\"\")"
+        );
+        assert_eq!(aliases.resolve("slow"), "pytest.mark.slow");
+        assert_eq!(aliases.resolve("value"), "value");
+        assert_eq!(aliases.resolve("pytest.mark.unit"), "pytest.mark.unit");
+        assert_eq!(aliases.modules(), [root.join("tests/helpers.py")]);
+    }
+}
@@ -0,0 +1,209 @@
+//! Central exemptions of functions from rules (`allowlist`, `allowlist_file`)
+//!
+//! Where an inline `noqa` is not possible (generated files) or a team wants its exemptions
+//! reviewed in one place, functions are listed by fully qualified name, `pkg.mod.func` or
+//! `pkg.mod.Class.method`, with the rules they are exempt from. The allowlist file has one
+//! entry per line, the name followed by the rule ids; `#` starts a comment. Entries may
+//! expire (`until=YYYY-MM-DD`, see `expiry`), inline ones with an `until=` list item:
+//!
+//! ```text
+//! # Generated gRPC stubs
+//! pkg.api_pb2.ServiceStub.call  PL001, PL002
+//! pkg.legacy.migrate  PL001  until=2025-12-31
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::expiry::{self, Date};
+use crate::models::LintViolation;
+use crate::rules::metadata::validate_rule_ids;
+
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    /// Entries per qualified function name
+    entries: HashMap<String, Vec<Entry>>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    rules: Vec<String>,
+    /// The text after `until=`
+    until: Option<String>,
+    /// Allowlist file and line of the entry; None for inline entries
+    origin: Option<(PathBuf, usize)>,
+}
+
+impl Allowlist {
+    /// Entries given inline (`[tool.proboscis.allowlist]`) plus those of `file`, if any
+    pub fn new(inline: HashMap<String, Vec<String>>, file: Option<&str>) -> Result<Self, String> {
+        let mut allowlist = Self::default();
+        for (name, rules) in inline {
+            allowlist.add(&name, rules, None)?;
+        }
+        if let Some(file) = file {
+            let content = fs::read_to_string(file)
+                .map_err(|e| format!("cannot read allowlist_file '{}': {}", file, e))?;
+            allowlist
+                .parse(&content, Path::new(file))
+                .map_err(|e| format!("{}: {}", file, e))?;
+        }
+        Ok(allowlist)
+    }
+
+    fn parse(&mut self, content: &str, file: &Path) -> Result<(), String> {
+        for (idx, line) in content.lines().enumerate() {
+            let entry = line.split('#').next().unwrap_or_default().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (name, rules) = entry.split_once(char::is_whitespace).ok_or_else(|| {
+                format!("line {}: expected a function name and rule ids", idx + 1)
+            })?;
+            let rules = rules
+                .split([',', ' ', '\t'])
+                .filter(|rule| !rule.is_empty())
+                .map(str::to_string)
+                .collect();
+            self.add(name, rules, Some((file.to_path_buf(), idx + 1)))
+                .map_err(|e| format!("line {}: {}", idx + 1, e))?;
+        }
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        name: &str,
+        items: Vec<String>,
+        origin: Option<(PathBuf, usize)>,
+    ) -> Result<(), String> {
+        let (until, rules): (Vec<String>, Vec<String>) = items
+            .into_iter()
+            .partition(|item| item.starts_with(expiry::UNTIL));
+        if rules.is_empty() {
+            return Err(format!("no rules given for '{}'", name));
+        }
+        validate_rule_ids(rules.iter().map(String::as_str))?;
+        self.entries
+            .entry(name.to_string())
+            .or_default()
+            .push(Entry {
+                rules,
+                until: until
+                    .last()
+                    .map(|until| until[expiry::UNTIL.len()..].to_string()),
+                origin,
+            });
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rules the function `name` (a method of `class_name`, if any) of `module_path` is
+    /// exempt from on `today`
+    pub fn exempt_rules(
+        &self,
+        module_path: &str,
+        class_name: Option<&str>,
+        name: &str,
+        today: Date,
+    ) -> impl Iterator<Item = &String> {
+        let qualified = match class_name {
+            Some(class) => format!("{}.{}.{}", module_path, class, name),
+            None => format!("{}.{}", module_path, name),
+        };
+        self.entries
+            .get(&qualified)
+            .into_iter()
+            .flatten()
+            .filter(move |entry| expiry::is_active(entry.until.as_deref(), today))
+            .flat_map(|entry| &entry.rules)
+    }
+
+    /// `PL000:expired-suppression` violations for the entries expired on `today`, reported
+    /// against their allowlist file, or the pyproject.toml of `project_root` for inline ones
+    pub fn expired(&self, today: Date, project_root: &Path) -> Vec<LintViolation> {
+        let mut expired: Vec<LintViolation> = self
+            .entries
+            .iter()
+            .flat_map(|(name, entries)| entries.iter().map(move |entry| (name, entry)))
+            .filter_map(|(name, entry)| {
+                let until = entry
+                    .until
+                    .as_deref()
+                    .filter(|until| !expiry::is_active(Some(until), today))?;
+                let (file, line) = entry
+                    .origin
+                    .clone()
+                    .unwrap_or_else(|| (project_root.join("pyproject.toml"), 1));
+                let subject = format!("{} for {}", entry.rules.join(", "), name);
+                Some(expiry::violation(&file, line, &subject, until))
+            })
+            .collect();
+        expired.sort_by(|a, b| {
+            (&a.file_path, a.line_number, &a.message).cmp(&(
+                &b.file_path,
+                b.line_number,
+                &b.message,
+            ))
+        });
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exempt_rules() {
+        let mut allowlist = Allowlist::new(
+            HashMap::from([("pkg.mod.load".to_string(), vec!["PL001".to_string()])]),
+            None,
+        )
+        .unwrap();
+        let file = Path::new("allowlist.txt");
+        allowlist
+            .parse(
+                "# generated\npkg.api_pb2.Stub.call  PL001, PL002  # grpc\n\npkg.mod.load PL003\npkg.mod.save PL001 until=2025-06-29\n",
+                file,
+            )
+            .unwrap();
+
+        let today = Date::parse("2025-06-30").unwrap();
+        let exempt = |module: &str, class: Option<&str>, name: &str| {
+            let mut rules: Vec<&str> = allowlist
+                .exempt_rules(module, class, name, today)
+                .map(String::as_str)
+                .collect();
+            rules.sort();
+            rules
+        };
+        assert_eq!(
+            exempt("pkg.api_pb2", Some("Stub"), "call"),
+            ["PL001", "PL002"]
+        );
+        assert_eq!(exempt("pkg.mod", None, "load"), ["PL001", "PL003"]);
+        assert!(exempt("pkg.api_pb2", None, "call").is_empty());
+        assert!(exempt("pkg.mod", None, "save").is_empty());
+
+        let expired = allowlist.expired(today, Path::new("/repo"));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(
+            (&*expired[0].file_path, expired[0].line_number),
+            ("allowlist.txt", 5)
+        );
+        assert!(expired[0]
+            .message
+            .contains("Suppression of PL001 for pkg.mod.save expired on 2025-06-29"));
+
+        assert!(allowlist.parse("pkg.mod.save\n", file).is_err());
+        assert!(allowlist
+            .parse("pkg.mod.save PL999\n", file)
+            .unwrap_err()
+            .contains("line 1"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Per-file time budgets, so one pathological file cannot stall a whole lint run
+//!
+//! A file's budget covers reading it, parsing it (checked between lines) and running the
+//! rules (checked between functions, whose body metrics are only parsed when reached): a
+//! file that runs over is abandoned and reported as a `PL000` violation instead of its
+//! partial results. Paths sharing one content each count the time spent parsing it.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::models::LintViolation;
+
+/// Time limit per file unless configured with `file_time_limit`
+pub const DEFAULT_FILE_TIME_LIMIT: Duration = Duration::from_secs(10);
+
+/// Time left to lint one file
+#[derive(Debug, Clone, Copy)]
+pub struct FileBudget {
+    started: Instant,
+    /// None when unlimited
+    limit: Option<Duration>,
+}
+
+impl FileBudget {
+    pub fn start(limit: Option<Duration>) -> Self {
+        Self {
+            started: Instant::now(),
+            limit,
+        }
+    }
+
+    /// A budget of which `spent` is already used, e.g. by reading and parsing the file
+    pub fn resume(limit: Option<Duration>, spent: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            started: now.checked_sub(spent).unwrap_or(now),
+            limit,
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.limit
+            .is_some_and(|limit| self.started.elapsed() >= limit)
+    }
+
+    /// The lint error reported for `path` once the budget is exceeded
+    pub fn violation(&self, path: &Path) -> LintViolation {
+        LintViolation {
+            rule_name: "PL000:file-time-limit".to_string(),
+            file_path: path.to_string_lossy().into(),
+            line_number: 1,
+            function_name: String::new(),
+            message: format!(
+                "[PL000] Linting stopped after exceeding the per-file time limit of {:.1}s; this file was not checked.\nLocation: {}\n\nTip: Raise file_time_limit, or exclude generated files via exclude_patterns",
+                self.limit.unwrap_or_default().as_secs_f64(),
+                path.display()
+            ),
+            severity: "error".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse the `file_time_limit` option in seconds; infinite means unlimited
+pub fn parse_limit(seconds: Option<f64>) -> Result<Option<Duration>, String> {
+    match seconds {
+        None => Ok(Some(DEFAULT_FILE_TIME_LIMIT)),
+        Some(seconds) if seconds == f64::INFINITY => Ok(None),
+        Some(seconds) => Duration::try_from_secs_f64(seconds).map(Some).map_err(|_| {
+            format!(
+                "file_time_limit must be a non-negative number of seconds, got {}",
+                seconds
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        assert!(FileBudget::start(Some(Duration::ZERO)).is_exceeded());
+        assert!(!FileBudget::start(Some(DEFAULT_FILE_TIME_LIMIT)).is_exceeded());
+        assert!(!FileBudget::start(None).is_exceeded());
+        assert!(
+            FileBudget::resume(Some(Duration::from_secs(2)), Duration::from_secs(3)).is_exceeded()
+        );
+
+        let violation = FileBudget::start(Some(Duration::from_secs(2)))
+            .violation(Path::new("pkg/generated.py"));
+        assert_eq!(violation.rule_name, "PL000:file-time-limit");
+        assert!(violation.message.contains("2.0s"));
+    }
+
+    #[test]
+    fn test_parse_limit() {
+        assert_eq!(parse_limit(None), Ok(Some(DEFAULT_FILE_TIME_LIMIT)));
+        assert_eq!(parse_limit(Some(0.5)), Ok(Some(Duration::from_millis(500))));
+        assert_eq!(parse_limit(Some(f64::INFINITY)), Ok(None));
+        assert!(parse_limit(Some(-1.0)).is_err());
+        assert!(parse_limit(Some(f64::NAN)).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+//! Intra-project call graph used to weight violations by fan-in
+//!
+//! Calls are resolved by name only: `obj.save()` and `save()` both count towards every
+//! function called `save`. That over-approximates for common names but needs no imports
+//! or type information, and is good enough to rank untested functions by how widely
+//! they are used.
+
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::LintViolation;
+use crate::parser;
+use crate::patterns::PATTERNS;
+
+/// Severity given to violations whose fan-in reaches the `critical_fan_in` threshold
+pub const CRITICAL_SEVERITY: &str = "critical";
+
+/// Number of call sites per function name
+#[derive(Debug, Default)]
+pub struct CallGraph {
+    fan_in: HashMap<String, usize>,
+}
+
+impl CallGraph {
+    /// Count call sites across `files`
+    pub fn build(files: &[PathBuf]) -> Self {
+        let fan_in = files
+            .par_iter()
+            .filter_map(|file| fs::read_to_string(file).ok())
+            .map(|content| Self::from_source(&content).fan_in)
+            .reduce(HashMap::new, |mut total, counts| {
+                for (name, count) in counts {
+                    *total.entry(name).or_default() += count;
+                }
+                total
+            });
+        Self { fan_in }
+    }
+
+    fn from_source(content: &str) -> Self {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut fan_in: HashMap<String, usize> = HashMap::new();
+        for code in parser::code_lines(&lines) {
+            for captures in PATTERNS.call.captures_iter(&code) {
+                // `def name(` and `class Name(` are definitions, not calls
+                if captures.get(1).is_none() {
+                    *fan_in.entry(captures[2].to_string()).or_default() += 1;
+                }
+            }
+        }
+        Self { fan_in }
+    }
+
+    pub fn fan_in(&self, function_name: &str) -> usize {
+        self.fan_in.get(function_name).copied().unwrap_or(0)
+    }
+}
+
+/// Record each violation's fan-in, escalate widely-called functions to critical and
+/// order violations so the most-called functions come first
+pub fn weight_by_fan_in(
+    violations: &mut [LintViolation],
+    graph: &CallGraph,
+    critical_fan_in: Option<usize>,
+) {
+    for violation in violations.iter_mut() {
+        let fan_in = graph.fan_in(&violation.function_name);
+        violation.fan_in = Some(fan_in);
+        if !violation.satisfied && critical_fan_in.is_some_and(|threshold| fan_in >= threshold) {
+            violation.severity = CRITICAL_SEVERITY.to_string();
+        }
+    }
+    violations.sort_by_key(|violation| Reverse(violation.fan_in));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_call_sites_not_definitions() {
+        let graph = CallGraph::from_source(
+            "def load(path):\n    return parse(read(path))\n\nclass Loader(Base):\n    def run(self):\n        # load(ignored)\n        text = 'load(ignored)'\n        return load(self.path) + self.load(\n            1)\n",
+        );
+        assert_eq!(graph.fan_in("load"), 2);
+        assert_eq!(graph.fan_in("parse"), 1);
+        assert_eq!(graph.fan_in("run"), 0);
+        assert_eq!(graph.fan_in("Loader"), 0);
+    }
+
+    #[test]
+    fn test_weight_by_fan_in() {
+        let graph = CallGraph {
+            fan_in: HashMap::from([("hot".to_string(), 5), ("warm".to_string(), 2)]),
+        };
+        let violation = |name: &str| LintViolation {
+            function_name: name.to_string(),
+            severity: "error".to_string(),
+            ..Default::default()
+        };
+        let mut violations = vec![violation("cold"), violation("warm"), violation("hot")];
+        weight_by_fan_in(&mut violations, &graph, Some(5));
+
+        let names: Vec<&str> = violations
+            .iter()
+            .map(|v| v.function_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["hot", "warm", "cold"]);
+        assert_eq!(violations[0].severity, CRITICAL_SEVERITY);
+        assert_eq!(violations[1].severity, "error");
+        assert_eq!(violations[2].fan_in, Some(0));
+    }
+}
@@ -0,0 +1,78 @@
+//! Ranking violations by recent churn (`RustLinter.prioritized_violations`)
+//!
+//! A gap in code that changes often is riskier than one in code nobody touches, so each
+//! violation gets the number of commits that touched its file in the window (`churn`),
+//! and the most churned files come first.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+
+/// Attach `churn` from the per-file `commit_counts` (see `git::commit_counts_since`) and
+/// order by it, keeping the order of violations with the same churn
+pub fn prioritize(
+    mut violations: Vec<LintViolation>,
+    commit_counts: HashMap<PathBuf, usize>,
+) -> Vec<LintViolation> {
+    // Violation paths and git paths may spell the same file differently (`./src`, symlinks)
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let commit_counts: HashMap<PathBuf, usize> = commit_counts
+        .into_iter()
+        .map(|(path, count)| (canonical(&path), count))
+        .collect();
+    let mut per_file: HashMap<Arc<str>, usize> = HashMap::new();
+    for violation in violations.iter_mut() {
+        let churn = *per_file
+            .entry(violation.file_path.clone())
+            .or_insert_with(|| {
+                let path = canonical(Path::new(&*violation.file_path));
+                commit_counts.get(&path).copied().unwrap_or(0)
+            });
+        violation.churn = Some(churn);
+    }
+    violations.sort_by_key(|violation| Reverse(violation.churn));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prioritize() {
+        let violation = |file: &str, line: usize| LintViolation {
+            file_path: file.into(),
+            line_number: line,
+            ..Default::default()
+        };
+        let counts = HashMap::from([
+            (PathBuf::from("/repo/hot.py"), 7),
+            (PathBuf::from("/repo/warm.py"), 2),
+        ]);
+        let ranked = prioritize(
+            vec![
+                violation("/repo/cold.py", 1),
+                violation("/repo/warm.py", 1),
+                violation("/repo/hot.py", 1),
+                violation("/repo/hot.py", 5),
+            ],
+            counts,
+        );
+        let order: Vec<(&str, usize, Option<usize>)> = ranked
+            .iter()
+            .map(|v| (&*v.file_path, v.line_number, v.churn))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("/repo/hot.py", 1, Some(7)),
+                ("/repo/hot.py", 5, Some(7)),
+                ("/repo/warm.py", 1, Some(2)),
+                ("/repo/cold.py", 1, Some(0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+
+/// Locations GitHub/GitLab look for a CODEOWNERS file, in precedence order
+const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+struct OwnerRule {
+    pattern: Regex,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS file; the last matching rule wins
+pub struct CodeOwners {
+    root: PathBuf,
+    rules: Vec<OwnerRule>,
+}
+
+impl CodeOwners {
+    /// Find and parse the CODEOWNERS file governing `project_root`, searching upwards
+    pub fn discover(project_root: &Path) -> Option<Self> {
+        let start = project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf());
+        for dir in start.ancestors() {
+            for location in CODEOWNERS_LOCATIONS {
+                let candidate = dir.join(location);
+                if let Ok(content) = fs::read_to_string(&candidate) {
+                    return Some(Self::parse(dir, &content));
+                }
+            }
+        }
+        None
+    }
+
+    /// Parse CODEOWNERS content; paths are matched relative to `root`
+    pub fn parse(root: &Path, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let pattern = pattern_to_regex(parts.next()?)?;
+                let owners = parts
+                    .take_while(|part| !part.starts_with('#'))
+                    .map(str::to_string)
+                    .collect();
+                Some(OwnerRule { pattern, owners })
+            })
+            .collect();
+
+        CodeOwners {
+            root: root.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Owners of a file; empty when no rule matches or the matching rule lists no owners
+    pub fn owners_of(&self, file: &Path) -> &[String] {
+        let absolute = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        let relative = absolute.strip_prefix(&self.root).unwrap_or(&absolute);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.is_match(&relative))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Attach the owning teams to each violation's file
+    pub fn annotate(&self, violations: &mut [LintViolation]) {
+        for violation in violations.iter_mut() {
+            violation.code_owners = self.owners_of(Path::new(&*violation.file_path)).to_vec();
+        }
+    }
+}
+
+/// Convert a gitignore-style CODEOWNERS pattern to a regex over root-relative paths
+fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    // Patterns containing a slash (other than a trailing one) are anchored at the root
+    let anchored = trimmed.contains('/');
+    let trimmed = trimmed.trim_start_matches('/');
+
+    let mut regex = String::from("^");
+    if !anchored {
+        regex.push_str("(?:.*/)?");
+    }
+
+    let chars: Vec<char> = trimmed.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    regex.push_str(".*");
+                    i += 2;
+                }
+            }
+            '*' => {
+                regex.push_str("[^/]*");
+                i += 1;
+            }
+            '?' => {
+                regex.push_str("[^/]");
+                i += 1;
+            }
+            c => {
+                regex.push_str(&regex::escape(&c.to_string()));
+                i += 1;
+            }
+        }
+    }
+
+    // A pattern matching a directory owns everything beneath it
+    if dir_only {
+        regex.push_str("/.*$");
+    } else {
+        regex.push_str("(?:/.*)?$");
+    }
+
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(codeowners: &CodeOwners, path: &str) -> Vec<String> {
+        codeowners
+            .owners_of(&Path::new("/repo").join(path))
+            .to_vec()
+    }
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let codeowners = CodeOwners::parse(
+            Path::new("/repo"),
+            "# comment\n* @org/everyone\n*.py @org/python\n/src/billing/ @org/billing alice@example.com\n",
+        );
+        assert_eq!(owners(&codeowners, "README.md"), vec!["@org/everyone"]);
+        assert_eq!(owners(&codeowners, "src/api/views.py"), vec!["@org/python"]);
+        assert_eq!(
+            owners(&codeowners, "src/billing/invoice.py"),
+            vec!["@org/billing", "alice@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_unanchored_and_directory_patterns() {
+        let codeowners = CodeOwners::parse(
+            Path::new("/repo"),
+            "migrations/ @org/dba\ndocs/*.md @org/docs\nsrc/**/legacy.py @org/legacy\n",
+        );
+        assert_eq!(
+            owners(&codeowners, "app/migrations/0001.py"),
+            vec!["@org/dba"]
+        );
+        assert_eq!(owners(&codeowners, "docs/index.md"), vec!["@org/docs"]);
+        assert!(owners(&codeowners, "docs/api/index.md").is_empty());
+        assert_eq!(
+            owners(&codeowners, "src/a/b/legacy.py"),
+            vec!["@org/legacy"]
+        );
+        assert_eq!(owners(&codeowners, "src/legacy.py"), vec!["@org/legacy"]);
+    }
+
+    #[test]
+    fn test_rule_without_owners_clears_ownership() {
+        let codeowners = CodeOwners::parse(Path::new("/repo"), "* @org/everyone\n/generated/\n");
+        assert!(owners(&codeowners, "generated/models.py").is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! Settings from a project's own configuration file (`config_root`)
+//!
+//! Callers of the Rust API need not repeat what a project already configures: with
+//! `config_root`, `RustLinter` reads the `[tool.proboscis]` section of the `pyproject.toml`
+//! in that directory or, without one, a `proboscis.toml` holding the same settings at its
+//! top level. The settings read are those a subproject can set (see `subprojects`): test
+//! directories and patterns, exclusions, rule switches under `[rules]`, and so on. Options
+//! passed to the constructor win over the file, and paths in it are relative to the
+//! project linted, as those of the constructor are. `lint` and `open_session` read the
+//! configuration of the project they lint unless given another `config_root`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::subprojects::Subproject;
+
+pub const PYPROJECT: &str = "pyproject.toml";
+pub const PROBOSCIS_TOML: &str = "proboscis.toml";
+
+/// The settings configured in `root`, as a subproject rooted there, leaving out those
+/// the `explicit` constructor options set; None when `root` configures nothing
+pub fn load(root: &Path, explicit: &[&str]) -> Result<Option<Subproject>, String> {
+    let Some((file, mut settings)) = read(root)? else {
+        return Ok(None);
+    };
+    settings.retain(|key, _| !overridden(key, explicit));
+    Ok(Some(Subproject {
+        root: root.to_path_buf(),
+        file,
+        settings,
+    }))
+}
+
+/// Whether the setting `key` is left to the `explicit` constructor options
+fn overridden(key: &str, explicit: &[&str]) -> bool {
+    match key {
+        "rules" => explicit.contains(&"rule_switches"),
+        key => explicit.contains(&key),
+    }
+}
+
+/// The configuration file of `root` with its proboscis settings: `[tool.proboscis]` of
+/// `pyproject.toml`, else the whole of `proboscis.toml`
+fn read(root: &Path) -> Result<Option<(PathBuf, toml::Table)>, String> {
+    let pyproject = root.join(PYPROJECT);
+    if let Some(table) = parse(&pyproject)? {
+        if let Some(settings) = table
+            .get("tool")
+            .and_then(|tool| tool.get("proboscis"))
+            .and_then(|section| section.as_table())
+        {
+            return Ok(Some((pyproject, settings.clone())));
+        }
+    }
+    let file = root.join(PROBOSCIS_TOML);
+    Ok(parse(&file)?.map(|settings| (file, settings)))
+}
+
+fn parse(file: &Path) -> Result<Option<toml::Table>, String> {
+    match fs::read_to_string(file) {
+        Ok(content) => content
+            .parse()
+            .map(Some)
+            .map_err(|e| format!("{}: {}", file.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", file.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        assert!(load(root, &[]).unwrap().is_none());
+
+        // proboscis.toml holds the settings at its top level
+        fs::write(
+            root.join(PROBOSCIS_TOML),
+            "test_directories = [\"spec\"]\nenabled_rules = [\"PL026\"]\n\n[rules]\nPL002 = false\n",
+        )
+        .unwrap();
+        let config = load(root, &[]).unwrap().unwrap();
+        assert_eq!(config.file, root.join(PROBOSCIS_TOML));
+        assert_eq!(
+            config.strings("test_directories").unwrap(),
+            Some(vec!["spec".to_string()])
+        );
+        assert_eq!(config.rule_switches().get("PL002"), Some(&false));
+        assert_eq!(
+            config.strings("enabled_rules").unwrap(),
+            Some(vec!["PL026".to_string()])
+        );
+
+        // A pyproject.toml wins over proboscis.toml only with a [tool.proboscis] section
+        fs::write(root.join(PYPROJECT), "[project]\nname = \"app\"\n").unwrap();
+        assert_eq!(
+            load(root, &[]).unwrap().unwrap().file,
+            root.join(PROBOSCIS_TOML)
+        );
+        fs::write(
+            root.join(PYPROJECT),
+            "[tool.proboscis]\ntest_patterns = [\"check_*.py\"]\nstrict_mode = true\n\n[tool.proboscis.rules]\nPL003 = false\n",
+        )
+        .unwrap();
+        let config = load(root, &["strict_mode", "rule_switches"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.file, root.join(PYPROJECT));
+        assert_eq!(config.strings("test_directories").unwrap(), None);
+        assert_eq!(config.bool("strict_mode").unwrap(), None);
+        assert!(config.rule_switches().is_empty());
+        assert_eq!(
+            config.strings("test_patterns").unwrap(),
+            Some(vec!["check_*.py".to_string()])
+        );
+
+        fs::write(root.join(PYPROJECT), "[tool.proboscis\n").unwrap();
+        assert!(load(root, &[]).unwrap_err().contains(PYPROJECT));
+    }
+}
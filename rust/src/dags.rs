@@ -0,0 +1,140 @@
+//! Airflow DAGs and tasks, which are often only testable by running the DAG
+//!
+//! A function belongs to a DAG when it carries an Airflow `@dag` or `@task` decorator
+//! (`@task.virtualenv`, `@task.branch` and other `@task.<kind>` variants included), or is
+//! a top-level function of a DAG file, one in the `dag_directories` (matched like
+//! `test_directories`: `dags` by default). Methods of DAG files, such as those of custom
+//! operators, are ordinary code. Depending on `dag_mode`, DAG functions are checked like
+//! any other, held to PL003 only, reported as warnings, or skipped.
+
+use std::path::Path;
+
+use crate::file_discovery::TestDirectories;
+use crate::parser::FunctionDef;
+
+/// How the functions of DAGs are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DagMode {
+    /// Not checked at all
+    Exempt,
+    /// Only required to have end-to-end tests (PL003)
+    E2e,
+    /// Checked, with violations reported as warnings
+    Downgrade,
+    /// Checked like any other function
+    Check,
+}
+
+impl DagMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "exempt" => Ok(Self::Exempt),
+            "e2e" => Ok(Self::E2e),
+            "downgrade" => Ok(Self::Downgrade),
+            "check" => Ok(Self::Check),
+            other => Err(format!(
+                "dag_mode must be 'exempt', 'e2e', 'downgrade' or 'check', got '{}'",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Exempt => "exempt",
+            Self::E2e => "e2e",
+            Self::Downgrade => "downgrade",
+            Self::Check => "check",
+        }
+    }
+
+    /// Whether the rule `rule_id` checks DAG functions
+    pub fn applies(self, rule_id: &str) -> bool {
+        match self {
+            Self::Exempt => false,
+            Self::E2e => rule_id == "PL003",
+            Self::Downgrade | Self::Check => true,
+        }
+    }
+}
+
+/// Whether `decorator` is Airflow's `@dag` or `@task` (plain or `airflow.decorators.`),
+/// or a `@task.<kind>` variant
+fn is_dag_decorator(decorator: &str) -> bool {
+    let name = decorator
+        .strip_prefix("airflow.decorators.")
+        .unwrap_or(decorator);
+    matches!(name, "dag" | "task") || name.starts_with("task.")
+}
+
+/// DAG detection for a lint run
+#[derive(Debug, Clone)]
+pub struct Dags {
+    /// The `dag_directories` setting
+    pub directories: Vec<String>,
+    matcher: TestDirectories,
+    pub mode: DagMode,
+}
+
+impl Dags {
+    pub fn new(directories: &[String], mode: DagMode) -> Self {
+        Self {
+            directories: directories.to_vec(),
+            matcher: TestDirectories::new(directories),
+            mode,
+        }
+    }
+
+    /// Whether the file at `relative_path` (relative to the project root) is a DAG file
+    pub fn is_dag_file(&self, relative_path: &Path) -> bool {
+        self.matcher.contains(relative_path)
+    }
+
+    /// Whether `function`, a method when `is_method`, belongs to a DAG; `in_dag_file` tells
+    /// whether its file is a DAG file
+    pub fn is_dag_function(
+        &self,
+        function: &FunctionDef,
+        is_method: bool,
+        in_dag_file: bool,
+    ) -> bool {
+        (in_dag_file && !is_method)
+            || function
+                .decorators
+                .iter()
+                .any(|decorator| is_dag_decorator(decorator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_function;
+
+    #[test]
+    fn test_is_dag_function() {
+        let dags = Dags::new(&["dags".to_string()], DagMode::E2e);
+        assert!(dags.is_dag_file(Path::new("dags/etl.py")));
+        assert!(dags.is_dag_file(Path::new("pipelines/dags/etl.py")));
+        assert!(!dags.is_dag_file(Path::new("src/pkg/dags.py")));
+
+        let function = |source: &str| {
+            let lines: Vec<&str> = source.lines().collect();
+            let def_line = lines.iter().position(|l| l.contains("def ")).unwrap() + 1;
+            parse_function(&lines, def_line)
+        };
+        let task = function("@task.virtualenv(requirements=[\"pandas\"])\ndef load():\n    pass\n");
+        assert!(dags.is_dag_function(&task, false, false));
+        let dag = function("@airflow.decorators.dag(schedule=None)\ndef etl():\n    pass\n");
+        assert!(dags.is_dag_function(&dag, false, false));
+        let plain = function("def transform(rows):\n    return rows\n");
+        assert!(dags.is_dag_function(&plain, false, true));
+        assert!(!dags.is_dag_function(&plain, true, true));
+        assert!(!dags.is_dag_function(&plain, false, false));
+        let other = function("@app.task\ndef send():\n    pass\n");
+        assert!(!dags.is_dag_function(&other, false, false));
+
+        assert!(DagMode::E2e.applies("PL003") && !DagMode::E2e.applies("PL001"));
+        assert!(DagMode::parse("skip").is_err());
+    }
+}
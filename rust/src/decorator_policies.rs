@@ -0,0 +1,148 @@
+//! Test types required of functions by their decorators (`decorator_test_types`)
+//!
+//! Some decorated functions only make sense tested one way: a Celery task
+//! (`@shared_task`, `@app.task`) needs a broker or an eager worker, a Django signal
+//! receiver (`@receiver(post_save, ...)`) fires from a model save. The setting maps
+//! decorator patterns to the test types such functions need; PL001–PL003 only check the
+//! types listed, and an empty list exempts the function:
+//!
+//! ```toml
+//! [tool.proboscis.decorator_test_types]
+//! shared_task = ["integration"]
+//! "*.task" = ["integration"]
+//! receiver = []
+//! ```
+//!
+//! Patterns match the decorator name without its arguments; `*` matches any run of
+//! characters, and an undotted pattern also matches dotted names ending in it, so
+//! `shared_task` matches `@celery.shared_task`. A function matching several patterns
+//! needs the test types of all of them.
+
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+use crate::file_discovery::glob_to_file_name_regex;
+
+/// The missing-test rule checking each test type
+const RULES_BY_TEST_TYPE: &[(&str, &str)] = &[
+    ("unit", "PL001"),
+    ("integration", "PL002"),
+    ("e2e", "PL003"),
+];
+
+#[derive(Debug, Clone)]
+struct Policy {
+    pattern: Regex,
+    /// Whether the pattern has no dot, and so also matches the last segment of a name
+    undotted: bool,
+    /// Ids of the missing-test rules that apply
+    rules: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DecoratorPolicies {
+    policies: Vec<Policy>,
+}
+
+impl DecoratorPolicies {
+    pub fn new(test_types: HashMap<String, Vec<String>>) -> Result<Self, String> {
+        let mut policies = Vec::new();
+        for (decorator, types) in test_types {
+            let pattern = glob_to_file_name_regex(&decorator)
+                .ok_or_else(|| format!("invalid decorator pattern '{}'", decorator))?;
+            let rules = types
+                .iter()
+                .map(|name| {
+                    rule_of_test_type(name).ok_or_else(|| {
+                            format!(
+                                "invalid test type '{}' for decorator '{}', expected unit, integration or e2e",
+                                name, decorator
+                            )
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+            policies.push(Policy {
+                pattern,
+                undotted: !decorator.contains('.'),
+                rules,
+            });
+        }
+        Ok(Self { policies })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Ids of the missing-test rules that apply to a function with `decorators`, or None
+    /// when no pattern matches them and every rule applies
+    pub fn required_rules(&self, decorators: &[String]) -> Option<HashSet<&'static str>> {
+        let mut matched = false;
+        let mut rules = HashSet::new();
+        for policy in &self.policies {
+            let matches = decorators.iter().any(|decorator| {
+                policy.pattern.is_match(decorator)
+                    || (policy.undotted
+                        && policy
+                            .pattern
+                            .is_match(decorator.rsplit('.').next().unwrap_or(decorator)))
+            });
+            if matches {
+                matched = true;
+                rules.extend(&policy.rules);
+            }
+        }
+        matched.then_some(rules)
+    }
+}
+
+/// The missing-test rule checking the test type `name` ("unit", "integration" or "e2e")
+pub fn rule_of_test_type(name: &str) -> Option<&'static str> {
+    RULES_BY_TEST_TYPE
+        .iter()
+        .find(|(test_type, _)| *test_type == name)
+        .map(|(_, rule_id)| *rule_id)
+}
+
+/// Whether `rule_id` is one of the missing-test rules the policies restrict
+pub fn is_missing_test_rule(rule_id: &str) -> bool {
+    RULES_BY_TEST_TYPE.iter().any(|(_, id)| *id == rule_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_rules() {
+        let policies = DecoratorPolicies::new(HashMap::from([
+            ("shared_task".to_string(), vec!["integration".to_string()]),
+            (
+                "*.task".to_string(),
+                vec!["integration".to_string(), "e2e".to_string()],
+            ),
+            ("receiver".to_string(), vec![]),
+        ]))
+        .unwrap();
+        let required = |decorators: &[&str]| {
+            let decorators: Vec<String> = decorators.iter().map(|d| d.to_string()).collect();
+            policies.required_rules(&decorators).map(|rules| {
+                let mut rules: Vec<&str> = rules.into_iter().collect();
+                rules.sort();
+                rules
+            })
+        };
+        assert_eq!(required(&["celery.shared_task"]), Some(vec!["PL002"]));
+        assert_eq!(required(&["app.task"]), Some(vec!["PL002", "PL003"]));
+        assert_eq!(required(&["django.dispatch.receiver"]), Some(vec![]));
+        assert_eq!(required(&["staticmethod"]), None);
+        assert_eq!(required(&[]), None);
+
+        assert!(DecoratorPolicies::new(HashMap::from([(
+            "shared_task".to_string(),
+            vec!["smoke".to_string()]
+        )]))
+        .unwrap_err()
+        .contains("'smoke'"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Crediting facades with the tests of the function they delegate to (`credit_delegation`)
+//!
+//! A function whose body, docstring aside, is a single `return` of a call to another
+//! function of the project is a facade: `api.create_user` returning
+//! `service.create_user(...)` adds nothing to test beyond its delegate. With
+//! `credit_delegation`, a facade without tests of its own passes PL001–PL003 when its
+//! delegate has tests of the same type. Credit goes through one hop only: a facade of a
+//! facade needs the tests of the second one.
+//!
+//! The callee is resolved through the module's imports (see `aliases`): a function of the
+//! same module, a method of the same class (`self.`/`cls.`), or a function of a project
+//! module imported by name or as a module.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::aliases::Aliases;
+use crate::patterns::Patterns;
+use crate::source_index::SourceFile;
+
+/// The function a facade delegates to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delegate {
+    pub file_path: PathBuf,
+    pub module_path: String,
+    pub name: String,
+    pub class_name: Option<String>,
+}
+
+/// Whether `source` defines `name`, as a method of `class_name` or a top-level function
+fn defines(source: &SourceFile, name: &str, class_name: Option<&str>) -> bool {
+    source
+        .functions
+        .iter()
+        .any(|function| function.name == name && function.class_name.as_deref() == class_name)
+}
+
+/// Resolve `callee`, the function called by the facade `name` (a method of `class_name`)
+/// of `source` at `file_path` in the module `module_path`, to a function of the project
+///
+/// `module_file` maps a dotted module to its file and module path, when in the project.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve(
+    callee: &str,
+    name: &str,
+    class_name: Option<&str>,
+    file_path: &Path,
+    module_path: &str,
+    source: &SourceFile,
+    aliases: &Aliases,
+    patterns: &Patterns,
+    module_file: impl Fn(&str) -> Option<(PathBuf, String)>,
+) -> Option<Delegate> {
+    let same_module = |name: &str, class_name: Option<&str>| Delegate {
+        file_path: file_path.to_path_buf(),
+        module_path: module_path.to_string(),
+        name: name.to_string(),
+        class_name: class_name.map(str::to_string),
+    };
+
+    if let (Some(class), Some((receiver, method))) = (class_name, callee.split_once('.')) {
+        if matches!(receiver, "self" | "cls") {
+            return (method != name && defines(source, method, Some(class)))
+                .then(|| same_module(method, Some(class)));
+        }
+    }
+    let target = aliases.resolve(callee);
+    match target.rsplit_once('.') {
+        // A function of the same module, unless it is the facade itself
+        None => ((class_name.is_some() || target != name) && defines(source, &target, None))
+            .then(|| same_module(&target, None)),
+        Some((module, function)) => {
+            let (delegate_file, delegate_module) = module_file(module)?;
+            let content = fs::read_to_string(&delegate_file).ok()?;
+            let delegate_source = SourceFile::parse(content, patterns);
+            defines(&delegate_source, function, None).then(|| Delegate {
+                file_path: delegate_file,
+                module_path: delegate_module,
+                name: function.to_string(),
+                class_name: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_resolve() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        fs::write(
+            dir.join("pkg/service.py"),
+            "def create_user(name):\n    return User(name)\n",
+        )
+        .unwrap();
+        let api = dir.join("pkg/api.py");
+        let content = "from pkg import service\nfrom pkg.service import create_user as make\n\ndef create_user(name):\n    return service.create_user(name)\n\ndef add(name):\n    return make(name)\n\ndef remove(name):\n    return _remove(name)\n\ndef _remove(name):\n    pass\n\nclass Client:\n    def send(self, msg):\n        return self._send(msg)\n\n    def _send(self, msg):\n        pass\n";
+        fs::write(&api, content).unwrap();
+        let source = SourceFile::parse(content.to_string(), &PATTERNS);
+        let aliases = Aliases::from_source(&api, content, dir);
+        let module_file = |module: &str| {
+            let file = dir.join(module.replace('.', "/")).with_extension("py");
+            file.is_file().then(|| (file, module.to_string()))
+        };
+        let resolve = |callee: &str, name: &str, class_name: Option<&str>| {
+            resolve(
+                callee,
+                name,
+                class_name,
+                &api,
+                "pkg.api",
+                &source,
+                &aliases,
+                &PATTERNS,
+                module_file,
+            )
+            .map(|delegate| (delegate.module_path, delegate.class_name, delegate.name))
+        };
+
+        let service = Some(("pkg.service".to_string(), None, "create_user".to_string()));
+        assert_eq!(resolve("service.create_user", "create_user", None), service);
+        assert_eq!(resolve("make", "add", None), service);
+        assert_eq!(
+            resolve("_remove", "remove", None),
+            Some(("pkg.api".to_string(), None, "_remove".to_string()))
+        );
+        assert_eq!(
+            resolve("self._send", "send", Some("Client")),
+            Some((
+                "pkg.api".to_string(),
+                Some("Client".to_string()),
+                "_send".to_string()
+            ))
+        );
+        assert_eq!(resolve("remove", "remove", None), None);
+        assert_eq!(resolve("service.delete_user", "delete", None), None);
+        assert_eq!(resolve("requests.get", "fetch", None), None);
+    }
+}
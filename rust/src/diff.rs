@@ -0,0 +1,163 @@
+//! Comparing two result sets (`diff_results`)
+//!
+//! Violations are matched by an identity key that survives unrelated edits: the rule, the
+//! file relative to the project root and the function (`Class.method` for methods). Only
+//! violations not tied to a function, such as those of test files, fall back to their line.
+//! A key present several times matches as many times as it occurs on both sides; the
+//! surplus is new or fixed.
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+use crate::report::{ndjson, relative_path};
+
+/// A result set: violations in memory, or an NDJSON export of them
+#[derive(FromPyObject)]
+pub enum ResultSet {
+    File(PathBuf),
+    Violations(Vec<LintViolation>),
+}
+
+impl ResultSet {
+    pub fn load(self, project_root: &Path) -> Result<Vec<LintViolation>, String> {
+        match self {
+            Self::Violations(violations) => Ok(violations),
+            Self::File(path) => {
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+                ndjson::parse(&content, project_root)
+                    .map_err(|e| format!("{}: {}", path.display(), e))
+            }
+        }
+    }
+}
+
+/// Violations of the new result set that were not in the old one, those of the old one no
+/// longer in the new one, and those in both
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct ResultDiff {
+    #[pyo3(get)]
+    pub new: Vec<LintViolation>,
+    #[pyo3(get)]
+    pub fixed: Vec<LintViolation>,
+    /// As found in the new result set
+    #[pyo3(get)]
+    pub unchanged: Vec<LintViolation>,
+}
+
+#[pymethods]
+impl ResultDiff {
+    fn __repr__(&self) -> String {
+        format!(
+            "ResultDiff(new={}, fixed={}, unchanged={})",
+            self.new.len(),
+            self.fixed.len(),
+            self.unchanged.len()
+        )
+    }
+}
+
+/// Identity of a violation across runs
+pub fn identity_key(violation: &LintViolation, project_root: &Path) -> String {
+    let path = relative_path(&violation.file_path, project_root);
+    let anchor = match (&violation.class_name, violation.function_name.as_str()) {
+        (_, "") => format!("line {}", violation.line_number),
+        (Some(class), function) => format!("{}.{}", class, function),
+        (None, function) => function.to_string(),
+    };
+    format!("{}|{}|{}", violation.rule_name, path, anchor)
+}
+
+/// Classify the violations of two result sets as new, fixed or unchanged, keeping the
+/// order of each set
+pub fn diff(old: Vec<LintViolation>, new: Vec<LintViolation>, project_root: &Path) -> ResultDiff {
+    let mut unmatched: HashMap<String, usize> = HashMap::new();
+    for violation in &old {
+        *unmatched
+            .entry(identity_key(violation, project_root))
+            .or_default() += 1;
+    }
+
+    let mut result = ResultDiff::default();
+    for violation in new {
+        match unmatched.get_mut(&identity_key(&violation, project_root)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                result.unchanged.push(violation);
+            }
+            _ => result.new.push(violation),
+        }
+    }
+    // The last occurrences of a key in the old set are the ones left over
+    for violation in old.into_iter().rev() {
+        if let Some(count) = unmatched.get_mut(&identity_key(&violation, project_root)) {
+            if *count > 0 {
+                *count -= 1;
+                result.fixed.push(violation);
+            }
+        }
+    }
+    result.fixed.reverse();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let violation = |rule: &str, file: &str, line: usize, function: &str| LintViolation {
+            rule_name: rule.to_string(),
+            file_path: file.into(),
+            line_number: line,
+            function_name: function.to_string(),
+            ..Default::default()
+        };
+        let old = vec![
+            violation("PL001:require-unit-test", "/repo/src/a.py", 3, "load"),
+            violation("PL001:require-unit-test", "/repo/src/a.py", 9, "save"),
+            violation("PL026:no-sleep-in-tests", "/repo/test/t.py", 4, "test_x"),
+            violation("PL004:require-test-markers", "/repo/test/t.py", 1, ""),
+        ];
+        // `load` moved down a few lines, and `save` into a class
+        let new = vec![
+            violation("PL001:require-unit-test", "/repo/src/a.py", 12, "load"),
+            violation("PL026:no-sleep-in-tests", "/repo/test/t.py", 4, "test_x"),
+            violation("PL026:no-sleep-in-tests", "/repo/test/t.py", 8, "test_x"),
+            violation("PL004:require-test-markers", "/repo/test/t.py", 2, ""),
+            LintViolation {
+                class_name: Some("Store".to_string()),
+                ..violation("PL001:require-unit-test", "/repo/src/a.py", 20, "save")
+            },
+        ];
+
+        let result = diff(old, new, Path::new("/repo"));
+        let lines = |violations: &[LintViolation]| {
+            violations
+                .iter()
+                .map(|v| (v.rule_id().to_string(), v.line_number))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines(&result.unchanged),
+            [("PL001".to_string(), 12), ("PL026".to_string(), 4)]
+        );
+        assert_eq!(
+            lines(&result.new),
+            [
+                ("PL026".to_string(), 8),
+                ("PL004".to_string(), 2),
+                ("PL001".to_string(), 20)
+            ]
+        );
+        assert_eq!(
+            lines(&result.fixed),
+            [("PL001".to_string(), 9), ("PL004".to_string(), 1)]
+        );
+    }
+}
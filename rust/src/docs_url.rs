@@ -0,0 +1,81 @@
+//! Links from violations to the documentation of their rule (`LintViolation.docs_url`)
+//!
+//! The links are rendered from the `docs_url` template, so teams documenting the rules on
+//! an internal wiki can point editors and reports there. The template takes `{rule_id}`
+//! (`PL001`), `{rule_name}` (`require-unit-test`) and `{anchor}` (`pl001-require-unit-test`,
+//! the anchor of the rule's section in the README, which the default links to). Rules
+//! outside the registry, such as `PL000`, get no link.
+
+use std::collections::HashMap;
+
+use crate::rules::metadata;
+
+/// Link to the rule's section of the README on GitHub
+pub const DEFAULT_TEMPLATE: &str = "https://github.com/proboscis/proboscis-linter#{anchor}";
+
+const PLACEHOLDERS: &[&str] = &["{rule_id}", "{rule_name}", "{anchor}"];
+
+/// The documentation links of the rules, rendered once
+#[derive(Debug, Clone)]
+pub struct DocsUrl {
+    by_rule: HashMap<&'static str, String>,
+}
+
+impl DocsUrl {
+    /// Validate the template: it may only use the placeholders above
+    pub fn new(template: Option<&str>) -> Result<Self, String> {
+        let template = template.unwrap_or(DEFAULT_TEMPLATE).to_string();
+        let unknown = PLACEHOLDERS
+            .iter()
+            .fold(template.clone(), |rest, placeholder| {
+                rest.replace(placeholder, "")
+            })
+            .contains(['{', '}']);
+        if unknown {
+            return Err(format!(
+                "docs_url only supports the {{rule_id}}, {{rule_name}} and {{anchor}} placeholders, got '{}'",
+                template
+            ));
+        }
+        let by_rule = metadata::all_rules()
+            .iter()
+            .map(|rule| {
+                let anchor = format!("{}-{}", rule.rule_id.to_lowercase(), rule.name);
+                let url = template
+                    .replace("{rule_id}", rule.rule_id)
+                    .replace("{rule_name}", rule.name)
+                    .replace("{anchor}", &anchor);
+                (rule.rule_id, url)
+            })
+            .collect();
+        Ok(Self { by_rule })
+    }
+
+    /// The documentation of the rule `rule_id`, if it is a rule of the registry
+    pub fn of(&self, rule_id: &str) -> Option<&str> {
+        self.by_rule.get(rule_id).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let default = DocsUrl::new(None).unwrap();
+        assert_eq!(
+            default.of("PL001"),
+            Some("https://github.com/proboscis/proboscis-linter#pl001-require-unit-test")
+        );
+        assert_eq!(default.of("PL000"), None);
+
+        let wiki =
+            DocsUrl::new(Some("https://wiki.example.com/lint/{rule_id}/{rule_name}")).unwrap();
+        assert_eq!(
+            wiki.of("PL026"),
+            Some("https://wiki.example.com/lint/PL026/no-sleep-in-tests")
+        );
+        assert!(DocsUrl::new(Some("https://wiki.example.com/{rule}")).is_err());
+    }
+}
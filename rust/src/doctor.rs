@@ -0,0 +1,272 @@
+//! Environment and project diagnostics (`RustLinter.doctor`)
+//!
+//! Answers "why is it reporting everything (or nothing)?": which test directories and test
+//! files the linter sees, the settings in effect, and the misconfigurations that most often
+//! explain a surprising result.
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::file_discovery::glob_to_file_name_regex;
+use crate::git;
+use crate::test_cache::TestCache;
+
+/// Diagnostics for a project, as returned by `RustLinter.doctor`; `str()` renders a report
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    /// "gitoxide" (in-process) or "git command"
+    #[pyo3(get)]
+    pub git_backend: &'static str,
+    /// Whether git operations can run (with the `git command` backend, whether `git` is on
+    /// PATH)
+    #[pyo3(get)]
+    pub git_available: bool,
+    #[pyo3(get)]
+    pub git_repository: bool,
+    /// Configured test directories present under the project root
+    #[pyo3(get)]
+    pub test_directories_found: Vec<String>,
+    /// Configured test directories absent from the project root
+    #[pyo3(get)]
+    pub test_directories_missing: Vec<String>,
+    /// Indexed test files per type ("unit", "integration", "e2e", "general")
+    #[pyo3(get)]
+    pub test_files: HashMap<String, usize>,
+    /// Source files the per-function rules check
+    #[pyo3(get)]
+    pub source_files: usize,
+    /// Settings in effect, as (name, value) pairs
+    #[pyo3(get)]
+    pub settings: Vec<(String, String)>,
+    /// Likely misconfigurations, as sentences
+    #[pyo3(get)]
+    pub problems: Vec<String>,
+}
+
+#[pymethods]
+impl DoctorReport {
+    fn __str__(&self) -> String {
+        let mut report = format!(
+            "git: {} ({})\n",
+            self.git_backend,
+            match (self.git_available, self.git_repository) {
+                (false, _) => "not available",
+                (true, false) => "not a repository",
+                (true, true) => "repository",
+            }
+        );
+        report.push_str(&format!(
+            "test directories: {}",
+            list_or_none(&self.test_directories_found)
+        ));
+        if !self.test_directories_missing.is_empty() {
+            report.push_str(&format!(
+                " (missing: {})",
+                self.test_directories_missing.join(", ")
+            ));
+        }
+        let count = |test_type: &str| self.test_files.get(test_type).copied().unwrap_or(0);
+        report.push_str(&format!(
+            "\ntest files: {} unit, {} integration, {} e2e, {} general\n",
+            count("unit"),
+            count("integration"),
+            count("e2e"),
+            count("general")
+        ));
+        report.push_str(&format!("source files: {}\nsettings:\n", self.source_files));
+        for (name, value) in &self.settings {
+            report.push_str(&format!("  {} = {}\n", name, value));
+        }
+        if self.problems.is_empty() {
+            report.push_str("no problems found\n");
+        } else {
+            report.push_str("problems:\n");
+            for problem in &self.problems {
+                report.push_str(&format!("  - {}\n", problem));
+            }
+        }
+        report
+    }
+}
+
+/// What the linter sees of a project, gathered by `RustLinter.doctor`
+pub struct Project<'a> {
+    pub root: &'a Path,
+    pub test_directories: &'a [String],
+    pub test_patterns: &'a [String],
+    /// `source_roots` as configured (None: derived from pyproject.toml, else `src`)
+    pub configured_source_roots: Option<&'a [String]>,
+    pub source_files: &'a [PathBuf],
+    /// Files in the test directories matching `test_patterns`
+    pub test_files: &'a [PathBuf],
+    pub test_cache: &'a TestCache,
+    /// Ids of the per-function rules that run
+    pub rules: &'a [&'static str],
+}
+
+/// Diagnose `project`, reporting `settings` as the settings in effect
+pub fn diagnose(project: &Project, settings: Vec<(String, String)>) -> DoctorReport {
+    let (found, missing): (Vec<String>, Vec<String>) = project
+        .test_directories
+        .iter()
+        .cloned()
+        .partition(|dir| project.root.join(dir).is_dir());
+    let mut report = DoctorReport {
+        git_backend: git::BACKEND,
+        git_available: git::is_available(),
+        git_repository: git::is_git_repository(project.root),
+        test_directories_found: found,
+        test_directories_missing: missing,
+        test_files: project
+            .test_cache
+            .count_by_type()
+            .into_iter()
+            .map(|(test_type, count)| (test_type.to_string(), count))
+            .collect(),
+        source_files: project.source_files.len(),
+        settings,
+        problems: Vec::new(),
+    };
+    report.problems = problems(project, &report);
+    report
+}
+
+/// The likely misconfigurations of `project`, most fundamental first
+fn problems(project: &Project, report: &DoctorReport) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if !report.git_available {
+        problems.push(
+            "git is not available (the `git` command is not on PATH): changed-file linting reports nothing"
+                .to_string(),
+        );
+    } else if !report.git_repository {
+        problems.push(format!(
+            "{} is not in a git repository: changed-file linting reports nothing",
+            project.root.display()
+        ));
+    }
+
+    let indexed: usize = report.test_files.values().sum();
+    if report.test_directories_found.is_empty() {
+        problems.push(format!(
+            "None of the test directories ({}) exist: every function is reported as untested",
+            project.test_directories.join(", ")
+        ));
+    } else if indexed == 0 {
+        problems.push(format!(
+            "The test directories ({}) contain no test functions: every function is reported as untested",
+            report.test_directories_found.join(", ")
+        ));
+    } else if project.test_files.is_empty() {
+        problems.push(format!(
+            "No file in the test directories matches test_patterns ({}): the test file rules check nothing",
+            project.test_patterns.join(", ")
+        ));
+    }
+
+    if project.source_files.is_empty() {
+        problems.push(
+            "No source files found: check exclude_patterns, and that no test directory covers the sources"
+                .to_string(),
+        );
+    }
+
+    for root in project.configured_source_roots.unwrap_or_default() {
+        if !project.root.join(root).is_dir() {
+            problems.push(format!(
+                "source_roots entry '{}' does not exist: module paths (and expected test locations) are computed from the project root",
+                root
+            ));
+        }
+    }
+
+    // Test files outside the test directories are linted as source code
+    let test_file_regexes: Vec<_> = project
+        .test_patterns
+        .iter()
+        .filter_map(|pattern| glob_to_file_name_regex(pattern))
+        .collect();
+    let stray: Vec<&PathBuf> = project
+        .source_files
+        .iter()
+        .filter(|file| {
+            file.file_name()
+                .map(|name| name.to_string_lossy())
+                .is_some_and(|name| test_file_regexes.iter().any(|re| re.is_match(&name)))
+        })
+        .collect();
+    if let Some(first) = stray.first() {
+        problems.push(format!(
+            "{} file(s) matching test_patterns lie outside the test directories and are linted as source code, e.g. {}",
+            stray.len(),
+            first.strip_prefix(project.root).unwrap_or(first).display()
+        ));
+    }
+
+    if project.rules.is_empty() {
+        problems.push(
+            "No per-function rule (PL001–PL003, PL022) is enabled: no function is checked for tests"
+                .to_string(),
+        );
+    }
+
+    problems
+}
+
+fn list_or_none(items: &[String]) -> String {
+    if items.is_empty() {
+        "none".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_diagnose() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("tests/unit")).unwrap();
+        fs::write(
+            root.join("tests/unit/check_core.py"),
+            "def test_a():\n    pass\n",
+        )
+        .unwrap();
+        let test_cache =
+            TestCache::build_from_directories(root, &["tests".to_string()], false, None);
+        let source_files = vec![root.join("core.py"), root.join("test_scratch.py")];
+        let test_directories = ["test".to_string(), "tests".to_string()];
+        let test_patterns = ["test_*.py".to_string()];
+        let project = Project {
+            root,
+            test_directories: &test_directories,
+            test_patterns: &test_patterns,
+            configured_source_roots: Some(&["lib".to_string()]),
+            source_files: &source_files,
+            test_files: &[],
+            test_cache: &test_cache,
+            rules: &["PL001"],
+        };
+
+        let report = diagnose(
+            &project,
+            vec![("profile".to_string(), "standard".to_string())],
+        );
+        assert_eq!(report.test_directories_found, vec!["tests".to_string()]);
+        assert_eq!(report.test_directories_missing, vec!["test".to_string()]);
+        assert_eq!(report.test_files.get("unit"), Some(&1));
+        let problems = report.problems.join("\n");
+        assert!(problems.contains("matches test_patterns"));
+        assert!(problems.contains("source_roots entry 'lib'"));
+        assert!(problems.contains("1 file(s) matching test_patterns"));
+        assert!(!problems.contains("untested"));
+        assert!(report.__str__().contains("  profile = standard\n"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Raising the severity of the most neglected code
+//!
+//! A file with more than `escalate_file_violations` violations, and a function missing
+//! all three test types (`escalate_untested_functions`), have their violations raised one
+//! severity level (warning to error, error to critical) and listed first, so they stand
+//! out in the output and fail CI gates that only look at errors.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::call_graph::CRITICAL_SEVERITY;
+use crate::models::LintViolation;
+
+/// Rules whose violations together mean a function has no test of any type
+const MISSING_TEST_RULES: &[&str] = &["PL001", "PL002", "PL003"];
+
+#[derive(Debug, Clone, Default)]
+pub struct Escalation {
+    /// Violation count above which every violation of a file is escalated
+    pub file_violations: Option<usize>,
+    /// Escalate the violations of functions with neither unit, integration nor e2e tests
+    pub untested_functions: bool,
+}
+
+impl Escalation {
+    pub fn is_enabled(&self) -> bool {
+        self.file_violations.is_some() || self.untested_functions
+    }
+
+    /// Escalate violations and move the escalated ones to the front, keeping the order
+    /// within each group; the violations of a file must all be present
+    pub fn apply(&self, mut violations: Vec<LintViolation>) -> Vec<LintViolation> {
+        if !self.is_enabled() {
+            return violations;
+        }
+
+        let mut per_file: HashMap<&str, usize> = HashMap::new();
+        let mut missing: HashMap<(&str, usize), HashSet<&str>> = HashMap::new();
+        for violation in violations.iter() {
+            *per_file.entry(&violation.file_path).or_default() += 1;
+            let rule_id = violation.rule_id();
+            if MISSING_TEST_RULES.contains(&rule_id) {
+                missing
+                    .entry((&violation.file_path, violation.line_number))
+                    .or_default()
+                    .insert(rule_id);
+            }
+        }
+        let crowded: HashSet<Arc<str>> = per_file
+            .into_iter()
+            .filter(|(_, count)| self.file_violations.is_some_and(|limit| *count > limit))
+            .map(|(file, _)| file.into())
+            .collect();
+        let untested: HashSet<(Arc<str>, usize)> = missing
+            .into_iter()
+            .filter(|(_, rules)| self.untested_functions && rules.len() == MISSING_TEST_RULES.len())
+            .map(|((file, line), _)| (file.into(), line))
+            .collect();
+
+        let mut escalated = vec![false; violations.len()];
+        for (violation, escalated) in violations.iter_mut().zip(&mut escalated) {
+            let is_untested = MISSING_TEST_RULES.contains(&violation.rule_id())
+                && untested.contains(&(violation.file_path.clone(), violation.line_number));
+            if crowded.contains(&violation.file_path) || is_untested {
+                violation.severity = raise(&violation.severity).to_string();
+                *escalated = true;
+            }
+        }
+        let (first, rest): (Vec<_>, Vec<_>) = violations
+            .into_iter()
+            .zip(escalated)
+            .partition(|(_, escalated)| *escalated);
+        first
+            .into_iter()
+            .chain(rest)
+            .map(|(violation, _)| violation)
+            .collect()
+    }
+}
+
+/// The severity one level above `severity`
+fn raise(severity: &str) -> &str {
+    match severity {
+        "warning" => "error",
+        _ => CRITICAL_SEVERITY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let violation = |rule: &str, file: &str, line: usize, severity: &str| LintViolation {
+            rule_name: rule.to_string(),
+            file_path: file.into(),
+            line_number: line,
+            severity: severity.to_string(),
+            ..Default::default()
+        };
+        let violations = vec![
+            violation("PL001:require-unit-test", "a.py", 1, "error"),
+            violation("PL001:require-unit-test", "b.py", 1, "error"),
+            violation("PL002:require-integration-test", "b.py", 1, "warning"),
+            violation("PL003:require-e2e-test", "b.py", 1, "error"),
+            violation("PL001:require-unit-test", "b.py", 9, "error"),
+            violation("PL001:require-unit-test", "c.py", 1, "warning"),
+            violation("PL002:require-integration-test", "c.py", 5, "warning"),
+        ];
+
+        let untested = Escalation {
+            untested_functions: true,
+            ..Default::default()
+        }
+        .apply(violations.clone());
+        let summary: Vec<(&str, usize, &str)> = untested
+            .iter()
+            .map(|v| (&*v.file_path, v.line_number, v.severity.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("b.py", 1, "critical"),
+                ("b.py", 1, "error"),
+                ("b.py", 1, "critical"),
+                ("a.py", 1, "error"),
+                ("b.py", 9, "error"),
+                ("c.py", 1, "warning"),
+                ("c.py", 5, "warning"),
+            ]
+        );
+
+        let crowded = Escalation {
+            file_violations: Some(1),
+            ..Default::default()
+        }
+        .apply(violations.clone());
+        assert_eq!(crowded[0].file_path.as_ref(), "b.py");
+        assert_eq!(crowded[4].severity, "error");
+        assert_eq!(crowded[5].severity, "error");
+        assert_eq!(crowded[6].file_path.as_ref(), "a.py");
+        assert_eq!(crowded[6].severity, "error");
+
+        let untouched = Escalation::default().apply(violations);
+        assert_eq!(untouched[2].severity, "warning");
+    }
+}
@@ -0,0 +1,98 @@
+//! Example and documentation code, which is not held to the testing rules by default
+//!
+//! A file is example code when it lies in one of the `example_directories` (matched like
+//! `test_directories`: `examples`, `docs` and `samples` by default). Depending on
+//! `example_mode`, such files are skipped, checked with violations reported as warnings,
+//! or checked like any other code.
+
+use std::path::Path;
+
+use crate::file_discovery::TestDirectories;
+
+/// How example code is treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleMode {
+    /// Not linted at all
+    Skip,
+    /// Checked, with violations reported as warnings
+    Warn,
+    /// Checked like any other code
+    Full,
+}
+
+impl ExampleMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "skip" => Ok(Self::Skip),
+            "warn" => Ok(Self::Warn),
+            "full" => Ok(Self::Full),
+            other => Err(format!(
+                "example_mode must be 'skip', 'warn' or 'full', got '{}'",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Warn => "warn",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Example code detection for a lint run
+#[derive(Debug, Clone)]
+pub struct Examples {
+    /// The `example_directories` setting
+    pub directories: Vec<String>,
+    matcher: TestDirectories,
+    pub mode: ExampleMode,
+}
+
+impl Examples {
+    pub fn new(directories: &[String], mode: ExampleMode) -> Self {
+        Self {
+            directories: directories.to_vec(),
+            matcher: TestDirectories::new(directories),
+            mode,
+        }
+    }
+
+    /// How the file at `relative_path` (relative to the project root) is treated: `Full`
+    /// for anything but example code
+    pub fn mode_for(&self, relative_path: &Path) -> ExampleMode {
+        if self.matcher.contains(relative_path) {
+            self.mode
+        } else {
+            ExampleMode::Full
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_for() {
+        let examples = Examples::new(
+            &["examples".to_string(), "docs".to_string()],
+            ExampleMode::Warn,
+        );
+        assert_eq!(
+            examples.mode_for(Path::new("examples/quickstart.py")),
+            ExampleMode::Warn
+        );
+        assert_eq!(
+            examples.mode_for(Path::new("docs/source/conf.py")),
+            ExampleMode::Warn
+        );
+        assert_eq!(
+            examples.mode_for(Path::new("src/pkg/examples.py")),
+            ExampleMode::Full
+        );
+        assert!(ExampleMode::parse("downgrade").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Exit codes of a run by its outcome (`exit_codes`)
+//!
+//! CI pipelines tell a run that found violations apart from one that could not run at all
+//! by its exit code, and some only want to fail the build on errors, not warnings. The
+//! setting maps each outcome to the code a run exits with; `RustLinter.exit_code` and
+//! `LintRun.exit_code` compute it, so the Python CLI and any other wrapper exit alike:
+//!
+//! ```toml
+//! [tool.proboscis.exit_codes]
+//! warnings = 0        # only warnings: pass the build
+//! internal_error = 70
+//! ```
+//!
+//! The outcomes, with their default codes, are `success` (0), `warnings` (1: every
+//! violation is a warning), `violations` (1: at least one error), `config_error` (2: the
+//! configuration was rejected) and `internal_error` (3: the run failed). The records of
+//! satisfied rules (severity "info") do not count as violations.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    Warnings,
+    Violations,
+    ConfigError,
+    InternalError,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 5] = [
+        Outcome::Success,
+        Outcome::Warnings,
+        Outcome::Violations,
+        Outcome::ConfigError,
+        Outcome::InternalError,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Warnings => "warnings",
+            Outcome::Violations => "violations",
+            Outcome::ConfigError => "config_error",
+            Outcome::InternalError => "internal_error",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|outcome| outcome.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "unknown outcome '{}', expected success, warnings, violations, config_error or internal_error",
+                    name
+                )
+            })
+    }
+
+    fn default_code(self) -> i32 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::Warnings | Outcome::Violations => 1,
+            Outcome::ConfigError => 2,
+            Outcome::InternalError => 3,
+        }
+    }
+
+    /// Outcome of a run reporting violations of the given `severities`
+    pub fn of_severities<'a>(severities: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut outcome = Outcome::Success;
+        for severity in severities {
+            match severity {
+                "error" | "critical" => return Outcome::Violations,
+                "info" => {}
+                _ => outcome = Outcome::Warnings,
+            }
+        }
+        outcome
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitCodes {
+    /// Code of each outcome, in the order of `Outcome::ALL`
+    codes: [i32; 5],
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        Self {
+            codes: Outcome::ALL.map(Outcome::default_code),
+        }
+    }
+}
+
+impl ExitCodes {
+    /// The default codes, with those of the outcomes in `overrides` replaced
+    pub fn new(overrides: HashMap<String, i32>) -> Result<Self, String> {
+        let mut exit_codes = Self::default();
+        for (name, code) in overrides {
+            let outcome = Outcome::parse(&name)?;
+            if !(0..=255).contains(&code) {
+                return Err(format!(
+                    "invalid exit code {} for '{}', expected 0 to 255",
+                    code, name
+                ));
+            }
+            exit_codes.codes[outcome as usize] = code;
+        }
+        Ok(exit_codes)
+    }
+
+    pub fn of(&self, outcome: Outcome) -> i32 {
+        self.codes[outcome as usize]
+    }
+
+    /// The codes by outcome name, as `doctor` reports them
+    pub fn describe(&self) -> String {
+        Outcome::ALL
+            .iter()
+            .map(|outcome| format!("{}={}", outcome.name(), self.of(*outcome)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(Outcome::of_severities([]), Outcome::Success);
+        assert_eq!(Outcome::of_severities(["info"]), Outcome::Success);
+        assert_eq!(
+            Outcome::of_severities(["warning", "info"]),
+            Outcome::Warnings
+        );
+        assert_eq!(
+            Outcome::of_severities(["warning", "error"]),
+            Outcome::Violations
+        );
+
+        let exit_codes = ExitCodes::new(HashMap::from([
+            ("warnings".to_string(), 0),
+            ("internal_error".to_string(), 70),
+        ]))
+        .unwrap();
+        assert_eq!(exit_codes.of(Outcome::Warnings), 0);
+        assert_eq!(exit_codes.of(Outcome::Violations), 1);
+        assert_eq!(exit_codes.of(Outcome::InternalError), 70);
+        assert_eq!(
+            ExitCodes::default().describe(),
+            "success=0, warnings=1, violations=1, config_error=2, internal_error=3"
+        );
+        assert!(ExitCodes::new(HashMap::from([("failure".to_string(), 1)]))
+            .unwrap_err()
+            .contains("'failure'"));
+        assert!(
+            ExitCodes::new(HashMap::from([("violations".to_string(), 256)]))
+                .unwrap_err()
+                .contains("0 to 255")
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Expiry dates of suppressions (`until=YYYY-MM-DD`)
+//!
+//! A `noqa` comment or allowlist entry carrying `until=2025-12-31` suppresses through that
+//! day; afterwards it stops suppressing and is reported as `PL000:expired-suppression`, so
+//! temporary exemptions do not quietly become permanent. A date that does not parse counts
+//! as expired.
+
+use std::fmt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::models::LintViolation;
+
+pub const RULE_NAME: &str = "PL000:expired-suppression";
+
+/// Marker of the expiry date in suppressions
+pub const UNTIL: &str = "until=";
+
+/// A calendar date, ordered chronologically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Parse a `YYYY-MM-DD` date
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        let date = Self { year, month, day };
+        let valid = (1..=12).contains(&month) && (1..=date.days_in_month()).contains(&day);
+        valid.then_some(date)
+    }
+
+    fn days_in_month(&self) -> u32 {
+        match self.month {
+            2 if self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Today's date in UTC
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() / 86_400) as i64;
+        Self::from_days(days)
+    }
+
+    /// The date `days` days after 1970-01-01 (Howard Hinnant's `civil_from_days`)
+    pub(crate) fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Whether a suppression with the expiry `until` (the text after `until=`) still applies
+/// on `today`
+pub fn is_active(until: Option<&str>, today: Date) -> bool {
+    until.is_none_or(|until| Date::parse(until).is_some_and(|until| until >= today))
+}
+
+/// The `PL000:expired-suppression` violation of a suppression of `rules` that expired
+/// (`until`), at `line_number` of `file_path`
+pub fn violation(file_path: &Path, line_number: usize, rules: &str, until: &str) -> LintViolation {
+    let reason = match Date::parse(until) {
+        Some(date) => format!("expired on {}", date),
+        None => format!(
+            "has an invalid expiry date '{}' (expected YYYY-MM-DD)",
+            until
+        ),
+    };
+    LintViolation {
+        rule_name: RULE_NAME.to_string(),
+        file_path: file_path.to_string_lossy().into(),
+        line_number,
+        message: format!(
+            "[PL000] Suppression of {} {} and no longer applies.\nLocation: {}:{}\n\nTip: Fix the underlying violations, or extend the date if the exemption is still justified",
+            rules,
+            reason,
+            file_path.display(),
+            line_number
+        ),
+        severity: "warning".to_string(),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(Date::from_days(19_723).to_string(), "2024-01-01");
+        assert_eq!(Date::from_days(19_782).to_string(), "2024-02-29");
+        assert_eq!(Date::parse("2024-02-29").unwrap().to_string(), "2024-02-29");
+        assert!(Date::parse("2023-02-29").is_none());
+        assert!(Date::parse("2025-13-01").is_none());
+        assert!(Date::parse("31/12/2025").is_none());
+
+        let today = Date::parse("2025-06-30").unwrap();
+        assert!(is_active(None, today));
+        assert!(is_active(Some("2025-06-30"), today));
+        assert!(!is_active(Some("2025-06-29"), today));
+        assert!(!is_active(Some("someday"), today));
+    }
+}
@@ -1,7 +1,11 @@
+use rayon::prelude::*;
 use regex::Regex;
-use std::path::{Path, PathBuf};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 use walkdir::WalkDir;
 
+use crate::models::LintViolation;
+
 /// Convert glob pattern to regex
 pub fn glob_to_regex(pattern: &str) -> Option<Regex> {
     let mut regex_pattern = String::new();
@@ -41,61 +45,513 @@ pub fn glob_to_regex(pattern: &str) -> Option<Regex> {
     Regex::new(&regex_pattern).ok()
 }
 
-/// Find all Python files in a directory, excluding test and virtual environment directories
-pub fn find_python_files(root: &Path, exclude_patterns: &[String]) -> Vec<PathBuf> {
+/// Convert a file-name glob (e.g. `test_*.py`) to a regex matching whole file names
+pub fn glob_to_file_name_regex(pattern: &str) -> Option<Regex> {
+    let inner = glob_to_regex(pattern)?;
+    Regex::new(&format!("^{}$", inner.as_str())).ok()
+}
+
+/// Convert a dotted module pattern (e.g. `pkg.internal.*`, `*.migrations.*`) to a regex
+///
+/// `*` matches any run of characters including dots, so `pkg.internal.*` covers every
+/// submodule at any depth; `?` matches a single character.
+pub fn module_pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let mut regex_pattern = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex_pattern.push_str(".*"),
+            '?' => regex_pattern.push('.'),
+            _ => regex_pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex_pattern.push('$');
+    Regex::new(&regex_pattern).ok()
+}
+
+/// Test directories as configured by `test_directories`
+///
+/// A bare name (`tests`) matches a directory of that name at any depth, so
+/// `pkg/sub/tests/helpers.py` is test code. An entry with a path (`python/tests`, or
+/// `./tests` for the top level only) is an explicit root relative to the project root.
+#[derive(Debug, Clone, Default)]
+pub struct TestDirectories {
+    names: Vec<String>,
+    roots: Vec<PathBuf>,
+}
+
+impl TestDirectories {
+    pub fn new(test_directories: &[String]) -> Self {
+        let mut directories = Self::default();
+        for entry in test_directories {
+            let path: PathBuf = Path::new(entry)
+                .components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect();
+            let is_bare_name = path.components().count() == 1 && !entry.starts_with("./");
+            if is_bare_name {
+                directories.names.push(entry.clone());
+            } else if path.components().next().is_some() {
+                directories.roots.push(path);
+            }
+        }
+        directories
+    }
+
+    /// Whether `relative_path` (relative to the project root) lies in a test directory
+    pub fn contains(&self, relative_path: &Path) -> bool {
+        if self
+            .roots
+            .iter()
+            .any(|root| relative_path.starts_with(root))
+        {
+            return true;
+        }
+        let Some(parent) = relative_path.parent() else {
+            return false;
+        };
+        parent.components().any(|c| {
+            c.as_os_str()
+                .to_str()
+                .is_some_and(|s| self.names.iter().any(|name| name == s))
+        })
+    }
+}
+
+/// Whether `dir` is a virtual environment, whatever its name: it has a `pyvenv.cfg` (venv,
+/// virtualenv, uv), keeps a `site-packages` directory where virtualenvs do (`lib/pythonX.Y/`,
+/// or `Lib/` on Windows), or is a `site-packages` directory itself
+pub fn is_virtualenv(dir: &Path) -> bool {
+    if dir.file_name().is_some_and(|name| name == "site-packages")
+        || dir.join("pyvenv.cfg").is_file()
+        || dir.join("Lib/site-packages").is_dir()
+    {
+        return true;
+    }
+    fs::read_dir(dir.join("lib")).is_ok_and(|entries| {
+        entries.filter_map(Result::ok).any(|entry| {
+            entry.file_name().to_string_lossy().starts_with("python")
+                && entry.path().join("site-packages").is_dir()
+        })
+    })
+}
+
+/// Manifests of projects in other languages
+const FOREIGN_MANIFESTS: &[&str] = &[
+    "package.json",
+    "go.mod",
+    "Cargo.toml",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "Gemfile",
+    "composer.json",
+    "mix.exs",
+];
+
+/// Files that mark a directory as Python code, besides `.py` files
+const PYTHON_MARKERS: &[&str] = &["pyproject.toml", "setup.py", "setup.cfg"];
+
+/// Whether `dir` is the root of a project in another language, which is not walked: it has
+/// the manifest of one (`package.json`, `go.mod`, `Cargo.toml`, ...) but no Python file or
+/// Python packaging file of its own, or it is a `node_modules` directory
+///
+/// Only the entries of `dir` itself are looked at, so the check stays cheap however large
+/// the tree below is.
+pub fn is_foreign_project(dir: &Path) -> bool {
+    if dir.file_name().is_some_and(|name| name == "node_modules") {
+        return true;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let mut foreign = false;
+    for entry in entries.filter_map(Result::ok) {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.ends_with(".py") || PYTHON_MARKERS.contains(&name.as_ref()) {
+            return false;
+        }
+        foreign |= FOREIGN_MANIFESTS.contains(&name.as_ref());
+    }
+    foreign
+}
+
+pub const LIMIT_RULE_NAME: &str = "PL000:discovery-limit";
+
+/// Default of `max_depth`: directory levels below the project root that are walked
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+/// Default of `max_files`: Python files found before discovery stops
+pub const DEFAULT_MAX_FILES: usize = 100_000;
+
+/// Guards against walking far more than a project, as when run from `$HOME` by mistake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiscoveryLimits {
+    pub max_depth: Option<usize>,
+    pub max_files: Option<usize>,
+}
+
+impl DiscoveryLimits {
+    pub const UNLIMITED: Self = Self {
+        max_depth: None,
+        max_files: None,
+    };
+
+    /// Limits from the `max_depth` and `max_files` options: the defaults when unset, and
+    /// no limit when 0
+    pub fn new(max_depth: Option<usize>, max_files: Option<usize>) -> Self {
+        let limit = |value: Option<usize>, default| match value.unwrap_or(default) {
+            0 => None,
+            limit => Some(limit),
+        };
+        Self {
+            max_depth: limit(max_depth, DEFAULT_MAX_DEPTH),
+            max_files: limit(max_files, DEFAULT_MAX_FILES),
+        }
+    }
+}
+
+/// A discovery guard that tripped, leaving part of the tree out
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LimitReached {
+    /// A directory (the first one met) nested deeper than `max_depth` was not walked
+    Depth(PathBuf),
+    /// Discovery stopped once `max_files` Python files were found
+    Files,
+}
+
+impl LimitReached {
+    /// The warning reported for the walk of `root` that tripped the guard
+    pub fn warning(&self, root: &Path, limits: DiscoveryLimits) -> LintViolation {
+        let (what, tip) = match self {
+            Self::Depth(directory) => (
+                format!(
+                    "skipped directories nested more than {} levels deep (max_depth), starting with {}",
+                    limits.max_depth.unwrap_or_default(),
+                    directory.display()
+                ),
+                "Exclude the deep directories via exclude_patterns, or raise max_depth (0 disables the guard)",
+            ),
+            Self::Files => (
+                format!(
+                    "stopped after {} Python files (max_files); the rest of the tree was not linted",
+                    limits.max_files.unwrap_or_default()
+                ),
+                "Run from the project root, narrow discovery with source_roots or exclude_patterns, or raise max_files (0 disables the guard)",
+            ),
+        };
+        LintViolation {
+            rule_name: LIMIT_RULE_NAME.to_string(),
+            file_path: root.to_string_lossy().into(),
+            line_number: 1,
+            function_name: String::new(),
+            message: format!(
+                "[PL000] Source discovery {}.\nLocation: {}\n\nTip: {}",
+                what,
+                root.display(),
+                tip
+            ),
+            severity: "warning".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The Python files found by a walk, and the guard that cut it short, if any
+#[derive(Debug, Default)]
+pub struct Discovery {
+    pub files: Vec<PathBuf>,
+    pub limit_reached: Option<LimitReached>,
+}
+
+/// Find all Python files in a directory, excluding test directories, virtual environments
+/// and projects in other languages (which are not walked at all)
+pub fn find_python_files(
+    root: &Path,
+    exclude_patterns: &[String],
+    test_directories: &TestDirectories,
+) -> Vec<PathBuf> {
+    find_python_files_in(
+        root,
+        &[root.to_path_buf()],
+        exclude_patterns,
+        test_directories,
+        DiscoveryLimits::UNLIMITED,
+    )
+    .files
+}
+
+/// Find the Python files below `directories` of the project at `root`, like
+/// `find_python_files` but within `limits`; test directories and depth are relative to
+/// `root`, and directories nested in another one are only walked once
+pub fn find_python_files_in(
+    root: &Path,
+    directories: &[PathBuf],
+    exclude_patterns: &[String],
+    test_directories: &TestDirectories,
+    limits: DiscoveryLimits,
+) -> Discovery {
     let exclude_regexes: Vec<Regex> = exclude_patterns
         .iter()
         .filter_map(|p| glob_to_regex(p))
         .collect();
 
-    let files: Vec<PathBuf> = WalkDir::new(root)
-        .into_iter()
-        .filter_map(Result::ok)
-        .filter(|entry| {
+    let mut discovery = Discovery::default();
+    let mut too_deep: Option<PathBuf> = None;
+    let walked = directories.iter().filter(|directory| {
+        !directories
+            .iter()
+            .any(|other| other != *directory && directory.starts_with(other))
+    });
+    for directory in walked {
+        let entries = WalkDir::new(directory).into_iter().filter_entry(|entry| {
+            // Prune __pycache__, hidden entries (.venv, .git, .tox, ...), virtualenvs
+            // under any other name (ENV/, .direnv/python-3.11, ...) and the projects of
+            // other languages in a monorepo
+            if entry.depth() == 0 {
+                return true;
+            }
+            let name = entry.file_name().to_string_lossy();
+            if name == "__pycache__" || name.starts_with('.') {
+                return false;
+            }
+            if !entry.file_type().is_dir() {
+                return true;
+            }
+            // Test directories hold no source files, so their depth does not matter
+            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            if test_directories.contains(&relative.join("_")) {
+                return false;
+            }
+            let depth = relative.components().count();
+            if limits.max_depth.is_some_and(|max_depth| depth > max_depth) {
+                too_deep.get_or_insert_with(|| entry.path().to_path_buf());
+                return false;
+            }
+            !(is_virtualenv(entry.path()) || is_foreign_project(entry.path()))
+        });
+
+        for entry in entries.filter_map(Result::ok) {
             let path = entry.path();
 
             // Skip if it's not a Python file
             if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("py") {
-                return false;
+                continue;
             }
 
-            // Skip __pycache__ and virtual environment directories
-            if path.components().any(|c| {
-                c.as_os_str()
-                    .to_str()
-                    .map(|s| {
-                        s == "__pycache__"
-                            || s == ".venv"
-                            || s == "venv"
-                            || s == "env"
-                            || s == ".env"
-                            || (s.starts_with('.') && s != "." && s != "..")
-                    })
-                    .unwrap_or(false)
-            }) {
-                return false;
-            }
-
-            // Only skip test files if they are in test/tests directories at the root
+            // Skip test code, wherever the test directories are nested
             let relative_path = path.strip_prefix(root).unwrap_or(path);
-            if let Some(first_component) = relative_path.components().next() {
-                if let Some(s) = first_component.as_os_str().to_str() {
-                    if s == "test" || s == "tests" {
-                        return false;
-                    }
-                }
+            if test_directories.contains(relative_path) {
+                continue;
             }
 
             // Check exclude patterns
             let path_str = path.to_str().unwrap_or("");
             if exclude_regexes.iter().any(|re| re.is_match(path_str)) {
-                return false;
+                continue;
             }
 
-            true
-        })
-        .map(|entry| entry.path().to_path_buf())
+            if limits
+                .max_files
+                .is_some_and(|max_files| discovery.files.len() >= max_files)
+            {
+                discovery.limit_reached = Some(LimitReached::Files);
+                return discovery;
+            }
+            discovery.files.push(path.to_path_buf());
+        }
+    }
+    discovery.limit_reached = too_deep.map(LimitReached::Depth);
+    discovery
+}
+
+/// Find the test files (matching `test_patterns`) in every test directory
+pub fn find_test_files(
+    project_root: &Path,
+    test_directories: &[String],
+    test_patterns: &[String],
+    exclude_patterns: &[String],
+) -> Vec<PathBuf> {
+    let test_file_regexes: Vec<Regex> = test_patterns
+        .iter()
+        .filter_map(|p| glob_to_file_name_regex(p))
         .collect();
 
-    files
+    test_directories
+        .par_iter()
+        .flat_map(|test_dir| {
+            let test_path = project_root.join(test_dir);
+            if test_path.exists() {
+                find_python_files(&test_path, exclude_patterns, &TestDirectories::default())
+                    .into_iter()
+                    .filter(|path| {
+                        // Only check files matching the configured test patterns
+                        if let Some(file_name) = path.file_name() {
+                            let name = file_name.to_string_lossy();
+                            test_file_regexes.iter().any(|re| re.is_match(&name))
+                        } else {
+                            false
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_pattern_to_regex() {
+        let internal = module_pattern_to_regex("pkg.internal.*").unwrap();
+        assert!(internal.is_match("pkg.internal.helpers"));
+        assert!(internal.is_match("pkg.internal.deep.helpers"));
+        assert!(!internal.is_match("pkg.internal"));
+        assert!(!internal.is_match("pkg.internalx"));
+
+        let migrations = module_pattern_to_regex("*.migrations.*").unwrap();
+        assert!(migrations.is_match("app.migrations.0001_initial"));
+        assert!(!migrations.is_match("app.models"));
+    }
+
+    #[test]
+    fn test_test_directories() {
+        let dirs = TestDirectories::new(&[
+            "tests".to_string(),
+            "python/spec".to_string(),
+            "./e2e".to_string(),
+        ]);
+        assert!(dirs.contains(Path::new("tests/test_a.py")));
+        assert!(dirs.contains(Path::new("pkg/subpkg/tests/helpers.py")));
+        assert!(dirs.contains(Path::new("python/spec/test_b.py")));
+        assert!(dirs.contains(Path::new("e2e/test_c.py")));
+        assert!(!dirs.contains(Path::new("pkg/e2e/flows.py")));
+        assert!(!dirs.contains(Path::new("pkg/spec/models.py")));
+        assert!(!dirs.contains(Path::new("pkg/tests.py")));
+        assert!(!dirs.contains(Path::new("pkg/testsuite/a.py")));
+    }
+
+    #[test]
+    fn test_virtualenvs_are_pruned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let write = |relative: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        };
+        write("ENV/pyvenv.cfg");
+        write("ENV/lib/python3.11/site-packages/dep.py");
+        write(".direnv/python-3.11/bin/tool.py");
+        write("legacy/lib/python2.7/site-packages/old.py");
+        write("legacy/bin/activate_this.py");
+        write("win/Lib/site-packages/dep.py");
+        write("win/Scripts/tool.py");
+        // A package that merely happens to be called `env`
+        write("env/settings.py");
+        write("pkg/lib/helpers.py");
+
+        let mut files: Vec<String> = find_python_files(root, &[], &TestDirectories::default())
+            .iter()
+            .map(|file| {
+                file.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["env/settings.py", "pkg/lib/helpers.py"]);
+        assert!(is_virtualenv(&root.join("ENV")));
+        assert!(!is_virtualenv(&root.join("pkg")));
+    }
+
+    #[test]
+    fn test_foreign_projects_are_pruned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let write = |relative: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        };
+        write("web/package.json");
+        write("web/scripts/build.py");
+        write("api/go.mod");
+        write("bindings/Cargo.toml");
+        write("bindings/pyproject.toml");
+        write("bindings/python/pkg/core.py");
+        write("tools/node_modules/dep/gyp.py");
+        write("tools/release.py");
+        write("services/py/src/pkg/app.py");
+
+        let relative = |files: Vec<PathBuf>| {
+            let mut files: Vec<String> = files
+                .iter()
+                .map(|file| {
+                    file.strip_prefix(root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            files.sort();
+            files
+        };
+        assert_eq!(
+            relative(find_python_files(root, &[], &TestDirectories::default())),
+            vec![
+                "bindings/python/pkg/core.py",
+                "services/py/src/pkg/app.py",
+                "tools/release.py"
+            ]
+        );
+        assert_eq!(
+            relative(
+                find_python_files_in(
+                    root,
+                    &[
+                        root.join("services/py/src"),
+                        root.join("services/py/src/pkg")
+                    ],
+                    &[],
+                    &TestDirectories::default(),
+                    DiscoveryLimits::UNLIMITED,
+                )
+                .files
+            ),
+            vec!["services/py/src/pkg/app.py"]
+        );
+        let limited = |max_depth, max_files| {
+            find_python_files_in(
+                root,
+                &[root.to_path_buf()],
+                &[],
+                &TestDirectories::default(),
+                DiscoveryLimits::new(max_depth, max_files),
+            )
+        };
+        let shallow = limited(Some(2), Some(0));
+        assert_eq!(relative(shallow.files), vec!["tools/release.py"]);
+        assert!(matches!(
+            shallow.limit_reached,
+            Some(LimitReached::Depth(directory))
+                if directory.strip_prefix(root).unwrap().components().count() == 3
+        ));
+        let few = limited(None, Some(2));
+        assert_eq!(few.files.len(), 2);
+        assert_eq!(few.limit_reached, Some(LimitReached::Files));
+        assert!(few
+            .limit_reached
+            .unwrap()
+            .warning(root, DiscoveryLimits::new(None, Some(2)))
+            .message
+            .contains("stopped after 2 Python files"));
+        assert_eq!(limited(None, None).limit_reached, None);
+        assert!(is_foreign_project(&root.join("api")));
+        assert!(!is_foreign_project(&root.join("bindings")));
+    }
 }
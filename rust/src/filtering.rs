@@ -0,0 +1,115 @@
+//! Slicing result sets (`filter_violations`)
+//!
+//! UIs narrowing tens of thousands of violations by rule, path, severity or function do it
+//! here rather than in Python loops. Each criterion left out (None) matches everything;
+//! the criteria given must all match.
+
+use regex::Regex;
+use std::collections::HashSet;
+
+use crate::file_discovery::glob_to_regex;
+use crate::models::LintViolation;
+
+#[derive(Debug, Default)]
+pub struct ViolationFilter {
+    /// Rule ids (`PL001`) or full rule names (`PL001:require-unit-test`)
+    rules: Option<HashSet<String>>,
+    /// Path globs, matched against the file path like `exclude_patterns`
+    paths: Option<Vec<Regex>>,
+    severities: Option<HashSet<String>>,
+    /// Function names, or `Class.method` for methods
+    functions: Option<HashSet<String>>,
+}
+
+impl ViolationFilter {
+    pub fn new(
+        rules: Option<Vec<String>>,
+        paths: Option<Vec<String>>,
+        severities: Option<Vec<String>>,
+        functions: Option<Vec<String>>,
+    ) -> Result<Self, String> {
+        let paths = paths
+            .map(|paths| {
+                paths
+                    .iter()
+                    .map(|path| {
+                        glob_to_regex(path)
+                            .ok_or_else(|| format!("invalid path pattern '{}'", path))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        Ok(Self {
+            rules: rules.map(|rules| rules.into_iter().collect()),
+            paths,
+            severities: severities.map(|severities| severities.into_iter().collect()),
+            functions: functions.map(|functions| functions.into_iter().collect()),
+        })
+    }
+
+    pub fn matches(&self, violation: &LintViolation) -> bool {
+        self.rules.as_ref().is_none_or(|rules| {
+            rules.contains(violation.rule_id()) || rules.contains(&violation.rule_name)
+        }) && self
+            .paths
+            .as_ref()
+            .is_none_or(|paths| paths.iter().any(|re| re.is_match(&violation.file_path)))
+            && self
+                .severities
+                .as_ref()
+                .is_none_or(|severities| severities.contains(&violation.severity))
+            && self.functions.as_ref().is_none_or(|functions| {
+                functions.contains(&violation.function_name)
+                    || violation.class_name.as_ref().is_some_and(|class| {
+                        functions.contains(&format!("{}.{}", class, violation.function_name))
+                    })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let violation = |rule_name: &str, file_path: &str, severity: &str| LintViolation {
+            rule_name: rule_name.to_string(),
+            file_path: file_path.into(),
+            severity: severity.to_string(),
+            function_name: "get".to_string(),
+            class_name: Some("Cache".to_string()),
+            ..Default::default()
+        };
+        let unit = violation("PL001:require-unit-test", "/repo/src/pkg/cache.py", "error");
+        let e2e = violation("PL003:require-e2e-test", "/repo/src/app/cli.py", "warning");
+
+        assert!(ViolationFilter::default().matches(&unit));
+        let by_rule =
+            ViolationFilter::new(Some(vec!["PL001".to_string()]), None, None, None).unwrap();
+        assert!(by_rule.matches(&unit) && !by_rule.matches(&e2e));
+        let by_name = ViolationFilter::new(
+            Some(vec!["PL003:require-e2e-test".to_string()]),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(by_name.matches(&e2e) && !by_name.matches(&unit));
+
+        let by_path = ViolationFilter::new(
+            None,
+            Some(vec!["src/pkg/**".to_string()]),
+            Some(vec!["error".to_string()]),
+            None,
+        )
+        .unwrap();
+        assert!(by_path.matches(&unit) && !by_path.matches(&e2e));
+
+        let by_function =
+            ViolationFilter::new(None, None, None, Some(vec!["Cache.get".to_string()])).unwrap();
+        assert!(by_function.matches(&unit));
+        let nothing = ViolationFilter::new(None, None, None, Some(vec![])).unwrap();
+        assert!(!nothing.matches(&unit));
+    }
+}
@@ -0,0 +1,677 @@
+//! Applying the fixes attached to violations (`fix_type` / `fix_content` / `fix_line`)
+//!
+//! Supported fix types:
+//! - `add_decorator`: insert `fix_content` above the function's decorator block
+//! - `add_todo_comment`: insert a `# TODO(proboscis): ...` comment above the function,
+//!   unless the same comment is already there
+//! - `add_docstring`: insert `fix_content` as the first line of the body, at `fix_line`,
+//!   unless the body already starts with it
+//! - `create_test_skeleton`: create the missing test directories and `__init__.py` files
+//!   listed in `fix_content` (see `skeleton`)
+//! - `add_test_stub`: add the test stub in `fix_content` to the violation's
+//!   `expected_test_file`, unless the test is already there (see `test_stubs`)
+//!
+//! Like ruff, every fix is classified as safe or unsafe. Safe fixes only insert markers or
+//! add/remove comments and never change behaviour; unsafe fixes (creating files, moving
+//! code) may need review, so they are only applied when explicitly requested.
+//!
+//! `apply_fixes` returns a `FixReport` of the fixes applied and skipped, with a unified diff
+//! of each file it edits. Violations carrying a fix record the hash of the file they were
+//! linted in (`content_hash`); when the file has changed since, each fix is re-anchored to
+//! the function of its violation, found by name, and skipped rather than applied at the
+//! wrong place when the function is gone or defined more than once.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use pyo3::prelude::*;
+
+use crate::models::LintViolation;
+use crate::patterns::PATTERNS;
+use crate::source_index::SourceFile;
+
+/// Prefix of the comments inserted by the `add_todo_comment` fix
+pub const TODO_MARKER: &str = "# TODO(proboscis):";
+
+/// Whether a fix can be applied without review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FixSafety {
+    Safe,
+    Unsafe,
+}
+
+impl FixSafety {
+    /// Classify a fix type; unknown fix types are treated as unsafe
+    pub fn of(fix_type: &str) -> Self {
+        match fix_type {
+            // Creating `__init__.py` files (`create_test_skeleton`) changes how pytest
+            // resolves and collects test packages, so it is unsafe
+            "add_decorator" | "add_docstring" | "add_todo_comment" => FixSafety::Safe,
+            _ => FixSafety::Unsafe,
+        }
+    }
+
+    /// Parse the `safety` level accepted by `apply_fixes`
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "safe" => Some(FixSafety::Safe),
+            "unsafe" => Some(FixSafety::Unsafe),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FixSafety::Safe => "safe",
+            FixSafety::Unsafe => "unsafe",
+        }
+    }
+}
+
+/// Record the safety of each violation's fix in `fix_safety`
+pub fn classify(violations: &mut [LintViolation]) {
+    for violation in violations.iter_mut() {
+        violation.fix_safety = violation
+            .fix_type
+            .as_deref()
+            .map(|fix_type| FixSafety::of(fix_type).as_str().to_string());
+    }
+}
+
+/// A fix `apply_fixes` left unapplied, and why
+#[pyclass]
+#[derive(Clone)]
+pub struct SkippedFix {
+    /// The violation carrying the fix
+    #[pyo3(get)]
+    pub violation: LintViolation,
+    /// Why the fix was skipped: "already_applied", "conflict" (another fix of the function
+    /// takes its place), "file_changed" (the file changed since it was linted and the
+    /// function cannot be found again), "encoding" (the file is not UTF-8), "unsafe" (above
+    /// the requested safety) or "unsupported" (unknown fix type)
+    #[pyo3(get)]
+    pub reason: &'static str,
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl SkippedFix {
+    fn __repr__(&self) -> String {
+        format!(
+            "SkippedFix({}:{} {}, reason='{}')",
+            self.violation.file_path,
+            self.violation.line_number,
+            self.violation.rule_name,
+            self.reason
+        )
+    }
+}
+
+/// What `apply_fixes` did, per file
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct FixReport {
+    /// Number of fixes applied per file
+    #[pyo3(get)]
+    pub applied: HashMap<String, usize>,
+    #[pyo3(get)]
+    pub skipped: Vec<SkippedFix>,
+    /// Unified diff of each file edited in place; the empty files and directories created
+    /// by `create_test_skeleton` have none
+    #[pyo3(get)]
+    pub diffs: HashMap<String, String>,
+}
+
+impl FixReport {
+    fn skip(&mut self, violation: &LintViolation, reason: &'static str, detail: impl Into<String>) {
+        self.skipped.push(SkippedFix {
+            violation: violation.clone(),
+            reason,
+            detail: detail.into(),
+        });
+    }
+
+    fn record(&mut self, file: &str, count: usize) {
+        if count > 0 {
+            *self.applied.entry(file.to_string()).or_default() += count;
+        }
+    }
+
+    /// Record the changes made to `file`, whose content was `old` (None if it was created)
+    fn record_diff(&mut self, file: &str, old: Option<&str>, new: &str) {
+        let diff = crate::unified_diff::unified(file, old, new);
+        if !diff.is_empty() {
+            self.diffs
+                .entry(file.to_string())
+                .or_default()
+                .push_str(&diff);
+        }
+    }
+}
+
+#[pymethods]
+impl FixReport {
+    /// Files the fixes changed or created, sorted
+    #[getter]
+    fn files_modified(&self) -> Vec<String> {
+        let mut files: Vec<String> = self.applied.keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    /// Total number of fixes applied
+    #[getter]
+    fn fixes_applied(&self) -> usize {
+        self.applied.values().sum()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FixReport(files_modified={}, fixes_applied={}, skipped={})",
+            self.applied.len(),
+            self.fixes_applied(),
+            self.skipped.len()
+        )
+    }
+}
+
+/// Apply the fixes carried by `violations` up to the given safety level, reporting those
+/// applied and skipped per file
+///
+/// With `FixSafety::Safe` only safe fixes are applied; `FixSafety::Unsafe` applies all.
+pub fn apply_fixes(violations: &[LintViolation], max_safety: FixSafety) -> io::Result<FixReport> {
+    let mut report = FixReport::default();
+    let mut by_file: BTreeMap<&str, Vec<&LintViolation>> = BTreeMap::new();
+    for violation in violations {
+        let (Some(fix_type), Some(fix_content)) = (&violation.fix_type, &violation.fix_content)
+        else {
+            continue;
+        };
+        if FixSafety::of(fix_type) > max_safety {
+            report.skip(violation, "unsafe", "applied with safety=\"unsafe\"");
+            continue;
+        }
+        if fix_type == "create_test_skeleton" {
+            let created = crate::skeleton::create(fix_content)?;
+            if created == 0 {
+                report.skip(violation, "already_applied", "the test skeleton exists");
+            }
+            report.record(&violation.file_path, created);
+            continue;
+        }
+        if fix_type == "add_test_stub" {
+            if let (Some(test_file), Some(test_name)) =
+                (&violation.expected_test_file, &violation.expected_test_name)
+            {
+                add_test_stub(&mut report, violation, test_file, test_name, fix_content)?;
+            }
+            continue;
+        }
+        by_file
+            .entry(&*violation.file_path)
+            .or_default()
+            .push(violation);
+    }
+
+    for (file, fixes) in by_file {
+        apply_fixes_to_file(Path::new(file), &fixes, &mut report)?;
+    }
+    Ok(report)
+}
+
+fn add_test_stub(
+    report: &mut FixReport,
+    violation: &LintViolation,
+    test_file: &str,
+    test_name: &str,
+    stub: &str,
+) -> io::Result<()> {
+    let path = Path::new(test_file);
+    let old = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            report.skip(
+                violation,
+                "encoding",
+                format!("{} is not valid UTF-8", test_file),
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    if !crate::test_stubs::add(path, test_name, stub)? {
+        report.skip(
+            violation,
+            "already_applied",
+            format!("{} already has {}", test_file, test_name),
+        );
+        return Ok(());
+    }
+    report.record(test_file, 1);
+    report.record_diff(test_file, old.as_deref(), &fs::read_to_string(path)?);
+    Ok(())
+}
+
+/// Hash of a file's content, as recorded on violations in `content_hash`
+pub fn content_hash(content: &str) -> String {
+    crate::report::stable_hash(&[content])
+}
+
+/// Record the hash of `content`, that of the linted file, on the `violations` carrying a fix
+pub fn record_content_hash(violations: &mut [LintViolation], content: &str) {
+    let mut hash: Option<String> = None;
+    for violation in violations.iter_mut() {
+        if violation.fix_type.is_some() {
+            let hash = hash.get_or_insert_with(|| content_hash(content));
+            violation.content_hash = Some(hash.clone());
+        }
+    }
+}
+
+/// Whether `line` is the `def` line of the function `name`; any line matches an empty name
+fn defines(line: &str, name: &str) -> bool {
+    name.is_empty()
+        || PATTERNS
+            .function_definition(line)
+            .is_some_and(|(_, defined)| defined == name)
+}
+
+/// Line the function of `fix` is defined at in `content`, found by its name (and class,
+/// when the violation has one); `source` caches the parse of `content`
+fn reanchor(
+    fix: &LintViolation,
+    content: &str,
+    source: &mut Option<SourceFile>,
+) -> Result<usize, String> {
+    if fix.function_name.is_empty() {
+        return Err("the file changed since it was linted".to_string());
+    }
+    let source = source.get_or_insert_with(|| SourceFile::parse(content.to_string(), &PATTERNS));
+    let mut matches = source.functions.iter().filter(|function| {
+        function.name == fix.function_name
+            && (fix.class_name.is_none() || function.class_name == fix.class_name)
+    });
+    let function = match &fix.class_name {
+        Some(class) => format!("{}.{}", class, fix.function_name),
+        None => fix.function_name.clone(),
+    };
+    match (matches.next(), matches.next()) {
+        (Some(defined), None) => Ok(defined.line_number),
+        (None, _) => Err(format!(
+            "the file changed since it was linted and no longer defines {}",
+            function
+        )),
+        (Some(_), Some(_)) => Err(format!(
+            "the file changed since it was linted and defines {} more than once",
+            function
+        )),
+    }
+}
+
+fn apply_fixes_to_file(
+    path: &Path,
+    fixes: &[&LintViolation],
+    report: &mut FixReport,
+) -> io::Result<()> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+            for fix in fixes {
+                report.skip(
+                    fix,
+                    "encoding",
+                    format!("{} is not valid UTF-8", path.display()),
+                );
+            }
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+
+    // Group fixes by function, in rule order, so each group is inserted as one block. Fixes
+    // linted against other content, or whose line no longer defines their function, are
+    // moved to where the function is now, with their `fix_line`
+    let hash = content_hash(&content);
+    let mut source: Option<SourceFile> = None;
+    let mut by_line: BTreeMap<usize, Vec<(&LintViolation, Option<usize>)>> = BTreeMap::new();
+    for fix in fixes {
+        let unchanged = fix
+            .content_hash
+            .as_ref()
+            .is_none_or(|linted| *linted == hash);
+        let in_place = lines
+            .get(fix.line_number.wrapping_sub(1))
+            .is_some_and(|line| defines(line, &fix.function_name));
+        if unchanged && in_place {
+            by_line
+                .entry(fix.line_number)
+                .or_default()
+                .push((fix, fix.fix_line));
+            continue;
+        }
+        match reanchor(fix, &content, &mut source) {
+            Ok(line_number) => {
+                let shift = line_number as isize - fix.line_number as isize;
+                let fix_line = fix.fix_line.map(|line| line.saturating_add_signed(shift));
+                by_line
+                    .entry(line_number)
+                    .or_default()
+                    .push((fix, fix_line));
+            }
+            Err(detail) => report.skip(fix, "file_changed", detail),
+        }
+    }
+
+    // Insert bottom-up so earlier line numbers stay valid
+    let mut applied = 0;
+    for (line_number, mut group) in by_line.into_iter().rev() {
+        let def_idx = line_number.saturating_sub(1);
+        let Some(def_line) = lines.get(def_idx) else {
+            continue;
+        };
+        let indent = def_line[..def_line.len() - def_line.trim_start().len()].to_string();
+        let start = decorator_block_start(&lines, def_idx);
+        group.sort_by(|(a, _), (b, _)| a.rule_name.cmp(&b.rule_name));
+
+        // Docstrings go below the `def`, so inserting them first keeps `start` valid
+        let mut docstring: Option<&str> = None;
+        for &(fix, fix_line) in &group {
+            if fix.fix_type.as_deref() != Some("add_docstring") {
+                continue;
+            }
+            let (Some(fix_content), Some(fix_line)) = (&fix.fix_content, fix_line) else {
+                continue;
+            };
+            if let Some(inserted) = docstring {
+                let reason = if inserted == fix_content {
+                    "already_applied"
+                } else {
+                    "conflict"
+                };
+                report.skip(fix, reason, "another fix adds the function's docstring");
+                continue;
+            }
+            let at = fix_line.saturating_sub(1).clamp(def_idx + 1, lines.len());
+            let body = lines[at..].iter().find(|line| !line.trim().is_empty());
+            if body.is_some_and(|line| line.trim() == fix_content) {
+                report.skip(fix, "already_applied", "the function has the docstring");
+                continue;
+            }
+            // Match the body's indentation, or indent one level if it cannot be told
+            let body_indent = body
+                .map(|line| &line[..line.len() - line.trim_start().len()])
+                .filter(|body_indent| body_indent.len() > indent.len())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("{}    ", indent));
+            lines.insert(at, format!("{}{}\n", body_indent, fix_content));
+            docstring = Some(fix_content);
+            applied += 1;
+        }
+
+        let mut block: Vec<String> = Vec::new();
+        for (fix, _) in group {
+            let (Some(fix_type), Some(fix_content)) = (&fix.fix_type, &fix.fix_content) else {
+                continue;
+            };
+            match fix_type.as_str() {
+                "add_docstring" => continue,
+                "add_decorator" => {
+                    if lines[start..def_idx]
+                        .iter()
+                        .any(|line| line.trim() == fix_content)
+                    {
+                        report.skip(fix, "already_applied", "the function has the decorator");
+                        continue;
+                    }
+                }
+                "add_todo_comment" => {
+                    if comment_block_above(&lines, start).any(|line| line.trim() == fix_content) {
+                        report.skip(fix, "already_applied", "the comment is there");
+                        continue;
+                    }
+                }
+                _ => {
+                    report.skip(
+                        fix,
+                        "unsupported",
+                        format!("unknown fix type '{}'", fix_type),
+                    );
+                    continue;
+                }
+            }
+            let line = format!("{}{}\n", indent, fix_content);
+            if block.contains(&line) {
+                report.skip(fix, "already_applied", "another fix inserts the same line");
+                continue;
+            }
+            block.push(line);
+        }
+
+        applied += block.len();
+        lines.splice(start..start, block);
+    }
+
+    if applied > 0 {
+        let new_content = lines.concat();
+        fs::write(path, &new_content)?;
+        let file = path.to_string_lossy();
+        report.record(&file, applied);
+        report.record_diff(&file, Some(&content), &new_content);
+    }
+    Ok(())
+}
+
+/// Index of the first decorator line above the `def` at `def_idx` (or `def_idx` itself)
+fn decorator_block_start(lines: &[String], def_idx: usize) -> usize {
+    let mut start = def_idx;
+    while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+        start -= 1;
+    }
+    start
+}
+
+/// Contiguous comment lines directly above line `idx`, nearest first
+fn comment_block_above(lines: &[String], idx: usize) -> impl Iterator<Item = &String> {
+    lines[..idx]
+        .iter()
+        .rev()
+        .take_while(|line| line.trim_start().starts_with('#'))
+}
+
+/// Remove `# TODO(proboscis):` comments from `path` that are not listed in `keep`
+///
+/// Returns the number of comments removed.
+pub fn remove_stale_todos(path: &Path, keep: &HashSet<String>) -> io::Result<usize> {
+    let content = fs::read_to_string(path)?;
+    let mut removed = 0;
+    let kept: Vec<&str> = content
+        .split_inclusive('\n')
+        .filter(|line| {
+            let trimmed = line.trim();
+            let stale = trimmed.starts_with(TODO_MARKER) && !keep.contains(trimmed);
+            if stale {
+                removed += 1;
+            }
+            !stale
+        })
+        .collect();
+
+    if removed > 0 {
+        fs::write(path, kept.concat())?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(dir: &tempfile::TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn todo_fix(path: &Path, line_number: usize, content: &str) -> LintViolation {
+        LintViolation {
+            file_path: path.to_string_lossy().into(),
+            line_number,
+            fix_type: Some("add_todo_comment".to_string()),
+            fix_content: Some(content.to_string()),
+            fix_line: Some(line_number),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_todo_comment_inserted_above_decorators_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(
+            &dir,
+            "todo.py",
+            "class A:\n    @property\n    def value(self):\n        return 1\n",
+        );
+        let todo = "# TODO(proboscis): add unit test test_A_value in test/unit/test_todo.py";
+        let fix = todo_fix(&path, 3, todo);
+
+        let report = apply_fixes(std::slice::from_ref(&fix), FixSafety::Safe).unwrap();
+        assert_eq!(report.applied[&*fix.file_path], 1);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "class A:\n    {}\n    @property\n    def value(self):\n        return 1\n",
+                todo
+            )
+        );
+
+        // Re-applying against the shifted function is a no-op
+        let report = apply_fixes(&[todo_fix(&path, 4, todo)], FixSafety::Safe).unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(report.skipped[0].reason, "already_applied");
+    }
+
+    #[test]
+    fn test_docstring_inserted_below_signature_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(
+            &dir,
+            "docstring.py",
+            "class TestA:\n    def test_value(\n        self,\n    ):\n        assert A().value\n",
+        );
+        let docstring = "\"\"\"TODO(proboscis): describe the behavior under test.\"\"\"";
+        let fix = LintViolation {
+            fix_type: Some("add_docstring".to_string()),
+            fix_content: Some(docstring.to_string()),
+            fix_line: Some(5),
+            ..todo_fix(&path, 2, "")
+        };
+
+        let report = apply_fixes(std::slice::from_ref(&fix), FixSafety::Safe).unwrap();
+        assert_eq!(report.applied[&*fix.file_path], 1);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "class TestA:\n    def test_value(\n        self,\n    ):\n        {}\n        assert A().value\n",
+                docstring
+            )
+        );
+
+        let report = apply_fixes(std::slice::from_ref(&fix), FixSafety::Safe).unwrap();
+        assert!(report.applied.is_empty());
+    }
+
+    #[test]
+    fn test_fix_report() {
+        let linted = "def load():\n    pass\n\n\ndef save():\n    pass\n";
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir, "report.py", linted);
+        let todo = |line_number, function: &str, content: &str| LintViolation {
+            function_name: function.to_string(),
+            content_hash: Some(content_hash(linted)),
+            ..todo_fix(&path, line_number, content)
+        };
+        let docstring = |content: &str| LintViolation {
+            fix_type: Some("add_docstring".to_string()),
+            fix_line: Some(2),
+            ..todo(1, "load", content)
+        };
+        let fixes = [
+            todo(1, "load", "# TODO(proboscis): test load"),
+            docstring("\"\"\"Load.\"\"\""),
+            docstring("\"\"\"Load it.\"\"\""),
+            todo(5, "save", "# TODO(proboscis): test save"),
+            todo(8, "drop", "# TODO(proboscis): test drop"),
+        ];
+        // Since linting, an import went in above the functions and `drop` went away
+        fs::write(&path, format!("import os\n\n{}", linted)).unwrap();
+
+        let report = apply_fixes(&fixes, FixSafety::Safe).unwrap();
+        let file = path.to_string_lossy().to_string();
+        assert_eq!(report.applied[&file], 3);
+        let reasons: Vec<&str> = report.skipped.iter().map(|skip| skip.reason).collect();
+        assert_eq!(reasons, ["file_changed", "conflict"]);
+        assert!(report.skipped[0].detail.contains("no longer defines drop"));
+        assert_eq!(
+            report.diffs[&file],
+            format!(
+                "--- {0}\n+++ {0}\n@@ -1,8 +1,11 @@\n import os\n \n+# TODO(proboscis): test load\n def load():\n+    \"\"\"Load.\"\"\"\n     pass\n \n \n+# TODO(proboscis): test save\n def save():\n     pass\n",
+                file
+            )
+        );
+
+        let binary = temp_file(&dir, "latin1.py", "");
+        fs::write(&binary, b"def caf\xe9():\n    pass\n").unwrap();
+        let report = apply_fixes(
+            &[todo_fix(&binary, 1, "# TODO(proboscis): x")],
+            FixSafety::Safe,
+        )
+        .unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(report.skipped[0].reason, "encoding");
+    }
+
+    #[test]
+    fn test_unsafe_fixes_require_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(&dir, "unsafe.py", "def a():\n    pass\n");
+        let fix = LintViolation {
+            fix_type: Some("create_test_file".to_string()),
+            ..todo_fix(&path, 1, "# moved")
+        };
+        assert_eq!(FixSafety::of("create_test_file"), FixSafety::Unsafe);
+        assert_eq!(FixSafety::of("create_test_skeleton"), FixSafety::Unsafe);
+        let report = apply_fixes(std::slice::from_ref(&fix), FixSafety::Safe).unwrap();
+        assert!(report.applied.is_empty());
+        assert_eq!(report.skipped[0].reason, "unsafe");
+
+        let mut violations = vec![fix, todo_fix(&path, 1, "# TODO(proboscis): x")];
+        classify(&mut violations);
+        assert_eq!(violations[0].fix_safety.as_deref(), Some("unsafe"));
+        assert_eq!(violations[1].fix_safety.as_deref(), Some("safe"));
+    }
+
+    #[test]
+    fn test_remove_stale_todos() {
+        let keep_line = "# TODO(proboscis): add unit test test_a in test/unit/test_m.py";
+        let dir = tempfile::tempdir().unwrap();
+        let path = temp_file(
+            &dir,
+            "stale.py",
+            &format!(
+                "{}\ndef a():\n    pass\n\n# TODO(proboscis): add unit test test_b in test/unit/test_m.py\ndef b():\n    pass\n",
+                keep_line
+            ),
+        );
+        let keep: HashSet<String> = [keep_line.to_string()].into_iter().collect();
+        assert_eq!(remove_stale_todos(&path, &keep).unwrap(), 1);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\ndef a():\n    pass\n\ndef b():\n    pass\n", keep_line)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! pytest fixtures defined in test code (`@pytest.fixture` functions)
+
+use pyo3::prelude::*;
+use regex::Regex;
+use std::path::Path;
+
+use crate::parser;
+use crate::patterns::PATTERNS;
+
+/// A fixture definition
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// Name tests request the fixture by (the `name=` argument, or the function name)
+    #[pyo3(get)]
+    pub name: String,
+    /// `function` unless the decorator sets `scope=`
+    #[pyo3(get)]
+    pub scope: String,
+    #[pyo3(get)]
+    pub autouse: bool,
+    #[pyo3(get)]
+    pub file_path: String,
+    /// 1-based line of the `def`
+    #[pyo3(get)]
+    pub line_number: usize,
+}
+
+#[pymethods]
+impl Fixture {
+    fn __repr__(&self) -> String {
+        format!(
+            "Fixture(name={:?}, scope={:?}, autouse={}, file_path={:?}, line_number={})",
+            self.name,
+            self.scope,
+            if self.autouse { "True" } else { "False" },
+            self.file_path,
+            self.line_number
+        )
+    }
+}
+
+/// Whether a decorator call (as returned by `parser::decorator_calls`) declares a fixture
+fn is_fixture_decorator(call: &str) -> bool {
+    let name = call.split('(').next().unwrap_or(call);
+    name == "pytest.fixture" || name == "fixture"
+}
+
+/// Every fixture defined in `content`, the source of `file_path`
+pub fn parse_fixtures(file_path: &Path, content: &str) -> Vec<Fixture> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let function_name = PATTERNS.any_def.captures(line)?.get(1)?.as_str();
+            if !parser::decorator_calls(&lines, idx + 1)
+                .iter()
+                .any(|call| is_fixture_decorator(call))
+            {
+                return None;
+            }
+            // `decorator_calls` blanks strings, so the arguments are read from the source
+            let source = parser::decorator_source(&lines, idx + 1).join("\n");
+            let arguments = source.find("fixture").map_or("", |at| &source[at..]);
+            let string_argument = |regex: &Regex| {
+                regex
+                    .captures(arguments)
+                    .and_then(|captures| captures.get(1))
+                    .map(|value| value.as_str().to_string())
+            };
+            Some(Fixture {
+                name: string_argument(&PATTERNS.fixture_name)
+                    .unwrap_or_else(|| function_name.to_string()),
+                scope: string_argument(&PATTERNS.fixture_scope)
+                    .unwrap_or_else(|| "function".to_string()),
+                autouse: PATTERNS.fixture_autouse.is_match(arguments),
+                file_path: file_path.to_string_lossy().to_string(),
+                line_number: idx + 1,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixtures() {
+        let fixtures = parse_fixtures(
+            Path::new("tests/conftest.py"),
+            "import pytest\n\n@pytest.fixture\ndef client():\n    return Client()\n\n@pytest.fixture(\n    scope=\"session\",\n    autouse=True,\n)\ndef db():\n    yield connect()\n\n@fixture(name='user')\ndef user_fixture():\n    return User()\n\ndef helper():\n    pass\n",
+        );
+        let found: Vec<(&str, &str, bool, usize)> = fixtures
+            .iter()
+            .map(|f| (f.name.as_str(), f.scope.as_str(), f.autouse, f.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("client", "function", false, 4),
+                ("db", "session", true, 11),
+                ("user", "function", false, 15),
+            ]
+        );
+        assert_eq!(fixtures[0].file_path, "tests/conftest.py");
+    }
+}
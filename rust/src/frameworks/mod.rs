@@ -0,0 +1,155 @@
+//! Framework rule packs (`framework = "django"`)
+//!
+//! A pack adjusts the settings to a framework's conventions: the modules that are wiring
+//! rather than logic (migrations, settings), where its tests live, and the rule options
+//! that fit it, such as which functions are entry points for PL003. Packs are data, one
+//! TOML file per framework using the keys of `[tool.proboscis]`, built into the extension.
+//!
+//! A pack is layered under the configuration: its exclusions and test locations are added
+//! to the configured ones, and its rule options and `dag_mode` apply where the
+//! configuration sets none.
+
+use std::collections::HashMap;
+
+use crate::rules::options::OptionValue;
+
+/// The packs by framework name, in alphabetical order
+const PACKS: &[(&str, &str)] = &[
+    ("airflow", include_str!("airflow.toml")),
+    ("django", include_str!("django.toml")),
+    ("fastapi", include_str!("fastapi.toml")),
+    ("flask", include_str!("flask.toml")),
+];
+
+/// The settings of a framework pack
+#[derive(Debug, Clone, Default)]
+pub struct Framework {
+    pub exclude_modules: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub test_directories: Vec<String>,
+    pub test_patterns: Vec<String>,
+    pub rule_options: HashMap<String, HashMap<String, OptionValue>>,
+    /// Default of `dag_mode`
+    pub dag_mode: Option<String>,
+}
+
+impl Framework {
+    /// The pack of the framework `name`
+    pub fn load(name: &str) -> Result<Self, String> {
+        let (_, pack) = PACKS
+            .iter()
+            .find(|(framework, _)| *framework == name)
+            .ok_or_else(|| {
+                let names: Vec<String> = PACKS
+                    .iter()
+                    .map(|(framework, _)| format!("'{}'", framework))
+                    .collect();
+                format!(
+                    "framework must be one of {}, got '{}'",
+                    names.join(", "),
+                    name
+                )
+            })?;
+        Self::parse(pack).map_err(|e| format!("framework pack '{}': {}", name, e))
+    }
+
+    fn parse(pack: &str) -> Result<Self, String> {
+        let table: toml::Table = pack.parse().map_err(|e| format!("{}", e))?;
+        let mut framework = Self::default();
+        for (key, value) in table {
+            match key.as_str() {
+                "exclude_modules" => framework.exclude_modules = strings(&key, &value)?,
+                "exclude_patterns" => framework.exclude_patterns = strings(&key, &value)?,
+                "test_directories" => framework.test_directories = strings(&key, &value)?,
+                "test_patterns" => framework.test_patterns = strings(&key, &value)?,
+                "rule_options" => framework.rule_options = rule_options(&value)?,
+                "dag_mode" => {
+                    framework.dag_mode = Some(
+                        value
+                            .as_str()
+                            .ok_or("dag_mode must be a string")?
+                            .to_string(),
+                    )
+                }
+                other => return Err(format!("unsupported setting '{}'", other)),
+            }
+        }
+        Ok(framework)
+    }
+}
+
+fn strings(key: &str, value: &toml::Value) -> Result<Vec<String>, String> {
+    value
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .ok_or_else(|| format!("{} must be a list of strings", key))
+}
+
+fn rule_options(
+    value: &toml::Value,
+) -> Result<HashMap<String, HashMap<String, OptionValue>>, String> {
+    let rules = value
+        .as_table()
+        .ok_or("rule_options must be a table of rule ids")?;
+    rules
+        .iter()
+        .map(|(rule_id, options)| {
+            let options = options
+                .as_table()
+                .ok_or_else(|| format!("rule_options.{} must be a table", rule_id))?
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        toml::Value::Boolean(value) => OptionValue::Bool(*value),
+                        toml::Value::Integer(value) => OptionValue::Int(*value),
+                        toml::Value::Float(value) => OptionValue::Float(*value),
+                        toml::Value::String(value) => OptionValue::Str(value.clone()),
+                        toml::Value::Array(_) => OptionValue::List(strings(key, value)?),
+                        _ => return Err(format!("unsupported value of {}.{}", rule_id, key)),
+                    };
+                    Ok((key.clone(), value))
+                })
+                .collect::<Result<_, String>>()?;
+            Ok((rule_id.clone(), options))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::metadata::validate_rule_ids;
+
+    #[test]
+    fn test_load() {
+        for (name, _) in PACKS {
+            let framework = Framework::load(name).unwrap();
+            validate_rule_ids(framework.rule_options.keys().map(String::as_str)).unwrap();
+        }
+        let django = Framework::load("django").unwrap();
+        assert!(django
+            .exclude_modules
+            .contains(&"*.migrations.*".to_string()));
+        assert_eq!(
+            django.rule_options["PL003"]["entry_points_only"],
+            OptionValue::Bool(true)
+        );
+        assert_eq!(
+            Framework::load("fastapi").unwrap().rule_options["PL027"]["unit_allow"],
+            OptionValue::List(vec!["httpx".to_string()])
+        );
+        assert!(Framework::load("rails")
+            .unwrap_err()
+            .contains("'airflow', 'django', 'fastapi', 'flask'"));
+        assert_eq!(
+            Framework::load("airflow").unwrap().dag_mode.as_deref(),
+            Some("e2e")
+        );
+        assert!(Framework::parse("strict_mode = true").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use super::BlameInfo;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run `git` in `dir` and return its stdout if the command succeeded
+fn run_git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        None
+    }
+}
+
+/// Absolute path of the top-level working tree directory
+fn toplevel(project_root: &Path) -> PathBuf {
+    run_git(project_root, &["rev-parse", "--show-toplevel"])
+        .map(|out| PathBuf::from(out.trim()))
+        .unwrap_or_else(|| project_root.to_path_buf())
+}
+
+pub fn is_available() -> bool {
+    run_git(Path::new("."), &["--version"]).is_some()
+}
+
+pub fn is_git_repository(path: &Path) -> bool {
+    run_git(path, &["rev-parse", "--git-dir"]).is_some()
+}
+
+/// Get files with staged, unstaged or untracked changes, leaving out deleted ones
+pub fn changed_paths(project_root: &Path) -> Vec<PathBuf> {
+    let top = toplevel(project_root);
+    let mut changed_files = Vec::new();
+
+    // Staged files (in the index), unstaged files (modified in working directory)
+    // and untracked files, all reported relative to the top-level directory
+    for args in [
+        &["diff", "--cached", "--name-only", "--diff-filter=d"][..],
+        &["diff", "--name-only", "--diff-filter=d"][..],
+        &["ls-files", "--others", "--exclude-standard", "--full-name"][..],
+    ] {
+        if let Some(stdout) = run_git(project_root, args) {
+            changed_files.extend(stdout.lines().map(|line| top.join(line)));
+        }
+    }
+
+    changed_files
+}
+
+/// Get files that differ between two revisions, leaving out those deleted in `new`
+pub fn paths_changed_between(project_root: &Path, old: &str, new: &str) -> Vec<PathBuf> {
+    let top = toplevel(project_root);
+    run_git(
+        project_root,
+        &["diff", "--name-only", "--diff-filter=d", old, new],
+    )
+    .map(|stdout| stdout.lines().map(|line| top.join(line)).collect())
+    .unwrap_or_default()
+}
+
+/// Commits since the Unix time `cutoff` per touched file, from one `git log`
+pub fn commit_counts_since(project_root: &Path, cutoff: i64) -> HashMap<PathBuf, usize> {
+    let top = toplevel(project_root);
+    let since = format!("--since=@{}", cutoff);
+    run_git(
+        project_root,
+        &[
+            "log",
+            &since,
+            "--no-renames",
+            "--name-only",
+            "--format=",
+            "HEAD",
+        ],
+    )
+    .map(|stdout| count_paths(&stdout, &top))
+    .unwrap_or_default()
+}
+
+/// Count the file names of `git log --name-only --format=` output: each commit lists
+/// the files it touched once
+fn count_paths(output: &str, top: &Path) -> HashMap<PathBuf, usize> {
+    let mut counts = HashMap::new();
+    for line in output.lines().filter(|line| !line.is_empty()) {
+        *counts.entry(top.join(line)).or_default() += 1;
+    }
+    counts
+}
+
+pub fn merge_base(project_root: &Path, one: &str, two: &str) -> Option<String> {
+    run_git(project_root, &["merge-base", one, two])
+        .map(|out| out.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+pub fn head_commit(project_root: &Path) -> Option<String> {
+    run_git(project_root, &["rev-parse", "--verify", "HEAD"])
+        .map(|out| out.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+pub fn blame_range(
+    project_root: &Path,
+    file: &Path,
+    start: usize,
+    end: usize,
+) -> Option<BlameInfo> {
+    let range = format!("{},{}", start, end);
+    let file = file.to_str()?;
+    let stdout = run_git(
+        project_root,
+        &["blame", "--porcelain", "-L", &range, "HEAD", "--", file],
+    )?;
+    parse_porcelain_latest(&stdout)
+}
+
+/// Pick the newest commit out of `git blame --porcelain` output
+fn parse_porcelain_latest(output: &str) -> Option<BlameInfo> {
+    let mut latest: Option<BlameInfo> = None;
+    let mut current: Option<BlameInfo> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        if key.len() == 40 && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            if let Some(done) = current.take() {
+                keep_newest(&mut latest, done);
+            }
+            current = Some(BlameInfo {
+                commit: key.to_string(),
+                author: String::new(),
+                author_email: String::new(),
+                timestamp: 0,
+            });
+            continue;
+        }
+        if let Some(info) = current.as_mut() {
+            match key {
+                "author" => info.author = value.to_string(),
+                "author-mail" => {
+                    info.author_email = value.trim_matches(|c| c == '<' || c == '>').to_string()
+                }
+                "author-time" => info.timestamp = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+    if let Some(done) = current {
+        keep_newest(&mut latest, done);
+    }
+
+    latest
+}
+
+fn keep_newest(latest: &mut Option<BlameInfo>, candidate: BlameInfo) {
+    // Porcelain output only repeats commit headers for the first line of each commit
+    if candidate.author.is_empty() {
+        return;
+    }
+    if latest
+        .as_ref()
+        .is_none_or(|current| candidate.timestamp > current.timestamp)
+    {
+        *latest = Some(candidate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_latest() {
+        let output = "\
+1111111111111111111111111111111111111111 3 3 1
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+summary first
+filename pkg/mod.py
+\tdef foo():
+2222222222222222222222222222222222222222 4 4 1
+author Bob
+author-mail <bob@example.com>
+author-time 1710000000
+summary second
+filename pkg/mod.py
+\t    return 1
+1111111111111111111111111111111111111111 5 5
+\t    pass
+";
+        let info = parse_porcelain_latest(output).unwrap();
+        assert_eq!(info.commit, "2222222222222222222222222222222222222222");
+        assert_eq!(info.author, "Bob");
+        assert_eq!(info.author_email, "bob@example.com");
+        assert_eq!(info.timestamp, 1710000000);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let output = "src/a.py\nsrc/b.py\n\nsrc/a.py\n";
+        let counts = count_paths(output, Path::new("/repo"));
+        assert_eq!(counts[Path::new("/repo/src/a.py")], 2);
+        assert_eq!(counts[Path::new("/repo/src/b.py")], 1);
+    }
+}
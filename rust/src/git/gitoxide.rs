@@ -0,0 +1,188 @@
+use super::BlameInfo;
+use gix::bstr::ByteSlice;
+use gix::object::tree::diff::ChangeDetached;
+use gix::revision::walk::Sorting;
+use gix::status::plumbing::index_as_worktree::{Change, EntryStatus};
+use gix::traverse::commit::simple::CommitTimeOrder;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub fn is_available() -> bool {
+    true
+}
+
+pub fn is_git_repository(path: &Path) -> bool {
+    gix::discover(path).is_ok()
+}
+
+/// Whether a status item is the deletion of its file, from the index or the worktree
+fn is_deletion(item: &gix::status::Item) -> bool {
+    match item {
+        gix::status::Item::IndexWorktree(gix::status::index_worktree::Item::Modification {
+            status,
+            ..
+        }) => matches!(status, EntryStatus::Change(Change::Removed)),
+        gix::status::Item::TreeIndex(change) => {
+            matches!(change, gix::diff::index::Change::Deletion { .. })
+        }
+        _ => false,
+    }
+}
+
+/// Get files with staged, unstaged or untracked changes, leaving out deleted ones
+pub fn changed_paths(project_root: &Path) -> Vec<PathBuf> {
+    let Ok(repo) = gix::discover(project_root) else {
+        return Vec::new();
+    };
+    let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+        return Vec::new();
+    };
+
+    let Ok(platform) = repo.status(gix::progress::Discard) else {
+        return Vec::new();
+    };
+    let Ok(iter) = platform
+        .untracked_files(gix::status::UntrackedFiles::Files)
+        .into_iter(None)
+    else {
+        return Vec::new();
+    };
+
+    iter.filter_map(Result::ok)
+        .filter(|item| !is_deletion(item))
+        .filter_map(|item| {
+            let rela_path = item.location().to_path().ok()?;
+            Some(workdir.join(rela_path))
+        })
+        .collect()
+}
+
+/// Get files that differ between two revisions, leaving out those deleted in `new`
+pub fn paths_changed_between(project_root: &Path, old: &str, new: &str) -> Vec<PathBuf> {
+    let Ok(repo) = gix::discover(project_root) else {
+        return Vec::new();
+    };
+    let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+        return Vec::new();
+    };
+
+    let tree_of = |rev: &str| -> Option<gix::Tree<'_>> {
+        repo.rev_parse_single(rev)
+            .ok()?
+            .object()
+            .ok()?
+            .peel_to_tree()
+            .ok()
+    };
+    let (Some(old_tree), Some(new_tree)) = (tree_of(old), tree_of(new)) else {
+        return Vec::new();
+    };
+
+    repo.diff_tree_to_tree(&old_tree, &new_tree, None)
+        .map(|changes| {
+            changes
+                .iter()
+                .filter(|change| !matches!(change, ChangeDetached::Deletion { .. }))
+                .filter_map(|change| change.location().to_path().ok().map(|p| workdir.join(p)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Commits since the Unix time `cutoff` per touched file: a walk of the history newest
+/// first, diffing each non-merge commit against its parent
+pub fn commit_counts_since(project_root: &Path, cutoff: i64) -> HashMap<PathBuf, usize> {
+    let mut counts = HashMap::new();
+    let Ok(repo) = gix::discover(project_root) else {
+        return counts;
+    };
+    let (Some(workdir), Ok(head)) = (repo.workdir().map(Path::to_path_buf), repo.head_id()) else {
+        return counts;
+    };
+    let Ok(walk) = repo
+        .rev_walk([head.detach()])
+        .sorting(Sorting::ByCommitTimeCutoff {
+            order: CommitTimeOrder::NewestFirst,
+            seconds: cutoff,
+        })
+        .all()
+    else {
+        return counts;
+    };
+
+    for info in walk.filter_map(Result::ok) {
+        if info.parent_ids.len() > 1 {
+            continue;
+        }
+        let Some(tree) = info.object().ok().and_then(|commit| commit.tree().ok()) else {
+            continue;
+        };
+        let parent_tree = match info.parent_ids().next() {
+            Some(parent) => match parent.object().ok().and_then(|p| p.peel_to_tree().ok()) {
+                Some(tree) => tree,
+                None => continue,
+            },
+            None => repo.empty_tree(),
+        };
+        let Ok(changes) = repo.diff_tree_to_tree(&parent_tree, &tree, None) else {
+            continue;
+        };
+        let touched: HashSet<PathBuf> = changes
+            .iter()
+            .filter_map(|change| change.location().to_path().ok().map(|p| workdir.join(p)))
+            .collect();
+        for path in touched {
+            *counts.entry(path).or_default() += 1;
+        }
+    }
+    counts
+}
+
+pub fn merge_base(project_root: &Path, one: &str, two: &str) -> Option<String> {
+    let repo = gix::discover(project_root).ok()?;
+    let one = repo.rev_parse_single(one).ok()?.detach();
+    let two = repo.rev_parse_single(two).ok()?.detach();
+    repo.merge_base(one, two).ok().map(|id| id.to_string())
+}
+
+pub fn head_commit(project_root: &Path) -> Option<String> {
+    let repo = gix::discover(project_root).ok()?;
+    repo.head_id().ok().map(|id| id.to_string())
+}
+
+pub fn blame_range(
+    project_root: &Path,
+    file: &Path,
+    start: usize,
+    end: usize,
+) -> Option<BlameInfo> {
+    let repo = gix::discover(project_root).ok()?;
+    let workdir = repo.workdir()?.canonicalize().ok()?;
+    let absolute = project_root.join(file).canonicalize().ok()?;
+    let rela_path = absolute.strip_prefix(&workdir).ok()?;
+    let rela_path = gix::path::to_unix_separators_on_windows(gix::path::into_bstr(rela_path));
+
+    let head = repo.head_id().ok()?.detach();
+    let options = gix::blame::Options {
+        range: gix::blame::BlameRanges::from_range(start as u32..=end as u32),
+        ..Default::default()
+    };
+    let outcome = repo.blame_file(rela_path.as_ref(), head, options).ok()?;
+
+    let mut latest: Option<BlameInfo> = None;
+    for entry in &outcome.entries {
+        let commit = repo.find_commit(entry.commit_id).ok()?;
+        let author = commit.author().ok()?;
+        let timestamp = author.time().map(|t| t.seconds).unwrap_or(0);
+        if latest.as_ref().is_none_or(|l| timestamp > l.timestamp) {
+            latest = Some(BlameInfo {
+                commit: entry.commit_id.to_string(),
+                author: author.name.to_string(),
+                author_email: author.email.to_string(),
+                timestamp,
+            });
+        }
+    }
+
+    latest
+}
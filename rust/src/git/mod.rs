@@ -0,0 +1,171 @@
+//! Git integration: changed-file detection, merge-base computation, blame lookups and
+//! per-file commit counts.
+//!
+//! With the default `gitoxide` feature everything is answered in-process by `gix`, so no
+//! `git` binary has to be installed or on `PATH`. Building without the feature falls back
+//! to shelling out to the `git` command.
+
+#[cfg(not(feature = "gitoxide"))]
+mod command;
+#[cfg(feature = "gitoxide")]
+mod gitoxide;
+
+#[cfg(not(feature = "gitoxide"))]
+use command as backend;
+#[cfg(feature = "gitoxide")]
+use gitoxide as backend;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The commit that last touched a range of lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameInfo {
+    pub commit: String,
+    pub author: String,
+    pub author_email: String,
+    /// Author time in seconds since the Unix epoch
+    pub timestamp: i64,
+}
+
+/// How git is accessed: `gix` in-process, or the `git` command
+pub const BACKEND: &str = if cfg!(feature = "gitoxide") {
+    "gitoxide"
+} else {
+    "git command"
+};
+
+/// Whether git operations can run at all (the `git` command needs to be on `PATH`)
+pub fn is_available() -> bool {
+    backend::is_available()
+}
+
+/// Check if we're in a git repository
+pub fn is_git_repository(path: &Path) -> bool {
+    backend::is_git_repository(path)
+}
+
+/// Get Python files with staged, unstaged or untracked changes
+pub fn get_changed_files(project_root: &Path) -> Vec<PathBuf> {
+    python_files_only(backend::changed_paths(project_root))
+}
+
+/// Get Python files changed on the current branch since it diverged from `base`,
+/// together with any uncommitted changes
+pub fn get_changed_files_since(project_root: &Path, base: &str) -> Vec<PathBuf> {
+    let mut paths = match merge_base(project_root, base, "HEAD") {
+        Some(fork_point) => backend::paths_changed_between(project_root, &fork_point, "HEAD"),
+        None => Vec::new(),
+    };
+    paths.extend(backend::changed_paths(project_root));
+    python_files_only(paths)
+}
+
+/// Find the best common ancestor of two revisions, returned as a hex commit id
+pub fn merge_base(project_root: &Path, one: &str, two: &str) -> Option<String> {
+    backend::merge_base(project_root, one, two)
+}
+
+/// Hex id of the commit checked out at `HEAD`, if `project_root` is in a repository with
+/// commits
+pub fn head_commit(project_root: &Path) -> Option<String> {
+    backend::head_commit(project_root)
+}
+
+/// Find the most recent commit touching lines `start..=end` (1-based) of `file` at `HEAD`
+pub fn blame_range(
+    project_root: &Path,
+    file: &Path,
+    start: usize,
+    end: usize,
+) -> Option<BlameInfo> {
+    if start == 0 || end < start {
+        return None;
+    }
+    backend::blame_range(project_root, file, start, end)
+}
+
+/// Number of commits reachable from `HEAD` and made in the last `days` days that touched
+/// each file, keyed by absolute path; one history walk answers every file. Merge commits
+/// are not counted, like in `git log`
+pub fn commit_counts_since(project_root: &Path, days: u64) -> HashMap<PathBuf, usize> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    backend::commit_counts_since(project_root, now.saturating_sub(days * 86_400) as i64)
+}
+
+/// Keep only `.py` paths, preserving the order in which they were first reported
+fn python_files_only(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if path.extension().and_then(|s| s.to_str()) == Some("py") && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_files_only_filters_and_dedups() {
+        let paths = vec![
+            PathBuf::from("/repo/a.py"),
+            PathBuf::from("/repo/README.md"),
+            PathBuf::from("/repo/b.py"),
+            PathBuf::from("/repo/a.py"),
+        ];
+        assert_eq!(
+            python_files_only(paths),
+            vec![PathBuf::from("/repo/a.py"), PathBuf::from("/repo/b.py")]
+        );
+    }
+
+    #[test]
+    fn test_changed_files_leave_out_deleted_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        for file in ["kept.py", "staged.py", "unstaged.py"] {
+            std::fs::write(root.join(file), "x = 1\n").unwrap();
+        }
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "base"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        git(&["rm", "-q", "staged.py"]);
+        git(&["commit", "-q", "-m", "remove"]);
+        std::fs::write(root.join("kept.py"), "x = 2\n").unwrap();
+        std::fs::remove_file(root.join("unstaged.py")).unwrap();
+        std::fs::write(root.join("new.py"), "y = 1\n").unwrap();
+
+        // Paths of deleted files could not be canonicalized
+        let canonical = |paths: Vec<PathBuf>| {
+            let mut paths: Vec<PathBuf> = paths.iter().map(|p| p.canonicalize().unwrap()).collect();
+            paths.sort();
+            paths
+        };
+        let expected = canonical(vec![root.join("kept.py"), root.join("new.py")]);
+        assert_eq!(canonical(get_changed_files(root)), expected);
+        assert_eq!(canonical(get_changed_files_since(root, "main")), expected);
+    }
+
+    #[test]
+    fn test_blame_range_rejects_invalid_ranges() {
+        let root = Path::new(".");
+        assert_eq!(blame_range(root, Path::new("a.py"), 0, 3), None);
+        assert_eq!(blame_range(root, Path::new("a.py"), 5, 4), None);
+    }
+}
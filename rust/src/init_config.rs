@@ -0,0 +1,348 @@
+//! Scaffolding a `[tool.proboscis]` section for a project (`proboscis-lint init`)
+//!
+//! The project is surveyed first: the directories its test files live in, the test types
+//! present (`unit`, `integration`, `e2e`), the package roots, and the pytest markers the
+//! tests already use. The section written from the survey leaves off the rules for test
+//! types the project has no tests of yet, and the markers PL004 expects are registered
+//! with pytest alongside those in use, unless pytest is configured outside pyproject.toml.
+
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::file_discovery::{find_python_files, glob_to_file_name_regex, TestDirectories};
+use crate::patterns::PATTERNS;
+use crate::pyproject;
+use crate::test_cache::TestType;
+
+/// Directory names recognised as holding tests
+const TEST_DIRECTORY_NAMES: &[&str] = &["test", "tests", "testing", "spec", "specs"];
+
+/// Markers built into pytest, which need no registration
+const BUILTIN_MARKERS: &[&str] = &[
+    "parametrize",
+    "skip",
+    "skipif",
+    "xfail",
+    "usefixtures",
+    "filterwarnings",
+];
+
+/// The markers PL004 expects, with their descriptions
+const TEST_TYPE_MARKERS: &[(&str, &str)] = &[
+    ("unit", "unit tests"),
+    ("integration", "integration tests"),
+    ("e2e", "end-to-end tests"),
+];
+
+/// What `init_config` found out about a project
+#[derive(Debug, Default)]
+pub struct Survey {
+    /// Names of the directories holding test files, in order
+    pub test_directories: Vec<String>,
+    /// Directories holding top-level packages, when the default (`src`, or the project
+    /// root) does not cover them
+    pub source_roots: Vec<String>,
+    pub has_integration_tests: bool,
+    pub has_e2e_tests: bool,
+    /// Custom markers used by the tests
+    pub markers: BTreeSet<String>,
+}
+
+impl Survey {
+    /// Survey the project at `project_root`, recognising test files by `test_patterns`
+    pub fn of(project_root: &Path, test_patterns: &[String]) -> Self {
+        let test_file_regexes: Vec<Regex> = test_patterns
+            .iter()
+            .filter_map(|pattern| glob_to_file_name_regex(pattern))
+            .collect();
+        let files = find_python_files(project_root, &[], &TestDirectories::default());
+
+        let mut survey = Self::default();
+        let mut package_roots = BTreeSet::new();
+        for file in &files {
+            let relative = file.strip_prefix(project_root).unwrap_or(file);
+            let test_directory = relative.parent().and_then(|parent| {
+                parent
+                    .components()
+                    .filter_map(|c| c.as_os_str().to_str())
+                    .find(|name| TEST_DIRECTORY_NAMES.contains(name))
+            });
+
+            let Some(test_directory) = test_directory else {
+                if let Some(root) = package_root(project_root, relative) {
+                    package_roots.insert(root);
+                }
+                continue;
+            };
+            let is_test_file = relative
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .is_some_and(|name| test_file_regexes.iter().any(|re| re.is_match(&name)));
+            if !is_test_file {
+                continue;
+            }
+
+            if !survey.test_directories.iter().any(|d| d == test_directory) {
+                survey.test_directories.push(test_directory.to_string());
+            }
+            match TestType::from_path(relative) {
+                TestType::Integration => survey.has_integration_tests = true,
+                TestType::E2E => survey.has_e2e_tests = true,
+                TestType::Unit | TestType::General => {}
+            }
+            if let Ok(content) = fs::read_to_string(file) {
+                survey.markers.extend(
+                    PATTERNS
+                        .pytest_marker
+                        .captures_iter(&content)
+                        .map(|captures| captures[1].to_string())
+                        .filter(|marker| {
+                            !BUILTIN_MARKERS.contains(&marker.as_str())
+                                && !TEST_TYPE_MARKERS.iter().any(|(name, _)| name == marker)
+                        }),
+                );
+            }
+        }
+
+        // `src` and the project root are found without configuration
+        if package_roots
+            .iter()
+            .any(|root| root != "src" && root != ".")
+        {
+            survey.source_roots = package_roots.into_iter().collect();
+        }
+        survey
+    }
+
+    /// The `[tool.proboscis]` section for the project
+    pub fn proboscis_section(&self) -> String {
+        let mut section = String::from("[tool.proboscis]\n");
+        let test_directories = if self.test_directories.is_empty() {
+            vec!["test".to_string(), "tests".to_string()]
+        } else {
+            self.test_directories.clone()
+        };
+        section.push_str(&format!(
+            "test_directories = {}\n",
+            toml_list(&test_directories)
+        ));
+        if !self.source_roots.is_empty() {
+            section.push_str(&format!(
+                "source_roots = {}\n",
+                toml_list(&self.source_roots)
+            ));
+        }
+
+        let mut rules = String::new();
+        if !self.has_integration_tests {
+            rules.push_str("PL002 = false  # No integration tests yet\n");
+        }
+        if !self.has_e2e_tests {
+            rules.push_str("PL003 = false  # No end-to-end tests yet\n");
+        }
+        if !rules.is_empty() {
+            section.push_str("\n[tool.proboscis.rules]\n");
+            section.push_str(&rules);
+        }
+        section
+    }
+
+    /// The marker registrations (`name: description`) pytest lacks, given those in
+    /// `registered`
+    pub fn unregistered_markers(&self, registered: &[String]) -> Vec<String> {
+        let is_registered = |marker: &str| {
+            registered
+                .iter()
+                .any(|entry| entry.split(':').next().unwrap_or("").trim() == marker)
+        };
+        TEST_TYPE_MARKERS
+            .iter()
+            .map(|(name, description)| (name.to_string(), format!("{}: {}", name, description)))
+            .chain(
+                self.markers
+                    .iter()
+                    .map(|marker| (marker.clone(), marker.clone())),
+            )
+            .filter(|(name, _)| !is_registered(name))
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+}
+
+/// Scaffold the configuration of the project at `project_root`: append the
+/// `[tool.proboscis]` section to its `pyproject.toml` (created if missing) and register the
+/// markers pytest lacks, returning the text appended
+///
+/// Fails with `AlreadyExists` if the project already has a `[tool.proboscis]` section.
+pub fn write(project_root: &Path, survey: &Survey) -> io::Result<String> {
+    let path = project_root.join("pyproject.toml");
+    let parsed = pyproject::load(project_root).unwrap_or_default();
+    if parsed
+        .get("tool")
+        .and_then(|tool| tool.get("proboscis"))
+        .is_some()
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already has a [tool.proboscis] section", path.display()),
+        ));
+    }
+
+    let mut added = survey.proboscis_section();
+    let mut content = fs::read_to_string(&path).unwrap_or_default();
+    if !pytest_configured_elsewhere(project_root) {
+        let pytest = parsed
+            .get("tool")
+            .and_then(|tool| tool.get("pytest"))
+            .and_then(|pytest| pytest.get("ini_options"));
+        let registered: Vec<String> = pytest
+            .and_then(|options| options.get("markers"))
+            .and_then(|markers| markers.as_array())
+            .map(|markers| {
+                markers
+                    .iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let markers = survey.unregistered_markers(&registered);
+        if !markers.is_empty() {
+            let entries: String = markers
+                .iter()
+                .map(|marker| format!("    \"{}\",\n", marker))
+                .collect();
+            match pytest {
+                // Add to the pytest section in place, leaving a `markers` list as written
+                // when it is not a multi-line array
+                Some(options) => {
+                    let insertion = if options.get("markers").is_some() {
+                        PATTERNS
+                            .markers_list_start
+                            .find(&content)
+                            .map(|m| (m.end(), entries.clone()))
+                    } else {
+                        PATTERNS
+                            .pytest_ini_header
+                            .find(&content)
+                            .map(|m| (m.end(), format!("markers = [\n{}]\n", entries)))
+                    };
+                    if let Some((offset, text)) = insertion {
+                        content.insert_str(offset, &text);
+                    }
+                }
+                None => added.push_str(&format!(
+                    "\n[tool.pytest.ini_options]\nmarkers = [\n{}]\n",
+                    entries
+                )),
+            }
+        }
+    }
+
+    if !content.is_empty() {
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push('\n');
+    }
+    content.push_str(&added);
+    fs::write(&path, content)?;
+    Ok(added)
+}
+
+/// The directory (relative to `project_root`, `.` for the root itself) holding the
+/// top-level package `relative` belongs to, if it lies in a package
+fn package_root(project_root: &Path, relative: &Path) -> Option<String> {
+    let is_package = |dir: &Path| project_root.join(dir).join("__init__.py").is_file();
+    let mut package = relative.parent()?;
+    if !is_package(package) {
+        return None;
+    }
+    loop {
+        let parent = package.parent()?;
+        if parent.as_os_str().is_empty() {
+            return Some(".".to_string());
+        }
+        if !is_package(parent) {
+            return Some(parent.to_string_lossy().replace('\\', "/"));
+        }
+        package = parent;
+    }
+}
+
+/// Whether pytest reads its settings from a file other than pyproject.toml, which takes
+/// precedence over it
+fn pytest_configured_elsewhere(project_root: &Path) -> bool {
+    let has_section = |file: &str, section: &str| {
+        fs::read_to_string(project_root.join(file))
+            .is_ok_and(|content| content.lines().any(|line| line.trim() == section))
+    };
+    project_root.join("pytest.ini").is_file()
+        || has_section("tox.ini", "[pytest]")
+        || has_section("setup.cfg", "[tool:pytest]")
+}
+
+/// A TOML array of strings
+fn toml_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| format!("\"{}\"", item)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let package = root.join("python/src/pkg");
+        let unit = root.join("tests/unit");
+        let integration = root.join("tests/integration");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir_all(&unit).unwrap();
+        fs::create_dir_all(&integration).unwrap();
+        fs::write(package.join("__init__.py"), "").unwrap();
+        fs::write(package.join("core.py"), "def run():\n    pass\n").unwrap();
+        fs::write(
+            unit.join("test_core.py"),
+            "import pytest\n\n@pytest.mark.unit\n@pytest.mark.slow\n@pytest.mark.parametrize(\"x\", [1])\ndef test_run(x):\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            integration.join("test_flow.py"),
+            "def test_flow():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"pkg\"\n\n[tool.pytest.ini_options]\naddopts = \"-q\"\n",
+        )
+        .unwrap();
+
+        let survey = Survey::of(root, &["test_*.py".to_string()]);
+        assert_eq!(survey.test_directories, vec!["tests".to_string()]);
+        assert_eq!(survey.source_roots, vec!["python/src".to_string()]);
+        assert!(survey.has_integration_tests && !survey.has_e2e_tests);
+        assert_eq!(survey.markers.iter().collect::<Vec<_>>(), vec!["slow"]);
+
+        let added = write(root, &survey).unwrap();
+        assert!(added.contains("PL003 = false"));
+        assert!(!added.contains("PL002"));
+        let pyproject = pyproject::load(root).unwrap();
+        let markers = pyproject["tool"]["pytest"]["ini_options"]["markers"]
+            .as_array()
+            .unwrap();
+        assert_eq!(markers.len(), 4);
+        assert_eq!(
+            pyproject["tool"]["proboscis"]["source_roots"][0].as_str(),
+            Some("python/src")
+        );
+        assert_eq!(
+            write(root, &survey).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+    }
+}
@@ -1,21 +1,89 @@
+mod aggregate;
+mod aliases;
+mod allowlist;
+mod budget;
+mod call_graph;
+mod churn;
+mod codeowners;
+mod config;
+mod dags;
+mod decorator_policies;
+mod delegation;
+mod diff;
+mod docs_url;
+mod doctor;
+mod escalation;
+mod examples;
+mod exit_codes;
+mod expiry;
 mod file_discovery;
-mod git;
+mod filtering;
+mod fixes;
+mod fixtures;
+mod frameworks;
+pub mod git;
+mod init_config;
+mod lifecycle;
 mod models;
+mod moved_aliases;
 mod noqa;
+mod ownership;
+mod packages;
+mod parser;
+mod patterns;
+mod profiles;
 mod public_api;
+mod pyproject;
+mod quality;
+mod report;
+mod rule_selection;
 mod rules;
+mod run_metadata;
+mod scripts;
+mod self_check;
+mod session;
+mod skeleton;
+mod snippets;
+mod source_index;
+mod store;
+mod stream;
+#[cfg(test)]
+mod stubs;
+mod subprojects;
+mod suppressions;
+mod surface_policies;
 mod test_cache;
-mod test_discovery;
+mod test_cache_store;
+mod test_names;
+mod test_stubs;
+mod unified_diff;
+mod usage_stats;
 
+use pyo3::exceptions::{PyFileExistsError, PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use rayon::prelude::*;
 use regex::Regex;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
-use crate::file_discovery::find_python_files;
+use crate::file_discovery::{
+    find_python_files, find_python_files_in, find_test_files, module_pattern_to_regex, Discovery,
+    DiscoveryLimits, LimitReached, TestDirectories,
+};
 use crate::models::LintViolation;
-use crate::rules::{get_all_rules, pl004_require_test_markers::check_test_markers};
+use crate::patterns::{Patterns, PATTERNS};
+use crate::rules::options::{OptionValue, RuleOptions};
+use crate::rules::{
+    get_all_rules, pl004_require_test_markers::check_test_markers, pl006_duplicate_definition,
+    pl007_dead_private_helper, pl023_test_naming, pl024_test_docstrings, pl025_test_length,
+    pl026_no_sleep_in_tests, pl027_no_external_access, pl028_conftest_placement,
+    pl029_test_package_consistency, pl030_public_api_imports, pl031_tautological_assertion,
+    pl032_empty_test_file, pl034_noqa_density,
+};
+use crate::source_index::{DefinedFunction, SourceFile, SourceIndex};
 use crate::test_cache::TestCache;
 
 #[pyclass]
@@ -25,64 +93,658 @@ pub struct RustLinter {
     test_patterns: Vec<String>,
     exclude_patterns: Vec<String>,
     strict_mode: bool,
-    function_regex: Regex,
-    class_regex: Regex,
+    attribute_owners: bool,
+    attach_code_owners: bool,
+    todo_comments: bool,
+    /// Attach `add_test_stub` fixes to missing-test violations (see `test_stubs`)
+    test_stubs: bool,
+    rule_options: RuleOptions,
+    fan_in_analysis: bool,
+    critical_fan_in: Option<usize>,
+    /// Directories (relative to the project root) that contain top-level packages
+    source_roots: Option<Vec<String>>,
+    /// Dotted module patterns (e.g. `*.migrations.*`) whose modules are not linted
+    exclude_modules: Vec<Regex>,
+    /// Per-file time budget (None: unlimited)
+    file_time_limit: Option<Duration>,
+    /// Guards on how much of the tree source discovery walks (`max_depth`, `max_files`)
+    discovery_limits: DiscoveryLimits,
+    /// Guards tripped by source discovery, by the directory walked, until reported
+    limits_reached: Arc<Mutex<HashMap<PathBuf, LimitReached>>>,
+    /// Directory (relative to the project root) storing what was extracted from the test
+    /// files, so later runs only parse those that changed (see `test_cache_store`)
+    cache_dir: Option<PathBuf>,
+    /// File (relative to the project root) each run over a whole project is recorded in
+    /// (see `usage_stats`)
+    stats_file: Option<PathBuf>,
+    /// Code a run exits with for each outcome (see `exit_codes`)
+    exit_codes: exit_codes::ExitCodes,
+    /// Source lines around the offending definition attached to each violation (see
+    /// `snippets`)
+    context_lines: Option<usize>,
+    /// Report missing test directories for expected test files (PL005)
+    check_test_skeleton: bool,
+    /// Merge the PL001–PL003 violations of each function into one (see `aggregate`)
+    aggregate_missing_tests: bool,
+    /// Raise the severity of crowded files and wholly untested functions (see `escalation`)
+    escalation: escalation::Escalation,
+    /// Functions exempt from rules by qualified name (see `allowlist`)
+    allowlist: Arc<allowlist::Allowlist>,
+    /// Report `noqa` comments that give no reason (`# noqa: PL001 -- reason`)
+    require_noqa_reason: bool,
+    /// Strictness preset supplying the defaults below
+    profile: profiles::Profile,
+    /// Rules disabled, enabled and switched on and off over the profile (see
+    /// `rule_selection`)
+    rule_selection: rule_selection::RuleSelection,
+    /// Only accept tests of methods whose names include the class (see `TestCache`)
+    qualified_test_names: bool,
+    /// Behavior versions of the rules, defaults plus those pinned by `rule_versions`
+    rule_versions: rules::versions::RuleVersions,
+    /// Only lint the packages pyproject.toml declares for distribution (see `packages`)
+    declared_packages_only: bool,
+    /// Format violation messages (off for `lint_project_counts`)
+    build_messages: bool,
+    /// List the missing-test rules functions satisfy, with the matching tests
+    include_satisfied: bool,
+    /// Credit facades with the tests of the function they delegate to (see `delegation`)
+    credit_delegation: bool,
+    /// Keep suppressed violations, marked, to count them (on for `lint_project_counts`)
+    keep_suppressed: bool,
+    /// Shared source patterns, plus the user's `function_patterns`
+    patterns: Arc<Patterns>,
+    /// Bodies (e.g. `...`) marking `@overload` and abstract methods as interface
+    /// declarations, which no rule checks
+    stub_bodies: Vec<String>,
+    /// One-off scripts and how their top-level functions are checked
+    scripts: scripts::Scripts,
+    /// Example and documentation code and how it is checked
+    examples: examples::Examples,
+    /// Deprecated and experimental functions and how they are checked
+    lifecycles: lifecycle::Lifecycles,
+    /// Airflow DAG functions and how they are checked
+    dags: dags::Dags,
+    /// Test types required of functions by their decorators (see `decorator_policies`)
+    decorator_policies: Arc<decorator_policies::DecoratorPolicies>,
+    /// Test types required of modules by the size of their public API (see
+    /// `surface_policies`)
+    surface_policies: Arc<surface_policies::SurfacePolicies>,
+    /// Templates of the test names missing-test violations recommend
+    test_names: test_names::TestNameTemplates,
+    /// Documentation links attached to violations
+    docs_url: docs_url::DocsUrl,
+}
+
+/// Run-wide data for `RustLinter::annotate`
+struct Annotations {
+    code_owners: Option<codeowners::CodeOwners>,
+    call_graph: Option<call_graph::CallGraph>,
+}
+
+/// A function definition selected by `RustLinter::checked_functions`
+struct CheckedFunction<'a> {
+    function: &'a DefinedFunction,
+    /// The `def` line itself, for `noqa` comments
+    line: &'a str,
+    /// Package re-exporting the function from a private module, whose tests cover it
+    public_module: Option<String>,
 }
 
 #[pymethods]
 impl RustLinter {
     #[new]
-    #[pyo3(signature = (test_directories=None, test_patterns=None, exclude_patterns=None, strict_mode=None))]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (test_directories=None, test_patterns=None, exclude_patterns=None, strict_mode=None, attribute_owners=None, attach_code_owners=None, todo_comments=None, rule_options=None, fan_in_analysis=None, critical_fan_in=None, source_roots=None, exclude_modules=None, file_time_limit=None, check_test_skeleton=None, aggregate_missing_tests=None, disabled_rules=None, function_patterns=None, stub_bodies=None, script_directories=None, script_mode=None, profile=None, enabled_rules=None, qualified_test_names=None, rule_versions=None, declared_packages_only=None, example_directories=None, example_mode=None, experimental_decorators=None, lifecycle_mode=None, test_name_template=None, method_test_name_template=None, test_suggestion_style=None, test_stubs=None, escalate_file_violations=None, escalate_untested_functions=None, allowlist=None, allowlist_file=None, require_noqa_reason=None, include_satisfied=None, framework=None, dag_directories=None, dag_mode=None, decorator_test_types=None, surface_test_types=None, credit_delegation=None, max_depth=None, max_files=None, cache_dir=None, stats_file=None, exit_codes=None, docs_url=None, context_lines=None, rule_switches=None, config_root=None))]
     fn new(
         test_directories: Option<Vec<String>>,
         test_patterns: Option<Vec<String>>,
         exclude_patterns: Option<Vec<String>>,
         strict_mode: Option<bool>,
+        attribute_owners: Option<bool>,
+        attach_code_owners: Option<bool>,
+        todo_comments: Option<bool>,
+        rule_options: Option<HashMap<String, HashMap<String, OptionValue>>>,
+        fan_in_analysis: Option<bool>,
+        critical_fan_in: Option<usize>,
+        source_roots: Option<Vec<String>>,
+        exclude_modules: Option<Vec<String>>,
+        file_time_limit: Option<f64>,
+        check_test_skeleton: Option<bool>,
+        aggregate_missing_tests: Option<bool>,
+        disabled_rules: Option<Vec<String>>,
+        function_patterns: Option<Vec<String>>,
+        stub_bodies: Option<Vec<String>>,
+        script_directories: Option<Vec<String>>,
+        script_mode: Option<&str>,
+        profile: Option<&str>,
+        enabled_rules: Option<Vec<String>>,
+        qualified_test_names: Option<bool>,
+        rule_versions: Option<HashMap<String, u32>>,
+        declared_packages_only: Option<bool>,
+        example_directories: Option<Vec<String>>,
+        example_mode: Option<&str>,
+        experimental_decorators: Option<Vec<String>>,
+        lifecycle_mode: Option<&str>,
+        test_name_template: Option<&str>,
+        method_test_name_template: Option<&str>,
+        test_suggestion_style: Option<&str>,
+        test_stubs: Option<bool>,
+        escalate_file_violations: Option<usize>,
+        escalate_untested_functions: Option<bool>,
+        allowlist: Option<HashMap<String, Vec<String>>>,
+        allowlist_file: Option<&str>,
+        require_noqa_reason: Option<bool>,
+        include_satisfied: Option<bool>,
+        framework: Option<&str>,
+        dag_directories: Option<Vec<String>>,
+        dag_mode: Option<&str>,
+        decorator_test_types: Option<HashMap<String, Vec<String>>>,
+        surface_test_types: Option<HashMap<usize, Vec<String>>>,
+        credit_delegation: Option<bool>,
+        max_depth: Option<usize>,
+        max_files: Option<usize>,
+        cache_dir: Option<String>,
+        stats_file: Option<String>,
+        exit_codes: Option<HashMap<String, i32>>,
+        docs_url: Option<&str>,
+        context_lines: Option<usize>,
+        rule_switches: Option<HashMap<String, bool>>,
+        config_root: Option<&str>,
     ) -> PyResult<Self> {
-        Ok(Self {
+        // The settings of the configuration file these options replace (see `config`)
+        let explicit: Vec<&str> = [
+            ("test_directories", test_directories.is_some()),
+            ("test_patterns", test_patterns.is_some()),
+            ("exclude_patterns", exclude_patterns.is_some()),
+            ("exclude_modules", exclude_modules.is_some()),
+            ("source_roots", source_roots.is_some()),
+            ("strict_mode", strict_mode.is_some()),
+            ("require_noqa_reason", require_noqa_reason.is_some()),
+            ("declared_packages_only", declared_packages_only.is_some()),
+            ("credit_delegation", credit_delegation.is_some()),
+            ("function_patterns", function_patterns.is_some()),
+            ("stub_bodies", stub_bodies.is_some()),
+            ("script_directories", script_directories.is_some()),
+            ("script_mode", script_mode.is_some()),
+            ("example_directories", example_directories.is_some()),
+            ("example_mode", example_mode.is_some()),
+            ("experimental_decorators", experimental_decorators.is_some()),
+            ("lifecycle_mode", lifecycle_mode.is_some()),
+            ("dag_directories", dag_directories.is_some()),
+            ("dag_mode", dag_mode.is_some()),
+            ("framework", framework.is_some()),
+            ("test_name_template", test_name_template.is_some()),
+            (
+                "method_test_name_template",
+                method_test_name_template.is_some(),
+            ),
+            ("test_suggestion_style", test_suggestion_style.is_some()),
+            ("disabled_rules", disabled_rules.is_some()),
+            ("enabled_rules", enabled_rules.is_some()),
+            ("rule_switches", rule_switches.is_some()),
+            ("rule_versions", rule_versions.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(option, given)| given.then_some(option))
+        .collect();
+        let profile = profiles::Profile::parse(profile.unwrap_or("standard"))
+            .map_err(PyValueError::new_err)?;
+        let rule_selection = rule_selection::RuleSelection::default()
+            .merged(
+                disabled_rules.unwrap_or_default(),
+                enabled_rules,
+                rule_switches.unwrap_or_default(),
+            )
+            .map_err(PyValueError::new_err)?;
+        let rule_options = rule_options.unwrap_or_default();
+        let rule_versions = rule_versions.unwrap_or_default();
+        rules::metadata::validate_rule_ids(
+            rule_options
+                .keys()
+                .chain(rule_versions.keys())
+                .map(String::as_str),
+        )
+        .map_err(PyValueError::new_err)?;
+        let rule_versions =
+            rules::versions::RuleVersions::new(rule_versions).map_err(PyValueError::new_err)?;
+        let patterns = PATTERNS
+            .clone()
+            .with_function_patterns(&function_patterns.unwrap_or_default())
+            .map_err(PyValueError::new_err)?;
+        let framework = framework
+            .map(frameworks::Framework::load)
+            .transpose()
+            .map_err(PyValueError::new_err)?;
+        let dag_mode = dag_mode
+            .or_else(|| framework.as_ref()?.dag_mode.as_deref())
+            .unwrap_or("check");
+        let mut linter = Self {
             test_directories: test_directories
                 .unwrap_or_else(|| vec!["test".to_string(), "tests".to_string()]),
             test_patterns: test_patterns
                 .unwrap_or_else(|| vec!["test_*.py".to_string(), "*_test.py".to_string()]),
             exclude_patterns: exclude_patterns.unwrap_or_default(),
-            strict_mode: strict_mode.unwrap_or(false),
-            function_regex: Regex::new(r"^(\s*)def\s+(\w+)\s*\(").unwrap(),
-            class_regex: Regex::new(r"^(\s*)class\s+(\w+)").unwrap(),
-        })
+            strict_mode: strict_mode.unwrap_or(profile.strict_mode()),
+            attribute_owners: attribute_owners.unwrap_or(false),
+            attach_code_owners: attach_code_owners.unwrap_or(false),
+            todo_comments: todo_comments.unwrap_or(false),
+            test_stubs: test_stubs.unwrap_or(false),
+            rule_options: rule_options.into(),
+            // A critical threshold needs fan-in counts, so it implies the analysis
+            fan_in_analysis: fan_in_analysis.unwrap_or(critical_fan_in.is_some()),
+            critical_fan_in,
+            source_roots,
+            exclude_modules: exclude_modules
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|pattern| module_pattern_to_regex(pattern))
+                .collect(),
+            file_time_limit: budget::parse_limit(file_time_limit).map_err(PyValueError::new_err)?,
+            discovery_limits: DiscoveryLimits::new(max_depth, max_files),
+            limits_reached: Arc::default(),
+            cache_dir: cache_dir.map(PathBuf::from),
+            stats_file: stats_file.map(PathBuf::from),
+            exit_codes: exit_codes::ExitCodes::new(exit_codes.unwrap_or_default())
+                .map_err(PyValueError::new_err)?,
+            context_lines,
+            check_test_skeleton: check_test_skeleton.unwrap_or(false),
+            aggregate_missing_tests: aggregate_missing_tests.unwrap_or(false),
+            escalation: escalation::Escalation {
+                file_violations: escalate_file_violations,
+                untested_functions: escalate_untested_functions.unwrap_or(false),
+            },
+            allowlist: Arc::new(
+                allowlist::Allowlist::new(allowlist.unwrap_or_default(), allowlist_file)
+                    .map_err(PyValueError::new_err)?,
+            ),
+            require_noqa_reason: require_noqa_reason.unwrap_or(false),
+            profile,
+            rule_selection,
+            qualified_test_names: qualified_test_names.unwrap_or(profile.qualified_test_names()),
+            rule_versions,
+            declared_packages_only: declared_packages_only.unwrap_or(false),
+            build_messages: true,
+            keep_suppressed: false,
+            include_satisfied: include_satisfied.unwrap_or(false),
+            credit_delegation: credit_delegation.unwrap_or(false),
+            patterns: Arc::new(patterns),
+            stub_bodies: stub_bodies.unwrap_or_else(|| {
+                vec!["...".to_string(), "raise NotImplementedError".to_string()]
+            }),
+            scripts: scripts::Scripts::new(
+                &script_directories
+                    .unwrap_or_else(|| vec!["scripts".to_string(), "bin".to_string()]),
+                scripts::ScriptMode::parse(script_mode.unwrap_or("exempt"))
+                    .map_err(PyValueError::new_err)?,
+            ),
+            examples: examples::Examples::new(
+                &example_directories.unwrap_or_else(|| {
+                    vec![
+                        "examples".to_string(),
+                        "docs".to_string(),
+                        "samples".to_string(),
+                    ]
+                }),
+                examples::ExampleMode::parse(example_mode.unwrap_or("skip"))
+                    .map_err(PyValueError::new_err)?,
+            ),
+            lifecycles: lifecycle::Lifecycles::new(
+                &experimental_decorators.unwrap_or_else(|| vec!["experimental".to_string()]),
+                lifecycle::LifecycleMode::parse(lifecycle_mode.unwrap_or("check"))
+                    .map_err(PyValueError::new_err)?,
+            ),
+            dags: dags::Dags::new(
+                &dag_directories.unwrap_or_else(|| vec!["dags".to_string()]),
+                dags::DagMode::parse(dag_mode).map_err(PyValueError::new_err)?,
+            ),
+            decorator_policies: Arc::new(
+                decorator_policies::DecoratorPolicies::new(
+                    decorator_test_types.unwrap_or_default(),
+                )
+                .map_err(PyValueError::new_err)?,
+            ),
+            surface_policies: Arc::new(
+                surface_policies::SurfacePolicies::new(surface_test_types.unwrap_or_default())
+                    .map_err(PyValueError::new_err)?,
+            ),
+            test_names: test_names::TestNameTemplates::new(
+                test_name_template,
+                method_test_name_template,
+                test_suggestion_style,
+            )
+            .map_err(PyValueError::new_err)?,
+            docs_url: docs_url::DocsUrl::new(docs_url).map_err(PyValueError::new_err)?,
+        };
+        if let Some(framework) = &framework {
+            linter.add_framework(framework);
+        }
+        if let Some(root) = config_root {
+            if let Some(config) =
+                config::load(Path::new(root), &explicit).map_err(PyValueError::new_err)?
+            {
+                linter = linter
+                    .with_settings(&config)
+                    .map_err(PyValueError::new_err)?;
+            }
+        }
+        Ok(linter)
     }
 
     fn lint_project(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
         let project_path = Path::new(project_root);
+        let started = SystemTime::now();
+        let violations = self.lint_source_files(project_path)?;
+        let violations = self.finalize(violations, project_path);
+        self.record_run(project_path, started, &violations);
+        Ok(violations)
+    }
 
-        // Build test cache once for the entire project
-        let test_cache = TestCache::build_from_directories(project_path, &self.test_directories);
-
-        // Find all Python files
-        let python_files = find_python_files(project_path, &self.exclude_patterns);
+    /// Lint a project, returning its violations with the metadata of the run
+    fn lint_project_run(
+        &self,
+        py: Python<'_>,
+        project_root: &str,
+    ) -> PyResult<run_metadata::LintRun> {
+        let project_path = Path::new(project_root);
+        let started = SystemTime::now();
+        py.allow_threads(|| {
+            let violations = self.lint_source_files(project_path)?;
+            let violations = self.finalize(violations, project_path);
+            let metadata = self.run_metadata_since(project_path, started);
+            self.record_run(project_path, started, &violations);
+            Ok(run_metadata::LintRun {
+                exit_code: self.outcome_code(&violations),
+                violations,
+                metadata,
+            })
+        })
+    }
 
-        // Get all rules
-        let rules = get_all_rules();
+    /// Metadata of a run over the project at `project_root` with this configuration,
+    /// for runs linted another way (e.g. `lint_project_iter`) that took `duration_seconds`
+    #[pyo3(signature = (project_root, duration_seconds=0.0))]
+    fn run_metadata(&self, project_root: &str, duration_seconds: f64) -> run_metadata::RunMetadata {
+        let started = SystemTime::now() - Duration::from_secs_f64(duration_seconds.max(0.0));
+        self.run_metadata_since(Path::new(project_root), started)
+    }
 
-        // Process files in parallel with shared test cache
-        let violations: Vec<LintViolation> = python_files
-            .par_iter()
-            .filter_map(|file| {
-                self.lint_file_internal_with_cache(file, &rules, &test_cache, project_path)
-                    .ok()
+    /// Code a run reporting `violations` (from this linter or the Python wrapper: anything
+    /// with a `severity`) exits with (see `exit_codes`); suppressed violations do not count
+    fn exit_code(&self, violations: Vec<Bound<'_, PyAny>>) -> PyResult<i32> {
+        let violations = violations
+            .iter()
+            .map(|violation| match violation.downcast::<LintViolation>() {
+                Ok(violation) => Ok(violation.borrow().clone()),
+                Err(_) => Ok(LintViolation {
+                    severity: violation.getattr("severity")?.extract()?,
+                    ..Default::default()
+                }),
             })
-            .flatten()
-            .collect();
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(self.outcome_code(&violations))
+    }
 
-        Ok(violations)
+    /// Code a run with the `outcome` (e.g. `"internal_error"`) exits with
+    fn exit_code_for(&self, outcome: &str) -> PyResult<i32> {
+        let outcome = exit_codes::Outcome::parse(outcome).map_err(PyValueError::new_err)?;
+        Ok(self.exit_codes.of(outcome))
+    }
+
+    /// Lint a project a chunk at a time: an iterator of violation lists, each holding the
+    /// violations of up to `chunk_size` source files (see `stream`)
+    #[pyo3(signature = (project_root, chunk_size=200))]
+    fn lint_project_iter(
+        &self,
+        project_root: &str,
+        chunk_size: usize,
+    ) -> PyResult<stream::ViolationStream> {
+        let project_path = PathBuf::from(project_root);
+        let batches = self.source_batches(&project_path)?;
+        Ok(stream::ViolationStream::new(
+            self,
+            project_path,
+            batches,
+            chunk_size,
+        ))
+    }
+
+    /// Lint a project and write its violations to `path` in one of the report formats,
+    /// returning how many were written; nothing is handed to Python, and line-oriented
+    /// formats (csv, ndjson) are written a chunk at a time
+    #[pyo3(signature = (project_root, path, format="sarif"))]
+    fn lint_project_to_file(
+        &self,
+        py: Python<'_>,
+        project_root: &str,
+        path: &str,
+        format: &str,
+    ) -> PyResult<usize> {
+        let project_path = PathBuf::from(project_root);
+        // Reject unknown formats before linting
+        report::render(format, &[], &project_path, None).map_err(PyValueError::new_err)?;
+        let started = SystemTime::now();
+        let batches = self.source_batches(&project_path)?;
+        py.allow_threads(|| {
+            let mut out = std::io::BufWriter::new(fs::File::create(path)?);
+            let (written, metadata) = if report::STREAMABLE.contains(&format) {
+                out.write_all(report::header(format).as_bytes())?;
+                let mut stream =
+                    stream::ViolationStream::new(self, project_path.clone(), batches, 200);
+                let mut written = 0;
+                let mut by_rule = HashMap::new();
+                while let Some(chunk) = stream.next_chunk() {
+                    let output = report::render_rows(format, &chunk, &project_path)
+                        .map_err(PyValueError::new_err)?;
+                    out.write_all(output.as_bytes())?;
+                    written += chunk.len();
+                    usage_stats::count_by_rule(&chunk, &mut by_rule);
+                }
+                self.record_stats(&project_path, started, by_rule);
+                (written, self.run_metadata_since(&project_path, started))
+            } else {
+                let violations = batches
+                    .iter()
+                    .flat_map(|(linter, root, files)| linter.lint_files(root, files))
+                    .collect();
+                let violations = self.finalize(violations, &project_path);
+                let metadata = self.run_metadata_since(&project_path, started);
+                self.record_run(&project_path, started, &violations);
+                let output = report::render(format, &violations, &project_path, Some(&metadata))
+                    .map_err(PyValueError::new_err)?;
+                out.write_all(output.as_bytes())?;
+                (violations.len(), metadata)
+            };
+            out.flush()?;
+            fs::write(
+                run_metadata::sidecar_path(Path::new(path)),
+                metadata.to_json(),
+            )?;
+            Ok(written)
+        })
+    }
+
+    /// Lint a project and record its violations as a new run in the SQLite database
+    /// `database` (created if needed; see `store`), returning the run id
+    fn lint_project_to_store(
+        &self,
+        py: Python<'_>,
+        project_root: &str,
+        database: &str,
+    ) -> PyResult<i64> {
+        let violations = self.lint_project(project_root)?;
+        py.allow_threads(|| {
+            store::write(Path::new(database), Path::new(project_root), &violations)
+                .map_err(PyRuntimeError::new_err)
+        })
+    }
+
+    /// The runs over the project at `project_root` recorded in the `stats_file`, oldest
+    /// first (see `usage_stats`)
+    fn stats(&self, project_root: &str) -> PyResult<usage_stats::UsageStats> {
+        let path = self.stats_path(Path::new(project_root)).ok_or_else(|| {
+            PyValueError::new_err("No stats_file is configured, so no runs are recorded")
+        })?;
+        Ok(usage_stats::load(&path, Path::new(project_root))?)
+    }
+
+    /// Violations of a stored run (the latest when `run_id` is None) matching `sql_filter`,
+    /// a SQL `WHERE` expression over the export columns, e.g. `"rule_id = 'PL001' AND file
+    /// LIKE 'src/api/%'"`; the database is opened read-only
+    #[pyo3(signature = (database, sql_filter=None, run_id=None))]
+    fn query_store(
+        &self,
+        database: &str,
+        sql_filter: Option<&str>,
+        run_id: Option<i64>,
+    ) -> PyResult<Vec<LintViolation>> {
+        store::query(Path::new(database), sql_filter, run_id).map_err(PyValueError::new_err)
+    }
+
+    /// The runs recorded in a results store, oldest first
+    fn store_runs(&self, database: &str) -> PyResult<Vec<store::StoredRun>> {
+        store::runs(Path::new(database)).map_err(PyValueError::new_err)
+    }
+
+    /// The violations of `lint_project`, riskiest first: ranked by the commits touching
+    /// their file in the last `days` days (see `churn`); without git history every file
+    /// has a churn of 0 and the order is unchanged
+    #[pyo3(signature = (project_root, days=90))]
+    fn prioritized_violations(
+        &self,
+        py: Python<'_>,
+        project_root: &str,
+        days: u64,
+    ) -> PyResult<Vec<LintViolation>> {
+        let violations = self.lint_project(project_root)?;
+        Ok(py.allow_threads(|| {
+            let commit_counts = git::commit_counts_since(Path::new(project_root), days);
+            churn::prioritize(violations, commit_counts)
+        }))
+    }
+
+    /// Count the violations `lint_project` reports, per rule id and per file, without
+    /// formatting their messages; also counts those `noqa` comments and the allowlist
+    /// suppress, per rule id. A counts query is not recorded in the stats file as a run
+    fn lint_project_counts(&self, project_root: &str) -> PyResult<models::ViolationCounts> {
+        let project_path = Path::new(project_root);
+        let linter = Self {
+            build_messages: false,
+            keep_suppressed: true,
+            include_satisfied: false,
+            todo_comments: false,
+            test_stubs: false,
+            ..self.clone()
+        };
+        let (suppressed, violations): (Vec<_>, Vec<_>) = linter
+            .lint_source_files(project_path)?
+            .into_iter()
+            .partition(|violation| violation.suppressed);
+        let mut violations = linter.add_advisories(violations, project_path);
+        violations.extend(suppressed);
+        Ok(models::ViolationCounts::count(&violations))
+    }
+
+    /// Awaitable `lint_project`: the linting runs on a worker thread, so the event loop
+    /// stays responsive
+    #[cfg(feature = "asyncio")]
+    fn lint_project_async<'py>(
+        &self,
+        py: Python<'py>,
+        project_root: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let linter = self.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            tokio::task::spawn_blocking(move || linter.lint_project(&project_root))
+                .await
+                .map_err(|e| PyRuntimeError::new_err(format!("Linting task failed: {}", e)))?
+        })
     }
 
     fn lint_file(&self, file_path: &str) -> PyResult<Vec<LintViolation>> {
         let path = Path::new(file_path);
-        let rules = get_all_rules();
-        self.lint_file_internal(path, &rules)
+        let project_root = Self::find_project_root(path);
+        let rules = self.rules(project_root);
+        let budget = budget::FileBudget::start(self.file_time_limit);
+        let source = SourceFile::parse_within(fs::read_to_string(path)?, &self.patterns, budget);
+        let test_cache = self.test_cache(project_root);
+        let source_roots = self.source_roots(project_root);
+        let moved_aliases = self.moved_aliases(project_root, &source_roots);
+        let violations = self.lint_file_internal_with_cache(
+            path,
+            &source,
+            &rules,
+            &test_cache,
+            &moved_aliases,
+            project_root,
+            &source_roots,
+        );
+        Ok(self.finalize(violations, project_root))
     }
 
-    fn lint_changed_files(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+    /// Lint a single function, named by its qualified name (`pkg.mod.func` or
+    /// `pkg.mod.Class.method`), with the settings of the subproject holding it
+    fn lint_symbol(&self, project_root: &str, symbol: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        for (linter, root, files) in self.source_batches(project_path)? {
+            let source_roots = linter.source_roots(&root);
+            for file in files {
+                let module_path = Self::get_module_path(&file, &root, &source_roots);
+                let Some((class_name, name)) = symbol
+                    .strip_prefix(module_path.as_str())
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .map(|rest| match rest.rsplit_once('.') {
+                        Some((class_name, name)) => (Some(class_name), name),
+                        None => (None, rest),
+                    })
+                else {
+                    continue;
+                };
+                let budget = budget::FileBudget::start(linter.file_time_limit);
+                let source =
+                    SourceFile::parse_within(fs::read_to_string(&file)?, &linter.patterns, budget);
+                let is_symbol = |function_name: &str, class: Option<&str>| {
+                    function_name == name && class == class_name
+                };
+                if !source
+                    .functions
+                    .iter()
+                    .any(|function| is_symbol(&function.name, function.class_name.as_deref()))
+                {
+                    continue;
+                }
+
+                let rules = linter.rules(&root);
+                let test_cache = linter.test_cache(&root);
+                let moved_aliases = linter.moved_aliases(&root, &source_roots);
+                let mut violations = linter.lint_file_internal_with_cache(
+                    &file,
+                    &source,
+                    &rules,
+                    &test_cache,
+                    &moved_aliases,
+                    &root,
+                    &source_roots,
+                );
+                violations.retain(|violation| {
+                    is_symbol(&violation.function_name, violation.class_name.as_deref())
+                });
+                return Ok(linter.finalize(violations, &root));
+            }
+        }
+        Err(PyValueError::new_err(format!(
+            "No function '{}' found in {}",
+            symbol, project_root
+        )))
+    }
+
+    /// Lint files with uncommitted changes, plus (when `base` is given) every file changed
+    /// on the current branch since it diverged from `base`
+    #[pyo3(signature = (project_root, base=None))]
+    fn lint_changed_files(
+        &self,
+        project_root: &str,
+        base: Option<&str>,
+    ) -> PyResult<Vec<LintViolation>> {
         let project_path = Path::new(project_root);
 
         // Check if we're in a git repository
@@ -91,30 +753,39 @@ impl RustLinter {
             return Ok(Vec::new());
         }
 
-        // Get changed files
-        let changed_files = git::get_changed_files(project_path);
-
+        let changed_files = match base {
+            Some(base) => git::get_changed_files_since(project_path, base),
+            None => git::get_changed_files(project_path),
+        };
         if changed_files.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Build test cache once for the entire project
-        let test_cache = TestCache::build_from_directories(project_path, &self.test_directories);
-
-        // Get all rules
-        let rules = get_all_rules();
-
-        // Process changed files in parallel with shared test cache
-        let violations: Vec<LintViolation> = changed_files
-            .par_iter()
-            .filter_map(|file| {
-                self.lint_file_internal_with_cache(file, &rules, &test_cache, project_path)
-                    .ok()
-            })
-            .flatten()
-            .collect();
+        // Lint each changed file with the settings of its subproject, if any, leaving out
+        // test code
+        let subprojects = subprojects::discover(project_path);
+        let mut violations = Vec::new();
+        for subproject in std::iter::once(None).chain(subprojects.iter().map(Some)) {
+            let linter = match subproject {
+                None => self.clone(),
+                Some(subproject) => self
+                    .with_settings(subproject)
+                    .map_err(PyValueError::new_err)?,
+            };
+            let root = subproject.map_or(project_path, |s| s.root.as_path());
+            let test_directories = TestDirectories::new(&linter.test_directories);
+            let files: Vec<PathBuf> = changed_files
+                .iter()
+                .filter(|file| {
+                    subprojects::owns(&subprojects, subproject, file)
+                        && !test_directories.contains(file.strip_prefix(root).unwrap_or(file))
+                })
+                .cloned()
+                .collect();
+            violations.extend(linter.lint_files(root, &files));
+        }
 
-        Ok(violations)
+        Ok(self.finalize(violations, project_path))
     }
 
     fn check_test_markers(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
@@ -122,35 +793,1089 @@ impl RustLinter {
         let violations = check_test_markers(
             project_path.to_path_buf(),
             self.test_directories.clone(),
+            self.test_patterns.clone(),
             self.exclude_patterns.clone(),
         )?;
-        Ok(violations)
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check test function names against the `PL023` naming convention
+    fn check_test_naming(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let convention = pl023_test_naming::NamingConvention::from_options(&self.rule_options)
+            .map_err(PyValueError::new_err)?;
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations =
+            pl023_test_naming::check_test_naming(&test_files, &convention, &self.patterns);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check that test functions have a docstring (`PL024`)
+    fn check_test_docstrings(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl024_test_docstrings::check_test_docstrings(&test_files, &self.patterns);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check test functions against the `PL025` length and assertion limits
+    fn check_test_length(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let limits = pl025_test_length::TestLimits::from_options(&self.rule_options);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl025_test_length::check_test_length(&test_files, limits, &self.patterns);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check tests for `time.sleep` calls (`PL026`)
+    fn check_sleep_calls(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let check = pl026_no_sleep_in_tests::SleepCheck::from_options(&self.rule_options)
+            .map_err(PyValueError::new_err)?;
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations =
+            pl026_no_sleep_in_tests::check_sleep_calls(&test_files, &check, &self.patterns);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check tests for network and file-system access (`PL027`)
+    fn check_external_access(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let policy = pl027_no_external_access::AccessPolicy::from_options(&self.rule_options);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl027_no_external_access::check_external_access(&test_files, &policy);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check that tests import from the public API rather than private modules (`PL030`)
+    fn check_public_imports(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let private = pl030_public_api_imports::PrivateModules::from_options(&self.rule_options)
+            .map_err(PyValueError::new_err)?;
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl030_public_api_imports::check_public_imports(&test_files, &private);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check tests for assertions that cannot fail (`PL031`)
+    fn check_tautological_assertions(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl031_tautological_assertion::check_tautological_assertions(
+            &test_files,
+            &self.patterns,
+        );
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check that test files contain tests pytest collects (`PL032`)
+    fn check_test_files_have_tests(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations = pl032_empty_test_file::check_test_files_have_tests(&test_files);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check conftest.py placement and clashing conftest fixtures (`PL028`)
+    fn check_conftests(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let violations = pl028_conftest_placement::check_conftests(
+            project_path,
+            &self.test_directories,
+            &self.exclude_patterns,
+            &self.rule_options,
+        );
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Check that sibling test directories agree on having an `__init__.py` (`PL029`)
+    fn check_test_packages(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let import_mode =
+            pl029_test_package_consistency::import_mode(&self.rule_options, project_path)
+                .map_err(PyValueError::new_err)?;
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let violations =
+            pl029_test_package_consistency::check_test_packages(&test_files, &import_mode);
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Find private functions referenced nowhere in the project's sources or tests
+    /// (`PL007`)
+    fn check_dead_helpers(&self, project_root: &str) -> PyResult<Vec<LintViolation>> {
+        let project_path = Path::new(project_root);
+        let source_roots = self.source_roots(project_path);
+        let mut checked_files = self.find_source_files(project_path);
+        checked_files.retain(|file| {
+            !self.is_excluded_module(&Self::get_module_path(file, project_path, &source_roots))
+        });
+        let project_files = find_python_files(
+            project_path,
+            &self.exclude_patterns,
+            &TestDirectories::new(&[]),
+        );
+        let violations = pl007_dead_private_helper::check_dead_helpers(
+            &checked_files,
+            &project_files,
+            &self.patterns,
+        );
+        Ok(self.finalize(violations, project_path))
+    }
+
+    /// Score how thoroughly each checked function is tested (see `quality`)
+    fn coverage_report(&self, project_root: &str) -> PyResult<quality::CoverageReport> {
+        let project_path = Path::new(project_root);
+        let files = self.find_source_files(project_path);
+        let functions = self.function_qualities(project_path, &files);
+        Ok(quality::CoverageReport::new(functions))
+    }
+
+    /// Roll functions, tests and violations up per Python module (see `report::modules`);
+    /// subprojects are included, with their own settings, like in `lint_project`
+    fn module_report(&self, project_root: &str) -> PyResult<Vec<report::modules::ModuleSummary>> {
+        let functions: Vec<quality::FunctionQuality> = self
+            .source_batches(Path::new(project_root))?
+            .iter()
+            .flat_map(|(linter, root, files)| linter.function_qualities(root, files))
+            .collect();
+        let violations = self.lint_project(project_root)?;
+        Ok(report::modules::summarize_by_module(
+            &functions,
+            &violations,
+        ))
+    }
+
+    /// Write the JSON test map of a project to `path`: every checked function, by qualified
+    /// name, with the tests satisfying each missing-test rule (see `report::test_map`);
+    /// returns the number of functions
+    fn export_test_map(&self, py: Python<'_>, project_root: &str, path: &str) -> PyResult<usize> {
+        let batches = self.source_batches(Path::new(project_root))?;
+        py.allow_threads(|| {
+            let functions: serde_json::Map<String, serde_json::Value> = batches
+                .iter()
+                .flat_map(|(linter, root, files)| {
+                    let test_cache = linter.test_cache(root);
+                    linter.map_checked_functions(root, files, |function, module_path| {
+                        report::test_map::entry(function, module_path, &test_cache, root)
+                    })
+                })
+                .collect();
+            let count = functions.len();
+            fs::write(path, report::test_map::render(functions))?;
+            Ok(count)
+        })
+    }
+
+    /// Every `noqa` comment naming rules in the source and test files of a project, with
+    /// its reason and expiry (see `suppressions`)
+    fn suppression_report(&self, project_root: &str) -> PyResult<Vec<suppressions::Suppression>> {
+        let project_path = Path::new(project_root);
+        let mut files: Vec<PathBuf> = self
+            .source_batches(project_path)?
+            .into_iter()
+            .flat_map(|(_, _, files)| files)
+            .collect();
+        files.extend(find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        ));
+        Ok(suppressions::scan(
+            &self.patterns,
+            &files,
+            expiry::Date::today(),
+        ))
+    }
+
+    /// Fixtures defined in the conftest.py files of the test directories
+    ///
+    /// With `test_file`, only the fixtures available to that file are returned, nearest
+    /// conftest first, so the first fixture of a name is the one pytest uses. `name`
+    /// restricts the result to the fixtures of that name.
+    #[pyo3(signature = (project_root, name=None, test_file=None))]
+    fn fixtures(
+        &self,
+        project_root: &str,
+        name: Option<&str>,
+        test_file: Option<&str>,
+    ) -> Vec<fixtures::Fixture> {
+        let project_path = Path::new(project_root);
+        let test_cache = self.test_cache(project_path);
+        let candidates = match test_file {
+            Some(test_file) => test_cache.fixtures_visible_from(&project_path.join(test_file)),
+            None => test_cache.fixtures().iter().collect(),
+        };
+        candidates
+            .into_iter()
+            .filter(|fixture| name.is_none_or(|name| fixture.name == name))
+            .cloned()
+            .collect()
+    }
+
+    /// Diagnose the environment and the project at `project_root`: git availability, the
+    /// test directories and test files found, the settings in effect, and likely
+    /// misconfigurations
+    fn doctor(&self, project_root: &str) -> doctor::DoctorReport {
+        let project_path = Path::new(project_root);
+        let test_cache = self.test_cache(project_path);
+        let source_files = self.find_source_files(project_path);
+        let test_files = find_test_files(
+            project_path,
+            &self.test_directories,
+            &self.test_patterns,
+            &self.exclude_patterns,
+        );
+        let rules: Vec<&'static str> = self
+            .rules(project_path)
+            .iter()
+            .map(|rule| rule.rule_id())
+            .collect();
+
+        doctor::diagnose(
+            &doctor::Project {
+                root: project_path,
+                test_directories: &self.test_directories,
+                test_patterns: &self.test_patterns,
+                configured_source_roots: self.source_roots.as_deref(),
+                source_files: &source_files,
+                test_files: &test_files,
+                test_cache: &test_cache,
+                rules: &rules,
+            },
+            self.settings(project_path),
+        )
+    }
+
+    /// Check the internal consistency of the linter on the project at `project_root`,
+    /// returning the discrepancies found (see `self_check`): tests matched to satisfied
+    /// functions that do not exist on disk, expected test paths that do not map back to
+    /// their module, and test cache contents that differ from a fresh scan
+    fn self_check(
+        &self,
+        py: Python<'_>,
+        project_root: &str,
+    ) -> PyResult<Vec<self_check::Discrepancy>> {
+        let project_path = Path::new(project_root);
+        let linter = Self {
+            include_satisfied: true,
+            ..self.clone()
+        };
+        let batches = linter.source_batches(project_path)?;
+        Ok(py.allow_threads(|| {
+            // Matched tests are relative to the root each file was linted from
+            let mut discrepancies: Vec<self_check::Discrepancy> = batches
+                .iter()
+                .flat_map(|(linter, root, files)| {
+                    let satisfied: Vec<LintViolation> = linter
+                        .lint_files(root, files)
+                        .into_iter()
+                        .filter(|violation| violation.satisfied)
+                        .collect();
+                    self_check::missing_matched_tests(&satisfied, root, project_path)
+                })
+                .collect();
+
+            let test_cache = self.test_cache(project_path);
+            let source_roots = self.source_roots(project_path);
+            let module_of = |file: &Path| Self::get_module_path(file, project_path, &source_roots);
+            let mut files = self.find_source_files(project_path);
+            files.sort();
+            discrepancies.extend(files.iter().filter_map(|file| {
+                let module_path = module_of(file);
+                if self.is_excluded_module(&module_path) {
+                    return None;
+                }
+                self_check::expected_path_round_trip(
+                    file,
+                    &module_path,
+                    &test_cache,
+                    project_path,
+                    module_of,
+                )
+            }));
+
+            let fresh = TestCache::build_from_directories(
+                project_path,
+                &self.test_directories,
+                self.qualified_test_names,
+                None,
+            );
+            discrepancies.extend(self_check::test_cache_differences(
+                &test_cache,
+                &fresh,
+                project_path,
+            ));
+            discrepancies
+        }))
+    }
+
+    /// Write a `[tool.proboscis]` section tailored to the project at `project_root` (its
+    /// test directories, test types, package roots and pytest markers) to its
+    /// pyproject.toml, registering the markers pytest lacks, and return the text appended
+    ///
+    /// Raises `FileExistsError` if the project already has a `[tool.proboscis]` section.
+    fn init_config(&self, project_root: &str) -> PyResult<String> {
+        let project_path = Path::new(project_root);
+        let survey = init_config::Survey::of(project_path, &self.test_patterns);
+        init_config::write(project_path, &survey).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => PyFileExistsError::new_err(e.to_string()),
+            _ => e.into(),
+        })
+    }
+
+    /// Remove `# TODO(proboscis):` comments whose test now exists, returning how many
+    /// comments were removed
+    fn remove_stale_todo_comments(&self, project_root: &str) -> PyResult<usize> {
+        let project_path = Path::new(project_root);
+        let linter = Self {
+            todo_comments: true,
+            test_stubs: false,
+            ..self.clone()
+        };
+
+        let mut current: HashMap<String, HashSet<String>> = HashMap::new();
+        let violations = linter.lint_source_files(project_path)?;
+        for violation in linter.finalize(violations, project_path) {
+            if let Some(todo) = violation.fix_content {
+                current
+                    .entry(violation.file_path.to_string())
+                    .or_default()
+                    .insert(todo);
+            }
+        }
+
+        let empty = HashSet::new();
+        let mut removed = 0;
+        for file in self.find_source_files(project_path) {
+            let keep = current
+                .get(file.to_string_lossy().as_ref())
+                .unwrap_or(&empty);
+            removed += fixes::remove_stale_todos(&file, keep)?;
+        }
+        Ok(removed)
     }
 }
 
 impl RustLinter {
+    /// Run the per-function rules over every source file of the project
+    fn lint_source_files(&self, project_path: &Path) -> PyResult<Vec<LintViolation>> {
+        Ok(self
+            .source_batches(project_path)?
+            .iter()
+            .flat_map(|(linter, root, files)| linter.lint_files(root, files))
+            .collect())
+    }
+
+    /// The source files of a project run and the linter and root each is linted with:
+    /// files of a subproject with its own [tool.proboscis] are linted with its settings,
+    /// from its own directory
+    fn source_batches(&self, project_path: &Path) -> PyResult<Vec<(Self, PathBuf, Vec<PathBuf>)>> {
+        let subprojects = subprojects::discover(project_path);
+        let files_of = |linter: &Self, subproject: Option<&subprojects::Subproject>| {
+            let root = subproject.map_or(project_path, |s| s.root.as_path());
+            // Only the walk of a run reports a guard it tripped (see `config_warnings`)
+            let discovery = linter.discover_source_files(root);
+            if let Some(limit_reached) = discovery.limit_reached {
+                if let Ok(mut limits_reached) = linter.limits_reached.lock() {
+                    limits_reached.insert(root.to_path_buf(), limit_reached);
+                }
+            }
+            let mut files = discovery.files;
+            files.retain(|file| subprojects::owns(&subprojects, subproject, file));
+            files
+        };
+
+        let mut batches = vec![(
+            self.clone(),
+            project_path.to_path_buf(),
+            files_of(self, None),
+        )];
+        for subproject in &subprojects {
+            let linter = self
+                .with_settings(subproject)
+                .map_err(PyValueError::new_err)?;
+            let files = files_of(&linter, Some(subproject));
+            batches.push((linter, subproject.root.clone(), files));
+        }
+        Ok(batches)
+    }
+
+    /// Lint `files` of the project at `project_root` with the per-function rules
+    fn lint_files(&self, project_root: &Path, files: &[PathBuf]) -> Vec<LintViolation> {
+        if files.is_empty() {
+            return Vec::new();
+        }
+
+        // Build test cache once for the entire project
+        let test_cache = self.test_cache(project_root);
+        let source_roots = self.source_roots(project_root);
+        let moved_aliases = self.moved_aliases(project_root, &source_roots);
+
+        // Parse the files once per distinct content
+        let index = SourceIndex::build(files, &self.patterns, self.file_time_limit);
+
+        // Get all rules
+        let rules = self.rules(project_root);
+
+        // Process files in parallel with shared test cache
+        index.flat_map(|file, source| {
+            self.lint_file_internal_with_cache(
+                file,
+                source,
+                &rules,
+                &test_cache,
+                &moved_aliases,
+                project_root,
+                &source_roots,
+            )
+        })
+    }
+
+    /// Layer a framework pack under the settings: its exclusions and test locations are
+    /// added, and its rule options fill in those not configured
+    fn add_framework(&mut self, framework: &frameworks::Framework) {
+        self.exclude_modules.extend(
+            framework
+                .exclude_modules
+                .iter()
+                .filter_map(|pattern| module_pattern_to_regex(pattern)),
+        );
+        for (configured, added) in [
+            (&mut self.exclude_patterns, &framework.exclude_patterns),
+            (&mut self.test_directories, &framework.test_directories),
+            (&mut self.test_patterns, &framework.test_patterns),
+        ] {
+            for value in added {
+                if !configured.contains(value) {
+                    configured.push(value.clone());
+                }
+            }
+        }
+        self.rule_options.add_defaults(&framework.rule_options);
+    }
+
+    /// This linter with the `[tool.proboscis]` settings of `subproject` applied on top
+    fn with_settings(&self, subproject: &subprojects::Subproject) -> Result<Self, String> {
+        let mut linter = self.clone();
+        let framework = subproject
+            .string("framework")?
+            .map(frameworks::Framework::load)
+            .transpose()
+            .map_err(|e| format!("{}: {}", subproject.root.display(), e))?;
+        if let Some(dirs) = subproject.strings("test_directories")? {
+            linter.test_directories = dirs;
+        }
+        if let Some(patterns) = subproject.strings("test_patterns")? {
+            linter.test_patterns = patterns;
+        }
+        if let Some(patterns) = subproject.strings("exclude_patterns")? {
+            linter.exclude_patterns = patterns;
+        }
+        if let Some(patterns) = subproject.strings("exclude_modules")? {
+            linter.exclude_modules = patterns
+                .iter()
+                .filter_map(|pattern| module_pattern_to_regex(pattern))
+                .collect();
+        }
+        if let Some(roots) = subproject.strings("source_roots")? {
+            linter.source_roots = Some(roots);
+        }
+        if let Some(strict_mode) = subproject.bool("strict_mode")? {
+            linter.strict_mode = strict_mode;
+        }
+        if let Some(require_noqa_reason) = subproject.bool("require_noqa_reason")? {
+            linter.require_noqa_reason = require_noqa_reason;
+        }
+        if let Some(declared_packages_only) = subproject.bool("declared_packages_only")? {
+            linter.declared_packages_only = declared_packages_only;
+        }
+        if let Some(credit_delegation) = subproject.bool("credit_delegation")? {
+            linter.credit_delegation = credit_delegation;
+        }
+        if let Some(patterns) = subproject.strings("function_patterns")? {
+            linter.patterns = Arc::new(PATTERNS.clone().with_function_patterns(&patterns)?);
+        }
+        if let Some(bodies) = subproject.strings("stub_bodies")? {
+            linter.stub_bodies = bodies;
+        }
+        let script_directories = subproject.strings("script_directories")?;
+        let script_mode = subproject.string("script_mode")?;
+        if script_directories.is_some() || script_mode.is_some() {
+            linter.scripts = scripts::Scripts::new(
+                &script_directories.unwrap_or_else(|| self.scripts.directories.clone()),
+                match script_mode {
+                    Some(mode) => scripts::ScriptMode::parse(mode)?,
+                    None => self.scripts.mode,
+                },
+            );
+        }
+        let example_directories = subproject.strings("example_directories")?;
+        let example_mode = subproject.string("example_mode")?;
+        if example_directories.is_some() || example_mode.is_some() {
+            linter.examples = examples::Examples::new(
+                &example_directories.unwrap_or_else(|| self.examples.directories.clone()),
+                match example_mode {
+                    Some(mode) => examples::ExampleMode::parse(mode)?,
+                    None => self.examples.mode,
+                },
+            );
+        }
+        let experimental_decorators = subproject.strings("experimental_decorators")?;
+        let lifecycle_mode = subproject.string("lifecycle_mode")?;
+        if experimental_decorators.is_some() || lifecycle_mode.is_some() {
+            linter.lifecycles = lifecycle::Lifecycles::new(
+                &experimental_decorators
+                    .unwrap_or_else(|| self.lifecycles.experimental_decorators.clone()),
+                match lifecycle_mode {
+                    Some(mode) => lifecycle::LifecycleMode::parse(mode)?,
+                    None => self.lifecycles.mode,
+                },
+            );
+        }
+        let dag_directories = subproject.strings("dag_directories")?;
+        let dag_mode = subproject
+            .string("dag_mode")?
+            .or_else(|| framework.as_ref()?.dag_mode.as_deref());
+        if dag_directories.is_some() || dag_mode.is_some() {
+            linter.dags = dags::Dags::new(
+                &dag_directories.unwrap_or_else(|| self.dags.directories.clone()),
+                match dag_mode {
+                    Some(mode) => dags::DagMode::parse(mode)?,
+                    None => self.dags.mode,
+                },
+            );
+        }
+        let test_name_template = subproject.string("test_name_template")?;
+        let method_test_name_template = subproject.string("method_test_name_template")?;
+        let test_suggestion_style = subproject.string("test_suggestion_style")?;
+        if test_name_template.is_some()
+            || method_test_name_template.is_some()
+            || test_suggestion_style.is_some()
+        {
+            // The style only sets the method template when neither configuration does
+            let method_template = method_test_name_template.or(match test_suggestion_style {
+                Some(_) => None,
+                None => Some(&self.test_names.method),
+            });
+            linter.test_names = test_names::TestNameTemplates::new(
+                Some(test_name_template.unwrap_or(&self.test_names.function)),
+                method_template,
+                test_suggestion_style,
+            )?;
+        }
+        linter.rule_selection = linter
+            .rule_selection
+            .merged(
+                subproject.strings("disabled_rules")?.unwrap_or_default(),
+                subproject.strings("enabled_rules")?,
+                subproject.rule_switches(),
+            )
+            .map_err(|e| format!("{}: {}", subproject.root.display(), e))?;
+        if let Some(versions) = subproject.rule_versions()? {
+            linter.rule_versions =
+                rules::metadata::validate_rule_ids(versions.keys().map(String::as_str))
+                    .and_then(|()| linter.rule_versions.merged(versions))
+                    .map_err(|e| format!("{}: {}", subproject.root.display(), e))?;
+        }
+        if let Some(framework) = &framework {
+            linter.add_framework(framework);
+        }
+        Ok(linter)
+    }
+
+    /// The effective settings for the project at `project_path`, by name, as `doctor`
+    /// reports them
+    fn settings(&self, project_path: &Path) -> Vec<(String, String)> {
+        let rules: Vec<&'static str> = self
+            .rules(project_path)
+            .iter()
+            .map(|rule| rule.rule_id())
+            .collect();
+        let source_roots: Vec<String> = self
+            .source_roots(project_path)
+            .iter()
+            .map(|root| root.to_string_lossy().into_owned())
+            .collect();
+
+        [
+            ("profile", self.profile.name().to_string()),
+            ("test_directories", self.test_directories.join(", ")),
+            ("test_patterns", self.test_patterns.join(", ")),
+            ("exclude_patterns", self.exclude_patterns.join(", ")),
+            ("source_roots", source_roots.join(", ")),
+            ("strict_mode", self.strict_mode.to_string()),
+            (
+                "declared_packages_only",
+                self.declared_packages_only.to_string(),
+            ),
+            (
+                "qualified_test_names",
+                self.qualified_test_names.to_string(),
+            ),
+            ("rules", rules.join(", ")),
+            ("rule_versions", self.rule_versions.describe().join(", ")),
+            ("script_mode", self.scripts.mode.name().to_string()),
+            ("example_mode", self.examples.mode.name().to_string()),
+            ("lifecycle_mode", self.lifecycles.mode.name().to_string()),
+            ("dag_mode", self.dags.mode.name().to_string()),
+            ("test_name_template", self.test_names.function.clone()),
+            ("method_test_name_template", self.test_names.method.clone()),
+            (
+                "file_time_limit",
+                self.file_time_limit
+                    .map_or("unlimited".to_string(), |limit| format!("{:?}", limit)),
+            ),
+            (
+                "max_depth",
+                self.discovery_limits
+                    .max_depth
+                    .map_or("unlimited".to_string(), |limit| limit.to_string()),
+            ),
+            (
+                "max_files",
+                self.discovery_limits
+                    .max_files
+                    .map_or("unlimited".to_string(), |limit| limit.to_string()),
+            ),
+            (
+                "cache_dir",
+                self.cache_dir
+                    .as_ref()
+                    .map_or("none".to_string(), |dir| dir.to_string_lossy().into_owned()),
+            ),
+            (
+                "stats_file",
+                self.stats_file.as_ref().map_or("none".to_string(), |file| {
+                    file.to_string_lossy().into_owned()
+                }),
+            ),
+            ("exit_codes", self.exit_codes.describe()),
+            (
+                "context_lines",
+                self.context_lines
+                    .map_or("none".to_string(), |lines| lines.to_string()),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+    }
+
+    /// Metadata of a run over the project at `project_root` that started at `started`
+    fn run_metadata_since(
+        &self,
+        project_root: &Path,
+        started: SystemTime,
+    ) -> run_metadata::RunMetadata {
+        let settings: Vec<String> = self
+            .settings(project_root)
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        let settings: Vec<&str> = settings.iter().map(String::as_str).collect();
+        let rule_versions = self
+            .rules(project_root)
+            .iter()
+            .map(|rule| {
+                let rule_id = rule.rule_id();
+                (rule_id.to_string(), self.rule_versions.get(rule_id))
+            })
+            .collect();
+        run_metadata::RunMetadata {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: report::stable_hash(&settings),
+            rule_versions,
+            project_root: project_root.to_string_lossy().into_owned(),
+            git_sha: git::head_commit(project_root),
+            timestamp: run_metadata::timestamp(started),
+            duration_seconds: started
+                .elapsed()
+                .map_or(0.0, |elapsed| elapsed.as_secs_f64()),
+        }
+    }
+
+    /// Code a run reporting `violations` exits with
+    fn outcome_code(&self, violations: &[LintViolation]) -> i32 {
+        let outcome = exit_codes::Outcome::of_severities(
+            violations
+                .iter()
+                .filter(|violation| !violation.suppressed)
+                .map(|violation| violation.severity.as_str()),
+        );
+        self.exit_codes.of(outcome)
+    }
+
+    /// The stats file of the project at `project_root`, if one is configured
+    fn stats_path(&self, project_root: &Path) -> Option<PathBuf> {
+        self.stats_file.as_ref().map(|file| project_root.join(file))
+    }
+
+    /// Record a run over the project at `project_root` that started at `started` and
+    /// reported `violations` in the stats file, if one is configured
+    fn record_run(&self, project_root: &Path, started: SystemTime, violations: &[LintViolation]) {
+        if self.stats_file.is_some() {
+            let mut by_rule = HashMap::new();
+            usage_stats::count_by_rule(violations, &mut by_rule);
+            self.record_stats(project_root, started, by_rule);
+        }
+    }
+
+    /// Record a run with the violation counts `by_rule` in the stats file, if one is
+    /// configured; a stats file that cannot be written does not fail the run
+    fn record_stats(
+        &self,
+        project_root: &Path,
+        started: SystemTime,
+        by_rule: HashMap<String, usize>,
+    ) {
+        let Some(path) = self.stats_path(project_root) else {
+            return;
+        };
+        let record = usage_stats::RunRecord {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            project_root: project_root.to_string_lossy().into_owned(),
+            timestamp: run_metadata::timestamp(started),
+            duration_seconds: started
+                .elapsed()
+                .map_or(0.0, |elapsed| elapsed.as_secs_f64()),
+            violations: by_rule.values().sum(),
+            by_rule,
+        };
+        let _ = usage_stats::append(&path, &record);
+    }
+
+    /// Add the PL005 advisories and the warnings about the configuration, and merge
+    /// missing-test violations, as configured
+    fn add_advisories(
+        &self,
+        violations: Vec<LintViolation>,
+        project_root: &Path,
+    ) -> Vec<LintViolation> {
+        let mut violations = self.check_missing_tests(violations, project_root);
+        violations.extend(self.config_warnings(project_root));
+        violations
+    }
+
+    /// Add the PL005 advisories for the missing-test violations among `violations`, and
+    /// escalate and merge them per function, as configured
+    fn check_missing_tests(
+        &self,
+        violations: Vec<LintViolation>,
+        project_root: &Path,
+    ) -> Vec<LintViolation> {
+        // The records of satisfied rules (`include_satisfied`) go last, untouched
+        let (satisfied, violations): (Vec<_>, Vec<_>) = violations
+            .into_iter()
+            .partition(|violation| violation.satisfied);
+        let mut violations = self.escalation.apply(violations);
+        if self.check_test_skeleton {
+            let advisories = skeleton::check(&violations, project_root);
+            violations.extend(advisories);
+        }
+        if self.aggregate_missing_tests {
+            violations = aggregate::collapse_missing_tests(violations);
+        }
+        violations.extend(satisfied);
+        violations
+    }
+
+    /// Warnings about the configuration: deprecated rule versions this run uses, expired
+    /// allowlist entries and discovery guards tripped while finding the source files
+    fn config_warnings(&self, project_root: &Path) -> Vec<LintViolation> {
+        let running: Vec<&'static str> = self
+            .rules(project_root)
+            .iter()
+            .map(|rule| rule.rule_id())
+            .collect();
+        let mut warnings = self
+            .rule_versions
+            .deprecation_warnings(running, project_root);
+        warnings.extend(self.allowlist.expired(expiry::Date::today(), project_root));
+        if let Ok(mut limits_reached) = self.limits_reached.lock() {
+            let mut reached: Vec<(PathBuf, LimitReached)> = limits_reached
+                .extract_if(|root, _| root.starts_with(project_root))
+                .collect();
+            reached.sort();
+            warnings.extend(
+                reached
+                    .iter()
+                    .map(|(root, limit)| limit.warning(root, self.discovery_limits)),
+            );
+        }
+        warnings
+    }
+
+    /// Apply run-wide post-processing to violations produced by any entry point
+    fn finalize(&self, violations: Vec<LintViolation>, project_root: &Path) -> Vec<LintViolation> {
+        let mut violations = self.add_advisories(violations, project_root);
+        self.annotate(&mut violations, &self.annotations(project_root));
+        violations
+    }
+
+    /// What `annotate` needs from the project at `project_root`, gathered once per run
+    fn annotations(&self, project_root: &Path) -> Annotations {
+        Annotations {
+            code_owners: self
+                .attach_code_owners
+                .then(|| codeowners::CodeOwners::discover(project_root))
+                .flatten(),
+            call_graph: self
+                .fan_in_analysis
+                .then(|| call_graph::CallGraph::build(&self.find_source_files(project_root))),
+        }
+    }
+
+    /// Classify fixes and attach documentation links, owners, fan-in and source context to
+    /// `violations`, as configured
+    fn annotate(&self, violations: &mut [LintViolation], annotations: &Annotations) {
+        fixes::classify(violations);
+        for violation in violations.iter_mut() {
+            violation.docs_url = self.docs_url.of(violation.rule_id()).map(str::to_string);
+        }
+        if self.attribute_owners {
+            ownership::attribute_owners(violations);
+        }
+        if let Some(code_owners) = &annotations.code_owners {
+            code_owners.annotate(violations);
+        }
+        if let Some(graph) = &annotations.call_graph {
+            call_graph::weight_by_fan_in(violations, graph, self.critical_fan_in);
+        }
+        if let Some(lines) = self.context_lines {
+            snippets::attach(violations, lines);
+        }
+    }
+
+    /// The per-function rules to run (see `rule_selection`)
+    fn rules(&self, project_root: &Path) -> Vec<Box<dyn rules::LintRule + Send + Sync>> {
+        let mut rules = get_all_rules(&self.rule_options, &self.rule_versions, project_root);
+        rules.retain(|rule| self.is_rule_enabled(rule.rule_id()));
+        rules
+    }
+
+    /// Whether the rule `rule_id` runs (see `rule_selection`)
+    fn is_rule_enabled(&self, rule_id: &str) -> bool {
+        self.rule_selection.runs(rule_id, self.profile)
+    }
+
+    /// The test cache of the project at `project_root`
+    fn test_cache(&self, project_root: &Path) -> Arc<TestCache> {
+        TestCache::build_from_directories(
+            project_root,
+            &self.test_directories,
+            self.qualified_test_names,
+            self.cache_dir
+                .as_ref()
+                .map(|dir| project_root.join(dir))
+                .as_deref(),
+        )
+    }
+
+    /// Aliases left behind by functions moved to other modules of the project at
+    /// `project_root` (see `moved_aliases`)
+    fn moved_aliases(
+        &self,
+        project_root: &Path,
+        source_roots: &[PathBuf],
+    ) -> moved_aliases::MovedAliases {
+        moved_aliases::MovedAliases::scan(
+            &self.find_source_files(project_root),
+            project_root,
+            |file| Self::get_module_path(file, project_root, source_roots),
+            |module| Self::module_file(module, project_root, source_roots),
+        )
+    }
+
+    /// Python files to lint: everything outside excluded paths and test directories, below
+    /// the configured `source_roots` when set
+    ///
+    /// With `declared_packages_only`, files outside the packages pyproject.toml declares
+    /// are left out too, unless it declares none.
+    fn find_source_files(&self, project_root: &Path) -> Vec<PathBuf> {
+        self.discover_source_files(project_root).files
+    }
+
+    /// `find_source_files`, with the discovery guard the walk tripped, if any
+    fn discover_source_files(&self, project_root: &Path) -> Discovery {
+        let directories: Vec<PathBuf> = match &self.source_roots {
+            Some(roots) => roots.iter().map(|root| project_root.join(root)).collect(),
+            None => vec![project_root.to_path_buf()],
+        };
+        let discovery = find_python_files_in(
+            project_root,
+            &directories,
+            &self.exclude_patterns,
+            &TestDirectories::new(&self.test_directories),
+            self.discovery_limits,
+        );
+        let mut files = discovery.files;
+        if self.declared_packages_only {
+            let source_roots = self.source_roots(project_root);
+            if let Some(declared) =
+                packages::DeclaredPackages::discover(project_root, &source_roots)
+            {
+                files.retain(|file| {
+                    declared.contains(
+                        file.strip_prefix(project_root).unwrap_or(file),
+                        &Self::get_module_path(file, project_root, &source_roots),
+                    )
+                });
+            }
+        }
+        Discovery {
+            files,
+            limit_reached: discovery.limit_reached,
+        }
+    }
+
+    /// Find the project root for a single file by looking for pyproject.toml or setup.py
+    fn find_project_root(path: &Path) -> &Path {
+        let mut project_root = path.parent().unwrap_or(Path::new("."));
+        let mut current = project_root;
+        while current != current.parent().unwrap_or(current) {
+            if current.join("pyproject.toml").exists() || current.join("setup.py").exists() {
+                project_root = current;
+                break;
+            }
+            current = current.parent().unwrap_or(current);
+        }
+        project_root
+    }
+
+    /// Source roots of a project, most specific first: the `source_roots` option, else
+    /// setuptools' `packages.find.where` / `package-dir` from pyproject.toml, else `src`
+    fn source_roots(&self, project_root: &Path) -> Vec<PathBuf> {
+        let configured = self.source_roots.clone().or_else(|| {
+            let roots = pyproject::source_roots(&pyproject::load(project_root)?);
+            (!roots.is_empty()).then_some(roots)
+        });
+        let mut roots: Vec<PathBuf> = configured
+            .unwrap_or_else(|| vec!["src".to_string()])
+            .iter()
+            .map(|root| {
+                // Normalise `.` and `./python/src` so they strip cleanly
+                Path::new(root)
+                    .components()
+                    .filter(|c| !matches!(c, std::path::Component::CurDir))
+                    .collect()
+            })
+            .collect();
+        roots.sort_by_key(|root| std::cmp::Reverse(root.components().count()));
+        roots
+    }
+
+    /// The checked functions of `files` with their tests (see `quality`)
+    fn function_qualities(
+        &self,
+        project_path: &Path,
+        files: &[PathBuf],
+    ) -> Vec<quality::FunctionQuality> {
+        let test_cache = self.test_cache(project_path);
+        let index = quality::TestIndex::build(&test_cache);
+        self.map_checked_functions(project_path, files, |function, module_path| {
+            quality::FunctionQuality {
+                module_path: module_path.to_string(),
+                ..quality::assess(function, &test_cache, &index, project_path)
+            }
+        })
+    }
+
+    /// Apply `map` to the checked functions of `files`, along with the module defining
+    /// each (the function's `module_path` is the one its tests mirror)
+    fn map_checked_functions<T: Send>(
+        &self,
+        project_path: &Path,
+        files: &[PathBuf],
+        map: impl Fn(&quality::SourceFunction, &str) -> T + Sync,
+    ) -> Vec<T> {
+        let source_roots = self.source_roots(project_path);
+        let sources = SourceIndex::build(files, &self.patterns, None);
+        sources.flat_map(|file, source| {
+            let lines = source.lines();
+            let module_path = Self::get_module_path(file, project_path, &source_roots);
+            if self.is_excluded_module(&module_path) {
+                return Vec::new();
+            }
+            let public_api = Self::module_public_api(source, file, &module_path, project_path);
+
+            self.checked_functions(source, &lines, &public_api)
+                .map(|checked| {
+                    let function = quality::SourceFunction {
+                        name: &checked.function.name,
+                        class_name: checked.function.class_name.as_deref(),
+                        file_path: file,
+                        line_number: checked.function.line_number,
+                        module_path: checked.public_module.as_deref().unwrap_or(&module_path),
+                    };
+                    map(&function, &module_path)
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+
     /// Extract module path from file path (e.g., src/pkg/mod1/submod.py -> pkg.mod1.submod)
-    fn get_module_path(file_path: &Path, project_root: &Path) -> String {
+    fn get_module_path(file_path: &Path, project_root: &Path, source_roots: &[PathBuf]) -> String {
         // Get relative path from project root
         let relative_path = file_path.strip_prefix(project_root).unwrap_or(file_path);
 
-        // Remove src/ prefix if present
-        let module_path = if let Ok(stripped) = relative_path.strip_prefix("src") {
-            stripped
-        } else {
-            relative_path
-        };
+        // Remove the most specific source root containing the file
+        let module_path = source_roots
+            .iter()
+            .find_map(|root| relative_path.strip_prefix(root).ok())
+            .unwrap_or(relative_path);
 
         // Convert path to module notation
         let mut components = Vec::new();
         for component in module_path.components() {
             if let Some(s) = component.as_os_str().to_str() {
                 // Remove .py extension from the last component
-                let part = if s.ends_with(".py") {
-                    &s[..s.len() - 3]
-                } else {
-                    s
-                };
+                let part = s.strip_suffix(".py").unwrap_or(s);
                 // Skip __init__ files
                 if part != "__init__" && !part.is_empty() {
                     components.push(part);
@@ -161,127 +1886,585 @@ impl RustLinter {
         components.join(".")
     }
 
-    fn lint_file_internal(
-        &self,
+    /// File and module path of the project module `module`, looked up below the source
+    /// roots and the project root
+    fn module_file(
+        module: &str,
+        project_root: &Path,
+        source_roots: &[PathBuf],
+    ) -> Option<(PathBuf, String)> {
+        let relative: PathBuf = module.split('.').collect();
+        source_roots
+            .iter()
+            .map(|root| project_root.join(root))
+            .chain([project_root.to_path_buf()])
+            .find_map(|root| {
+                let base = root.join(&relative);
+                [base.with_extension("py"), base.join("__init__.py")]
+                    .into_iter()
+                    .find(|candidate| candidate.is_file())
+            })
+            .map(|file| {
+                let module_path = Self::get_module_path(&file, project_root, source_roots);
+                (file, module_path)
+            })
+    }
+
+    /// Public API of a module: `__all__`, plus re-exports when the module is private
+    fn module_public_api(
+        source: &SourceFile,
         path: &Path,
-        rules: &[Box<dyn rules::LintRule + Send + Sync>],
-    ) -> PyResult<Vec<LintViolation>> {
-        // For single file linting, find project root by looking for pyproject.toml or setup.py
-        let mut project_root = path.parent().unwrap_or(Path::new("."));
-        let mut current = project_root;
-        while current != current.parent().unwrap_or(current) {
-            if current.join("pyproject.toml").exists() || current.join("setup.py").exists() {
-                project_root = current;
-                break;
-            }
-            current = current.parent().unwrap_or(current);
-        }
+        module_path: &str,
+        project_root: &Path,
+    ) -> public_api::PublicApi {
+        let mut public_api = source.public_api.clone();
+        public_api.reexports = public_api::resolve_reexports(path, module_path, project_root);
+        public_api
+    }
+
+    /// Find the functions of a module that rules apply to, honouring `__all__` and
+    /// `strict_mode` and leaving out interface stubs (see `DefinedFunction::is_stub`);
+    /// lazily, so that body metrics are only parsed as the functions are reached
+    fn checked_functions<'a>(
+        &'a self,
+        source: &'a SourceFile,
+        lines: &'a [&'a str],
+        public_api: &'a public_api::PublicApi,
+    ) -> impl Iterator<Item = CheckedFunction<'a>> + 'a {
+        source
+            .functions
+            .iter()
+            .filter(|function| {
+                public_api::should_check_function(
+                    &function.name,
+                    function.class_name.as_deref(),
+                    public_api,
+                    self.strict_mode,
+                ) && !function.is_stub(lines, &self.stub_bodies)
+            })
+            .map(|function| CheckedFunction {
+                function,
+                line: lines[function.line_number - 1],
+                public_module: public_api
+                    .public_module(function.class_name.as_deref().unwrap_or(&function.name))
+                    .map(str::to_string),
+            })
+    }
 
-        let test_cache = TestCache::build_from_directories(project_root, &self.test_directories);
-        self.lint_file_internal_with_cache(path, rules, &test_cache, project_root)
+    /// Whether `module_path` matches one of the `exclude_modules` patterns
+    fn is_excluded_module(&self, module_path: &str) -> bool {
+        self.exclude_modules
+            .iter()
+            .any(|pattern| pattern.is_match(module_path))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn lint_file_internal_with_cache(
         &self,
         path: &Path,
+        source: &SourceFile,
         rules: &[Box<dyn rules::LintRule + Send + Sync>],
         test_cache: &std::sync::Arc<TestCache>,
+        moved_aliases: &moved_aliases::MovedAliases,
         project_root: &Path,
-    ) -> PyResult<Vec<LintViolation>> {
-        let content = fs::read_to_string(path)?;
-        let lines: Vec<&str> = content.lines().collect();
+        source_roots: &[PathBuf],
+    ) -> Vec<LintViolation> {
+        // Reading and parsing the file count against its time limit
+        let budget = budget::FileBudget::resume(self.file_time_limit, source.spent);
+        if source.timed_out {
+            return vec![budget.violation(path)];
+        }
+        let relative_path = path.strip_prefix(project_root).unwrap_or(path);
+        let example_mode = self.examples.mode_for(relative_path);
+        if example_mode == examples::ExampleMode::Skip {
+            return Vec::new();
+        }
+        let lines = source.lines();
 
         // Get module path for this file
-        let module_path = Self::get_module_path(path, project_root);
+        let module_path = Self::get_module_path(path, project_root, source_roots);
+        if self.is_excluded_module(&module_path) {
+            return Vec::new();
+        }
 
-        // Extract public API for this module
-        let public_api =
-            public_api::extract_module_all(path).unwrap_or(public_api::PublicApi::default());
+        // Suppressions past their expiry date no longer apply, and are reported like those
+        // without a required reason
+        let shared_path: std::sync::Arc<str> = path.to_string_lossy().into();
+        let today = expiry::Date::today();
+        let mut violations = noqa::check_suppressions(
+            &self.patterns,
+            &lines,
+            path,
+            today,
+            self.require_noqa_reason,
+        );
 
-        let mut violations = Vec::new();
-        let mut current_class = None;
-        let mut in_protocol = false;
-
-        for (line_num, line) in lines.iter().enumerate() {
-            // Check for class definitions
-            if let Some(captures) = self.class_regex.captures(line) {
-                let class_name = captures.get(2).unwrap().as_str();
-                current_class = Some(class_name.to_string());
-                in_protocol = line.contains("Protocol");
-                continue;
-            }
-
-            // Check for function definitions
-            if let Some(captures) = self.function_regex.captures(line) {
-                let indent = captures.get(1).unwrap().as_str();
-                let function_name = captures.get(2).unwrap().as_str();
-
-                // Create rule context
-                let context = rules::RuleContext {
-                    test_directories: &self.test_directories,
-                    test_cache,
-                    module_path: &module_path,
-                    project_root,
-                };
+        if self.is_rule_enabled(pl006_duplicate_definition::RULE_ID) {
+            violations.extend(pl006_duplicate_definition::check_duplicates(
+                source,
+                &lines,
+                path,
+                &shared_path,
+                &self.patterns,
+            ));
+        }
+        if self.is_rule_enabled(pl034_noqa_density::RULE_ID) {
+            violations.extend(pl034_noqa_density::check_noqa_density(
+                source,
+                &lines,
+                path,
+                &shared_path,
+                &self.rule_options,
+                &self.patterns,
+            ));
+        }
 
-                // Check if function should be checked based on public API
-                let is_method = current_class.is_some() && !indent.is_empty();
-                let class_name = if is_method {
-                    current_class.as_deref()
-                } else {
-                    None
-                };
+        if budget.is_exceeded() {
+            return vec![budget.violation(path)];
+        }
 
-                if !public_api::should_check_function(
-                    function_name,
-                    class_name,
+        // Extract public API for this module
+        let public_api = Self::module_public_api(source, path, &module_path, project_root);
+        // Modules with a small public API may only call for some test types
+        let surface_rules = if self.surface_policies.is_empty() {
+            None
+        } else {
+            self.surface_policies
+                .required_rules(surface_policies::public_symbols(
+                    source,
+                    &lines,
                     &public_api,
-                    self.strict_mode,
-                ) {
-                    continue;
-                }
+                ))
+        };
+        let file_name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("module.py");
+        let script_mode = self.scripts.mode;
+        let is_script = script_mode != scripts::ScriptMode::Check
+            && self.scripts.is_script(relative_path, &source.content);
+        let in_dag_file = self.dags.is_dag_file(relative_path);
+        // Imports of the module, to resolve the callees of facades
+        let mut aliases: Option<aliases::Aliases> = None;
+
+        for checked in self.checked_functions(source, &lines, &public_api) {
+            // Give up on the file rather than stall the whole run
+            if budget.is_exceeded() {
+                return vec![budget.violation(path)];
+            }
 
-                // Check against all rules
-                for rule in rules {
-                    let is_protocol_method = in_protocol && is_method;
+            let function = checked.function;
+            // Top-level functions of one-off scripts are exempt or only warned about
+            let in_script = is_script && function.class_name.is_none();
+            if in_script && script_mode == scripts::ScriptMode::Exempt {
+                continue;
+            }
 
-                    if let Some(violation) = rule.check_function(
-                        function_name,
+            // Create rule context
+            // Re-exported functions are tested like members of the re-exporting package
+            let (test_module, source_file_name) = match &checked.public_module {
+                Some(package) => (
+                    package.as_str(),
+                    format!("{}.py", package.rsplit('.').next().unwrap_or(package)),
+                ),
+                None => (module_path.as_str(), file_name.to_string()),
+            };
+            let metrics = function.metrics(&lines);
+            // Deprecated and experimental functions may be exempt or only warned about
+            let lifecycle = self.lifecycles.of(metrics);
+            if lifecycle.is_some() && self.lifecycles.mode == lifecycle::LifecycleMode::Exempt {
+                continue;
+            }
+            // DAG functions may be exempt, only need end-to-end tests or only be warned about
+            let in_dag = self.dags.mode != dags::DagMode::Check
+                && self
+                    .dags
+                    .is_dag_function(metrics, function.class_name.is_some(), in_dag_file);
+            if in_dag && self.dags.mode == dags::DagMode::Exempt {
+                continue;
+            }
+            // Decorators such as `@shared_task` may only call for some test types, whatever
+            // the size of the module
+            let decorator_rules = if self.decorator_policies.is_empty() {
+                None
+            } else {
+                self.decorator_policies.required_rules(&metrics.decorators)
+            };
+            let required_rules = decorator_rules.as_ref().or(surface_rules.as_ref());
+            let delegate = match metrics.delegated_call() {
+                Some(callee) if self.credit_delegation => {
+                    let aliases = aliases.get_or_insert_with(|| {
+                        aliases::Aliases::from_source(path, &source.content, project_root)
+                    });
+                    delegation::resolve(
+                        callee,
+                        &function.name,
+                        function.class_name.as_deref(),
                         path,
-                        line_num + 1,
-                        line,
-                        class_name,
-                        is_protocol_method,
-                        &context,
-                    ) {
-                        violations.push(violation);
-                    }
+                        &module_path,
+                        source,
+                        aliases,
+                        &self.patterns,
+                        |module| Self::module_file(module, project_root, source_roots),
+                    )
                 }
+                _ => None,
+            };
+            let context = rules::RuleContext {
+                test_cache,
+                file_path: &shared_path,
+                module_path: test_module,
+                source_file_name: &source_file_name,
+                project_root,
+                todo_comments: self.todo_comments,
+                test_stubs: self.test_stubs,
+                function: metrics,
+                options: &self.rule_options,
+                patterns: &self.patterns,
+                build_messages: self.build_messages,
+                test_names: &self.test_names,
+                include_satisfied: self.include_satisfied,
+                required_rules,
+                delegate: delegate.as_ref(),
+                moved_from: match &function.class_name {
+                    Some(_) => &[],
+                    None => moved_aliases.of(&module_path, &function.name),
+                },
+            };
+
+            // `noqa` on a decorator line applies like one on the `def` line, and the
+            // allowlist like a `noqa`
+            let mut suppressed =
+                noqa::function_noqa_rules(&self.patterns, &lines, function.line_number);
+            if !self.allowlist.is_empty() {
+                suppressed.extend(
+                    self.allowlist
+                        .exempt_rules(
+                            &module_path,
+                            function.class_name.as_deref(),
+                            &function.name,
+                            today,
+                        )
+                        .cloned(),
+                );
             }
 
-            // Reset class context on dedent (non-blank line with no indentation)
-            // But skip if it's a class or function definition
-            if current_class.is_some()
-                && !line.trim().is_empty()
-                && !line.starts_with(' ')
-                && !line.starts_with('\t')
-            {
-                // Don't reset if this line is defining a new class or function at module level
-                if !self.class_regex.is_match(line) && !self.function_regex.is_match(line) {
-                    current_class = None;
-                    in_protocol = false;
+            // Check against all rules
+            for rule in rules {
+                if in_dag && !self.dags.mode.applies(rule.rule_id()) {
+                    continue;
+                }
+                if !context.requires(rule.rule_id()) {
+                    continue;
+                }
+                if let Some(mut violation) = rule.check_function(
+                    &function.name,
+                    path,
+                    function.line_number,
+                    checked.line,
+                    function.class_name.as_deref(),
+                    function.is_protocol_method,
+                    &context,
+                ) {
+                    if !violation.satisfied && suppressed.contains(violation.rule_id()) {
+                        if self.keep_suppressed {
+                            violation.suppressed = true;
+                            violations.push(violation);
+                        }
+                        continue;
+                    }
+                    violation.decorator_start_line = Some(context.function.decorator_line);
+                    violation.lifecycle = lifecycle.map(|l| l.name().to_string());
+                    violation.module_path = Some(module_path.clone());
+                    if (in_script
+                        || (in_dag && self.dags.mode == dags::DagMode::Downgrade)
+                        || example_mode == examples::ExampleMode::Warn
+                        || (lifecycle.is_some()
+                            && self.lifecycles.mode == lifecycle::LifecycleMode::Downgrade)
+                        || self.profile.warnings_only())
+                        && violation.severity == "error"
+                    {
+                        violation.severity = "warning".to_string();
+                    }
+                    violations.push(violation);
                 }
             }
         }
 
-        Ok(violations)
+        fixes::record_content_hash(&mut violations, &source.content);
+        violations
+    }
+}
+
+/// Group violations by the owner attributed via `attribute_owners=True`
+#[pyfunction]
+fn violations_by_owner(violations: Vec<LintViolation>) -> HashMap<String, Vec<LintViolation>> {
+    ownership::group_by_owner(violations)
+}
+
+/// Summarize violations per CODEOWNERS owner (requires `attach_code_owners=True`)
+#[pyfunction]
+fn summarize_by_owner(violations: Vec<LintViolation>) -> Vec<report::owners::OwnerSummary> {
+    report::owners::summarize_by_owner(&violations)
+}
+
+/// POST a JSON summary of `violations` to a webhook, returning the HTTP status code
+///
+/// `template` is a JSON payload with `{{placeholder}}` markers (see `report::webhook`);
+/// the default is a Slack-compatible `{"text": ...}` message plus the summary object.
+#[pyfunction]
+#[pyo3(signature = (url, violations, project_root="", template=None))]
+fn send_webhook(
+    url: &str,
+    violations: Vec<LintViolation>,
+    project_root: &str,
+    template: Option<&str>,
+) -> PyResult<u16> {
+    let payload = report::webhook::render_payload(&violations, project_root, template)
+        .map_err(PyRuntimeError::new_err)?;
+    report::webhook::send(url, &payload).map_err(PyRuntimeError::new_err)
+}
+
+/// Render violations in a serialized report format ("checkstyle", "codeclimate", "csv",
+/// "ndjson" or "sarif"), embedding the `metadata` of their run where the format allows
+#[pyfunction]
+#[pyo3(signature = (violations, format, project_root=".", metadata=None))]
+fn format_violations(
+    violations: Vec<LintViolation>,
+    format: &str,
+    project_root: &str,
+    metadata: Option<run_metadata::RunMetadata>,
+) -> PyResult<String> {
+    report::render(
+        format,
+        &violations,
+        Path::new(project_root),
+        metadata.as_ref(),
+    )
+    .map_err(PyValueError::new_err)
+}
+
+/// Render violations for a terminal: grouped by file with paths relative to
+/// `project_root`, with the source lines around each, and colored per `color` ("auto",
+/// "always" or "never"; see `report::text`). Violations of the Python wrapper are
+/// accepted as well.
+#[pyfunction]
+#[pyo3(signature = (violations, project_root=".", color="auto"))]
+fn format_text(
+    violations: Vec<Bound<'_, PyAny>>,
+    project_root: &str,
+    color: &str,
+) -> PyResult<String> {
+    let color = report::text::ColorChoice::parse(color).map_err(PyValueError::new_err)?;
+    let violations = violations
+        .iter()
+        .map(violation_from_py)
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok(report::text::render(
+        &violations,
+        Path::new(project_root),
+        color.enabled(),
+    ))
+}
+
+/// A violation of this module, or the fields a text report shows of any object with the
+/// same attributes (e.g. the models of the Python wrapper)
+fn violation_from_py(violation: &Bound<'_, PyAny>) -> PyResult<LintViolation> {
+    if let Ok(violation) = violation.downcast::<LintViolation>() {
+        return Ok(violation.borrow().clone());
     }
+    Ok(LintViolation {
+        rule_name: violation.getattr("rule_name")?.extract()?,
+        file_path: violation.getattr("file_path")?.str()?.to_str()?.into(),
+        line_number: violation.getattr("line_number")?.extract()?,
+        message: violation.getattr("message")?.extract()?,
+        severity: violation.getattr("severity")?.extract()?,
+        lifecycle: violation.getattr("lifecycle")?.extract()?,
+        ..Default::default()
+    })
+}
+
+/// Classify violations as new, fixed or unchanged between two result sets, each a list
+/// of violations or the path of an NDJSON export; paths are compared relative to
+/// `project_root` (see `diff`)
+#[pyfunction]
+#[pyo3(signature = (old, new, project_root="."))]
+fn diff_results(
+    old: diff::ResultSet,
+    new: diff::ResultSet,
+    project_root: &str,
+) -> PyResult<diff::ResultDiff> {
+    let project_path = Path::new(project_root);
+    let old = old.load(project_path).map_err(PyValueError::new_err)?;
+    let new = new.load(project_path).map_err(PyValueError::new_err)?;
+    Ok(diff::diff(old, new, project_path))
+}
+
+/// The violations matching every criterion given: `rules` (ids like "PL001" or full rule
+/// names), `paths` (globs matched like `exclude_patterns`), `severities` and `functions`
+/// (names, or "Class.method"); the same violation objects are returned, in order
+#[pyfunction]
+#[pyo3(signature = (violations, rules=None, paths=None, severities=None, functions=None))]
+fn filter_violations<'py>(
+    violations: Vec<Bound<'py, LintViolation>>,
+    rules: Option<Vec<String>>,
+    paths: Option<Vec<String>>,
+    severities: Option<Vec<String>>,
+    functions: Option<Vec<String>>,
+) -> PyResult<Vec<Bound<'py, LintViolation>>> {
+    let filter = filtering::ViolationFilter::new(rules, paths, severities, functions)
+        .map_err(PyValueError::new_err)?;
+    Ok(violations
+        .into_iter()
+        .filter(|violation| filter.matches(&violation.borrow()))
+        .collect())
+}
+
+/// Apply the fixes attached to violations, returning a `FixReport` of the fixes applied and
+/// skipped (with the reason) and of the diff of each modified file
+///
+/// Violations carry fixes when produced with e.g. `todo_comments=True`. `safety="safe"`
+/// (the default) only applies fixes marked safe; `safety="unsafe"` applies every fix.
+#[pyfunction]
+#[pyo3(signature = (violations, safety="safe"))]
+fn apply_fixes(violations: Vec<LintViolation>, safety: &str) -> PyResult<fixes::FixReport> {
+    let max_safety = fixes::FixSafety::parse(safety).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "Unknown fix safety '{}' (expected 'safe' or 'unsafe')",
+            safety
+        ))
+    })?;
+    Ok(fixes::apply_fixes(&violations, max_safety)?)
+}
+
+/// Lint a project directory or a single file without managing a `RustLinter`
+///
+/// Keyword arguments are the `RustLinter` constructor options, e.g.
+/// `lint("src", strict_mode=True, exclude_modules=["*.migrations.*"])`; the settings
+/// configured in the directory linted (or the project of the file, as `lint_file` finds
+/// it) fill in the others, unless `config_root` names another directory.
+#[pyfunction]
+#[pyo3(signature = (path, **config))]
+fn lint(
+    py: Python<'_>,
+    path: &str,
+    config: Option<&Bound<'_, pyo3::types::PyDict>>,
+) -> PyResult<Vec<LintViolation>> {
+    let directory = match Path::new(path) {
+        file if file.is_file() => RustLinter::find_project_root(file),
+        directory => directory,
+    };
+    let linter = configured_linter(py, directory, config)?;
+    if Path::new(path).is_file() {
+        linter.lint_file(path)
+    } else {
+        linter.lint_project(path)
+    }
+}
+
+/// Open a `LintSession` on the project at `project_root`, building its state once
+///
+/// Keyword arguments are the `RustLinter` constructor options, as for `lint`, and the
+/// settings configured in the project fill in the others.
+#[pyfunction]
+#[pyo3(signature = (project_root, **config))]
+fn open_session(
+    py: Python<'_>,
+    project_root: &str,
+    config: Option<&Bound<'_, pyo3::types::PyDict>>,
+) -> PyResult<session::LintSession> {
+    let project_path = Path::new(project_root);
+    if !project_path.is_dir() {
+        return Err(PyValueError::new_err(format!(
+            "Project root {} is not a directory",
+            project_root
+        )));
+    }
+    let linter = configured_linter(py, project_path, config)?;
+    let project_path = project_path.to_path_buf();
+    Ok(py.allow_threads(|| session::LintSession::open(linter, project_path)))
+}
+
+/// A `RustLinter` with the constructor options `config`, reading the settings configured
+/// in `directory` unless they name a `config_root`
+fn configured_linter(
+    py: Python<'_>,
+    directory: &Path,
+    config: Option<&Bound<'_, pyo3::types::PyDict>>,
+) -> PyResult<RustLinter> {
+    let config = match config {
+        Some(config) => config.copy()?,
+        None => pyo3::types::PyDict::new(py),
+    };
+    if !config.contains("config_root")? {
+        config.set_item("config_root", directory.to_string_lossy())?;
+    }
+    py.get_type::<RustLinter>()
+        .call((), Some(&config))?
+        .extract()
+}
+
+/// Optional cargo features this extension was built with
+#[pyfunction]
+fn features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "gitoxide") {
+        features.push("gitoxide");
+    }
+    if cfg!(feature = "webhook") {
+        features.push("webhook");
+    }
+    if cfg!(feature = "asyncio") {
+        features.push("asyncio");
+    }
+    if cfg!(feature = "store") {
+        features.push("store");
+    }
+    features
 }
 
 /// Python module initialization
 #[pymodule]
-fn proboscis_linter_rust(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn proboscis_linter_rust(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add_class::<RustLinter>()?;
     m.add_class::<LintViolation>()?;
+    m.add_class::<models::ViolationCounts>()?;
+    m.add_class::<stream::ViolationStream>()?;
+    m.add_class::<session::LintSession>()?;
+    m.add_class::<run_metadata::RunMetadata>()?;
+    m.add_class::<run_metadata::LintRun>()?;
+    m.add_class::<report::owners::OwnerSummary>()?;
+    m.add_class::<report::modules::ModuleSummary>()?;
+    m.add_class::<suppressions::Suppression>()?;
+    m.add_class::<store::StoredRun>()?;
+    m.add_class::<diff::ResultDiff>()?;
+    m.add_class::<fixes::FixReport>()?;
+    m.add_class::<fixes::SkippedFix>()?;
+    m.add_class::<usage_stats::UsageStats>()?;
+    m.add_class::<usage_stats::RunRecord>()?;
+    m.add_class::<quality::CoverageReport>()?;
+    m.add_class::<quality::FunctionQuality>()?;
+    m.add_class::<fixtures::Fixture>()?;
+    m.add_class::<doctor::DoctorReport>()?;
+    m.add_class::<self_check::Discrepancy>()?;
+    m.add_function(wrap_pyfunction!(violations_by_owner, m)?)?;
+    m.add_function(wrap_pyfunction!(summarize_by_owner, m)?)?;
+    m.add_function(wrap_pyfunction!(send_webhook, m)?)?;
+    m.add_function(wrap_pyfunction!(format_violations, m)?)?;
+    m.add_function(wrap_pyfunction!(format_text, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_fixes, m)?)?;
+    m.add_function(wrap_pyfunction!(filter_violations, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_results, m)?)?;
+    m.add_function(wrap_pyfunction!(lint, m)?)?;
+    m.add_function(wrap_pyfunction!(open_session, m)?)?;
+    m.add_function(wrap_pyfunction!(features, m)?)?;
+
+    let rules = rules::metadata::module(py)?;
+    m.add_submodule(&rules)?;
+    // Make `import proboscis_linter_rust.rules` work, not just attribute access
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item("proboscis_linter_rust.rules", &rules)?;
     Ok(())
 }
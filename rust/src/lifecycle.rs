@@ -0,0 +1,141 @@
+//! Deprecated and experimental functions, which need not be held to the testing rules
+//!
+//! A function is deprecated when it carries a `@deprecated` decorator (PEP 702's
+//! `warnings.deprecated`, `typing_extensions.deprecated` or the `deprecated` package) or
+//! its body calls `warn(..., DeprecationWarning)`; it is experimental when it carries one
+//! of the `experimental_decorators` (`@experimental` by default). Depending on
+//! `lifecycle_mode`, such functions are checked like any other, reported as warnings, or
+//! skipped. Their violations are tagged with the lifecycle so they can be counted apart.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::parser::FunctionDef;
+
+/// `warn(...)` calls raising a (pending) deprecation warning
+static DEPRECATION_WARNING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bwarn\(.*\b(Pending)?DeprecationWarning\b").expect("valid regex")
+});
+
+/// Where a function stands in its lifecycle, when not simply supported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    Deprecated,
+    Experimental,
+}
+
+impl Lifecycle {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Deprecated => "deprecated",
+            Self::Experimental => "experimental",
+        }
+    }
+}
+
+/// How deprecated and experimental functions are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleMode {
+    /// Not checked at all
+    Exempt,
+    /// Checked, with violations reported as warnings
+    Downgrade,
+    /// Checked like any other function
+    Check,
+}
+
+impl LifecycleMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "exempt" => Ok(Self::Exempt),
+            "downgrade" => Ok(Self::Downgrade),
+            "check" => Ok(Self::Check),
+            other => Err(format!(
+                "lifecycle_mode must be 'exempt', 'downgrade' or 'check', got '{}'",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Exempt => "exempt",
+            Self::Downgrade => "downgrade",
+            Self::Check => "check",
+        }
+    }
+}
+
+/// Lifecycle detection for a lint run
+#[derive(Debug, Clone)]
+pub struct Lifecycles {
+    /// The `experimental_decorators` setting: decorator names, dotted or not
+    pub experimental_decorators: Vec<String>,
+    pub mode: LifecycleMode,
+}
+
+impl Lifecycles {
+    pub fn new(experimental_decorators: &[String], mode: LifecycleMode) -> Self {
+        Self {
+            experimental_decorators: experimental_decorators.to_vec(),
+            mode,
+        }
+    }
+
+    /// The lifecycle of `function`, or None for a supported function
+    pub fn of(&self, function: &FunctionDef) -> Option<Lifecycle> {
+        let last_segment = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+        if function
+            .decorators
+            .iter()
+            .any(|decorator| last_segment(decorator) == "deprecated")
+            || function
+                .body
+                .iter()
+                .any(|line| DEPRECATION_WARNING.is_match(line))
+        {
+            return Some(Lifecycle::Deprecated);
+        }
+        let experimental = function.decorators.iter().any(|decorator| {
+            self.experimental_decorators.iter().any(|name| {
+                // `experimental` matches `@experimental` and `@api.experimental`
+                decorator == name || (!name.contains('.') && last_segment(decorator) == *name)
+            })
+        });
+        experimental.then_some(Lifecycle::Experimental)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_function;
+
+    fn lifecycle_of(source: &str) -> Option<Lifecycle> {
+        let lines: Vec<&str> = source.lines().collect();
+        let def_line = lines.iter().position(|l| l.contains("def ")).unwrap() + 1;
+        Lifecycles::new(&["experimental".to_string()], LifecycleMode::Exempt)
+            .of(&parse_function(&lines, def_line))
+    }
+
+    #[test]
+    fn test_lifecycle_of() {
+        assert_eq!(
+            lifecycle_of("@typing_extensions.deprecated(\"use bar\")\ndef foo():\n    return 1\n"),
+            Some(Lifecycle::Deprecated)
+        );
+        assert_eq!(
+            lifecycle_of("def foo():\n    warnings.warn(\n        \"use bar\", DeprecationWarning, stacklevel=2\n    )\n    return bar()\n"),
+            Some(Lifecycle::Deprecated)
+        );
+        assert_eq!(
+            lifecycle_of("@api.experimental\ndef foo():\n    return 1\n"),
+            Some(Lifecycle::Experimental)
+        );
+        assert_eq!(
+            lifecycle_of("def foo():\n    warnings.warn(\"slow\", UserWarning)\n"),
+            None
+        );
+        assert!(LifecycleMode::parse("skip").is_err());
+    }
+}
@@ -1,17 +1,21 @@
 use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
 
 #[pyclass]
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct LintViolation {
     #[pyo3(get)]
     pub rule_name: String,
-    #[pyo3(get)]
-    pub file_path: String,
+    /// Shared by the violations of a file rather than copied into each of them
+    pub file_path: Arc<str>,
     #[pyo3(get)]
     pub line_number: usize,
     #[pyo3(get)]
     pub function_name: String,
-    #[pyo3(get)]
+    /// Message set by the rule; rules that leave it empty have it rendered from the
+    /// structured fields by `message()`
     pub message: String,
     #[pyo3(get)]
     pub severity: String,
@@ -21,4 +25,188 @@ pub struct LintViolation {
     pub fix_content: Option<String>,
     #[pyo3(get)]
     pub fix_line: Option<usize>,
+    /// "safe" or "unsafe"; only safe fixes are applied by default
+    #[pyo3(get)]
+    pub fix_safety: Option<String>,
+    /// Hash of the file's content when it was linted, on violations carrying a fix;
+    /// `apply_fixes` re-anchors the fix by its function when the file has changed since
+    #[pyo3(get)]
+    pub content_hash: Option<String>,
+    /// Documentation of the rule, rendered from the `docs_url` template (see `docs_url`)
+    #[pyo3(get)]
+    pub docs_url: Option<String>,
+    /// Author of the most recent commit touching the offending function
+    #[pyo3(get)]
+    pub author: Option<String>,
+    #[pyo3(get)]
+    pub author_email: Option<String>,
+    /// Hex id of the most recent commit touching the offending function
+    #[pyo3(get)]
+    pub commit: Option<String>,
+    /// Owners assigned to the file by CODEOWNERS
+    #[pyo3(get)]
+    pub code_owners: Vec<String>,
+    /// Number of call sites of the function across the project (with `fan_in_analysis=True`)
+    #[pyo3(get)]
+    pub fan_in: Option<usize>,
+    /// Commits touching the file in the window of `prioritized_violations`
+    #[pyo3(get)]
+    pub churn: Option<usize>,
+    /// Test file a missing-test violation (PL001–PL003) expects the test in
+    #[pyo3(get)]
+    pub expected_test_file: Option<String>,
+    /// First decorator line of the offending function (its `def` line when undecorated),
+    /// for violations reported against a function
+    #[pyo3(get)]
+    pub decorator_start_line: Option<usize>,
+    /// Class of the offending method
+    #[pyo3(get)]
+    pub class_name: Option<String>,
+    /// Test function a missing-test violation (PL001–PL003) expects
+    #[pyo3(get)]
+    pub expected_test_name: Option<String>,
+    /// "deprecated" or "experimental" for violations of such functions (see `lifecycle`)
+    #[pyo3(get)]
+    pub lifecycle: Option<String>,
+    /// Dotted module of the offending source file (e.g. `pkg.sub.mod`), for violations
+    /// reported against a function
+    #[pyo3(get)]
+    pub module_path: Option<String>,
+    /// Set on the records of rules a function satisfies, listed with
+    /// `include_satisfied=True` (severity "info"); never set on violations
+    #[pyo3(get)]
+    pub satisfied: bool,
+    /// Tests satisfying the rule, as `test_file::test_name` relative to the project root
+    #[pyo3(get)]
+    pub matched_tests: Vec<String>,
+    /// Source around the offending definition, decorators included (with `context_lines`)
+    #[pyo3(get)]
+    pub context: Option<String>,
+    /// Line number of the first line of `context`
+    #[pyo3(get)]
+    pub context_start_line: Option<usize>,
+    /// Whether a `noqa` comment or the allowlist suppresses the violation; suppressed
+    /// violations are only kept to be counted (see `ViolationCounts::suppressed_by_rule`)
+    pub suppressed: bool,
+    /// `message()` rendered from the structured fields, on first access
+    pub rendered_message: OnceLock<String>,
+}
+
+#[pymethods]
+impl LintViolation {
+    /// Path of the offending file, as an interned string shared by all violations of
+    /// the file
+    #[getter]
+    pub fn file_path<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        PyString::intern(py, &self.file_path)
+    }
+
+    /// Whether the violation carries a fix `apply_fixes` can apply
+    #[getter]
+    pub fn fix_available(&self) -> bool {
+        self.fix_type.is_some()
+    }
+
+    /// Human-readable description of the violation
+    #[getter]
+    pub fn message(&self) -> &str {
+        if !self.message.is_empty() {
+            return &self.message;
+        }
+        self.rendered_message
+            .get_or_init(|| crate::rules::render_message(self))
+    }
+}
+
+impl LintViolation {
+    /// Rule id portion of `rule_name` (e.g. "PL001" for "PL001:require-unit-test")
+    pub fn rule_id(&self) -> &str {
+        self.rule_name.split(':').next().unwrap_or(&self.rule_name)
+    }
+}
+
+/// Violation counts, as returned by `RustLinter.lint_project_counts`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct ViolationCounts {
+    #[pyo3(get)]
+    pub total: usize,
+    /// Violations per rule id (e.g. "PL001")
+    #[pyo3(get)]
+    pub by_rule: HashMap<String, usize>,
+    /// Violations per file path
+    #[pyo3(get)]
+    pub by_file: HashMap<String, usize>,
+    /// Violations of deprecated and experimental functions, per lifecycle; they are
+    /// included in the counts above
+    #[pyo3(get)]
+    pub by_lifecycle: HashMap<String, usize>,
+    /// Violations suppressed by `noqa` comments or the allowlist, per rule id; they are
+    /// not included in the counts above
+    #[pyo3(get)]
+    pub suppressed_by_rule: HashMap<String, usize>,
+}
+
+impl ViolationCounts {
+    pub fn count(violations: &[LintViolation]) -> Self {
+        let mut counts = Self::default();
+        for violation in violations {
+            if violation.suppressed {
+                *counts
+                    .suppressed_by_rule
+                    .entry(violation.rule_id().to_string())
+                    .or_default() += 1;
+                continue;
+            }
+            counts.total += 1;
+            *counts
+                .by_rule
+                .entry(violation.rule_id().to_string())
+                .or_default() += 1;
+            *counts
+                .by_file
+                .entry(violation.file_path.to_string())
+                .or_default() += 1;
+            if let Some(lifecycle) = &violation.lifecycle {
+                *counts.by_lifecycle.entry(lifecycle.clone()).or_default() += 1;
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation_counts() {
+        let violation = |rule_name: &str, file_path: &str| LintViolation {
+            rule_name: rule_name.to_string(),
+            file_path: file_path.into(),
+            ..Default::default()
+        };
+        let deprecated = LintViolation {
+            lifecycle: Some("deprecated".to_string()),
+            ..violation("PL001:require-unit-test", "b.py")
+        };
+        let suppressed = LintViolation {
+            suppressed: true,
+            ..violation("PL002:require-integration-test", "b.py")
+        };
+        let counts = ViolationCounts::count(&[
+            violation("PL001:require-unit-test", "a.py"),
+            violation("PL002:require-integration-test", "a.py"),
+            deprecated,
+            suppressed,
+        ]);
+        assert_eq!(counts.total, 3);
+        assert_eq!(counts.by_rule["PL001"], 2);
+        assert_eq!(counts.by_rule["PL002"], 1);
+        assert_eq!(counts.by_file["a.py"], 2);
+        assert_eq!(counts.by_lifecycle["deprecated"], 1);
+        assert!(!counts.by_lifecycle.contains_key("experimental"));
+        assert_eq!(counts.suppressed_by_rule["PL002"], 1);
+        assert!(!counts.suppressed_by_rule.contains_key("PL001"));
+    }
 }
@@ -0,0 +1,143 @@
+//! Aliases left behind by moved functions
+//!
+//! Moving `foo` from `old_mod` to `new_mod` often leaves a module-level alias behind so
+//! callers keep working: `foo = new_mod.foo`, or `from pkg.new_mod import foo as _foo`
+//! then `legacy_foo = _foo`. The tests usually stay where they were, testing the alias.
+//! Like a package re-exporting a function, the alias is another face of the same
+//! function: its tests are credited to the function, which passes PL001–PL003 when the
+//! alias is tested in the old module's test file.
+//!
+//! Only plain assignments of an imported name are aliases; calls, attributes of classes
+//! and assignments of names defined in the same module are not.
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::aliases::Aliases;
+use crate::delegation::Delegate;
+use crate::parser;
+use crate::patterns::PATTERNS;
+
+/// The aliases of moved functions in a project, by the module and name of the function
+#[derive(Debug, Default)]
+pub struct MovedAliases {
+    by_function: HashMap<(String, String), Vec<Delegate>>,
+}
+
+/// Module-level `name = dotted.name` assignments of `content` (no calls)
+fn assignments(content: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    parser::code_lines(&lines)
+        .iter()
+        .filter(|code| !code.starts_with(char::is_whitespace))
+        .filter_map(|code| PATTERNS.assignment.captures(code.trim_end()))
+        .filter(|captures| !captures[2].contains('('))
+        .map(|captures| (captures[1].to_string(), captures[2].trim().to_string()))
+        .collect()
+}
+
+impl MovedAliases {
+    /// Find the aliases in `files` of the project at `project_root`
+    ///
+    /// `module_path` gives the module path of a file, and `module_file` maps a dotted
+    /// module to its file and module path, when in the project.
+    pub fn scan(
+        files: &[PathBuf],
+        project_root: &Path,
+        module_path: impl Fn(&Path) -> String + Sync,
+        module_file: impl Fn(&str) -> Option<(PathBuf, String)> + Sync,
+    ) -> Self {
+        let found: Vec<((String, String), Delegate)> = files
+            .par_iter()
+            .flat_map_iter(|file| {
+                let content = fs::read_to_string(file).unwrap_or_default();
+                let assignments = assignments(&content);
+                // Imports are only resolved for files that may hold an alias
+                let aliases = (!assignments.is_empty())
+                    .then(|| Aliases::from_source(file, &content, project_root));
+                let alias_module = module_path(file);
+                assignments
+                    .into_iter()
+                    .filter_map(|(name, value)| {
+                        let target = aliases.as_ref()?.resolve(&value);
+                        // A bare name is defined in the same module, not imported
+                        let (module, function) = target.rsplit_once('.')?;
+                        let (_, module) = module_file(module)?;
+                        Some((
+                            (module, function.to_string()),
+                            Delegate {
+                                file_path: file.clone(),
+                                module_path: alias_module.clone(),
+                                name,
+                                class_name: None,
+                            },
+                        ))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut by_function: HashMap<(String, String), Vec<Delegate>> = HashMap::new();
+        for (function, alias) in found {
+            if alias.module_path != function.0 {
+                by_function.entry(function).or_default().push(alias);
+            }
+        }
+        Self { by_function }
+    }
+
+    /// The aliases of the function `name` of `module_path`
+    pub fn of(&self, module_path: &str, name: &str) -> &[Delegate] {
+        self.by_function
+            .get(&(module_path.to_string(), name.to_string()))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        fs::write(dir.join("pkg/__init__.py"), "").unwrap();
+        fs::write(
+            dir.join("pkg/new_mod.py"),
+            "def foo():\n    pass\n\ndef bar():\n    pass\n",
+        )
+        .unwrap();
+        let old_mod = dir.join("pkg/old_mod.py");
+        fs::write(
+            &old_mod,
+            "from pkg import new_mod\nfrom pkg.new_mod import bar as _bar\nimport requests\n\nfoo = new_mod.foo\nlegacy_bar = _bar\nget = requests.get\nclient = new_mod.Client()\n\ndef helper():\n    pass\n\nalias = helper\n",
+        )
+        .unwrap();
+        let files = vec![dir.join("pkg/new_mod.py"), old_mod.clone()];
+        let module_path = |file: &Path| {
+            let relative = file.strip_prefix(dir).unwrap().with_extension("");
+            relative.to_string_lossy().replace('/', ".")
+        };
+        let module_file = |module: &str| {
+            let file = dir.join(module.replace('.', "/")).with_extension("py");
+            file.is_file().then(|| (file, module.to_string()))
+        };
+        let moved = MovedAliases::scan(&files, dir, module_path, module_file);
+
+        let aliases = |name: &str| {
+            moved
+                .of("pkg.new_mod", name)
+                .iter()
+                .map(|alias| (alias.module_path.as_str(), alias.name.as_str()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(aliases("foo"), vec![("pkg.old_mod", "foo")]);
+        assert_eq!(aliases("bar"), vec![("pkg.old_mod", "legacy_bar")]);
+        assert!(moved.of("pkg.old_mod", "helper").is_empty());
+        assert!(moved.of("requests", "get").is_empty());
+    }
+}
@@ -1,5 +1,10 @@
-use regex::Regex;
 use std::collections::HashSet;
+use std::path::Path;
+
+use crate::expiry::{self, Date};
+use crate::models::LintViolation;
+use crate::parser;
+use crate::patterns::{Patterns, PATTERNS};
 
 /// Parse noqa comments and return the set of suppressed rules
 /// Supports formats:
@@ -7,30 +12,122 @@ use std::collections::HashSet;
 ///   - #noqa: PL001
 ///   - #noqa PL001, PL002
 ///   - #noqa: PL001, PL002
+///   - #noqa: PL001 until=2025-12-31 (suppresses nothing after that day; see `expiry`)
+///   - #noqa: PL001 -- covered by contract tests (a reason; see `require_noqa_reason`)
 pub fn parse_noqa_rules(line: &str) -> HashSet<String> {
-    let mut rules = HashSet::new();
-
-    // Match #noqa with optional colon, followed by rule codes
-    // This regex captures everything after #noqa or #noqa:
-    let noqa_regex = Regex::new(r"#\s*noqa(?:\s*:)?\s*(.*)").unwrap();
-
-    if let Some(captures) = noqa_regex.captures(line) {
-        if let Some(rules_str) = captures.get(1) {
-            // Split by comma and/or whitespace
-            let rules_part = rules_str.as_str();
-
-            // Split by comma first, then trim whitespace
-            for rule in rules_part.split(',') {
-                let trimmed = rule.trim();
-                // Only add if it matches pattern PLxxx
-                if trimmed.starts_with("PL") && trimmed.len() > 2 {
-                    rules.insert(trimmed.to_string());
-                }
-            }
+    parse_noqa_rules_with(&PATTERNS, line)
+}
+
+/// `parse_noqa_rules` matching `noqa` comments with the given patterns
+pub fn parse_noqa_rules_with(patterns: &Patterns, line: &str) -> HashSet<String> {
+    parse_noqa_with(patterns, line)
+        .filter(|noqa| expiry::is_active(noqa.until, Date::today()))
+        .map(|noqa| noqa.rules)
+        .unwrap_or_default()
+}
+
+/// Violation of a `noqa` comment without a reason, with `require_noqa_reason=True`
+pub const MISSING_REASON_RULE: &str = "PL000:noqa-without-reason";
+
+/// A `noqa` comment: the rules it names, its expiry date and its reason, if any
+#[derive(Debug, Default)]
+pub struct Noqa<'a> {
+    pub rules: HashSet<String>,
+    /// The text after `until=`
+    pub until: Option<&'a str>,
+    /// The text after `--`
+    pub reason: Option<&'a str>,
+}
+
+/// Parse the `noqa` comment of `line`, if it has one
+pub fn parse_noqa_with<'a>(patterns: &Patterns, line: &'a str) -> Option<Noqa<'a>> {
+    // The capture is everything after #noqa or #noqa:, up to the reason
+    let captured = patterns.noqa.captures(line)?.get(1)?.as_str();
+    let (rules_part, reason) = match captured.split_once("--") {
+        Some((rules_part, reason)) => (rules_part, Some(reason.trim())),
+        None => (captured, None),
+    };
+    let mut noqa = Noqa {
+        reason: reason.filter(|reason| !reason.is_empty()),
+        ..Default::default()
+    };
+    // Split by commas and/or whitespace
+    for token in rules_part.split([',', ' ', '\t']) {
+        let trimmed = token.trim();
+        if let Some(until) = trimmed.strip_prefix(expiry::UNTIL) {
+            noqa.until = Some(until);
+        } else if trimmed.starts_with("PL") && trimmed.len() > 2 {
+            // Only add if it matches pattern PLxxx
+            noqa.rules.insert(trimmed.to_string());
+        }
+    }
+    Some(noqa)
+}
+
+/// Violations of the `noqa` comments of `lines` (of `file_path`) that name rules:
+/// `PL000:expired-suppression` for those past their expiry date on `today`, and with
+/// `require_reason`, `PL000:noqa-without-reason` for those without a reason
+pub fn check_suppressions(
+    patterns: &Patterns,
+    lines: &[&str],
+    file_path: &Path,
+    today: Date,
+    require_reason: bool,
+) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if !line.contains("noqa") {
+            continue;
+        }
+        let Some(noqa) = parse_noqa_with(patterns, line) else {
+            continue;
+        };
+        if noqa.rules.is_empty() {
+            continue;
+        }
+        let mut rules: Vec<&str> = noqa.rules.iter().map(String::as_str).collect();
+        rules.sort();
+        let rules = rules.join(", ");
+        if let Some(until) = noqa
+            .until
+            .filter(|until| !expiry::is_active(Some(until), today))
+        {
+            violations.push(expiry::violation(file_path, idx + 1, &rules, until));
+        }
+        if require_reason && noqa.reason.is_none() {
+            violations.push(LintViolation {
+                rule_name: MISSING_REASON_RULE.to_string(),
+                file_path: file_path.to_string_lossy().into(),
+                line_number: idx + 1,
+                message: format!(
+                    "[PL000] Suppression of {} gives no reason.\nLocation: {}:{}\n\nTip: Say why after the rules, e.g. # noqa: {} -- covered by contract tests",
+                    rules,
+                    file_path.display(),
+                    idx + 1,
+                    rules
+                ),
+                severity: "error".to_string(),
+                ..Default::default()
+            });
         }
     }
+    violations
+}
 
-    rules
+/// Rules suppressed for the function whose `def` is on `def_line` (1-based): by `noqa`
+/// comments on the `def` line or on any line of its decorators
+pub fn function_noqa_rules(
+    patterns: &Patterns,
+    lines: &[&str],
+    def_line: usize,
+) -> HashSet<String> {
+    let start = parser::decorator_start_line(lines, def_line);
+    lines
+        .iter()
+        .take(def_line)
+        .skip(start.saturating_sub(1))
+        .flat_map(|line| parse_noqa_rules_with(patterns, line))
+        .collect()
 }
 
 #[cfg(test)]
@@ -87,4 +184,68 @@ mod tests {
         let rules = parse_noqa_rules("def foo():  # just a comment");
         assert_eq!(rules.len(), 0);
     }
+
+    #[test]
+    fn test_parse_noqa_with_expiry() {
+        assert!(parse_noqa_rules("def foo():  # noqa: PL001 until=2000-01-01").is_empty());
+        assert!(parse_noqa_rules("def foo():  # noqa: PL001 until=2999-01-01").contains("PL001"));
+
+        let lines = [
+            "def foo():  # noqa: PL001, PL002 until=2025-06-29",
+            "def bar():  # noqa: PL001 until=2025-06-30",
+            "def baz():  # noqa: PL001 until=tomorrow",
+        ];
+        let today = Date::parse("2025-06-30").unwrap();
+        let expired = check_suppressions(&PATTERNS, &lines, Path::new("m.py"), today, false);
+        assert_eq!(expired.len(), 2);
+        assert_eq!(expired[0].line_number, 1);
+        assert!(expired[0]
+            .message
+            .contains("Suppression of PL001, PL002 expired on 2025-06-29"));
+        assert_eq!(expired[1].line_number, 3);
+        assert!(expired[1]
+            .message
+            .contains("invalid expiry date 'tomorrow'"));
+    }
+
+    #[test]
+    fn test_parse_noqa_with_reason() {
+        let noqa = parse_noqa_with(
+            &PATTERNS,
+            "def foo():  # noqa: PL001, PL002 until=2999-01-01 -- PLanned rewrite",
+        )
+        .unwrap();
+        assert_eq!(noqa.rules.len(), 2);
+        assert_eq!(noqa.until, Some("2999-01-01"));
+        assert_eq!(noqa.reason, Some("PLanned rewrite"));
+
+        let lines = [
+            "def foo():  # noqa: PL001",
+            "def bar():  # noqa: PL001 --",
+            "def baz():  # noqa: PL001 -- generated",
+            "x = 1  # noqa",
+        ];
+        let today = Date::parse("2025-06-30").unwrap();
+        let unjustified = check_suppressions(&PATTERNS, &lines, Path::new("m.py"), today, true);
+        let lines: Vec<usize> = unjustified.iter().map(|v| v.line_number).collect();
+        assert_eq!(lines, [1, 2]);
+        assert_eq!(unjustified[0].rule_name, MISSING_REASON_RULE);
+    }
+
+    #[test]
+    fn test_function_noqa_rules_include_decorators() {
+        let lines = [
+            "x = 1  # noqa: PL003",
+            "@app.route(  # noqa: PL001",
+            "    \"/\",",
+            ")",
+            "@login_required  # noqa: PL002",
+            "def index():",
+            "    pass",
+        ];
+        let rules = function_noqa_rules(&PATTERNS, &lines, 6);
+        assert_eq!(rules.len(), 2);
+        assert!(rules.contains("PL001") && rules.contains("PL002"));
+        assert!(function_noqa_rules(&PATTERNS, &lines, 7).is_empty());
+    }
 }
@@ -0,0 +1,103 @@
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::git::{self, BlameInfo};
+use crate::models::LintViolation;
+use crate::parser::function_end_line;
+
+/// Owner key used for violations whose function could not be blamed
+pub const UNKNOWN_OWNER: &str = "unknown";
+
+/// Annotate each violation with the last commit touching its function
+///
+/// Violations sharing a function (e.g. PL001–PL003 on the same `def`) are blamed once.
+pub fn attribute_owners(violations: &mut [LintViolation]) {
+    let targets: HashSet<(Arc<str>, usize)> = violations
+        .iter()
+        .map(|v| (v.file_path.clone(), v.line_number))
+        .collect();
+
+    let files: HashSet<&Arc<str>> = targets.iter().map(|(file, _)| file).collect();
+    let contents: HashMap<&Arc<str>, String> = files
+        .into_par_iter()
+        .filter_map(|file| {
+            fs::read_to_string(&**file)
+                .ok()
+                .map(|content| (file, content))
+        })
+        .collect();
+
+    let blames: HashMap<(Arc<str>, usize), BlameInfo> = targets
+        .par_iter()
+        .filter_map(|(file, line)| {
+            let content = contents.get(file)?;
+            let lines: Vec<&str> = content.lines().collect();
+            let end = function_end_line(&lines, *line);
+            let path = Path::new(&**file);
+            let repo_dir = path.parent().unwrap_or(Path::new("."));
+            let info = git::blame_range(repo_dir, path, *line, end)?;
+            Some(((file.clone(), *line), info))
+        })
+        .collect();
+
+    for violation in violations.iter_mut() {
+        if let Some(info) = blames.get(&(violation.file_path.clone(), violation.line_number)) {
+            violation.author = Some(info.author.clone());
+            violation.author_email = Some(info.author_email.clone());
+            violation.commit = Some(info.commit.clone());
+        }
+    }
+}
+
+/// Owner of a violation: the blamed author's email, falling back to their name
+pub fn owner_of(violation: &LintViolation) -> String {
+    violation
+        .author_email
+        .as_ref()
+        .filter(|email| !email.is_empty())
+        .or(violation.author.as_ref())
+        .cloned()
+        .unwrap_or_else(|| UNKNOWN_OWNER.to_string())
+}
+
+/// Group violations by owner
+pub fn group_by_owner(violations: Vec<LintViolation>) -> HashMap<String, Vec<LintViolation>> {
+    let mut groups: HashMap<String, Vec<LintViolation>> = HashMap::new();
+    for violation in violations {
+        groups
+            .entry(owner_of(&violation))
+            .or_default()
+            .push(violation);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_owner() {
+        let owned = LintViolation {
+            author: Some("Alice".to_string()),
+            author_email: Some("alice@example.com".to_string()),
+            ..Default::default()
+        };
+        let name_only = LintViolation {
+            author: Some("Bob".to_string()),
+            ..Default::default()
+        };
+        let groups = group_by_owner(vec![
+            owned.clone(),
+            owned,
+            name_only,
+            LintViolation::default(),
+        ]);
+        assert_eq!(groups["alice@example.com"].len(), 2);
+        assert_eq!(groups["Bob"].len(), 1);
+        assert_eq!(groups[UNKNOWN_OWNER].len(), 1);
+    }
+}
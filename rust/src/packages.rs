@@ -0,0 +1,232 @@
+//! Packages a project declares for distribution (`declared_packages_only`)
+//!
+//! Read from `[tool.setuptools]` (`packages`, `packages.find`, `py-modules`),
+//! `[tool.poetry] packages`, `[tool.hatch.build.targets.wheel] packages` and
+//! `[tool.flit.module]`; failing those, the package named after `[project] name`, if the
+//! project has one. Files outside them (stray scripts, examples, docs snippets) are not
+//! linted when discovery is limited to declared packages.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use crate::file_discovery::module_pattern_to_regex;
+use crate::pyproject;
+
+#[derive(Debug, Default)]
+pub struct DeclaredPackages {
+    /// Dotted module patterns, each covering its submodules
+    modules: Vec<Regex>,
+    /// Directories (relative to the project root) whose files all belong to a package
+    directories: Vec<PathBuf>,
+}
+
+impl DeclaredPackages {
+    /// The packages declared by the pyproject.toml of `project_root`, or None when it
+    /// declares none; `source_roots` locate the package named after the project
+    pub fn discover(project_root: &Path, source_roots: &[PathBuf]) -> Option<Self> {
+        let pyproject = pyproject::load(project_root)?;
+        let mut declared = Self::from_pyproject(&pyproject);
+        if declared.modules.is_empty() && declared.directories.is_empty() {
+            let name = pyproject
+                .get("project")
+                .and_then(|project| project.get("name"))
+                .and_then(|name| name.as_str())?;
+            let package = name.to_lowercase().replace(['-', '.'], "_");
+            let exists = source_roots
+                .iter()
+                .map(PathBuf::as_path)
+                .chain([Path::new("")])
+                .any(|root| {
+                    let base = project_root.join(root);
+                    base.join(&package).is_dir() || base.join(format!("{}.py", package)).is_file()
+                });
+            if !exists {
+                return None;
+            }
+            declared.add_module(&package);
+        }
+        Some(declared)
+    }
+
+    /// The packages listed explicitly in `pyproject`
+    fn from_pyproject(pyproject: &toml::Table) -> Self {
+        let mut declared = Self::default();
+        let tool = |name: &str| pyproject.get("tool").and_then(|tool| tool.get(name));
+        let strings = |value: Option<&toml::Value>| -> Vec<String> {
+            value
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        if let Some(setuptools) = tool("setuptools") {
+            match setuptools.get("packages") {
+                Some(toml::Value::Array(_)) => {
+                    for package in strings(setuptools.get("packages")) {
+                        declared.add_module(&package);
+                    }
+                }
+                Some(packages) => {
+                    if let Some(find) = packages.get("find") {
+                        let include = strings(find.get("include"));
+                        if include.is_empty() {
+                            let mut dirs = strings(find.get("where"));
+                            if dirs.is_empty() {
+                                dirs.push(".".to_string());
+                            }
+                            declared
+                                .directories
+                                .extend(dirs.iter().map(|dir| normalize(dir)));
+                        } else {
+                            declared
+                                .modules
+                                .extend(include.iter().filter_map(|p| module_pattern_to_regex(p)));
+                        }
+                    }
+                }
+                None => {}
+            }
+            for module in strings(setuptools.get("py-modules")) {
+                declared.add_module(&module);
+            }
+        }
+
+        let poetry_packages = tool("poetry")
+            .and_then(|poetry| poetry.get("packages"))
+            .and_then(|packages| packages.as_array());
+        for package in poetry_packages.into_iter().flatten() {
+            let Some(include) = package.get("include").and_then(|i| i.as_str()) else {
+                continue;
+            };
+            let from = package.get("from").and_then(|f| f.as_str()).unwrap_or(".");
+            let include = include.strip_suffix(".py").unwrap_or(include);
+            // A glob such as `pkg/**/*.py` covers the directory before its first wildcard
+            let base = include
+                .split('*')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches('/');
+            if base.is_empty() {
+                continue;
+            }
+            declared.directories.push(normalize(from).join(base));
+            if !include.contains('*') {
+                declared.add_module(&include.replace('/', "."));
+            }
+        }
+
+        let hatch_packages = tool("hatch")
+            .and_then(|hatch| hatch.get("build"))
+            .and_then(|build| build.get("targets"))
+            .and_then(|targets| targets.get("wheel"))
+            .and_then(|wheel| wheel.get("packages"));
+        for package in strings(hatch_packages) {
+            declared.directories.push(normalize(&package));
+        }
+
+        if let Some(module) = tool("flit")
+            .and_then(|flit| flit.get("module"))
+            .and_then(|module| module.get("name"))
+            .and_then(|name| name.as_str())
+        {
+            declared.add_module(module);
+        }
+        declared
+    }
+
+    /// Declare the module or package `name` (dotted), with its submodules
+    fn add_module(&mut self, name: &str) {
+        if let Ok(regex) = Regex::new(&format!(r"^{}(\..*)?$", regex::escape(name))) {
+            self.modules.push(regex);
+        }
+    }
+
+    /// Whether the file at `relative_path` (relative to the project root), whose module
+    /// path is `module_path`, belongs to a declared package
+    pub fn contains(&self, relative_path: &Path, module_path: &str) -> bool {
+        // A single-module package `cli` is declared like a directory
+        let without_extension = relative_path.with_extension("");
+        self.directories
+            .iter()
+            .any(|dir| relative_path.starts_with(dir) || without_extension == *dir)
+            || self
+                .modules
+                .iter()
+                .any(|module| module.is_match(module_path))
+    }
+}
+
+/// `dir` without `.` components, so `./src` and `.` compare as paths
+fn normalize(dir: &str) -> PathBuf {
+    Path::new(dir)
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared(content: &str) -> DeclaredPackages {
+        DeclaredPackages::from_pyproject(&content.parse().unwrap())
+    }
+
+    #[test]
+    fn test_setuptools_packages() {
+        let packages = declared(
+            "[tool.setuptools]\npackages = [\"pkg\", \"other.sub\"]\npy-modules = [\"tool\"]\n",
+        );
+        assert!(packages.contains(Path::new("src/pkg/a.py"), "pkg.a"));
+        assert!(packages.contains(Path::new("other/sub/__init__.py"), "other.sub"));
+        assert!(packages.contains(Path::new("tool.py"), "tool"));
+        assert!(!packages.contains(Path::new("other/b.py"), "other.b"));
+        assert!(!packages.contains(Path::new("pkgx/a.py"), "pkgx.a"));
+
+        let found = declared("[tool.setuptools.packages.find]\nwhere = [\"lib\"]\n");
+        assert!(found.contains(Path::new("lib/pkg/a.py"), "pkg.a"));
+        assert!(!found.contains(Path::new("examples/demo.py"), "examples.demo"));
+        let included = declared("[tool.setuptools.packages.find]\ninclude = [\"pkg*\"]\n");
+        assert!(included.contains(Path::new("pkg_extra/a.py"), "pkg_extra.a"));
+        assert!(!included.contains(Path::new("docs/conf.py"), "docs.conf"));
+    }
+
+    #[test]
+    fn test_poetry_hatch_and_flit_packages() {
+        let poetry = declared(
+            "[tool.poetry]\npackages = [{include = \"app\", from = \"python\"}, {include = \"cli.py\"}]\n",
+        );
+        assert!(poetry.contains(Path::new("python/app/core.py"), "python.app.core"));
+        assert!(poetry.contains(Path::new("cli.py"), "cli"));
+        assert!(!poetry.contains(Path::new("scripts/run.py"), "scripts.run"));
+
+        let hatch = declared("[tool.hatch.build.targets.wheel]\npackages = [\"src/pkg\"]\n");
+        assert!(hatch.contains(Path::new("src/pkg/a.py"), "pkg.a"));
+        assert!(!hatch.contains(Path::new("src/other/a.py"), "other.a"));
+
+        let flit = declared("[tool.flit.module]\nname = \"pkg\"\n");
+        assert!(flit.contains(Path::new("pkg/__init__.py"), "pkg"));
+    }
+
+    #[test]
+    fn test_discover_from_project_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(root.join("src/my_tool")).unwrap();
+        std::fs::write(
+            root.join("pyproject.toml"),
+            "[project]\nname = \"My-Tool\"\n",
+        )
+        .unwrap();
+        let packages = DeclaredPackages::discover(root, &[PathBuf::from("src")]).unwrap();
+        assert!(packages.contains(Path::new("src/my_tool/a.py"), "my_tool.a"));
+        assert!(!packages.contains(Path::new("examples/a.py"), "examples.a"));
+        // Without a package named after the project, nothing is declared
+        assert!(DeclaredPackages::discover(root, &[PathBuf::from("lib")]).is_none());
+    }
+}
@@ -0,0 +1,673 @@
+//! Lightweight structural parsing of Python source
+//!
+//! This is not a full Python parser: it understands just enough (string literals, comments,
+//! bracket nesting, line continuations and indentation) to split source into logical lines
+//! and measure individual functions.
+
+/// A function definition and the metrics computed from its body
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionDef {
+    /// 1-based line of the `def`
+    pub line: usize,
+    /// 1-based last line of the body (trailing blank lines excluded)
+    pub end_line: usize,
+    /// 1-based last line of the signature (the line ending in `:`)
+    pub signature_end: usize,
+    /// Code of the signature up to its closing `:`, with strings blanked and comments
+    /// removed
+    pub signature: String,
+    /// Whether the body starts with a docstring
+    pub has_docstring: bool,
+    /// Number of statements in the body, excluding docstrings and other bare strings
+    pub statements: usize,
+    /// McCabe cyclomatic complexity: 1 plus the number of decision points
+    pub complexity: usize,
+    /// Decorator names without arguments, outermost first (e.g. `app.route`)
+    pub decorators: Vec<String>,
+    /// 1-based line of the first decorator (the `def` line when there are none)
+    pub decorator_line: usize,
+    /// Code of each logical line of the body, with strings blanked and comments removed
+    pub body: Vec<String>,
+}
+
+/// A parameter of a function signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// Name, prefixed with `*` or `**` for variadic parameters
+    pub name: String,
+    /// Whether the parameter has a type annotation
+    pub annotated: bool,
+}
+
+/// Keywords that each add a decision point to the cyclomatic complexity
+const BRANCH_KEYWORDS: &[&str] = &["if", "elif", "for", "while", "except", "and", "or", "case"];
+
+/// One logical line: physical lines joined across brackets, strings and continuations
+#[derive(Debug)]
+struct LogicalLine {
+    /// 0-based index of the last physical line
+    last: usize,
+    /// Indentation of the first physical line
+    indent: usize,
+    /// Code with string contents blanked to `""` and comments removed
+    code: String,
+}
+
+/// Parse the function whose `def` is on `def_line` (1-based)
+pub fn parse_function(lines: &[&str], def_line: usize) -> FunctionDef {
+    let mut function = FunctionDef {
+        line: def_line,
+        end_line: def_line,
+        signature_end: def_line,
+        signature: String::new(),
+        has_docstring: false,
+        statements: 0,
+        complexity: 1,
+        decorators: Vec::new(),
+        decorator_line: def_line,
+        body: Vec::new(),
+    };
+    let Some(def) = lines.get(def_line.wrapping_sub(1)) else {
+        return function;
+    };
+    let def_indent = indentation(def);
+    function.decorators = decorators(lines, def_line - 1, def_indent);
+    function.decorator_line = decorator_start(lines, def_line - 1, def_indent) + 1;
+
+    let mut logical = logical_lines(lines, def_line - 1);
+    let Some(signature) = logical.next() else {
+        return function;
+    };
+    function.end_line = signature.last + 1;
+    function.signature_end = signature.last + 1;
+
+    // `def f(): return x` keeps its body on the signature line
+    let colon = top_level_colon(&signature.code);
+    let inline_body = colon
+        .map(|colon| signature.code[colon + 1..].trim().to_string())
+        .unwrap_or_default();
+    function.signature = match colon {
+        Some(colon) => signature.code[..colon].to_string(),
+        None => signature.code,
+    };
+    let mut body = vec![inline_body];
+    if body[0].is_empty() {
+        body.clear();
+        for line in logical {
+            if line.code.trim().is_empty() {
+                continue;
+            }
+            if line.indent <= def_indent {
+                break;
+            }
+            function.end_line = line.last + 1;
+            body.push(line.code);
+        }
+    }
+
+    function.has_docstring = body
+        .first()
+        .and_then(|code| split_statements(code).next())
+        .is_some_and(is_bare_string);
+    for code in &body {
+        function.statements += split_statements(code)
+            .filter(|statement| !is_bare_string(statement))
+            .count();
+        function.complexity += code
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| BRANCH_KEYWORDS.contains(word))
+            .count();
+    }
+    function.body = body;
+    function
+}
+
+impl FunctionDef {
+    /// Byte offsets of the parentheses around the parameters in `signature`
+    fn parameter_list(&self) -> Option<(usize, usize)> {
+        let open = self.signature.find('(')?;
+        let mut depth = 0usize;
+        for (i, c) in self.signature[open..].char_indices() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Some((open, open + i));
+            }
+        }
+        None
+    }
+
+    /// Parameters of the signature, without the bare `*` and `/` separators
+    pub fn parameters(&self) -> Vec<Parameter> {
+        let Some((open, close)) = self.parameter_list() else {
+            return Vec::new();
+        };
+        let list = &self.signature[open + 1..close];
+        let mut parts = Vec::new();
+        let (mut depth, mut start) = (0usize, 0);
+        for (i, c) in list.char_indices() {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(&list[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&list[start..]);
+        parts
+            .into_iter()
+            .map(str::trim)
+            .filter(|part| !matches!(*part, "" | "*" | "/"))
+            .map(|part| {
+                // The annotation comes before any default value
+                let end = part.find([':', '=']).unwrap_or(part.len());
+                Parameter {
+                    name: part[..end].trim().to_string(),
+                    annotated: part[end..].starts_with(':'),
+                }
+            })
+            .collect()
+    }
+
+    /// The return annotation of the signature (`-> int`), if any
+    pub fn return_annotation(&self) -> Option<&str> {
+        let (_, close) = self.parameter_list()?;
+        self.signature[close + 1..]
+            .trim()
+            .strip_prefix("->")
+            .map(str::trim)
+    }
+
+    /// Number of physical lines in the body, after the signature
+    pub fn body_line_count(&self) -> usize {
+        self.end_line - self.signature_end
+    }
+
+    /// Whether the body, docstring aside, is a single statement of one of the `forms`
+    /// (e.g. `...`, `raise NotImplementedError`); a form also matches when called with
+    /// arguments, as in `raise NotImplementedError("subclasses implement this")`
+    pub fn is_stub_body(&self, forms: &[String]) -> bool {
+        let mut statements = self
+            .body
+            .iter()
+            .flat_map(|code| split_statements(code))
+            .filter(|statement| !is_bare_string(statement));
+        let (Some(statement), None) = (statements.next(), statements.next()) else {
+            return false;
+        };
+        let statement: String = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+        forms.iter().any(|form| {
+            let form = form.split_whitespace().collect::<Vec<_>>().join(" ");
+            statement.strip_prefix(&form).is_some_and(|rest| {
+                let rest = rest.trim_start();
+                rest.is_empty() || (rest.starts_with('(') && rest.ends_with(')'))
+            })
+        })
+    }
+
+    /// The function called when the body, docstring aside, is a single `return` of a call
+    /// (`return service.create_user(name)`, `return await self._send(msg)`), as written
+    pub fn delegated_call(&self) -> Option<&str> {
+        let mut statements = self
+            .body
+            .iter()
+            .flat_map(|code| split_statements(code))
+            .filter(|statement| !is_bare_string(statement));
+        let (Some(statement), None) = (statements.next(), statements.next()) else {
+            return None;
+        };
+        let call = statement.strip_prefix("return ")?.trim_start();
+        let call = call.strip_prefix("await ").unwrap_or(call).trim();
+        let open = call.find('(')?;
+        let callee = call[..open].trim_end();
+        let is_name = !callee.is_empty()
+            && callee
+                .split('.')
+                .all(|part| part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+        // The call must be the whole expression: its opening parenthesis closes last
+        let mut depth = 0;
+        for (idx, c) in call.char_indices().skip(open) {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return (is_name && idx == call.len() - 1).then_some(callee);
+            }
+        }
+        None
+    }
+
+    /// Number of `assert...` calls/statements and `raises` checks in the body
+    pub fn assertion_count(&self) -> usize {
+        self.body
+            .iter()
+            .flat_map(|code| code.split(|c: char| !(c.is_alphanumeric() || c == '_')))
+            .filter(|word| word.starts_with("assert") || *word == "raises")
+            .count()
+    }
+}
+
+/// Find the last line (1-based) of the function whose `def` is on `def_line` (1-based)
+pub fn function_end_line(lines: &[&str], def_line: usize) -> usize {
+    parse_function(lines, def_line).end_line
+}
+
+/// Decorators applied to the `def` on `def_line` (1-based) with their arguments, without
+/// the `@` and whitespace (e.g. `pytest.mark.xfail(strict=True)`), outermost first
+pub fn decorator_calls(lines: &[&str], def_line: usize) -> Vec<String> {
+    let Some(def) = lines.get(def_line.wrapping_sub(1)) else {
+        return Vec::new();
+    };
+    decorator_code(lines, def_line - 1, indentation(def))
+}
+
+/// Names of the decorators applied to the `def` at `def_idx` (0-based)
+fn decorators(lines: &[&str], def_idx: usize, def_indent: usize) -> Vec<String> {
+    decorator_code(lines, def_idx, def_indent)
+        .into_iter()
+        .map(|call| match call.split_once('(') {
+            Some((name, _)) => name.to_string(),
+            None => call,
+        })
+        .collect()
+}
+
+/// 1-based line of the first decorator of the `def` on `def_line` (1-based), or `def_line`
+/// itself when it has none
+pub fn decorator_start_line(lines: &[&str], def_line: usize) -> usize {
+    let Some(def) = lines.get(def_line.wrapping_sub(1)) else {
+        return def_line;
+    };
+    decorator_start(lines, def_line - 1, indentation(def)) + 1
+}
+
+/// Source lines of the decorators applied to the `def` on `def_line` (1-based), as written
+///
+/// Unlike `decorator_calls`, string arguments are kept (e.g. `scope="session"`).
+pub fn decorator_source<'a>(lines: &'a [&'a str], def_line: usize) -> &'a [&'a str] {
+    if def_line == 0 || def_line > lines.len() {
+        return &[];
+    }
+    &lines[decorator_start_line(lines, def_line) - 1..def_line - 1]
+}
+
+fn decorator_code(lines: &[&str], def_idx: usize, def_indent: usize) -> Vec<String> {
+    let start = decorator_start(lines, def_idx, def_indent);
+    logical_lines(&lines[..def_idx], start)
+        .filter_map(|line| {
+            let call = line.code.trim().strip_prefix('@')?;
+            Some(call.chars().filter(|c| !c.is_whitespace()).collect())
+        })
+        .collect()
+}
+
+/// Index of the first decorator line above the `def` at `def_idx` (or `def_idx` itself)
+fn decorator_start(lines: &[&str], def_idx: usize, def_indent: usize) -> usize {
+    // Walk up over decorator lines and the continuation lines of multi-line decorators
+    let mut start = def_idx;
+    for idx in (0..def_idx).rev() {
+        let line = lines[idx];
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || indentation(line) < def_indent {
+            break;
+        }
+        if indentation(line) == def_indent {
+            if trimmed.starts_with('@') {
+                start = idx;
+            } else if !trimmed.starts_with([')', ']', '}']) {
+                break;
+            }
+        }
+    }
+    start
+}
+
+pub fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Code of every logical line in `lines`, with strings blanked and comments removed
+pub fn code_lines(lines: &[&str]) -> Vec<String> {
+    logical_lines(lines, 0).map(|line| line.code).collect()
+}
+
+/// Split source into logical lines starting at physical line `start` (0-based)
+fn logical_lines<'a>(lines: &'a [&str], start: usize) -> impl Iterator<Item = LogicalLine> + 'a {
+    let mut idx = start;
+    std::iter::from_fn(move || {
+        let first_line = lines.get(idx)?;
+        let mut scanner = Scanner::default();
+        let mut code = String::new();
+        loop {
+            let line = lines.get(idx)?;
+            idx += 1;
+            let (line_code, continued) = scanner.scan(line);
+            code.push_str(&line_code);
+            if !continued || idx >= lines.len() {
+                break;
+            }
+            code.push(' ');
+        }
+        Some(LogicalLine {
+            last: idx - 1,
+            indent: indentation(first_line),
+            code,
+        })
+    })
+}
+
+/// Tracks string and bracket state while scanning the physical lines of one logical line
+#[derive(Default)]
+struct Scanner {
+    /// Quote characters of an unterminated triple-quoted string
+    open_triple: Option<char>,
+    depth: usize,
+}
+
+impl Scanner {
+    /// Return the code of `line` and whether the logical line continues on the next line
+    fn scan(&mut self, line: &str) -> (String, bool) {
+        let chars: Vec<char> = line.chars().collect();
+        let mut code = String::new();
+        let mut i = 0;
+
+        if let Some(quote) = self.open_triple {
+            match find_triple_end(&chars, 0, quote) {
+                Some(end) => {
+                    self.open_triple = None;
+                    code.push_str("\"\"");
+                    i = end;
+                }
+                None => return (code, true),
+            }
+        }
+
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                '#' => break,
+                '"' | '\'' => {
+                    code.push_str("\"\"");
+                    if chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c) {
+                        match find_triple_end(&chars, i + 3, c) {
+                            Some(end) => i = end,
+                            None => {
+                                self.open_triple = Some(c);
+                                return (code, true);
+                            }
+                        }
+                    } else {
+                        i += 1;
+                        while i < chars.len() && chars[i] != c {
+                            i += if chars[i] == '\\' { 2 } else { 1 };
+                        }
+                        i += 1;
+                    }
+                    continue;
+                }
+                '(' | '[' | '{' => self.depth += 1,
+                ')' | ']' | '}' => self.depth = self.depth.saturating_sub(1),
+                _ => {}
+            }
+            code.push(c);
+            i += 1;
+        }
+
+        let explicit = code.ends_with('\\');
+        if explicit {
+            code.pop();
+        }
+        (code, explicit || self.depth > 0)
+    }
+}
+
+/// Index just past the closing triple quote at or after `from`, if any
+fn find_triple_end(chars: &[char], from: usize, quote: char) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == quote && chars.get(i + 1) == Some(&quote) && chars.get(i + 2) == Some(&quote)
+        {
+            return Some(i + 3);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Byte offset of the first `:` outside brackets (the end of a `def` signature)
+fn top_level_colon(code: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in code.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split a logical line into `;`-separated statements, dropping empty parts
+fn split_statements(code: &str) -> impl Iterator<Item = &str> {
+    code.split(';')
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty())
+}
+
+/// Whether a statement is only a string literal (e.g. a docstring)
+fn is_bare_string(statement: &str) -> bool {
+    // Each blanked literal may carry a prefix such as `r`, `b` or `f`
+    statement.contains("\"\"")
+        && statement.split("\"\"").all(|part| {
+            let part = part.trim();
+            part.len() <= 2 && part.chars().all(|c| "rbufRBUF".contains(c))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stub_body() {
+        let forms = vec!["...".to_string(), "raise NotImplementedError".to_string()];
+        let lines = vec![
+            "def a(x: int) -> int: ...",
+            "def b(self):",
+            "    \"\"\"Docs.\"\"\"",
+            "    raise NotImplementedError(\"subclasses\")",
+            "def c(self):",
+            "    raise NotImplementedErrorSubclass()",
+            "def d(self):",
+            "    x = 1",
+            "    ...",
+        ];
+        assert!(parse_function(&lines, 1).is_stub_body(&forms));
+        assert!(parse_function(&lines, 2).is_stub_body(&forms));
+        assert!(!parse_function(&lines, 5).is_stub_body(&forms));
+        assert!(!parse_function(&lines, 7).is_stub_body(&forms));
+    }
+
+    #[test]
+    fn test_delegated_call() {
+        let lines = vec![
+            "def a(name):",
+            "    \"\"\"Create a user.\"\"\"",
+            "    return service.create_user(",
+            "        name, role=\"admin\"",
+            "    )",
+            "async def b(self, msg):",
+            "    return await self._send(msg)",
+            "def c(x):",
+            "    return helper(x).value",
+            "def d(x):",
+            "    return helper(x) + 1",
+            "def e(x):",
+            "    log(x)",
+            "    return helper(x)",
+            "def f(x): return build(x)[0]",
+        ];
+        assert_eq!(
+            parse_function(&lines, 1).delegated_call(),
+            Some("service.create_user")
+        );
+        assert_eq!(
+            parse_function(&lines, 6).delegated_call(),
+            Some("self._send")
+        );
+        for def_line in [8, 10, 12, 15] {
+            assert_eq!(parse_function(&lines, def_line).delegated_call(), None);
+        }
+    }
+
+    #[test]
+    fn test_parameters_and_return_annotation() {
+        let lines = vec![
+            "def a(self, name: str, *args: int, flag=lambda x: x, /, d={1: 2}, **kw) -> Dict[str, int]:",
+            "    pass",
+            "async def b(",
+            "    items: \"List[Item]\",  # forward reference",
+            "    *,",
+            "    limit: int = 10,",
+            "): return items",
+        ];
+        let a = parse_function(&lines, 1);
+        let parameters = a.parameters();
+        assert_eq!(
+            parameters
+                .iter()
+                .map(|p| (p.name.as_str(), p.annotated))
+                .collect::<Vec<_>>(),
+            vec![
+                ("self", false),
+                ("name", true),
+                ("*args", true),
+                ("flag", false),
+                ("d", false),
+                ("**kw", false),
+            ]
+        );
+        assert_eq!(a.return_annotation(), Some("Dict[str, int]"));
+
+        let b = parse_function(&lines, 3);
+        assert!(b.parameters().iter().all(|p| p.annotated));
+        assert_eq!(b.parameters().len(), 2);
+        assert_eq!(b.return_annotation(), None);
+    }
+
+    #[test]
+    fn test_function_end_line_simple() {
+        let lines = vec![
+            "def foo():",
+            "    x = 1",
+            "",
+            "    return x",
+            "",
+            "def bar():",
+            "    pass",
+        ];
+        assert_eq!(function_end_line(&lines, 1), 4);
+        assert_eq!(function_end_line(&lines, 6), 7);
+    }
+
+    #[test]
+    fn test_function_end_line_multiline_signature() {
+        let lines = vec![
+            "class A:",
+            "    def method(",
+            "        self,",
+            "        x,",
+            "    ):",
+            "        return x",
+            "    def other(self):",
+            "        pass",
+        ];
+        assert_eq!(function_end_line(&lines, 2), 6);
+        assert_eq!(parse_function(&lines, 2).signature_end, 5);
+        assert_eq!(parse_function(&lines, 2).body_line_count(), 1);
+        assert!(!parse_function(&lines, 2).has_docstring);
+    }
+
+    #[test]
+    fn test_docstrings_and_strings_do_not_end_function() {
+        let lines = vec![
+            "def foo():",
+            "    \"\"\"Docstring",
+            "",
+            "not indented",
+            "    \"\"\"",
+            "    text = 'if # not a comment'",
+            "    return text",
+            "def bar(): return 1",
+        ];
+        let foo = parse_function(&lines, 1);
+        assert_eq!(foo.end_line, 7);
+        assert_eq!(foo.statements, 2);
+        assert_eq!(foo.complexity, 1);
+        assert!(foo.has_docstring);
+
+        let bar = parse_function(&lines, 8);
+        assert_eq!(bar.end_line, 8);
+        assert_eq!(bar.statements, 1);
+        assert!(!bar.has_docstring);
+    }
+
+    #[test]
+    fn test_decorators() {
+        let lines = vec![
+            "class A:",
+            "    @app.route(",
+            "        \"/items\",",
+            "    )",
+            "    @ staticmethod",
+            "    def items():",
+            "        pass",
+        ];
+        assert_eq!(
+            parse_function(&lines, 6).decorators,
+            vec!["app.route".to_string(), "staticmethod".to_string()]
+        );
+        assert!(parse_function(&lines, 1).decorators.is_empty());
+        assert_eq!(
+            decorator_calls(&lines, 6),
+            vec!["app.route(\"\",)".to_string(), "staticmethod".to_string()]
+        );
+        assert_eq!(parse_function(&lines, 6).decorator_line, 2);
+        assert_eq!(decorator_start_line(&lines, 1), 1);
+    }
+
+    #[test]
+    fn test_complexity_counts_decision_points() {
+        let lines = vec![
+            "def classify(values):",
+            "    result = []",
+            "    for value in values:",
+            "        if value > 0 and value < 10:",
+            "            result.append('small')",
+            "        elif value >= 10:",
+            "            result.append('large')",
+            "    try:",
+            "        check(result)",
+            "    except ValueError:",
+            "        pass",
+            "    return [v for v in result if v]",
+        ];
+        let function = parse_function(&lines, 1);
+        assert_eq!(function.statements, 11);
+        // 1 + for + if + and + elif + except + comprehension for/if
+        assert_eq!(function.complexity, 8);
+    }
+}
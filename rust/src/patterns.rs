@@ -0,0 +1,218 @@
+//! Registry of the regexes used to scan Python source
+//!
+//! Rules, the test cache, the source index and the noqa parser all match the same kinds of
+//! lines (`def`, decorators, imports, `noqa` comments). Every pattern lives here, compiled
+//! once on first use, so no caller recompiles one per call and no two callers disagree on
+//! what a `def` line looks like. Rules reach the registry through `RuleContext::patterns`.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// The shared registry
+pub static PATTERNS: LazyLock<Patterns> = LazyLock::new(Patterns::new);
+
+/// Compiled patterns, grouped by what they match
+#[derive(Clone)]
+pub struct Patterns {
+    // Definitions
+    /// `def name(`: captures the indent and the name; `async def` is not matched
+    pub def: Regex,
+    /// `def name(` or `async def name(`: captures the name
+    pub any_def: Regex,
+    /// `def test...(`, sync or async, as pytest collects them: captures the name
+    pub test_function: Regex,
+    /// User-defined function definitions (`function_patterns`), such as functions bound
+    /// by a factory call; each has a `name` group
+    pub function_patterns: Vec<Regex>,
+    /// `class Name`: captures the indent and the name
+    pub class: Regex,
+    /// `class Name(ABC)` or `class Name(metaclass=ABCMeta)`: an abstract base class
+    pub abstract_class: Regex,
+    /// `@decorator`: captures the indent and the decorator
+    pub decorator: Regex,
+
+    // Imports and module-level bindings
+    /// `import a, b as c`: captures the imported list
+    pub import: Regex,
+    /// `from .pkg import names`: captures the leading dots, the module and the names
+    pub from_import: Regex,
+    /// `from pkg import`: captures the absolute module
+    pub from_import_module: Regex,
+    /// `name = dotted.name(...)` at module level: captures the name and the value
+    pub assignment: Regex,
+    /// The start of `__all__ = [...]`, up to the bracket (the list may span lines; the
+    /// contents are found with a plain search, a lazy capture being slow on huge lists)
+    pub all_list: Regex,
+    /// A quoted string: captures its contents
+    pub quoted_name: Regex,
+
+    // Comments
+    /// `# noqa`, `# noqa: PL001, PL002`: captures the rule list
+    pub noqa: Regex,
+
+    // Calls
+    /// A call or a `def`/`class` header: captures the keyword and the name
+    pub call: Regex,
+    /// `open(...)` not reached through an attribute: captures the first argument
+    pub open_call: Regex,
+    /// A private name (`_helper`), or the `def` of one: captures the keyword and the name
+    pub private_name: Regex,
+    /// `time.sleep(`
+    pub time_sleep: Regex,
+    /// Bare `sleep(`
+    pub bare_sleep: Regex,
+    /// `from time import sleep`
+    pub sleep_import: Regex,
+    /// `mock.return_value = value`: captures the mock and the value
+    pub mock_return_value: Regex,
+    /// `self.assertEqual(` and the other unittest assertions: captures the method
+    pub unittest_assert: Regex,
+
+    // Fixtures
+    /// `scope="..."` in a fixture decorator: captures the scope
+    pub fixture_scope: Regex,
+    /// `name="..."` in a fixture decorator: captures the name
+    pub fixture_name: Regex,
+    /// `autouse=True` in a fixture decorator
+    pub fixture_autouse: Regex,
+    /// `@pytest.mark.name`: captures the marker name
+    pub pytest_marker: Regex,
+
+    // pyproject.toml
+    /// The `[tool.pytest.ini_options]` header line, newline included
+    pub pytest_ini_header: Regex,
+    /// The opening line of a multi-line `markers = [` array, newline included
+    pub markers_list_start: Regex,
+}
+
+impl Patterns {
+    fn new() -> Self {
+        let regex = |pattern: &str| Regex::new(pattern).unwrap();
+        Self {
+            def: regex(r"^(\s*)def\s+(\w+)\s*\("),
+            any_def: regex(r"^\s*(?:async\s+)?def\s+(\w+)\s*\("),
+            test_function: regex(r"^\s*(?:async\s+)?def\s+(test\w*)\s*\("),
+            function_patterns: Vec::new(),
+            class: regex(r"^(\s*)class\s+(\w+)"),
+            abstract_class: regex(r"^\s*class\s+\w+\s*\(.*\b(?:ABC|ABCMeta)\b"),
+            decorator: regex(r"^(\s*)@(.+)$"),
+
+            import: regex(r"^\s*import\s+(.+)$"),
+            from_import: regex(r"^\s*from\s+(\.*)([\w.]*)\s+import\s+(.+)$"),
+            from_import_module: regex(r"^\s*from\s+([\w.]+)\s+import\b"),
+            assignment: regex(r"^([A-Za-z_]\w*)\s*=\s*([A-Za-z_][\w.]*\s*(?:\(.*\))?)\s*$"),
+            all_list: regex(r"__all__\s*=\s*\["),
+            quoted_name: regex(r#"['"]([^'"]+)['"]"#),
+
+            noqa: regex(r"#\s*noqa(?:\s*:)?\s*(.*)"),
+
+            call: regex(r"(\b(?:def|class)\s+)?\b([A-Za-z_]\w*)\s*\("),
+            open_call: regex(r"(?:^|[^\w.])open\s*\(\s*([^,)]*)"),
+            private_name: regex(r"(\bdef\s+)?\b(_\w+)"),
+            time_sleep: regex(r"(?:^|[^\w.])time\.sleep\s*\("),
+            bare_sleep: regex(r"(?:^|[^\w.])sleep\s*\("),
+            sleep_import: regex(r"^\s*from\s+time\s+import\s+.*\bsleep\b"),
+            mock_return_value: regex(r"^\s*([\w.]+)\.return_value\s*=\s*([^=].*)$"),
+            unittest_assert: regex(r"\bself\.(assert\w+)\s*\("),
+
+            fixture_scope: regex(r#"\bscope\s*=\s*["'](\w+)["']"#),
+            fixture_name: regex(r#"\bname\s*=\s*["'](\w+)["']"#),
+            fixture_autouse: regex(r"\bautouse\s*=\s*True\b"),
+            pytest_marker: regex(r"@pytest\.mark\.(\w+)"),
+
+            pytest_ini_header: regex(r"(?m)^\[tool\.pytest\.ini_options\][^\n]*\n"),
+            markers_list_start: regex(r"(?m)^markers\s*=\s*\[[ \t]*\n"),
+        }
+    }
+
+    /// These patterns plus user-defined function definitions, each of which must capture
+    /// the function name in a group called `name`
+    pub fn with_function_patterns(mut self, patterns: &[String]) -> Result<Self, String> {
+        for pattern in patterns {
+            let regex = Regex::new(pattern)
+                .map_err(|e| format!("Invalid function pattern '{}': {}", pattern, e))?;
+            if !regex.capture_names().any(|name| name == Some("name")) {
+                return Err(format!(
+                    "Function pattern '{}' has no (?P<name>...) group",
+                    pattern
+                ));
+            }
+            self.function_patterns.push(regex);
+        }
+        Ok(self)
+    }
+
+    /// Name of the function `line` defines, with the line's indent: a `def`, or a match
+    /// of one of the `function_patterns`
+    pub fn function_definition<'l>(&self, line: &'l str) -> Option<(&'l str, &'l str)> {
+        if let Some(captures) = self.def.captures(line) {
+            return Some((captures.get(1)?.as_str(), captures.get(2)?.as_str()));
+        }
+        self.function_patterns.iter().find_map(|pattern| {
+            let name = pattern.captures(line)?.name("name")?.as_str();
+            let indent = &line[..line.len() - line.trim_start().len()];
+            Some((indent, name))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        let patterns = &*PATTERNS;
+        assert_eq!(
+            &patterns.def.captures("    def run(self):").unwrap()[2],
+            "run"
+        );
+        assert!(patterns.def.captures("async def run():").is_none());
+        assert_eq!(
+            &patterns.any_def.captures("async def run():").unwrap()[1],
+            "run"
+        );
+        assert_eq!(
+            &patterns
+                .test_function
+                .captures("async def test_run():")
+                .unwrap()[1],
+            "test_run"
+        );
+        assert_eq!(
+            &patterns
+                .from_import
+                .captures("from ..pkg import a")
+                .unwrap()[2],
+            "pkg"
+        );
+        assert_eq!(
+            &patterns.noqa.captures("x = 1  # noqa: PL001").unwrap()[1],
+            "PL001"
+        );
+    }
+
+    #[test]
+    fn test_function_patterns() {
+        let patterns = PATTERNS
+            .clone()
+            .with_function_patterns(&[r"^\s*(?P<name>\w+)\s*=\s*make_handler\(".to_string()])
+            .unwrap();
+        assert_eq!(
+            patterns.function_definition("    create = make_handler(\"create\")"),
+            Some(("    ", "create"))
+        );
+        assert_eq!(
+            patterns.function_definition("def plain():"),
+            Some(("", "plain"))
+        );
+        assert_eq!(
+            PATTERNS.function_definition("create = make_handler()"),
+            None
+        );
+        assert!(PATTERNS
+            .clone()
+            .with_function_patterns(&[r"(\w+) = make_handler".to_string()])
+            .is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Strictness presets, so new adopters can start small and tighten the checks later
+//!
+//! A profile only supplies defaults: `strict_mode`, `qualified_test_names` and rule
+//! switches set explicitly still win over it.
+
+/// Per-function rules that only run when switched on, or with the strict profile
+const OPT_IN_RULES: &[&str] = &["PL033"];
+
+/// A named preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// PL001 only, reported as warnings
+    Minimal,
+    /// The default settings
+    Standard,
+    /// Every rule, private functions included, with tests of methods named after their class
+    Strict,
+}
+
+impl Profile {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "minimal" => Ok(Self::Minimal),
+            "standard" => Ok(Self::Standard),
+            "strict" => Ok(Self::Strict),
+            other => Err(format!(
+                "profile must be 'minimal', 'standard' or 'strict', got '{}'",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Standard => "standard",
+            Self::Strict => "strict",
+        }
+    }
+
+    /// Whether the per-function rule `rule_id` runs unless switched on or off explicitly
+    pub fn enables(self, rule_id: &str) -> bool {
+        match self {
+            Self::Minimal => rule_id == "PL001",
+            Self::Standard => !OPT_IN_RULES.contains(&rule_id),
+            Self::Strict => true,
+        }
+    }
+
+    /// Default for `strict_mode`
+    pub fn strict_mode(self) -> bool {
+        self == Self::Strict
+    }
+
+    /// Default for `qualified_test_names`
+    pub fn qualified_test_names(self) -> bool {
+        self == Self::Strict
+    }
+
+    /// Whether violations are reported as warnings rather than errors
+    pub fn warnings_only(self) -> bool {
+        self == Self::Minimal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        let minimal = Profile::parse("minimal").unwrap();
+        assert!(minimal.enables("PL001"));
+        assert!(!minimal.enables("PL002"));
+        assert!(minimal.warnings_only());
+        let strict = Profile::parse("strict").unwrap();
+        assert!(strict.enables("PL022") && strict.enables("PL033"));
+        assert!(!Profile::parse("standard").unwrap().enables("PL033"));
+        assert!(strict.strict_mode() && strict.qualified_test_names());
+        assert!(!Profile::parse("standard").unwrap().strict_mode());
+        assert!(Profile::parse("lenient").is_err());
+    }
+}
@@ -1,19 +1,30 @@
-use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
+use crate::budget::FileBudget;
+use crate::parser;
+use crate::patterns::PATTERNS;
+
 /// Represents the public API of a module
 #[derive(Debug, Clone)]
 pub struct PublicApi {
     /// If Some, only these names are public. If None, use underscore convention.
     pub all_names: Option<HashSet<String>>,
+    /// Set for private modules (e.g. `pkg/_impl.py`) re-exporting names: those re-exported
+    /// by an enclosing package's `__init__.py`, mapped to that package's module path, whose
+    /// tests cover them. `*` stands for every public name. Whether a name is public does
+    /// not depend on it.
+    pub reexports: Option<HashMap<String, String>>,
 }
 
 impl PublicApi {
     /// Create a PublicApi that uses underscore convention
     pub fn default() -> Self {
-        PublicApi { all_names: None }
+        PublicApi {
+            all_names: None,
+            reexports: None,
+        }
     }
 
     /// Check if a function/class is public
@@ -23,36 +34,50 @@ impl PublicApi {
             None => !name.starts_with('_'),
         }
     }
+
+    /// Module path of the package that re-exports `name` from a private module
+    pub fn public_module(&self, name: &str) -> Option<&str> {
+        let reexports = self.reexports.as_ref()?;
+        reexports
+            .get(name)
+            .or_else(|| reexports.get("*").filter(|_| self.is_public(name)))
+            .map(String::as_str)
+    }
 }
 
-/// Extract __all__ from a Python module
-pub fn extract_module_all(file_path: &Path) -> Result<PublicApi, std::io::Error> {
-    let content = fs::read_to_string(file_path)?;
+/// Extract __all__ from the source of a Python module
+pub fn parse_module_all(content: &str) -> PublicApi {
+    parse_module_all_within(content, &FileBudget::start(None))
+}
 
+/// Extract __all__ from the source of a Python module, giving up on the names once
+/// `budget` is exceeded
+pub fn parse_module_all_within(content: &str, budget: &FileBudget) -> PublicApi {
     // Look for __all__ = [...] pattern (can be multi-line)
-    let all_regex = Regex::new(r"(?s)__all__\s*=\s*\[(.*?)\]").unwrap();
-
-    if let Some(captures) = all_regex.captures(&content) {
-        if let Some(names_str) = captures.get(1) {
-            let names = parse_all_names(names_str.as_str());
-            return Ok(PublicApi {
+    if let Some(start) = PATTERNS.all_list.find(content) {
+        let list = &content[start.end()..];
+        if let Some(end) = list.find(']') {
+            let names = parse_all_names(&list[..end], budget);
+            return PublicApi {
                 all_names: Some(names),
-            });
+                reexports: None,
+            };
         }
     }
 
     // No __all__ found, use default
-    Ok(PublicApi::default())
+    PublicApi::default()
 }
 
 /// Parse names from __all__ list content
-fn parse_all_names(content: &str) -> HashSet<String> {
+fn parse_all_names(content: &str, budget: &FileBudget) -> HashSet<String> {
     let mut names = HashSet::new();
 
     // Match both single and double quoted strings
-    let name_regex = Regex::new(r#"['"]([^'"]+)['"]"#).unwrap();
-
-    for capture in name_regex.captures_iter(content) {
+    for capture in PATTERNS.quoted_name.captures_iter(content) {
+        if budget.is_exceeded() {
+            break;
+        }
         if let Some(name) = capture.get(1) {
             names.insert(name.as_str().to_string());
         }
@@ -61,6 +86,136 @@ fn parse_all_names(content: &str) -> HashSet<String> {
     names
 }
 
+/// Whether a module is private by convention (any component starts with `_`)
+pub fn is_private_module(module_path: &str) -> bool {
+    module_path
+        .split('.')
+        .any(|component| component.starts_with('_') && !component.starts_with("__"))
+}
+
+/// A `from <module> import <names>` statement, with the module resolved to an absolute path
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImportFrom {
+    pub module: String,
+    /// (imported name, alias)
+    pub names: Vec<(String, Option<String>)>,
+}
+
+/// Parse the `from ... import ...` statements of a module in `package` (for an
+/// `__init__.py`, the package itself)
+pub fn parse_imports(content: &str, package: &str) -> Vec<ImportFrom> {
+    let lines: Vec<&str> = content.lines().collect();
+    parser::code_lines(&lines)
+        .iter()
+        .filter_map(|code| {
+            let captures = PATTERNS.from_import.captures(code)?;
+            let dots = captures[1].len();
+            let relative = &captures[2];
+
+            // `.` is the package itself, each further dot goes one package up
+            let module = if dots == 0 {
+                relative.to_string()
+            } else {
+                let mut base: Vec<&str> = package.split('.').filter(|c| !c.is_empty()).collect();
+                base.truncate(base.len().checked_sub(dots - 1)?);
+                base.extend(relative.split('.').filter(|c| !c.is_empty()));
+                base.join(".")
+            };
+
+            let names = captures[3]
+                .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+                .split(',')
+                .filter_map(|part| {
+                    let mut words = part.split_whitespace();
+                    let name = words.next()?.to_string();
+                    let alias = match (words.next(), words.next()) {
+                        (Some("as"), Some(alias)) => Some(alias.to_string()),
+                        _ => None,
+                    };
+                    Some((name, alias))
+                })
+                .collect();
+            Some(ImportFrom { module, names })
+        })
+        .collect()
+}
+
+/// Resolve which names of the private module at `file_path` are re-exported by enclosing
+/// packages, following chains such as `pkg/_internal/__init__.py` → `pkg/__init__.py`
+///
+/// Returns `None` for modules that are not private or have nothing re-exported.
+pub fn resolve_reexports(
+    file_path: &Path,
+    module_path: &str,
+    project_root: &Path,
+) -> Option<HashMap<String, String>> {
+    if !is_private_module(module_path) {
+        return None;
+    }
+
+    let mut reexports: HashMap<String, String> = HashMap::new();
+    // Names visible in `source`, mapped back to the names defined in the module
+    // (`None`: every public name, after a wildcard import)
+    let mut source = module_path.to_string();
+    let mut visible: Option<HashMap<String, String>> = None;
+    let mut dir = file_path.parent();
+    let mut package = module_path.rsplit_once('.').map(|(package, _)| package);
+
+    while let (Some(current_dir), Some(current_package)) = (dir, package) {
+        if !current_dir.starts_with(project_root) {
+            break;
+        }
+        let Ok(content) = fs::read_to_string(current_dir.join("__init__.py")) else {
+            break;
+        };
+
+        let mut wildcard = false;
+        let mut exported: HashMap<String, String> = HashMap::new();
+        for import in parse_imports(&content, current_package) {
+            if import.module != source {
+                continue;
+            }
+            for (name, alias) in import.names {
+                if name == "*" {
+                    match &visible {
+                        Some(names) => exported.extend(names.clone()),
+                        None => wildcard = true,
+                    }
+                    continue;
+                }
+                let original = match &visible {
+                    Some(names) => names.get(&name).cloned(),
+                    None => Some(name.clone()),
+                };
+                if let Some(original) = original {
+                    exported.insert(alias.unwrap_or(name), original);
+                }
+            }
+        }
+
+        if !wildcard && exported.is_empty() {
+            break;
+        }
+        // Exports into a private package are followed but are not public yet; outer
+        // packages take precedence as the public face of the name
+        if !is_private_module(current_package) {
+            if wildcard {
+                reexports.insert("*".to_string(), current_package.to_string());
+            }
+            for original in exported.values() {
+                reexports.insert(original.clone(), current_package.to_string());
+            }
+        }
+
+        source = current_package.to_string();
+        visible = if wildcard { None } else { Some(exported) };
+        dir = current_dir.parent();
+        package = current_package.rsplit_once('.').map(|(package, _)| package);
+    }
+
+    (!reexports.is_empty()).then_some(reexports)
+}
+
 /// Check if a function should be checked based on public API rules
 pub fn should_check_function(
     function_name: &str,
@@ -103,7 +258,7 @@ mod tests {
     #[test]
     fn test_parse_all_names() {
         let content = "'func1', 'func2', \"func3\"";
-        let names = parse_all_names(content);
+        let names = parse_all_names(content, &FileBudget::start(None));
         assert_eq!(names.len(), 3);
         assert!(names.contains("func1"));
         assert!(names.contains("func2"));
@@ -117,14 +272,14 @@ mod tests {
             "func2",
             'func3'
         "#;
-        let names = parse_all_names(content);
+        let names = parse_all_names(content, &FileBudget::start(None));
         assert_eq!(names.len(), 3);
     }
 
     #[test]
     fn test_parse_all_names_empty() {
         let content = "";
-        let names = parse_all_names(content);
+        let names = parse_all_names(content, &FileBudget::start(None));
         assert_eq!(names.len(), 0);
     }
 
@@ -135,6 +290,7 @@ mod tests {
 
         let public_api = PublicApi {
             all_names: Some(names),
+            reexports: None,
         };
 
         assert!(public_api.is_public("public_func"));
@@ -142,6 +298,78 @@ mod tests {
         assert!(!public_api.is_public("_private_func"));
     }
 
+    #[test]
+    fn test_parse_imports() {
+        let imports = parse_imports(
+            "from ._impl import foo, bar as baz\nfrom .. import sibling\nfrom pkg.x import (\n    a,\n    b,\n)\nimport os\n",
+            "pkg.sub",
+        );
+        assert_eq!(
+            imports,
+            vec![
+                ImportFrom {
+                    module: "pkg.sub._impl".to_string(),
+                    names: vec![
+                        ("foo".to_string(), None),
+                        ("bar".to_string(), Some("baz".to_string())),
+                    ],
+                },
+                ImportFrom {
+                    module: "pkg".to_string(),
+                    names: vec![("sibling".to_string(), None)],
+                },
+                ImportFrom {
+                    module: "pkg.x".to_string(),
+                    names: vec![("a".to_string(), None), ("b".to_string(), None)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_reexports_follows_chains() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let internal = root.join("pkg").join("_internal");
+        fs::create_dir_all(&internal).unwrap();
+        fs::write(
+            internal.join("impl.py"),
+            "def foo(): pass\ndef bar(): pass\n",
+        )
+        .unwrap();
+        fs::write(internal.join("__init__.py"), "from .impl import foo, bar\n").unwrap();
+        fs::write(
+            root.join("pkg").join("__init__.py"),
+            "from ._internal import foo\n",
+        )
+        .unwrap();
+
+        let reexports =
+            resolve_reexports(&internal.join("impl.py"), "pkg._internal.impl", root).unwrap();
+        assert_eq!(reexports["foo"], "pkg");
+        // Only re-exported into the private `pkg._internal`
+        assert!(!reexports.contains_key("bar"));
+
+        assert!(resolve_reexports(&root.join("pkg").join("mod.py"), "pkg.mod", root).is_none());
+
+        // A private module nothing re-exports keeps the underscore convention and `__all__`
+        fs::write(
+            root.join("pkg").join("_utils.py"),
+            "def helper(): pass
+",
+        )
+        .unwrap();
+        assert!(
+            resolve_reexports(&root.join("pkg").join("_utils.py"), "pkg._utils", root).is_none()
+        );
+        let api = PublicApi {
+            all_names: None,
+            reexports: Some(reexports),
+        };
+        assert!(api.is_public("bar") && api.public_module("bar").is_none());
+        assert!(!api.is_public("_helper"));
+    }
+
     #[test]
     fn test_is_public_without_all() {
         let public_api = PublicApi::default();
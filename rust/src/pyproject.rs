@@ -0,0 +1,158 @@
+//! Reading project metadata from `pyproject.toml`
+
+use std::fs;
+use std::path::Path;
+
+/// Parse `pyproject.toml` in `project_root`, if present and valid
+pub fn load(project_root: &Path) -> Option<toml::Table> {
+    let content = fs::read_to_string(project_root.join("pyproject.toml")).ok()?;
+    content.parse().ok()
+}
+
+/// A `[project.scripts]` / `[project.gui-scripts]` target such as `pkg.cli:main`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptTarget {
+    pub module: String,
+    /// Attribute path within the module, e.g. `main` or `Cli.run`
+    pub attribute: String,
+}
+
+/// Console and GUI script entry points declared in `[project]`
+pub fn script_targets(pyproject: &toml::Table) -> Vec<ScriptTarget> {
+    let Some(project) = pyproject.get("project").and_then(|p| p.as_table()) else {
+        return Vec::new();
+    };
+
+    ["scripts", "gui-scripts"]
+        .iter()
+        .filter_map(|key| project.get(*key)?.as_table())
+        .flat_map(|scripts| scripts.values())
+        .filter_map(|target| {
+            // Strip extras, e.g. `pkg.cli:main [color]`
+            let target = target.as_str()?.split('[').next()?.trim();
+            let (module, attribute) = target.split_once(':')?;
+            Some(ScriptTarget {
+                module: module.trim().to_string(),
+                attribute: attribute.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Source roots declared for setuptools: `[tool.setuptools.packages.find] where` and the
+/// root entry of `[tool.setuptools] package-dir`
+pub fn source_roots(pyproject: &toml::Table) -> Vec<String> {
+    let Some(setuptools) = pyproject
+        .get("tool")
+        .and_then(|tool| tool.get("setuptools"))
+        .and_then(|s| s.as_table())
+    else {
+        return Vec::new();
+    };
+
+    let mut roots: Vec<String> = setuptools
+        .get("packages")
+        .and_then(|packages| packages.get("find"))
+        .and_then(|find| find.get("where"))
+        .and_then(|where_| where_.as_array())
+        .map(|dirs| {
+            dirs.iter()
+                .filter_map(|dir| dir.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(root) = setuptools
+        .get("package-dir")
+        .and_then(|dirs| dirs.get(""))
+        .and_then(|dir| dir.as_str())
+    {
+        roots.push(root.to_string());
+    }
+    roots
+}
+
+/// pytest's `--import-mode` from `[tool.pytest.ini_options] addopts`, if set
+pub fn pytest_import_mode(pyproject: &toml::Table) -> Option<String> {
+    let addopts = pyproject
+        .get("tool")?
+        .get("pytest")?
+        .get("ini_options")?
+        .get("addopts")?;
+    let args: Vec<&str> = match addopts {
+        toml::Value::String(args) => args.split_whitespace().collect(),
+        toml::Value::Array(args) => args
+            .iter()
+            .filter_map(|arg| arg.as_str())
+            .flat_map(str::split_whitespace)
+            .collect(),
+        _ => return None,
+    };
+    args.iter().enumerate().find_map(|(idx, arg)| {
+        if let Some(mode) = arg.strip_prefix("--import-mode=") {
+            Some(mode.to_string())
+        } else if *arg == "--import-mode" {
+            args.get(idx + 1).map(|mode| mode.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_targets() {
+        let pyproject: toml::Table = r#"
+            [project.scripts]
+            tool = "pkg.cli:main"
+            other = "pkg.app:App.run [extra]"
+
+            [project.gui-scripts]
+            gui = "pkg.gui:start"
+        "#
+        .parse()
+        .unwrap();
+
+        let targets = script_targets(&pyproject);
+        assert_eq!(targets.len(), 3);
+        assert!(targets.contains(&ScriptTarget {
+            module: "pkg.app".to_string(),
+            attribute: "App.run".to_string(),
+        }));
+        assert!(script_targets(&toml::Table::new()).is_empty());
+    }
+
+    #[test]
+    fn test_source_roots() {
+        let pyproject: toml::Table = r#"
+            [tool.setuptools]
+            package-dir = {"" = "lib"}
+
+            [tool.setuptools.packages.find]
+            where = ["python/src", "plugins"]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            source_roots(&pyproject),
+            vec!["python/src", "plugins", "lib"]
+        );
+        assert!(source_roots(&toml::Table::new()).is_empty());
+    }
+
+    #[test]
+    fn test_pytest_import_mode() {
+        let parse = |content: &str| pytest_import_mode(&content.parse().unwrap());
+        assert_eq!(
+            parse("[tool.pytest.ini_options]\naddopts = \"-ra --import-mode=importlib\""),
+            Some("importlib".to_string())
+        );
+        assert_eq!(
+            parse("[tool.pytest.ini_options]\naddopts = [\"--import-mode\", \"append\"]"),
+            Some("append".to_string())
+        );
+        assert_eq!(parse("[tool.pytest.ini_options]\naddopts = \"-ra\""), None);
+    }
+}
@@ -0,0 +1,286 @@
+//! Test quality scoring: how well, not just whether, each function is tested
+//!
+//! A function's score (0–100) is built from:
+//! - 40 points for having at least one matching test
+//! - up to 30 points for assertions across its tests (full marks at `FULL_ASSERTIONS`)
+//! - 15 points if any matching test is parametrized
+//! - 15 points if every matching test carries a pytest marker
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parser;
+use crate::test_cache::{TestCache, TestType};
+
+/// Assertion count that earns the full assertion score
+const FULL_ASSERTIONS: usize = 5;
+
+/// What a single test function does
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestFunctionInfo {
+    pub assertions: usize,
+    pub parametrized: bool,
+    pub has_marker: bool,
+}
+
+/// Per-test details for every test file in a `TestCache`
+#[derive(Debug, Default)]
+pub struct TestIndex {
+    tests: HashMap<PathBuf, HashMap<String, TestFunctionInfo>>,
+}
+
+impl TestIndex {
+    pub fn build(test_cache: &TestCache) -> Self {
+        let paths: Vec<&Path> = test_cache.test_file_paths().collect();
+        let tests = paths
+            .par_iter()
+            .filter_map(|path| {
+                let content = fs::read_to_string(path).ok()?;
+                Some((path.to_path_buf(), Self::parse_file(&content)))
+            })
+            .collect();
+        Self { tests }
+    }
+
+    fn parse_file(content: &str) -> HashMap<String, TestFunctionInfo> {
+        let lines: Vec<&str> = content.lines().collect();
+        // A module-level `pytestmark = ...` marks every test in the file
+        let module_marked = lines.iter().any(|line| line.starts_with("pytestmark"));
+
+        let mut tests = HashMap::new();
+        for (idx, line) in lines.iter().enumerate() {
+            let Some(rest) = line.trim_start().strip_prefix("def ") else {
+                continue;
+            };
+            let name = rest.split('(').next().unwrap_or("").trim();
+            if !name.starts_with("test") {
+                continue;
+            }
+
+            let function = parser::parse_function(&lines, idx + 1);
+            let marks: Vec<&str> = function
+                .decorators
+                .iter()
+                .filter_map(|d| d.strip_prefix("pytest.mark."))
+                .collect();
+            tests.insert(
+                name.to_string(),
+                TestFunctionInfo {
+                    assertions: function.assertion_count(),
+                    parametrized: marks.contains(&"parametrize"),
+                    has_marker: module_marked || marks.iter().any(|m| *m != "parametrize"),
+                },
+            );
+        }
+        tests
+    }
+
+    pub fn get(&self, test_file: &Path, test_name: &str) -> Option<&TestFunctionInfo> {
+        self.tests.get(test_file)?.get(test_name)
+    }
+}
+
+/// Test quality of one source function
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct FunctionQuality {
+    #[pyo3(get)]
+    pub file_path: String,
+    #[pyo3(get)]
+    pub line_number: usize,
+    #[pyo3(get)]
+    pub function_name: String,
+    #[pyo3(get)]
+    pub class_name: Option<String>,
+    /// Dotted module of the function's file (e.g. `pkg.sub.mod`)
+    #[pyo3(get)]
+    pub module_path: String,
+    /// Matching tests as `test_file::test_name`
+    #[pyo3(get)]
+    pub tests: Vec<String>,
+    #[pyo3(get)]
+    pub unit_tests: usize,
+    #[pyo3(get)]
+    pub integration_tests: usize,
+    #[pyo3(get)]
+    pub e2e_tests: usize,
+    /// Total assertions across all matching tests
+    #[pyo3(get)]
+    pub assertion_count: usize,
+    #[pyo3(get)]
+    pub parametrized: bool,
+    /// Whether every matching test carries a pytest marker
+    #[pyo3(get)]
+    pub has_markers: bool,
+    #[pyo3(get)]
+    pub score: u32,
+}
+
+/// Test quality across a project, as returned by `RustLinter.coverage_report`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    #[pyo3(get)]
+    pub functions: Vec<FunctionQuality>,
+    #[pyo3(get)]
+    pub total_functions: usize,
+    #[pyo3(get)]
+    pub tested_functions: usize,
+    /// Mean score over all functions (0 when there are none)
+    #[pyo3(get)]
+    pub average_score: f64,
+}
+
+impl CoverageReport {
+    pub fn new(mut functions: Vec<FunctionQuality>) -> Self {
+        functions.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+        let total_functions = functions.len();
+        let tested_functions = functions.iter().filter(|f| !f.tests.is_empty()).count();
+        let average_score = if total_functions == 0 {
+            0.0
+        } else {
+            functions.iter().map(|f| f.score as f64).sum::<f64>() / total_functions as f64
+        };
+        Self {
+            functions,
+            total_functions,
+            tested_functions,
+            average_score,
+        }
+    }
+}
+
+/// A source function to assess, and where to look for its tests
+pub struct SourceFunction<'a> {
+    pub name: &'a str,
+    pub class_name: Option<&'a str>,
+    pub file_path: &'a Path,
+    pub line_number: usize,
+    pub module_path: &'a str,
+}
+
+/// Collect the tests of `function` and score them
+pub fn assess(
+    function: &SourceFunction,
+    test_cache: &TestCache,
+    index: &TestIndex,
+    project_root: &Path,
+) -> FunctionQuality {
+    let mut quality = FunctionQuality {
+        file_path: function.file_path.to_string_lossy().to_string(),
+        line_number: function.line_number,
+        function_name: function.name.to_string(),
+        class_name: function.class_name.map(str::to_string),
+        has_markers: true,
+        ..Default::default()
+    };
+
+    let test_types = [TestType::Unit, TestType::Integration, TestType::E2E];
+    let mut seen = Vec::new();
+    for test_type in &test_types {
+        let found = test_cache.find_tests_for_function_of_type(
+            function.name,
+            function.file_path,
+            function.class_name,
+            test_type,
+            function.module_path,
+            project_root,
+        );
+        for (test_file, test_name) in found {
+            // General tests match every type; count each test once
+            if seen.contains(&(test_file, test_name)) {
+                continue;
+            }
+            seen.push((test_file, test_name));
+
+            match TestType::from_path(test_file) {
+                TestType::Integration => quality.integration_tests += 1,
+                TestType::E2E => quality.e2e_tests += 1,
+                TestType::Unit | TestType::General => quality.unit_tests += 1,
+            }
+            let relative = test_file.strip_prefix(project_root).unwrap_or(test_file);
+            quality
+                .tests
+                .push(format!("{}::{}", relative.display(), test_name));
+
+            let info = index.get(test_file, test_name).cloned().unwrap_or_default();
+            quality.assertion_count += info.assertions;
+            quality.parametrized |= info.parametrized;
+            quality.has_markers &= info.has_marker;
+        }
+    }
+
+    if quality.tests.is_empty() {
+        quality.has_markers = false;
+    }
+    quality.score = score(&quality);
+    quality
+}
+
+fn score(quality: &FunctionQuality) -> u32 {
+    if quality.tests.is_empty() {
+        return 0;
+    }
+    let assertions = quality.assertion_count.min(FULL_ASSERTIONS) * 30 / FULL_ASSERTIONS;
+    let mut score = 40 + assertions as u32;
+    if quality.parametrized {
+        score += 15;
+    }
+    if quality.has_markers {
+        score += 15;
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_test_file() {
+        let tests = TestIndex::parse_file(
+            "import pytest\n\n@pytest.mark.unit\n@pytest.mark.parametrize('x', [1, 2])\ndef test_foo(x):\n    assert foo(x)\n    assert foo(x) != 'assert'\n    with pytest.raises(ValueError):\n        foo(None)\n\ndef test_bar():\n    self.assertEqual(bar(), 1)\n\ndef helper():\n    assert True\n",
+        );
+        assert_eq!(
+            tests["test_foo"],
+            TestFunctionInfo {
+                assertions: 3,
+                parametrized: true,
+                has_marker: true,
+            }
+        );
+        assert_eq!(
+            tests["test_bar"],
+            TestFunctionInfo {
+                assertions: 1,
+                parametrized: false,
+                has_marker: false,
+            }
+        );
+        assert!(!tests.contains_key("helper"));
+    }
+
+    #[test]
+    fn test_score() {
+        let untested = FunctionQuality::default();
+        assert_eq!(score(&untested), 0);
+
+        let basic = FunctionQuality {
+            tests: vec!["test/unit/test_m.py::test_foo".to_string()],
+            assertion_count: 1,
+            ..Default::default()
+        };
+        assert_eq!(score(&basic), 46);
+
+        let thorough = FunctionQuality {
+            assertion_count: 12,
+            parametrized: true,
+            has_markers: true,
+            ..basic
+        };
+        assert_eq!(score(&thorough), 100);
+    }
+}
@@ -0,0 +1,93 @@
+//! Checkstyle XML, as consumed by Jenkins Warnings-NG and most CI dashboards
+
+use std::collections::BTreeMap;
+
+use crate::models::LintViolation;
+
+/// Render violations as a Checkstyle XML document, one `<file>` element per file
+pub fn render(violations: &[LintViolation]) -> String {
+    let mut by_file: BTreeMap<&str, Vec<&LintViolation>> = BTreeMap::new();
+    for violation in violations {
+        by_file
+            .entry(&*violation.file_path)
+            .or_default()
+            .push(violation);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<checkstyle version=\"8.0\">\n");
+    for (file, file_violations) in by_file {
+        xml.push_str(&format!("  <file name=\"{}\">\n", escape(file)));
+        for violation in file_violations {
+            xml.push_str(&format!(
+                "    <error line=\"{}\" column=\"1\" severity=\"{}\" message=\"{}\" source=\"proboscis.{}\"/>\n",
+                violation.line_number,
+                checkstyle_severity(&violation.severity),
+                escape(violation.message()),
+                escape(&violation.rule_name)
+            ));
+        }
+        xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>\n");
+    xml
+}
+
+/// Checkstyle only knows error/warning/info, so fold `critical` into `error`
+fn checkstyle_severity(severity: &str) -> String {
+    match severity {
+        "critical" => "error".to_string(),
+        other => escape(other),
+    }
+}
+
+/// Escape text for use inside an XML attribute value
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_groups_by_file_and_escapes() {
+        let violations = vec![
+            LintViolation {
+                rule_name: "PL001:require-unit-test".to_string(),
+                file_path: "src/a.py".into(),
+                line_number: 3,
+                message: "Function 'f' <no test>\nExpected: test_f".to_string(),
+                severity: "error".to_string(),
+                ..Default::default()
+            },
+            LintViolation {
+                rule_name: "PL002:require-integration-test".to_string(),
+                file_path: "src/a.py".into(),
+                line_number: 3,
+                message: "missing".to_string(),
+                severity: "warning".to_string(),
+                ..Default::default()
+            },
+        ];
+        let xml = render(&violations);
+        assert_eq!(xml.matches("<file ").count(), 1);
+        assert!(
+            xml.contains("message=\"Function &apos;f&apos; &lt;no test&gt;&#10;Expected: test_f\"")
+        );
+        assert!(xml.contains("severity=\"warning\""));
+        assert!(xml.contains("source=\"proboscis.PL001:require-unit-test\""));
+    }
+}
@@ -0,0 +1,79 @@
+//! Code Climate issue JSON, as consumed by GitLab Code Quality
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::{relative_path, stable_hash};
+use crate::models::LintViolation;
+use crate::rules::metadata;
+
+/// Render violations as a Code Climate JSON array
+pub fn render(violations: &[LintViolation], project_root: &Path) -> String {
+    let issues: Vec<Value> = violations
+        .iter()
+        .map(|violation| issue(violation, project_root))
+        .collect();
+    serde_json::to_string_pretty(&issues).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn issue(violation: &LintViolation, project_root: &Path) -> Value {
+    let path = relative_path(&violation.file_path, project_root);
+    let severity = match violation.severity.as_str() {
+        "critical" => "critical",
+        "error" => "major",
+        "warning" => "minor",
+        _ => "info",
+    };
+    let category = match metadata::find(violation.rule_id()).map(|rule| rule.category) {
+        Some("organization") => "Style",
+        Some("quality") => "Clarity",
+        _ => "Bug Risk",
+    };
+    // Line numbers shift with unrelated edits, so identify issues by rule, file and function
+    let fingerprint = stable_hash(&[&violation.rule_name, &path, &violation.function_name]);
+
+    json!({
+        "type": "issue",
+        "check_name": violation.rule_name,
+        "description": violation.message().lines().next().unwrap_or_default(),
+        "content": {"body": violation.message()},
+        "categories": [category],
+        "severity": severity,
+        "fingerprint": fingerprint,
+        "location": {
+            "path": path,
+            "lines": {"begin": violation.line_number},
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_issue() {
+        let violations = vec![LintViolation {
+            rule_name: "PL001:require-unit-test".to_string(),
+            file_path: "/repo/src/a.py".into(),
+            line_number: 7,
+            function_name: "f".to_string(),
+            message: "[PL001] Function 'f' has no unit test found.\nExpected test function: test_f"
+                .to_string(),
+            severity: "error".to_string(),
+            ..Default::default()
+        }];
+        let issues: Value = serde_json::from_str(&render(&violations, Path::new("/repo"))).unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue["check_name"], "PL001:require-unit-test");
+        assert_eq!(
+            issue["description"],
+            "[PL001] Function 'f' has no unit test found."
+        );
+        assert_eq!(issue["severity"], "major");
+        assert_eq!(issue["categories"][0], "Bug Risk");
+        assert_eq!(issue["location"]["path"], "src/a.py");
+        assert_eq!(issue["location"]["lines"]["begin"], 7);
+        assert_eq!(issue["fingerprint"].as_str().unwrap().len(), 16);
+    }
+}
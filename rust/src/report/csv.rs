@@ -0,0 +1,68 @@
+//! CSV with a header row of the [`EXPORT_COLUMNS`], for spreadsheets and data tools
+//!
+//! Fields are quoted (RFC 4180) when they contain a comma, quote or line break, which
+//! multi-line messages do; missing values are empty.
+
+use std::path::Path;
+
+use super::{export_row, EXPORT_COLUMNS};
+use crate::models::LintViolation;
+
+/// The header row
+pub fn header() -> String {
+    format!("{}\n", EXPORT_COLUMNS.join(","))
+}
+
+/// One row per violation, without the header
+pub fn rows(violations: &[LintViolation], project_root: &Path) -> String {
+    violations
+        .iter()
+        .map(|violation| {
+            let fields: Vec<String> = export_row(violation, project_root)
+                .into_iter()
+                .map(|value| match value {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(text) => quote(&text),
+                    other => other.to_string(),
+                })
+                .collect();
+            format!("{}\n", fields.join(","))
+        })
+        .collect()
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows() {
+        let violation = LintViolation {
+            rule_name: "PL002:require-integration-test".to_string(),
+            file_path: "/repo/src/pkg/cache.py".into(),
+            line_number: 12,
+            function_name: "get".to_string(),
+            class_name: Some("Cache".to_string()),
+            message: "[PL002] no \"integration\" test,\nsee docs".to_string(),
+            severity: "warning".to_string(),
+            module_path: Some("pkg.cache".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            header(),
+            "module,file,line,rule_id,rule,severity,function,class,expected_test_file,expected_test_name,message,fix_available,docs_url,context_start_line,context\n"
+        );
+        assert_eq!(
+            rows(&[violation], Path::new("/repo")),
+            "pkg.cache,src/pkg/cache.py,12,PL002,PL002:require-integration-test,warning,get,Cache,,,\"[PL002] no \"\"integration\"\" test,\nsee docs\",false,,,\n"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Reporting over lint results: summaries and serialized output formats
+
+pub mod checkstyle;
+pub mod codeclimate;
+pub mod csv;
+pub mod modules;
+pub mod ndjson;
+pub mod owners;
+pub mod sarif;
+pub mod test_map;
+pub mod text;
+pub mod webhook;
+
+use std::path::Path;
+
+use crate::models::LintViolation;
+use crate::run_metadata::RunMetadata;
+
+/// Serialized output formats understood by [`render`]
+pub const FORMATS: &[&str] = &["checkstyle", "codeclimate", "csv", "ndjson", "sarif"];
+
+/// Formats written as the violations come (see `RustLinter.lint_project_to_file`): their
+/// [`header`], then the [`render_rows`] of each chunk of the run
+pub const STREAMABLE: &[&str] = &["csv", "ndjson"];
+
+/// Columns of the tabular exports (CSV and NDJSON), in order; this schema is documented
+/// and only ever extended at the end
+pub const EXPORT_COLUMNS: &[&str] = &[
+    "module",
+    "file",
+    "line",
+    "rule_id",
+    "rule",
+    "severity",
+    "function",
+    "class",
+    "expected_test_file",
+    "expected_test_name",
+    "message",
+    "fix_available",
+    "docs_url",
+    "context_start_line",
+    "context",
+];
+
+/// The values of a violation for [`EXPORT_COLUMNS`]; fields a violation lacks are null
+pub fn export_row(violation: &LintViolation, project_root: &Path) -> Vec<serde_json::Value> {
+    use serde_json::json;
+    vec![
+        json!(violation.module_path),
+        json!(relative_path(&violation.file_path, project_root)),
+        json!(violation.line_number),
+        json!(violation.rule_id()),
+        json!(violation.rule_name),
+        json!(violation.severity),
+        json!(violation.function_name),
+        json!(violation.class_name),
+        json!(violation
+            .expected_test_file
+            .as_deref()
+            .map(|path| relative_path(path, project_root))),
+        json!(violation.expected_test_name),
+        json!(violation.message()),
+        json!(violation.fix_available()),
+        json!(violation.docs_url),
+        json!(violation.context_start_line),
+        json!(violation.context),
+    ]
+}
+
+/// Leading output of a [`STREAMABLE`] format (the CSV header row), empty for the others
+pub fn header(format: &str) -> String {
+    match format {
+        "csv" => csv::header(),
+        _ => String::new(),
+    }
+}
+
+/// Output of a [`STREAMABLE`] format for some violations, without the [`header`]
+pub fn render_rows(
+    format: &str,
+    violations: &[LintViolation],
+    project_root: &Path,
+) -> Result<String, String> {
+    match format {
+        "csv" => Ok(csv::rows(violations, project_root)),
+        other => render(other, violations, project_root, None),
+    }
+}
+
+/// Render violations in one of the [`FORMATS`]
+///
+/// File paths are reported relative to `project_root` where the format expects it. The
+/// `metadata` of the run is embedded by the formats with a place for it (SARIF).
+pub fn render(
+    format: &str,
+    violations: &[LintViolation],
+    project_root: &Path,
+    metadata: Option<&RunMetadata>,
+) -> Result<String, String> {
+    match format {
+        "checkstyle" => Ok(checkstyle::render(violations)),
+        "codeclimate" => Ok(codeclimate::render(violations, project_root)),
+        "csv" => Ok(csv::header() + &csv::rows(violations, project_root)),
+        "ndjson" => Ok(ndjson::render(violations, project_root)),
+        "sarif" => Ok(sarif::render(violations, project_root, metadata)),
+        other => Err(format!(
+            "Unknown report format '{}'. Expected one of: {}",
+            other,
+            FORMATS.join(", ")
+        )),
+    }
+}
+
+/// Path of a violation's file relative to `project_root`, with `/` separators
+pub fn relative_path(file_path: &str, project_root: &Path) -> String {
+    let path = Path::new(file_path);
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Stable 64-bit FNV-1a hash, rendered as hex, for fingerprints that must not change
+/// between runs or toolchain versions
+pub fn stable_hash(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path("/repo/src/pkg/mod.py", Path::new("/repo")),
+            "src/pkg/mod.py"
+        );
+        assert_eq!(
+            relative_path("/elsewhere/mod.py", Path::new("/repo")),
+            "/elsewhere/mod.py"
+        );
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        assert_eq!(stable_hash(&["a", "bc"]), stable_hash(&["a", "bc"]));
+        assert_ne!(stable_hash(&["a", "bc"]), stable_hash(&["ab", "c"]));
+    }
+
+    #[test]
+    fn test_render_unknown_format() {
+        assert!(render("yaml", &[], Path::new("/repo"), None).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Per-module rollup (`RustLinter.module_report`): the granularity engineering managers
+//! review, rather than single files or functions
+//!
+//! Functions and their tests come from the coverage assessment (`quality`), violations
+//! from a full lint run; violations not tied to a source module (test files, advisories)
+//! are left out.
+
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::LintViolation;
+use crate::quality::FunctionQuality;
+
+/// Functions, tests and violations of one Python module
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct ModuleSummary {
+    /// Dotted module path, e.g. "pkg.sub.mod"
+    #[pyo3(get)]
+    pub module: String,
+    #[pyo3(get)]
+    pub total_functions: usize,
+    /// Functions with at least one test of any type
+    #[pyo3(get)]
+    pub tested_functions: usize,
+    #[pyo3(get)]
+    pub unit_tested: usize,
+    #[pyo3(get)]
+    pub integration_tested: usize,
+    #[pyo3(get)]
+    pub e2e_tested: usize,
+    #[pyo3(get)]
+    pub violation_count: usize,
+    /// Violation counts keyed by rule id (e.g. "PL001")
+    #[pyo3(get)]
+    pub rule_counts: HashMap<String, usize>,
+    /// Share of the functions with a test, 0–100 (0 when the module has none)
+    #[pyo3(get)]
+    pub coverage_percent: f64,
+}
+
+/// Summarize functions and violations per module, ordered by module path
+pub fn summarize_by_module(
+    functions: &[FunctionQuality],
+    violations: &[LintViolation],
+) -> Vec<ModuleSummary> {
+    let mut summaries: BTreeMap<&str, ModuleSummary> = BTreeMap::new();
+    for function in functions {
+        let summary = summaries.entry(&function.module_path).or_default();
+        summary.total_functions += 1;
+        summary.tested_functions += usize::from(!function.tests.is_empty());
+        summary.unit_tested += usize::from(function.unit_tests > 0);
+        summary.integration_tested += usize::from(function.integration_tests > 0);
+        summary.e2e_tested += usize::from(function.e2e_tests > 0);
+    }
+    for violation in violations {
+        let Some(module) = &violation.module_path else {
+            continue;
+        };
+        let summary = summaries.entry(module).or_default();
+        summary.violation_count += 1;
+        *summary
+            .rule_counts
+            .entry(violation.rule_id().to_string())
+            .or_default() += 1;
+    }
+
+    summaries
+        .into_iter()
+        .map(|(module, mut summary)| {
+            summary.module = module.to_string();
+            if summary.total_functions > 0 {
+                summary.coverage_percent =
+                    summary.tested_functions as f64 * 100.0 / summary.total_functions as f64;
+            }
+            summary
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_by_module() {
+        let function = |module: &str, unit: usize, e2e: usize| FunctionQuality {
+            module_path: module.to_string(),
+            unit_tests: unit,
+            e2e_tests: e2e,
+            tests: vec!["t".to_string(); unit + e2e],
+            ..Default::default()
+        };
+        let functions = vec![
+            function("pkg.b", 1, 0),
+            function("pkg.b", 0, 0),
+            function("pkg.a", 2, 1),
+            function("pkg.b", 0, 1),
+        ];
+        let violation = |rule: &str, module: Option<&str>| LintViolation {
+            rule_name: rule.to_string(),
+            module_path: module.map(str::to_string),
+            ..Default::default()
+        };
+        let violations = vec![
+            violation("PL001:require-unit-test", Some("pkg.b")),
+            violation("PL001:require-unit-test", Some("pkg.b")),
+            violation("PL003:require-e2e-test", Some("pkg.b")),
+            violation("PL004:require-test-markers", None),
+        ];
+        let summaries = summarize_by_module(&functions, &violations);
+
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].module, "pkg.a");
+        assert_eq!(summaries[0].coverage_percent, 100.0);
+        assert_eq!(summaries[0].violation_count, 0);
+        let b = &summaries[1];
+        assert_eq!(
+            (
+                b.total_functions,
+                b.tested_functions,
+                b.unit_tested,
+                b.e2e_tested
+            ),
+            (3, 2, 1, 1)
+        );
+        assert_eq!(b.violation_count, 3);
+        assert_eq!(b.rule_counts["PL001"], 2);
+        assert!((b.coverage_percent - 200.0 / 3.0).abs() < 1e-9);
+    }
+}
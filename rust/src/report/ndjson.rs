@@ -0,0 +1,111 @@
+//! Newline-delimited JSON: one object per violation and line, keyed by the
+//! [`EXPORT_COLUMNS`], for loading into pandas or BigQuery or archiving as a CI artifact
+//!
+//! Lines are independent, so a run can be written a chunk at a time (see `STREAMABLE`).
+
+use std::path::Path;
+
+use super::{export_row, EXPORT_COLUMNS};
+use crate::models::LintViolation;
+
+/// Render violations as NDJSON, each line ending in a newline
+pub fn render(violations: &[LintViolation], project_root: &Path) -> String {
+    violations
+        .iter()
+        .map(|violation| {
+            let record: serde_json::Map<String, serde_json::Value> = EXPORT_COLUMNS
+                .iter()
+                .map(|column| column.to_string())
+                .zip(export_row(violation, project_root))
+                .collect();
+            format!("{}\n", serde_json::Value::Object(record))
+        })
+        .collect()
+}
+
+/// Read violations back from NDJSON written by [`render`], with the file paths joined to
+/// `project_root` again; blank lines are skipped
+pub fn parse(content: &str, project_root: &Path) -> Result<Vec<LintViolation>, String> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let record: serde_json::Value =
+                serde_json::from_str(line).map_err(|e| format!("line {}: {}", idx + 1, e))?;
+            let text = |column: &str| record[column].as_str().map(str::to_string);
+            let rule_name =
+                text("rule").ok_or_else(|| format!("line {}: missing 'rule'", idx + 1))?;
+            let absolute = |path: String| project_root.join(path).to_string_lossy().to_string();
+            Ok(LintViolation {
+                rule_name,
+                module_path: text("module"),
+                file_path: absolute(text("file").unwrap_or_default()).into(),
+                line_number: record["line"].as_u64().unwrap_or_default() as usize,
+                severity: text("severity").unwrap_or_default(),
+                function_name: text("function").unwrap_or_default(),
+                class_name: text("class"),
+                expected_test_file: text("expected_test_file").map(absolute),
+                expected_test_name: text("expected_test_name"),
+                message: text("message").unwrap_or_default(),
+                context_start_line: record["context_start_line"]
+                    .as_u64()
+                    .map(|line| line as usize),
+                context: text("context"),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_lines() {
+        let violations = vec![
+            LintViolation {
+                rule_name: "PL001:require-unit-test".to_string(),
+                file_path: "/repo/src/a.py".into(),
+                line_number: 7,
+                function_name: "f".to_string(),
+                severity: "error".to_string(),
+                expected_test_file: Some("/repo/test/unit/test_a.py".to_string()),
+                expected_test_name: Some("test_f".to_string()),
+                module_path: Some("a".to_string()),
+                ..Default::default()
+            },
+            LintViolation {
+                rule_name: "PL004:require-test-markers".to_string(),
+                file_path: "/repo/test/unit/test_a.py".into(),
+                line_number: 1,
+                message: "[PL004] missing marker".to_string(),
+                severity: "error".to_string(),
+                ..Default::default()
+            },
+        ];
+        let output = render(&violations, Path::new("/repo"));
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["module"], "a");
+        assert_eq!(lines[0]["rule_id"], "PL001");
+        assert_eq!(lines[0]["file"], "src/a.py");
+        assert_eq!(lines[0]["line"], 7);
+        assert_eq!(lines[0]["expected_test_file"], "test/unit/test_a.py");
+        assert_eq!(lines[1]["message"], "[PL004] missing marker");
+        assert!(lines[1]["class"].is_null() && lines[1]["module"].is_null());
+
+        let parsed = parse(&output, Path::new("/repo")).unwrap();
+        assert_eq!(&*parsed[0].file_path, "/repo/src/a.py");
+        assert_eq!(
+            parsed[0].expected_test_file.as_deref(),
+            Some("/repo/test/unit/test_a.py")
+        );
+        assert_eq!(parsed[1].message, "[PL004] missing marker");
+        assert!(parse("{\"line\": 1}\n", Path::new("/repo")).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use pyo3::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::models::LintViolation;
+
+/// Owner key for violations in files no CODEOWNERS rule assigns
+pub const UNOWNED: &str = "unowned";
+
+/// Missing-test debt attributed to one CODEOWNERS owner
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct OwnerSummary {
+    #[pyo3(get)]
+    pub owner: String,
+    #[pyo3(get)]
+    pub violation_count: usize,
+    #[pyo3(get)]
+    pub file_count: usize,
+    /// Violation counts keyed by rule id (e.g. "PL001")
+    #[pyo3(get)]
+    pub rule_counts: HashMap<String, usize>,
+}
+
+/// Summarize violations per CODEOWNERS owner, largest debt first
+///
+/// A violation in a file with several owners counts towards each of them.
+pub fn summarize_by_owner(violations: &[LintViolation]) -> Vec<OwnerSummary> {
+    #[derive(Default)]
+    struct Tally<'a> {
+        violations: usize,
+        files: HashSet<&'a str>,
+        rules: HashMap<String, usize>,
+    }
+
+    let mut tallies: BTreeMap<&str, Tally> = BTreeMap::new();
+    for violation in violations {
+        let rule_id = violation.rule_id();
+        let owners: Vec<&str> = if violation.code_owners.is_empty() {
+            vec![UNOWNED]
+        } else {
+            violation.code_owners.iter().map(String::as_str).collect()
+        };
+        for owner in owners {
+            let tally = tallies.entry(owner).or_default();
+            tally.violations += 1;
+            tally.files.insert(&violation.file_path);
+            *tally.rules.entry(rule_id.to_string()).or_default() += 1;
+        }
+    }
+
+    let mut summaries: Vec<OwnerSummary> = tallies
+        .into_iter()
+        .map(|(owner, tally)| OwnerSummary {
+            owner: owner.to_string(),
+            violation_count: tally.violations,
+            file_count: tally.files.len(),
+            rule_counts: tally.rules,
+        })
+        .collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.violation_count));
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(rule: &str, file: &str, owners: &[&str]) -> LintViolation {
+        LintViolation {
+            rule_name: rule.to_string(),
+            file_path: file.into(),
+            code_owners: owners.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize_by_owner() {
+        let violations = vec![
+            violation("PL001:require-unit-test", "a.py", &["@org/api"]),
+            violation("PL002:require-integration-test", "a.py", &["@org/api"]),
+            violation(
+                "PL001:require-unit-test",
+                "b.py",
+                &["@org/api", "@org/core"],
+            ),
+            violation("PL001:require-unit-test", "c.py", &[]),
+        ];
+        let summaries = summarize_by_owner(&violations);
+
+        assert_eq!(summaries[0].owner, "@org/api");
+        assert_eq!(summaries[0].violation_count, 3);
+        assert_eq!(summaries[0].file_count, 2);
+        assert_eq!(summaries[0].rule_counts["PL001"], 2);
+        assert_eq!(summaries[0].rule_counts["PL002"], 1);
+
+        let unowned = summaries.iter().find(|s| s.owner == UNOWNED).unwrap();
+        assert_eq!(unowned.violation_count, 1);
+        let core = summaries.iter().find(|s| s.owner == "@org/core").unwrap();
+        assert_eq!(core.file_count, 1);
+    }
+}
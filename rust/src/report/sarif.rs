@@ -0,0 +1,176 @@
+//! SARIF 2.1.0, as consumed by GitHub code scanning and most IDEs
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+use super::{relative_path, stable_hash};
+use crate::models::LintViolation;
+use crate::rules::metadata::{self, RuleInfo};
+use crate::run_metadata::RunMetadata;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render violations as a SARIF log with one run, describing every rule in the registry
+/// (plus any reported rule outside it, such as `PL000`); the run's `metadata`, if given,
+/// goes in the run's `properties`
+pub fn render(
+    violations: &[LintViolation],
+    project_root: &Path,
+    metadata: Option<&RunMetadata>,
+) -> String {
+    let mut rule_ids: Vec<String> = metadata::all_rules()
+        .iter()
+        .map(|rule| rule.rule_id.to_string())
+        .collect();
+    let mut descriptors: Vec<Value> = metadata::all_rules().iter().map(descriptor).collect();
+    for violation in violations {
+        let rule_id = violation.rule_id();
+        if !rule_ids.iter().any(|id| id == rule_id) {
+            rule_ids.push(rule_id.to_string());
+            descriptors.push(json!({
+                "id": rule_id,
+                "name": violation.rule_name.split_once(':').map_or("", |(_, name)| name),
+            }));
+        }
+    }
+
+    let results: Vec<Value> = violations
+        .iter()
+        .map(|violation| {
+            let rule_index = rule_ids
+                .iter()
+                .position(|id| id == violation.rule_id())
+                .unwrap_or_default();
+            result(violation, rule_index, project_root)
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": "proboscis-linter",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": "https://github.com/proboscis/proboscis-linter",
+                "rules": descriptors,
+            },
+        },
+        "results": results,
+    });
+    if let Some(metadata) = metadata {
+        run["properties"] = json!({"runMetadata": metadata.to_value()});
+    }
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [run],
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// The `reportingDescriptor` of a rule
+fn descriptor(rule: &RuleInfo) -> Value {
+    json!({
+        "id": rule.rule_id,
+        "name": rule.name,
+        "shortDescription": {"text": rule.description},
+        "defaultConfiguration": {"level": level(rule.default_severity)},
+        "properties": {
+            "category": rule.category,
+            "tags": rule.tags,
+            "fixSafety": rule.fix_safety,
+            "since": rule.since,
+        },
+    })
+}
+
+fn result(violation: &LintViolation, rule_index: usize, project_root: &Path) -> Value {
+    let path = relative_path(&violation.file_path, project_root);
+    let mut location = json!({
+        "artifactLocation": {"uri": path},
+        "region": {"startLine": violation.line_number},
+    });
+    // Source context attached with `context_lines`, shown by viewers around the region
+    if let (Some(context), Some(start)) = (&violation.context, violation.context_start_line) {
+        location["contextRegion"] = json!({
+            "startLine": start,
+            "endLine": start + context.split('\n').count() - 1,
+            "snippet": {"text": context},
+        });
+    }
+    json!({
+        "ruleId": violation.rule_id(),
+        "ruleIndex": rule_index,
+        "level": level(&violation.severity),
+        "message": {"text": violation.message()},
+        "locations": [{"physicalLocation": location}],
+        // Same identity as the Code Climate fingerprint: stable across unrelated edits
+        "partialFingerprints": {
+            "proboscis/v1": stable_hash(&[&violation.rule_name, &path, &violation.function_name]),
+        },
+    })
+}
+
+/// SARIF knows error/warning/note, so fold `critical` into `error`
+fn level(severity: &str) -> &'static str {
+    match severity {
+        "critical" | "error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_sarif() {
+        let violations = vec![
+            LintViolation {
+                rule_name: "PL004:require-test-markers".to_string(),
+                file_path: "/repo/test/unit/test_a.py".into(),
+                line_number: 3,
+                function_name: "test_a".to_string(),
+                message: "Missing marker".to_string(),
+                severity: "error".to_string(),
+                context: Some("\ndef test_a():\n    assert a()".to_string()),
+                context_start_line: Some(2),
+                ..Default::default()
+            },
+            LintViolation {
+                rule_name: "PL000:file-time-limit".to_string(),
+                file_path: "/repo/src/big.py".into(),
+                line_number: 1,
+                message: "Too slow".to_string(),
+                severity: "warning".to_string(),
+                ..Default::default()
+            },
+        ];
+        let log: Value =
+            serde_json::from_str(&render(&violations, Path::new("/repo"), None)).unwrap();
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), metadata::all_rules().len() + 1);
+
+        let marker = &run["results"][0];
+        let descriptor = &rules[marker["ruleIndex"].as_u64().unwrap() as usize];
+        assert_eq!(descriptor["id"], "PL004");
+        assert_eq!(descriptor["properties"]["category"], "organization");
+        assert_eq!(descriptor["defaultConfiguration"]["level"], "error");
+        assert_eq!(
+            marker["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "test/unit/test_a.py"
+        );
+        let context_region = &marker["locations"][0]["physicalLocation"]["contextRegion"];
+        assert_eq!(context_region["startLine"], 2);
+        assert_eq!(context_region["endLine"], 4);
+        assert!(run["results"][1]["locations"][0]["physicalLocation"]["contextRegion"].is_null());
+
+        let time_limit = &run["results"][1];
+        assert_eq!(
+            rules[time_limit["ruleIndex"].as_u64().unwrap() as usize]["name"],
+            "file-time-limit"
+        );
+        assert_eq!(time_limit["level"], "warning");
+    }
+}
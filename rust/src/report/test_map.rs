@@ -0,0 +1,113 @@
+//! Test map (`RustLinter.export_test_map`): which tests cover each function
+//!
+//! A JSON object keyed by qualified function name (`pkg.mod.func`, `pkg.mod.Class.method`),
+//! for dev portals and other tooling that link code to its tests. Each entry gives the
+//! function's file (relative to the project root) and line, and per test type the tests
+//! satisfying the missing-test rule (PL001–PL003), or null when there are none:
+//!
+//! ```json
+//! {
+//!   "pkg.mod.load": {
+//!     "e2e": null,
+//!     "file": "src/pkg/mod.py",
+//!     "integration": null,
+//!     "line": 12,
+//!     "unit": ["test/unit/pkg/test_mod.py::test_load"]
+//!   }
+//! }
+//! ```
+
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+use crate::quality::SourceFunction;
+use crate::test_cache::{TestCache, TestType};
+
+/// Test types of the map, with their keys
+const TEST_TYPES: [(&str, TestType); 3] = [
+    ("unit", TestType::Unit),
+    ("integration", TestType::Integration),
+    ("e2e", TestType::E2E),
+];
+
+/// The qualified name of `function` (defined in `module_path`) and its map entry
+pub fn entry(
+    function: &SourceFunction,
+    module_path: &str,
+    test_cache: &TestCache,
+    project_root: &Path,
+) -> (String, Value) {
+    let name = match function.class_name {
+        Some(class) => format!("{}.{}.{}", module_path, class, function.name),
+        None => format!("{}.{}", module_path, function.name),
+    };
+    let mut entry = Map::new();
+    entry.insert(
+        "file".to_string(),
+        json!(super::relative_path(
+            &function.file_path.to_string_lossy(),
+            project_root
+        )),
+    );
+    entry.insert("line".to_string(), json!(function.line_number));
+    for (key, test_type) in &TEST_TYPES {
+        let tests: Vec<String> = test_cache
+            .find_tests_for_function_of_type(
+                function.name,
+                function.file_path,
+                function.class_name,
+                test_type,
+                function.module_path,
+                project_root,
+            )
+            .map(|(test_file, test_name)| {
+                format!(
+                    "{}::{}",
+                    super::relative_path(&test_file.to_string_lossy(), project_root),
+                    test_name
+                )
+            })
+            .collect();
+        let tests = if tests.is_empty() {
+            Value::Null
+        } else {
+            json!(tests)
+        };
+        entry.insert(key.to_string(), tests);
+    }
+    (name, Value::Object(entry))
+}
+
+/// The test map document, with sorted keys
+pub fn render(functions: Map<String, Value>) -> String {
+    let mut output = serde_json::to_string_pretty(&Value::Object(functions)).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_without_tests() {
+        let function = SourceFunction {
+            name: "load",
+            class_name: Some("Store"),
+            file_path: Path::new("/repo/src/pkg/mod.py"),
+            line_number: 4,
+            module_path: "pkg.mod",
+        };
+        let (name, entry) = entry(&function, "pkg.mod", &TestCache::new(), Path::new("/repo"));
+        assert_eq!(name, "pkg.mod.Store.load");
+        assert_eq!(entry["file"], "src/pkg/mod.py");
+        assert_eq!(entry["line"], 4);
+        assert!(entry["unit"].is_null() && entry["e2e"].is_null());
+
+        let output = render(Map::from_iter([
+            ("pkg.z".to_string(), json!({})),
+            (name, entry),
+        ]));
+        assert!(output.find("pkg.mod.Store.load") < output.find("pkg.z"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Human-readable text output for terminals (`format_text`)
+//!
+//! Violations are grouped by file, with paths relative to the project root, in line order.
+//! The violations of a line are listed with their severity and message, followed by the
+//! source lines around it; each file is read once. Severities are colored with ANSI
+//! escapes as `color` says: `always`, `never`, or `auto`, which colors when standard output
+//! is a terminal, unless `NO_COLOR` is set or `TERM` is `dumb`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::models::LintViolation;
+
+use super::relative_path;
+
+/// Source lines shown before and after the line of a violation
+const CONTEXT_LINES: usize = 1;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!(
+                "Unknown color choice '{}'. Expected auto, always or never",
+                other
+            )),
+        }
+    }
+
+    /// Whether output is colored, resolving `auto` against the environment
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && std::env::var("TERM").map_or(true, |term| term != "dumb")
+                    && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Style of a severity: bold red for errors, yellow for warnings, blue for the rest
+fn severity_style(severity: &str) -> &'static str {
+    match severity {
+        "critical" | "error" => "\x1b[1;31m",
+        "warning" => "\x1b[1;33m",
+        _ => "\x1b[1;34m",
+    }
+}
+
+fn paint(text: &str, style: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", style, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Render violations for a terminal, colored with `color`, with paths relative to
+/// `project_root`
+pub fn render(violations: &[LintViolation], project_root: &Path, color: bool) -> String {
+    if violations.is_empty() {
+        return "✓ No violations found. All functions have tests!".to_string();
+    }
+    let mut by_file: BTreeMap<&str, BTreeMap<usize, Vec<&LintViolation>>> = BTreeMap::new();
+    for violation in violations {
+        by_file
+            .entry(&*violation.file_path)
+            .or_default()
+            .entry(violation.line_number)
+            .or_default()
+            .push(violation);
+    }
+
+    let mut out = String::new();
+    for (file, by_line) in &by_file {
+        out.push_str(&paint(&relative_path(file, project_root), BOLD, color));
+        out.push('\n');
+        let source = fs::read_to_string(file).unwrap_or_default();
+        let source_lines: Vec<&str> = source.lines().collect();
+        let last_line = by_line.keys().next_back().copied().unwrap_or_default();
+        let last_shown = last_line.max((last_line + CONTEXT_LINES).min(source_lines.len()));
+        let width = last_shown.to_string().len();
+        for (&line_number, line_violations) in by_line {
+            for violation in line_violations {
+                let mut message = violation.message().lines();
+                out.push_str(&format!(
+                    "  {:>width$}  {} {}\n",
+                    line_number,
+                    paint(
+                        &format!("{}:", violation.severity.to_uppercase()),
+                        severity_style(&violation.severity),
+                        color
+                    ),
+                    message.next().unwrap_or_default(),
+                    width = width
+                ));
+                for line in message.filter(|line| !line.trim().is_empty()) {
+                    out.push_str(&format!("  {:width$}    {}\n", "", line, width = width));
+                }
+            }
+            out.push_str(&context(&source_lines, line_number, width, color));
+        }
+        out.push('\n');
+    }
+    out.push_str(&summary(violations, by_file.len()));
+    out
+}
+
+/// The source lines around `line_number` (1-based), the line itself marked
+fn context(source_lines: &[&str], line_number: usize, width: usize, color: bool) -> String {
+    if line_number == 0 || line_number > source_lines.len() {
+        return String::new();
+    }
+    let first = line_number.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line_number + CONTEXT_LINES).min(source_lines.len());
+    let mut out = String::new();
+    for number in first..=last {
+        let marker = if number == line_number { '>' } else { ' ' };
+        let gutter = format!("  {} {:>width$} |", marker, number, width = width);
+        out.push_str(&paint(&gutter, DIM, color));
+        let code = source_lines[number - 1];
+        if !code.is_empty() {
+            out.push(' ');
+            out.push_str(code);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The closing lines: the counts by severity and lifecycle, and how to suppress a rule
+fn summary(violations: &[LintViolation], files: usize) -> String {
+    let mut by_severity: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_lifecycle: BTreeMap<&str, usize> = BTreeMap::new();
+    for violation in violations {
+        *by_severity.entry(&violation.severity).or_default() += 1;
+        if let Some(lifecycle) = &violation.lifecycle {
+            *by_lifecycle.entry(lifecycle).or_default() += 1;
+        }
+    }
+    let plural = |count: usize, noun: &str| {
+        let suffix = if count == 1 || noun == "critical" {
+            ""
+        } else {
+            "s"
+        };
+        format!("{} {}{}", count, noun, suffix)
+    };
+    let severities: Vec<String> = by_severity
+        .iter()
+        .map(|(severity, count)| plural(*count, severity))
+        .collect();
+    let mut out = format!(
+        "Found {} ({}) in {}\n",
+        plural(violations.len(), "violation"),
+        severities.join(", "),
+        plural(files, "file")
+    );
+    if !by_lifecycle.is_empty() {
+        let lifecycles: Vec<String> = by_lifecycle
+            .iter()
+            .map(|(lifecycle, count)| format!("{} {}", count, lifecycle))
+            .collect();
+        out.push_str(&format!(
+            "Of which in deprecated or experimental functions: {}\n",
+            lifecycles.join(", ")
+        ));
+    }
+    out.push_str("\nTip: Suppress a rule for one function with a noqa comment:\n");
+    out.push_str("  def special_function():  # noqa: PL001");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        let file = dir.join("pkg/mod.py");
+        fs::write(
+            &file,
+            "import os\n\ndef load():\n    pass\n\ndef save():\n    pass\n",
+        )
+        .unwrap();
+        let file_path: std::sync::Arc<str> = file.to_string_lossy().into();
+        let violation =
+            |rule_name: &str, line_number: usize, message: &str, severity: &str| LintViolation {
+                rule_name: rule_name.to_string(),
+                file_path: file_path.clone(),
+                line_number,
+                message: message.to_string(),
+                severity: severity.to_string(),
+                ..Default::default()
+            };
+        let violations = vec![
+            violation(
+                "PL002:require-integration-test",
+                6,
+                "[PL002] save untested",
+                "error",
+            ),
+            violation(
+                "PL001:require-unit-test",
+                3,
+                "[PL001] load untested\nExpected test function: test_load",
+                "error",
+            ),
+            violation(
+                "PL022:skipped-test-only",
+                3,
+                "[PL022] load skipped",
+                "warning",
+            ),
+        ];
+
+        assert_eq!(
+            render(&violations, dir, false),
+            "pkg/mod.py\n  3  ERROR: [PL001] load untested\n       Expected test function: test_load\n  3  WARNING: [PL022] load skipped\n    2 |\n  > 3 | def load():\n    4 |     pass\n  6  ERROR: [PL002] save untested\n    5 |\n  > 6 | def save():\n    7 |     pass\n\nFound 3 violations (2 errors, 1 warning) in 1 file\n\nTip: Suppress a rule for one function with a noqa comment:\n  def special_function():  # noqa: PL001"
+        );
+        let mut lifecycles = violations.clone();
+        lifecycles[0].lifecycle = Some("deprecated".to_string());
+        lifecycles[1].lifecycle = Some("experimental".to_string());
+        lifecycles[2].lifecycle = Some("deprecated".to_string());
+        assert!(render(&lifecycles, dir, false).contains(
+            "in 1 file\nOf which in deprecated or experimental functions: 2 deprecated, 1 experimental\n"
+        ));
+        let colored = render(&violations[..1], dir, true);
+        assert!(colored.contains("\x1b[1;31mERROR:\x1b[0m [PL002] save untested"));
+        assert!(render(&[], dir, true).contains("No violations found"));
+        assert!(ColorChoice::parse("sometimes").is_err());
+        assert!(!ColorChoice::Never.enabled());
+    }
+}
@@ -0,0 +1,171 @@
+//! Webhook notification sink (e.g. Slack incoming webhooks)
+//!
+//! Payload templates are JSON documents containing `{{placeholder}}` markers. Each marker
+//! expands to a JSON value, so templates write `{"text": {{text}}}` rather than quoting it:
+//!
+//! - `{{project}}`: project root as a string
+//! - `{{total}}`: total number of violations
+//! - `{{text}}`: a human-readable one-paragraph summary
+//! - `{{by_rule}}`: object mapping rule ids to violation counts
+//! - `{{summary}}`: the full summary object
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::models::LintViolation;
+
+/// Default payload: Slack-compatible `text` plus the structured summary
+pub const DEFAULT_TEMPLATE: &str = r#"{"text": {{text}}, "summary": {{summary}}}"#;
+
+/// Number of files listed in the summary's `top_files`
+const TOP_FILES: usize = 5;
+
+/// Build the structured summary posted to webhooks
+pub fn summarize(violations: &[LintViolation], project: &str) -> Value {
+    let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_severity: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut by_file: BTreeMap<&str, usize> = BTreeMap::new();
+    for violation in violations {
+        *by_rule.entry(violation.rule_id()).or_default() += 1;
+        *by_severity.entry(&violation.severity).or_default() += 1;
+        *by_file.entry(&violation.file_path).or_default() += 1;
+    }
+
+    let mut top_files: Vec<(&str, usize)> = by_file.iter().map(|(f, n)| (*f, *n)).collect();
+    top_files.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    top_files.truncate(TOP_FILES);
+
+    let functions: BTreeSet<(&str, &str)> = violations
+        .iter()
+        .map(|v| (&*v.file_path, v.function_name.as_str()))
+        .collect();
+
+    json!({
+        "project": project,
+        "total_violations": violations.len(),
+        "files_with_violations": by_file.len(),
+        "functions_with_violations": functions.len(),
+        "by_rule": by_rule,
+        "by_severity": by_severity,
+        "top_files": top_files
+            .into_iter()
+            .map(|(file, count)| json!({"file": file, "violations": count}))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Human-readable summary line used for `{{text}}`
+fn summary_text(summary: &Value) -> String {
+    let total = summary["total_violations"].as_u64().unwrap_or(0);
+    let project = summary["project"].as_str().unwrap_or("");
+    if total == 0 {
+        return format!("proboscis-linter: no violations in {}", project);
+    }
+
+    let rules: Vec<String> = summary["by_rule"]
+        .as_object()
+        .map(|rules| {
+            rules
+                .iter()
+                .map(|(rule, count)| format!("{} {}", rule, count))
+                .collect()
+        })
+        .unwrap_or_default();
+    format!(
+        "proboscis-linter: {} violations in {} files of {} ({})",
+        total,
+        summary["files_with_violations"],
+        project,
+        rules.join(", ")
+    )
+}
+
+/// Render a payload template for the given violations
+pub fn render_payload(
+    violations: &[LintViolation],
+    project: &str,
+    template: Option<&str>,
+) -> Result<String, String> {
+    let summary = summarize(violations, project);
+    let text = summary_text(&summary);
+
+    let rendered = template
+        .unwrap_or(DEFAULT_TEMPLATE)
+        .replace("{{project}}", &Value::from(project).to_string())
+        .replace("{{total}}", &summary["total_violations"].to_string())
+        .replace("{{text}}", &Value::from(text).to_string())
+        .replace("{{by_rule}}", &summary["by_rule"].to_string())
+        .replace("{{summary}}", &summary.to_string());
+
+    // Reject templates that do not produce valid JSON before anything is sent
+    serde_json::from_str::<Value>(&rendered)
+        .map_err(|e| format!("Webhook template does not render to valid JSON: {}", e))?;
+    Ok(rendered)
+}
+
+/// POST a JSON payload, returning the HTTP status code
+#[cfg(feature = "webhook")]
+pub fn send(url: &str, payload: &str) -> Result<u16, String> {
+    let response = ureq::post(url)
+        .header("Content-Type", "application/json")
+        .send(payload)
+        .map_err(|e| format!("Webhook request to {} failed: {}", url, e))?;
+    Ok(response.status().as_u16())
+}
+
+#[cfg(not(feature = "webhook"))]
+pub fn send(_url: &str, _payload: &str) -> Result<u16, String> {
+    Err("Webhook support is not compiled in (enable the `webhook` feature)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(rule: &str, file: &str, function: &str) -> LintViolation {
+        LintViolation {
+            rule_name: rule.to_string(),
+            file_path: file.into(),
+            function_name: function.to_string(),
+            severity: "error".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let violations = vec![
+            violation("PL001:require-unit-test", "a.py", "foo"),
+            violation("PL002:require-integration-test", "a.py", "foo"),
+            violation("PL001:require-unit-test", "b.py", "bar"),
+        ];
+        let summary = summarize(&violations, "/repo");
+        assert_eq!(summary["total_violations"], 3);
+        assert_eq!(summary["files_with_violations"], 2);
+        assert_eq!(summary["functions_with_violations"], 2);
+        assert_eq!(summary["by_rule"]["PL001"], 2);
+        assert_eq!(summary["top_files"][0]["file"], "a.py");
+    }
+
+    #[test]
+    fn test_render_default_payload() {
+        let violations = vec![violation("PL001:require-unit-test", "a.py", "foo")];
+        let payload: Value =
+            serde_json::from_str(&render_payload(&violations, "/repo", None).unwrap()).unwrap();
+        assert_eq!(
+            payload["text"],
+            "proboscis-linter: 1 violations in 1 files of /repo (PL001 1)"
+        );
+        assert_eq!(payload["summary"]["total_violations"], 1);
+    }
+
+    #[test]
+    fn test_render_custom_template() {
+        let template = r##"{"channel": "#qa", "count": {{total}}, "rules": {{by_rule}}}"##;
+        let payload: Value =
+            serde_json::from_str(&render_payload(&[], "/repo", Some(template)).unwrap()).unwrap();
+        assert_eq!(payload["channel"], "#qa");
+        assert_eq!(payload["count"], 0);
+        assert!(render_payload(&[], "/repo", Some("{{text}")).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! Which rules run (`disabled_rules`, `enabled_rules` and `[rules]` switches)
+//!
+//! A repository adopting the linter one rule at a time lists the rules it wants in
+//! `enabled_rules`; one with a rule it cannot satisfy lists that rule in `disabled_rules`.
+//! Precedence, from highest:
+//!
+//! 1. A rule in `disabled_rules`, or switched off under `[rules]` (`PL002 = false`), does
+//!    not run.
+//! 2. With `enabled_rules`, only the rules it lists run, whatever the profile enables.
+//! 3. Otherwise the rules the profile enables run, plus those switched on under `[rules]`
+//!    (`PL033 = true`).
+//!
+//! The Python configuration applies the same order (`ProboscisConfig.is_rule_enabled`).
+//! Settings layered on top, such as those of a subproject, switch rules on and off again,
+//! and their `enabled_rules` replace the list of the run.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::profiles::Profile;
+use crate::rules::metadata::validate_rule_ids;
+
+#[derive(Debug, Clone, Default)]
+pub struct RuleSelection {
+    /// Rules listed in `disabled_rules` or switched off
+    disabled: HashSet<String>,
+    /// Rules switched on, run even when the profile leaves them off
+    switched_on: HashSet<String>,
+    /// The only rules to run, when `enabled_rules` is given
+    enabled: Option<HashSet<String>>,
+}
+
+impl RuleSelection {
+    /// This selection with another layer of settings applied over it; unknown rule ids
+    /// are rejected
+    pub fn merged(
+        &self,
+        disabled_rules: Vec<String>,
+        enabled_rules: Option<Vec<String>>,
+        switches: HashMap<String, bool>,
+    ) -> Result<Self, String> {
+        validate_rule_ids(
+            disabled_rules
+                .iter()
+                .chain(enabled_rules.iter().flatten())
+                .chain(switches.keys())
+                .map(String::as_str),
+        )?;
+        let mut selection = self.clone();
+        for (rule_id, on) in switches {
+            if on {
+                selection.disabled.remove(&rule_id);
+                selection.switched_on.insert(rule_id);
+            } else {
+                selection.switched_on.remove(&rule_id);
+                selection.disabled.insert(rule_id);
+            }
+        }
+        selection.disabled.extend(disabled_rules);
+        if let Some(enabled) = enabled_rules {
+            selection.enabled = Some(enabled.into_iter().collect());
+        }
+        Ok(selection)
+    }
+
+    /// Whether the rule `rule_id` runs under `profile`
+    pub fn runs(&self, rule_id: &str, profile: Profile) -> bool {
+        !self.disabled.contains(rule_id)
+            && match &self.enabled {
+                Some(enabled) => enabled.contains(rule_id),
+                None => self.switched_on.contains(rule_id) || profile.enables(rule_id),
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        let strings = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let switches = |pairs: &[(&str, bool)]| {
+            pairs
+                .iter()
+                .map(|(id, on)| (id.to_string(), *on))
+                .collect::<HashMap<_, _>>()
+        };
+
+        // Switches under [rules] layer over the profile
+        let table = RuleSelection::default()
+            .merged(vec![], None, switches(&[("PL002", false), ("PL033", true)]))
+            .unwrap();
+        assert!(table.runs("PL001", Profile::Standard));
+        assert!(!table.runs("PL002", Profile::Standard));
+        assert!(table.runs("PL033", Profile::Standard));
+        assert!(!table.runs("PL003", Profile::Minimal));
+
+        // enabled_rules runs only the rules it lists; being disabled wins over it
+        let only = RuleSelection::default()
+            .merged(
+                strings(&["PL023"]),
+                Some(strings(&["PL001", "PL023"])),
+                switches(&[("PL002", true)]),
+            )
+            .unwrap();
+        assert!(only.runs("PL001", Profile::Minimal));
+        assert!(!only.runs("PL002", Profile::Strict));
+        assert!(!only.runs("PL023", Profile::Strict));
+
+        // A later layer switches a disabled rule on again and replaces enabled_rules
+        let layered = only
+            .merged(
+                vec![],
+                Some(strings(&["PL023"])),
+                switches(&[("PL023", true)]),
+            )
+            .unwrap();
+        assert!(layered.runs("PL023", Profile::Standard));
+        assert!(!layered.runs("PL001", Profile::Standard));
+
+        assert!(RuleSelection::default()
+            .merged(vec![], Some(strings(&["PL999"])), HashMap::new())
+            .unwrap_err()
+            .contains("PL999"));
+    }
+}
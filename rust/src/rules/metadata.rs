@@ -0,0 +1,435 @@
+//! Static rule metadata, exposed to Python as the `proboscis_linter_rust.rules` submodule
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Description of a rule, as returned by `rules.all()` / `rules.get()`
+#[pyclass(name = "RuleInfo")]
+#[derive(Debug, Clone)]
+pub struct RuleInfo {
+    /// e.g. "PL001"
+    #[pyo3(get)]
+    pub rule_id: &'static str,
+    /// e.g. "require-unit-test"
+    #[pyo3(get)]
+    pub name: &'static str,
+    #[pyo3(get)]
+    pub description: &'static str,
+    /// What the rule checks: "coverage" (functions have tests), "organization" (where tests
+    /// live and how they are marked) or "quality" (how tests are written)
+    #[pyo3(get)]
+    pub category: &'static str,
+    /// Free-form labels for filtering, e.g. "markers"; rules not run unless enabled carry
+    /// "opt-in"
+    #[pyo3(get)]
+    pub tags: Vec<&'static str>,
+    /// Severity of the violations the rule reports ("error" or "warning")
+    #[pyo3(get)]
+    pub default_severity: &'static str,
+    /// Safety of the fixes the rule attaches ("safe", "unsafe"; see `fixes::FixSafety`), or
+    /// None when it attaches none
+    #[pyo3(get)]
+    pub fix_safety: Option<&'static str>,
+    /// Version the rule first shipped in
+    #[pyo3(get)]
+    pub since: &'static str,
+    /// Test type the rule requires ("unit", "integration", "e2e"), or None for rules without one
+    #[pyo3(get)]
+    pub test_type: Option<&'static str>,
+    /// Option names accepted under `[tool.proboscis.rules.<ID>] options`
+    #[pyo3(get)]
+    pub options: Vec<&'static str>,
+}
+
+#[pymethods]
+impl RuleInfo {
+    fn __repr__(&self) -> String {
+        format!("RuleInfo({}:{})", self.rule_id, self.name)
+    }
+
+    /// Behavior versions of the rule, oldest first (see `rule_versions`)
+    #[getter]
+    fn versions(&self) -> Vec<u32> {
+        super::versions::versions_of(self.rule_id)
+    }
+
+    /// Version runs use unless `rule_versions` pins another
+    #[getter]
+    fn default_version(&self) -> u32 {
+        super::versions::default_version(self.rule_id)
+    }
+}
+
+/// Rule categories, as in `RuleInfo::category`
+pub const CATEGORIES: &[&str] = &["coverage", "organization", "quality"];
+
+const SIZE_THRESHOLD_OPTIONS: &[&str] = &["min_statements", "min_complexity"];
+
+/// Every rule, in rule id order
+pub fn all_rules() -> Vec<RuleInfo> {
+    vec![
+        RuleInfo {
+            rule_id: "PL001",
+            name: "require-unit-test",
+            description: "Public functions must have a unit test under test/unit/",
+            category: "coverage",
+            tags: vec!["tests", "unit"],
+            default_severity: "error",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: Some("unit"),
+            options: SIZE_THRESHOLD_OPTIONS.to_vec(),
+        },
+        RuleInfo {
+            rule_id: "PL002",
+            name: "require-integration-test",
+            description: "Public functions must have an integration test under test/integration/",
+            category: "coverage",
+            tags: vec!["tests", "integration"],
+            default_severity: "error",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: Some("integration"),
+            options: SIZE_THRESHOLD_OPTIONS.to_vec(),
+        },
+        RuleInfo {
+            rule_id: "PL003",
+            name: "require-e2e-test",
+            description: "Public functions must have an end-to-end test under test/e2e/",
+            category: "coverage",
+            tags: vec!["tests", "e2e", "entry-points"],
+            default_severity: "error",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: Some("e2e"),
+            options: [
+                SIZE_THRESHOLD_OPTIONS,
+                &[
+                    "entry_points_only",
+                    "entry_point_names",
+                    "entry_point_decorators",
+                ],
+            ]
+            .concat(),
+        },
+        RuleInfo {
+            rule_id: "PL004",
+            name: "require-test-markers",
+            description: "Test functions must carry the pytest marker of their test directory",
+            category: "organization",
+            tags: vec!["markers", "pytest"],
+            default_severity: "error",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL005",
+            name: "missing-test-package",
+            description: "Advisory: the directories of an expected test file do not exist yet (with check_test_skeleton=True)",
+            category: "organization",
+            tags: vec!["layout", "advisory"],
+            default_severity: "warning",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL006",
+            name: "duplicate-definition",
+            description: "Functions must not be defined twice in the same module or class, which leaves the first definition dead",
+            category: "organization",
+            tags: vec!["dead-code"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL007",
+            name: "dead-private-helper",
+            description: "Private functions must be referenced somewhere in the project's sources or tests",
+            category: "organization",
+            tags: vec!["dead-code", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL022",
+            name: "skipped-test-only",
+            description: "Functions whose matching tests are all marked skip or non-strict xfail",
+            category: "coverage",
+            tags: vec!["skip", "xfail"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: SIZE_THRESHOLD_OPTIONS.to_vec(),
+        },
+        RuleInfo {
+            rule_id: "PL023",
+            name: "test-naming",
+            description: "Test function names must follow the configured naming convention",
+            category: "organization",
+            tags: vec!["naming", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["pattern", "forbid_camel_case", "require_when_then"],
+        },
+        RuleInfo {
+            rule_id: "PL024",
+            name: "require-test-docstring",
+            description: "Test functions must have a one-line docstring describing the behavior under test",
+            category: "quality",
+            tags: vec!["docstrings", "opt-in"],
+            default_severity: "warning",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL025",
+            name: "test-too-long",
+            description: "Test functions must not exceed the configured body lines or assertions",
+            category: "quality",
+            tags: vec!["size", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["max_lines", "max_assertions"],
+        },
+        RuleInfo {
+            rule_id: "PL026",
+            name: "no-sleep-in-tests",
+            description: "Tests must not wait with time.sleep (unit and integration tests by default)",
+            category: "quality",
+            tags: vec!["flaky", "sleep"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["test_types"],
+        },
+        RuleInfo {
+            rule_id: "PL027",
+            name: "no-external-access",
+            description: "Unit tests must not import network modules or open files outside temporary directories",
+            category: "quality",
+            tags: vec!["isolation", "network", "opt-in"],
+            default_severity: "error",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec![
+                "unit_deny",
+                "unit_allow",
+                "integration_deny",
+                "integration_allow",
+                "e2e_deny",
+                "e2e_allow",
+            ],
+        },
+        RuleInfo {
+            rule_id: "PL028",
+            name: "conftest-placement",
+            description: "conftest.py files must live in test directories without clashing fixtures",
+            category: "organization",
+            tags: vec!["conftest", "fixtures"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["allow_root_conftest"],
+        },
+        RuleInfo {
+            rule_id: "PL029",
+            name: "test-package-consistency",
+            description: "Sibling test directories must agree on having an __init__.py, as pytest's import mode requires",
+            category: "organization",
+            tags: vec!["layout", "imports"],
+            default_severity: "warning",
+            fix_safety: Some("safe"),
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["import_mode"],
+        },
+        RuleInfo {
+            rule_id: "PL030",
+            name: "public-api-imports",
+            description: "Tests must import from the public API, not from private modules such as pkg._internal",
+            category: "quality",
+            tags: vec!["imports", "api", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["private_patterns", "allow"],
+        },
+        RuleInfo {
+            rule_id: "PL031",
+            name: "tautological-assertion",
+            description: "Tests must not assert what cannot fail, such as x == x, a constant or a mock's own return value",
+            category: "quality",
+            tags: vec!["assertions", "mocks"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL032",
+            name: "empty-test-file",
+            description: "Files matching the test file patterns must contain tests pytest collects",
+            category: "organization",
+            tags: vec!["collection", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: Vec::new(),
+        },
+        RuleInfo {
+            rule_id: "PL033",
+            name: "untested-without-type-hints",
+            description: "Public functions without a unit test must at least be fully type annotated",
+            category: "coverage",
+            tags: vec!["unit", "typing", "opt-in"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: Some("unit"),
+            options: SIZE_THRESHOLD_OPTIONS.to_vec(),
+        },
+        RuleInfo {
+            rule_id: "PL034",
+            name: "noqa-density",
+            description: "Modules must not suppress rules with noqa on most of their functions",
+            category: "organization",
+            tags: vec!["noqa"],
+            default_severity: "warning",
+            fix_safety: None,
+            since: "0.1.0",
+            test_type: None,
+            options: vec!["max_percent", "min_functions"],
+        },
+    ]
+}
+
+/// Metadata of the rule with exactly `rule_id`
+pub fn find(rule_id: &str) -> Option<RuleInfo> {
+    all_rules().into_iter().find(|rule| rule.rule_id == rule_id)
+}
+
+/// Check that every id in `rule_ids` names a rule, e.g. the keys of `rule_options`
+pub fn validate_rule_ids<'a>(rule_ids: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+    let known = all_rules();
+    let unknown: Vec<&str> = rule_ids
+        .into_iter()
+        .filter(|rule_id| !known.iter().any(|rule| rule.rule_id == *rule_id))
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "Unknown rule id(s): {}. Known rules: {}",
+        unknown.join(", "),
+        known
+            .iter()
+            .map(|rule| rule.rule_id)
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// Metadata of every rule
+#[pyfunction(name = "all")]
+fn all_py() -> Vec<RuleInfo> {
+    all_rules()
+}
+
+/// Metadata of the rule with `rule_id` (e.g. "PL001"), or None if there is none
+#[pyfunction]
+fn get(rule_id: &str) -> Option<RuleInfo> {
+    all_rules()
+        .into_iter()
+        .find(|rule| rule.rule_id.eq_ignore_ascii_case(rule_id))
+}
+
+/// Metadata of the rules in `category` and carrying `tag`, in rule id order
+#[pyfunction]
+#[pyo3(signature = (category=None, tag=None))]
+fn list_rules(category: Option<&str>, tag: Option<&str>) -> PyResult<Vec<RuleInfo>> {
+    if let Some(category) = category {
+        if !CATEGORIES.contains(&category) {
+            return Err(PyValueError::new_err(format!(
+                "Unknown category '{}'. Expected one of: {}",
+                category,
+                CATEGORIES.join(", ")
+            )));
+        }
+    }
+    Ok(all_rules()
+        .into_iter()
+        .filter(|rule| category.is_none_or(|category| rule.category == category))
+        .filter(|rule| tag.is_none_or(|tag| rule.tags.contains(&tag)))
+        .collect())
+}
+
+/// Build the `rules` submodule
+pub fn module(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let m = PyModule::new(py, "rules")?;
+    m.add_class::<RuleInfo>()?;
+    m.add_function(wrap_pyfunction!(all_py, &m)?)?;
+    m.add_function(wrap_pyfunction!(get, &m)?)?;
+    m.add_function(wrap_pyfunction!(list_rules, &m)?)?;
+    Ok(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{get_all_rules, options::RuleOptions, versions::RuleVersions};
+    use std::path::Path;
+
+    #[test]
+    fn test_metadata_matches_rules() {
+        let rules = get_all_rules(
+            &RuleOptions::default(),
+            &RuleVersions::default(),
+            Path::new("."),
+        );
+        let metadata = all_rules();
+        for rule in &rules {
+            let info = metadata
+                .iter()
+                .find(|info| info.rule_id == rule.rule_id())
+                .unwrap();
+            assert_eq!(info.name, rule.rule_name());
+        }
+        // PL004–PL007, PL034 and the test file rules (PL023–PL032) run outside the
+        // per-function rules
+        assert_eq!(metadata.len(), rules.len() + 15);
+        for info in &metadata {
+            assert!(CATEGORIES.contains(&info.category), "{}", info.rule_id);
+        }
+    }
+
+    #[test]
+    fn test_validate_rule_ids() {
+        assert!(validate_rule_ids(["PL001", "PL029"]).is_ok());
+        let error = validate_rule_ids(["PL001", "PL01", "pl002"]).unwrap_err();
+        assert!(error.starts_with("Unknown rule id(s): PL01, pl002."));
+        assert_eq!(find("PL004").unwrap().category, "organization");
+    }
+}
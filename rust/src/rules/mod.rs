@@ -1,20 +1,271 @@
+pub mod metadata;
+pub mod options;
 pub mod pl001_require_test;
 pub mod pl002_require_integration_test;
 pub mod pl003_require_e2e_test;
 pub mod pl004_require_test_markers;
+pub mod pl006_duplicate_definition;
+pub mod pl007_dead_private_helper;
+pub mod pl022_skipped_test_only;
+pub mod pl023_test_naming;
+pub mod pl024_test_docstrings;
+pub mod pl025_test_length;
+pub mod pl026_no_sleep_in_tests;
+pub mod pl027_no_external_access;
+pub mod pl028_conftest_placement;
+pub mod pl029_test_package_consistency;
+pub mod pl030_public_api_imports;
+pub mod pl031_tautological_assertion;
+pub mod pl032_empty_test_file;
+pub mod pl033_untested_without_type_hints;
+pub mod pl034_noqa_density;
+pub mod versions;
 
 use crate::models::LintViolation;
+use crate::parser::FunctionDef;
+use std::collections::HashSet;
 use std::path::Path;
 
+use crate::patterns::{Patterns, PATTERNS};
 use crate::test_cache::TestCache;
 use std::sync::Arc;
 
 /// Context for rule checking
 pub struct RuleContext<'a> {
-    pub test_directories: &'a [String],
     pub test_cache: &'a Arc<TestCache>,
+    /// Path of the file being checked, shared by the violations reported in it
+    pub file_path: &'a Arc<str>,
+    /// Module whose tests cover the function: the defining module, or the package that
+    /// re-exports it from a private module
     pub module_path: &'a str,
+    /// File name the expected test file is derived from (`test_<name>`)
+    pub source_file_name: &'a str,
     pub project_root: &'a Path,
+    /// Attach `add_todo_comment` fixes to missing-test violations
+    pub todo_comments: bool,
+    /// The function being checked, with its body metrics
+    pub function: &'a FunctionDef,
+    pub options: &'a options::RuleOptions,
+    /// Shared source patterns (`patterns::PATTERNS`)
+    pub patterns: &'a Patterns,
+    /// Format the human-readable `message` (off when only counts are needed)
+    pub build_messages: bool,
+    /// Templates of the test names missing-test violations recommend
+    pub test_names: &'a crate::test_names::TestNameTemplates,
+    /// Attach `add_test_stub` fixes to missing-test violations (instead of TODO comments)
+    pub test_stubs: bool,
+    /// Report the rules a function satisfies, with the tests that satisfy them
+    pub include_satisfied: bool,
+    /// The missing-test rules that apply to the function, from `decorator_test_types` or
+    /// `surface_test_types`; None when all of them do
+    pub required_rules: Option<&'a HashSet<&'static str>>,
+    /// With `credit_delegation`, the function this one delegates to (see `delegation`)
+    pub delegate: Option<&'a crate::delegation::Delegate>,
+    /// Aliases of the function left in the modules it moved from (see `moved_aliases`)
+    pub moved_from: &'a [crate::delegation::Delegate],
+}
+
+impl RuleContext<'_> {
+    /// Whether the rule `rule_id` applies to the function: any rule but the missing-test
+    /// ones the required test types leave out
+    pub fn requires(&self, rule_id: &str) -> bool {
+        self.required_rules.is_none_or(|required| {
+            !crate::decorator_policies::is_missing_test_rule(rule_id) || required.contains(rule_id)
+        })
+    }
+
+    /// Whether the function is smaller than the rule's `min_statements` or simpler than
+    /// its `min_complexity` option, exempting it from needing a test
+    pub fn is_below_size_threshold(&self, rule_id: &str) -> bool {
+        let below = |key: &str, value: usize| {
+            self.options
+                .get_usize(rule_id, key)
+                .is_some_and(|min| value < min)
+        };
+        below("min_statements", self.function.statements)
+            || below("min_complexity", self.function.complexity)
+    }
+
+    /// The `test_type` tests of the function `function_name` (a method of `class_name`) of
+    /// `module_path` at `file_path`, as `test file::test name`; only those in the mirrored
+    /// test file with `mirrored_only`
+    #[allow(clippy::too_many_arguments)]
+    fn tests_of(
+        &self,
+        function_name: &str,
+        file_path: &Path,
+        class_name: Option<&str>,
+        module_path: &str,
+        test_type: &crate::test_cache::TestType,
+        mirrored_only: bool,
+    ) -> Vec<String> {
+        self.test_cache
+            .find_tests_for_function_of_type(
+                function_name,
+                file_path,
+                class_name,
+                test_type,
+                module_path,
+                self.project_root,
+            )
+            .filter(|(test_file, _)| {
+                !mirrored_only
+                    || self.test_cache.is_mirrored_test_file(
+                        test_file,
+                        module_path,
+                        self.project_root,
+                    )
+            })
+            .map(|(test_file, test_name)| {
+                let relative = test_file
+                    .strip_prefix(self.project_root)
+                    .unwrap_or(test_file);
+                format!("{}::{}", relative.display(), test_name)
+            })
+            .collect()
+    }
+
+    /// The `test_type` tests credited to the checked function: those of the function it
+    /// delegates to, and of its aliases in the modules it moved from
+    fn credited_tests(
+        &self,
+        test_type: &crate::test_cache::TestType,
+        mirrored_only: bool,
+    ) -> Vec<String> {
+        self.delegate
+            .into_iter()
+            .chain(self.moved_from)
+            .flat_map(|credited| {
+                self.tests_of(
+                    &credited.name,
+                    &credited.file_path,
+                    credited.class_name.as_deref(),
+                    &credited.module_path,
+                    test_type,
+                    mirrored_only,
+                )
+            })
+            .collect()
+    }
+
+    /// Whether the function the checked one delegates to, or one of its aliases in the
+    /// modules it moved from, has `test_type` tests (in its mirrored test file with
+    /// `mirrored_only`), crediting the checked one
+    pub fn credited_is_tested(
+        &self,
+        test_type: &crate::test_cache::TestType,
+        mirrored_only: bool,
+    ) -> bool {
+        !self.credited_tests(test_type, mirrored_only).is_empty()
+    }
+
+    /// With `include_satisfied`, the record of a missing-test rule (`rule_name`) the
+    /// function satisfies, listing its `test_type` tests (only those in the mirrored test
+    /// file with `mirrored_only`), or those credited to it when it has none
+    pub fn satisfied(
+        &self,
+        rule_name: String,
+        function_name: &str,
+        class_name: Option<&str>,
+        line_number: usize,
+        test_type: &crate::test_cache::TestType,
+        mirrored_only: bool,
+    ) -> Option<LintViolation> {
+        if !self.include_satisfied {
+            return None;
+        }
+        let mut matched_tests = self.tests_of(
+            function_name,
+            Path::new(&**self.file_path),
+            class_name,
+            self.module_path,
+            test_type,
+            mirrored_only,
+        );
+        if matched_tests.is_empty() {
+            matched_tests = self.credited_tests(test_type, mirrored_only);
+        }
+        Some(LintViolation {
+            rule_name,
+            file_path: self.file_path.clone(),
+            line_number,
+            function_name: function_name.to_string(),
+            class_name: class_name.map(str::to_string),
+            severity: "info".to_string(),
+            satisfied: true,
+            matched_tests,
+            ..Default::default()
+        })
+    }
+
+    /// Rules suppressed by a `noqa` comment on `line`
+    pub fn noqa_rules(&self, line: &str) -> HashSet<String> {
+        crate::noqa::parse_noqa_rules_with(self.patterns, line)
+    }
+
+    /// Attach the fix for a missing `kind` test to `violation`: an `add_test_stub` fix
+    /// with `test_stubs`, else an `add_todo_comment` fix if TODO comments are enabled
+    pub fn attach_missing_test_fix(
+        &self,
+        violation: &mut LintViolation,
+        kind: &str,
+        test_name: &str,
+        expected_test_file: &Path,
+    ) {
+        if self.test_stubs {
+            violation.fix_type = Some("add_test_stub".to_string());
+            violation.fix_content = Some(crate::test_stubs::render(test_name, kind));
+            return;
+        }
+        if !self.todo_comments {
+            return;
+        }
+        let test_file = expected_test_file
+            .strip_prefix(self.project_root)
+            .unwrap_or(expected_test_file);
+        violation.fix_type = Some("add_todo_comment".to_string());
+        violation.fix_content = Some(format!(
+            "{} add {} test {} in {}",
+            crate::fixes::TODO_MARKER,
+            kind,
+            test_name,
+            test_file.display()
+        ));
+        violation.fix_line = Some(violation.line_number);
+    }
+}
+
+/// Render the message of a violation whose rule left `message` empty: PL001–PL003 only
+/// record the missing test, as formatting every message is wasted on large runs whose
+/// callers only need counts or the structured fields
+pub fn render_message(violation: &LintViolation) -> String {
+    let test_type = match violation.rule_id() {
+        "PL001" => "unit",
+        "PL002" => "integration",
+        "PL003" => "e2e",
+        _ => return String::new(),
+    };
+    let subject = match &violation.class_name {
+        Some(class) => format!("Method '{}' of class '{}'", violation.function_name, class),
+        None => format!("Function '{}'", violation.function_name),
+    };
+    if violation.satisfied {
+        return format!(
+            "[{}] {} is covered by {} tests: {}",
+            violation.rule_id(),
+            subject,
+            test_type,
+            violation.matched_tests.join(", ")
+        );
+    }
+    format!(
+        "[{}] {} has no {} test found.\nExpected test function: {}\nIn test file: {}",
+        violation.rule_id(),
+        subject,
+        test_type,
+        violation.expected_test_name.as_deref().unwrap_or_default(),
+        violation.expected_test_file.as_deref().unwrap_or_default()
+    )
 }
 
 /// Trait that all linting rules must implement
@@ -26,6 +277,7 @@ pub trait LintRule {
     fn rule_name(&self) -> &'static str;
 
     /// Check if a function violates this rule
+    #[allow(clippy::too_many_arguments)]
     fn check_function(
         &self,
         function_name: &str,
@@ -38,11 +290,78 @@ pub trait LintRule {
     ) -> Option<LintViolation>;
 }
 
-/// Get all available rules
-pub fn get_all_rules() -> Vec<Box<dyn LintRule + Send + Sync>> {
+/// Test functions (`def test...`) among the `lines` of a test file, as (0-based line
+/// index, name); used by the rules checking test files
+pub fn test_functions<'a>(lines: &'a [&'a str]) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    lines.iter().enumerate().filter_map(|(idx, line)| {
+        let name = PATTERNS.test_function.captures(line)?.get(1)?.as_str();
+        Some((idx, name))
+    })
+}
+
+/// Whether pytest collects tests from the `lines` of a test file: a module-level
+/// `def test...`, or one in a top-level `class Test...`; used by PL032 and to keep files
+/// without tests out of the test cache
+pub fn collects_tests(lines: &[&str]) -> bool {
+    let mut in_test_class = false;
+    for line in lines {
+        if !line.starts_with([' ', '\t', '@', '#']) && !line.trim().is_empty() {
+            in_test_class = PATTERNS
+                .class
+                .captures(line)
+                .and_then(|captures| captures.get(2))
+                .is_some_and(|name| name.as_str().starts_with("Test"));
+        }
+        if PATTERNS.test_function.is_match(line)
+            && (in_test_class || !line.starts_with([' ', '\t']))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Modules imported by one line of code, without relative imports; used by the rules
+/// checking the imports of test files
+pub fn imported_modules(code: &str) -> Vec<&str> {
+    if let Some(captures) = PATTERNS.from_import_module.captures(code) {
+        let module = captures.get(1).unwrap().as_str();
+        // Relative imports stay within the test suite
+        return if module.starts_with('.') {
+            vec![]
+        } else {
+            vec![module]
+        };
+    }
+    let Some(captures) = PATTERNS.import.captures(code) else {
+        return vec![];
+    };
+    captures
+        .get(1)
+        .unwrap()
+        .as_str()
+        .split(',')
+        .filter_map(|part| part.split_whitespace().next())
+        .collect()
+}
+
+/// Get all available rules, configured for a run over `project_root` at the rule
+/// `versions` in use
+pub fn get_all_rules(
+    options: &options::RuleOptions,
+    versions: &versions::RuleVersions,
+    project_root: &Path,
+) -> Vec<Box<dyn LintRule + Send + Sync>> {
     vec![
-        Box::new(pl001_require_test::PL001RequireUnitTest::new()),
+        Box::new(pl001_require_test::PL001RequireUnitTest::new(
+            versions.get("PL001"),
+        )),
         Box::new(pl002_require_integration_test::PL002RequireIntegrationTest::new()),
-        Box::new(pl003_require_e2e_test::PL003RequireE2ETest::new()),
+        Box::new(pl003_require_e2e_test::PL003RequireE2ETest::new(
+            options,
+            project_root,
+        )),
+        Box::new(pl022_skipped_test_only::PL022SkippedTestOnly::new()),
+        Box::new(pl033_untested_without_type_hints::PL033UntestedWithoutTypeHints::new()),
     ]
 }
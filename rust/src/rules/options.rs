@@ -0,0 +1,90 @@
+//! Rule-specific options, mirroring `[tool.proboscis.rules.<ID>] options = {...}`
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// A single option value as passed from Python or pyproject.toml
+#[derive(Debug, Clone, PartialEq, FromPyObject)]
+pub enum OptionValue {
+    // `bool` must come first: Python booleans also extract as integers
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<String>),
+}
+
+/// Options for every rule, keyed by rule id (e.g. "PL001") and then option name
+#[derive(Debug, Clone, Default)]
+pub struct RuleOptions {
+    options: HashMap<String, HashMap<String, OptionValue>>,
+}
+
+impl From<HashMap<String, HashMap<String, OptionValue>>> for RuleOptions {
+    fn from(options: HashMap<String, HashMap<String, OptionValue>>) -> Self {
+        Self { options }
+    }
+}
+
+impl RuleOptions {
+    /// Add the options of `defaults` that are not set already
+    pub fn add_defaults(&mut self, defaults: &HashMap<String, HashMap<String, OptionValue>>) {
+        for (rule_id, options) in defaults {
+            let configured = self.options.entry(rule_id.clone()).or_default();
+            for (key, value) in options {
+                configured
+                    .entry(key.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    pub fn get(&self, rule_id: &str, key: &str) -> Option<&OptionValue> {
+        self.options.get(rule_id)?.get(key)
+    }
+
+    pub fn get_bool(&self, rule_id: &str, key: &str) -> Option<bool> {
+        match self.get(rule_id, key)? {
+            OptionValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// A list-of-strings option; a single string is treated as a one-element list
+    pub fn get_strings(&self, rule_id: &str, key: &str) -> Option<Vec<String>> {
+        match self.get(rule_id, key)? {
+            OptionValue::List(values) => Some(values.clone()),
+            OptionValue::Str(value) => Some(vec![value.clone()]),
+            _ => None,
+        }
+    }
+
+    /// A non-negative integer option
+    pub fn get_usize(&self, rule_id: &str, key: &str) -> Option<usize> {
+        match self.get(rule_id, key)? {
+            OptionValue::Int(value) => usize::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_usize() {
+        let options = RuleOptions::from(HashMap::from([(
+            "PL001".to_string(),
+            HashMap::from([
+                ("min_statements".to_string(), OptionValue::Int(2)),
+                ("min_complexity".to_string(), OptionValue::Int(-1)),
+                ("name".to_string(), OptionValue::Str("x".to_string())),
+            ]),
+        )]));
+        assert_eq!(options.get_usize("PL001", "min_statements"), Some(2));
+        assert_eq!(options.get_usize("PL001", "min_complexity"), None);
+        assert_eq!(options.get_usize("PL001", "name"), None);
+        assert_eq!(options.get_usize("PL002", "min_statements"), None);
+    }
+}
@@ -1,13 +1,16 @@
 use super::LintRule;
 use crate::models::LintViolation;
-use crate::noqa::parse_noqa_rules;
 use std::path::Path;
 
-pub struct PL001RequireUnitTest {}
+pub struct PL001RequireUnitTest {
+    /// Behavior version (see `versions`): from 2, tests only count in the test file
+    /// mirroring the module
+    version: u32,
+}
 
 impl PL001RequireUnitTest {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(version: u32) -> Self {
+        Self { version }
     }
 }
 
@@ -25,16 +28,12 @@ impl LintRule for PL001RequireUnitTest {
         function_name: &str,
         file_path: &Path,
         line_number: usize,
-        line_content: &str,
+        _line_content: &str,
         class_name: Option<&str>,
         is_protocol: bool,
         context: &super::RuleContext,
     ) -> Option<LintViolation> {
-        // Skip if has noqa comment
-        let suppressed_rules = parse_noqa_rules(line_content);
-        if suppressed_rules.contains(self.rule_id()) {
-            return None;
-        }
+        // `noqa` comments are applied by the caller, which counts the hits they suppress
 
         // Skip protocol methods
         if is_protocol && class_name.is_some() {
@@ -46,8 +45,13 @@ impl LintRule for PL001RequireUnitTest {
             return None;
         }
 
-        // Look for corresponding unit test using cache
-        let test_found = context.test_cache.has_test_for_function_of_type(
+        // Skip functions below the configured size/complexity threshold
+        if context.is_below_size_threshold(self.rule_id()) {
+            return None;
+        }
+
+        // Look for corresponding unit test using cache, or one credited to it
+        let mut tests = context.test_cache.find_tests_for_function_of_type(
             function_name,
             file_path,
             class_name,
@@ -55,61 +59,61 @@ impl LintRule for PL001RequireUnitTest {
             context.module_path,
             context.project_root,
         );
+        let test_found = if self.version >= 2 {
+            tests.any(|(test_file, _)| {
+                context.test_cache.is_mirrored_test_file(
+                    test_file,
+                    context.module_path,
+                    context.project_root,
+                )
+            })
+        } else {
+            tests.next().is_some()
+        } || context
+            .credited_is_tested(&crate::test_cache::TestType::Unit, self.version >= 2);
 
         if !test_found {
-            // Get the single canonical test pattern
-            let test_name = context.test_cache.get_canonical_test_pattern(
-                function_name,
-                class_name,
-                &crate::test_cache::TestType::Unit,
-            );
-
-            // Get source file name
-            let source_file_name = file_path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("module.py");
+            // The recommended test name, per the configured template
+            let test_name = context.test_names.render(function_name, class_name);
 
             // Get absolute path where test should be located
             let expected_test_file = context.test_cache.get_expected_test_file_path(
                 context.module_path,
-                source_file_name,
+                context.source_file_name,
                 &crate::test_cache::TestType::Unit,
                 context.project_root,
             );
 
-            let message = if let Some(class) = class_name {
-                format!(
-                    "[{}] Method '{}' of class '{}' has no unit test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    class,
-                    test_name,
-                    expected_test_file.display()
-                )
-            } else {
-                format!(
-                    "[{}] Function '{}' has no unit test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    test_name,
-                    expected_test_file.display()
-                )
-            };
-
-            Some(LintViolation {
+            let mut violation = LintViolation {
                 rule_name: format!("{}:{}", self.rule_id(), self.rule_name()),
-                file_path: file_path.to_string_lossy().to_string(),
+                file_path: context.file_path.clone(),
                 line_number,
                 function_name: function_name.to_string(),
-                message,
                 severity: "error".to_string(),
                 fix_type: None,
                 fix_content: None,
                 fix_line: None,
-            })
+                expected_test_file: Some(expected_test_file.to_string_lossy().to_string()),
+                expected_test_name: Some(test_name.clone()),
+                class_name: class_name.map(str::to_string),
+                ..Default::default()
+            };
+            context.attach_missing_test_fix(
+                &mut violation,
+                "unit",
+                &test_name,
+                &expected_test_file,
+            );
+            Some(violation)
         } else {
-            None
+            context.satisfied(
+                format!("{}:{}", self.rule_id(), self.rule_name()),
+                function_name,
+                class_name,
+                line_number,
+                &crate::test_cache::TestType::Unit,
+                self.version >= 2,
+            )
         }
     }
 }
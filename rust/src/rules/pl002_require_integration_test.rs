@@ -1,6 +1,5 @@
 use super::LintRule;
 use crate::models::LintViolation;
-use crate::noqa::parse_noqa_rules;
 use std::path::Path;
 
 pub struct PL002RequireIntegrationTest {}
@@ -25,16 +24,12 @@ impl LintRule for PL002RequireIntegrationTest {
         function_name: &str,
         file_path: &Path,
         line_number: usize,
-        line_content: &str,
+        _line_content: &str,
         class_name: Option<&str>,
         is_protocol: bool,
         context: &super::RuleContext,
     ) -> Option<LintViolation> {
-        // Skip if has noqa comment
-        let suppressed_rules = parse_noqa_rules(line_content);
-        if suppressed_rules.contains(self.rule_id()) {
-            return None;
-        }
+        // `noqa` comments are applied by the caller, which counts the hits they suppress
 
         // Skip protocol methods
         if is_protocol && class_name.is_some() {
@@ -46,7 +41,12 @@ impl LintRule for PL002RequireIntegrationTest {
             return None;
         }
 
-        // Look for corresponding integration test using cache
+        // Skip functions below the configured size/complexity threshold
+        if context.is_below_size_threshold(self.rule_id()) {
+            return None;
+        }
+
+        // Look for corresponding integration test using cache, or one credited to it
         let test_found = context.test_cache.has_test_for_function_of_type(
             function_name,
             file_path,
@@ -54,62 +54,51 @@ impl LintRule for PL002RequireIntegrationTest {
             &crate::test_cache::TestType::Integration,
             context.module_path,
             context.project_root,
-        );
+        ) || context
+            .credited_is_tested(&crate::test_cache::TestType::Integration, false);
 
         if !test_found {
-            // Get the single canonical test pattern
-            let test_name = context.test_cache.get_canonical_test_pattern(
-                function_name,
-                class_name,
-                &crate::test_cache::TestType::Integration,
-            );
-
-            // Get source file name
-            let source_file_name = file_path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("module.py");
+            // The recommended test name, per the configured template
+            let test_name = context.test_names.render(function_name, class_name);
 
             // Get absolute path where test should be located
             let expected_test_file = context.test_cache.get_expected_test_file_path(
                 context.module_path,
-                source_file_name,
+                context.source_file_name,
                 &crate::test_cache::TestType::Integration,
                 context.project_root,
             );
 
-            let message = if let Some(class) = class_name {
-                format!(
-                    "[{}] Method '{}' of class '{}' has no integration test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    class,
-                    test_name,
-                    expected_test_file.display()
-                )
-            } else {
-                format!(
-                    "[{}] Function '{}' has no integration test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    test_name,
-                    expected_test_file.display()
-                )
-            };
-
-            Some(LintViolation {
+            let mut violation = LintViolation {
                 rule_name: format!("{}:{}", self.rule_id(), self.rule_name()),
-                file_path: file_path.to_string_lossy().to_string(),
+                file_path: context.file_path.clone(),
                 line_number,
                 function_name: function_name.to_string(),
-                message,
                 severity: "error".to_string(),
                 fix_type: None,
                 fix_content: None,
                 fix_line: None,
-            })
+                expected_test_file: Some(expected_test_file.to_string_lossy().to_string()),
+                expected_test_name: Some(test_name.clone()),
+                class_name: class_name.map(str::to_string),
+                ..Default::default()
+            };
+            context.attach_missing_test_fix(
+                &mut violation,
+                "integration",
+                &test_name,
+                &expected_test_file,
+            );
+            Some(violation)
         } else {
-            None
+            context.satisfied(
+                format!("{}:{}", self.rule_id(), self.rule_name()),
+                function_name,
+                class_name,
+                line_number,
+                &crate::test_cache::TestType::Integration,
+                false,
+            )
         }
     }
 }
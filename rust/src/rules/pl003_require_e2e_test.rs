@@ -1,13 +1,91 @@
+use super::options::RuleOptions;
 use super::LintRule;
+use crate::file_discovery::glob_to_file_name_regex;
 use crate::models::LintViolation;
-use crate::noqa::parse_noqa_rules;
+use crate::pyproject::{self, ScriptTarget};
+use regex::Regex;
 use std::path::Path;
 
-pub struct PL003RequireE2ETest {}
+/// Function names treated as entry points unless `entry_point_names` is set
+const DEFAULT_ENTRY_POINT_NAMES: &[&str] = &["main"];
+
+/// CLI command and route handler decorators, unless `entry_point_decorators` is set
+const DEFAULT_ENTRY_POINT_DECORATORS: &[&str] = &[
+    "command",
+    "group",
+    "*.command",
+    "*.group",
+    "*.route",
+    "*.get",
+    "*.post",
+    "*.put",
+    "*.patch",
+    "*.delete",
+    "*.websocket",
+];
+
+/// The functions PL003 is restricted to when `entry_points_only` is enabled
+struct EntryPoints {
+    /// Targets of `[project.scripts]` and `[project.gui-scripts]`
+    scripts: Vec<ScriptTarget>,
+    names: Vec<Regex>,
+    decorators: Vec<Regex>,
+}
+
+impl EntryPoints {
+    fn from_options(options: &RuleOptions, rule_id: &str, project_root: &Path) -> Self {
+        let patterns = |key: &str, defaults: &[&str]| -> Vec<Regex> {
+            options
+                .get_strings(rule_id, key)
+                .unwrap_or_else(|| defaults.iter().map(|p| p.to_string()).collect())
+                .iter()
+                .filter_map(|pattern| glob_to_file_name_regex(pattern))
+                .collect()
+        };
+
+        Self {
+            scripts: pyproject::load(project_root)
+                .map(|table| pyproject::script_targets(&table))
+                .unwrap_or_default(),
+            names: patterns("entry_point_names", DEFAULT_ENTRY_POINT_NAMES),
+            decorators: patterns("entry_point_decorators", DEFAULT_ENTRY_POINT_DECORATORS),
+        }
+    }
+
+    fn contains(
+        &self,
+        function_name: &str,
+        class_name: Option<&str>,
+        module_path: &str,
+        decorators: &[String],
+    ) -> bool {
+        let qualified_name = match class_name {
+            Some(class) => format!("{}.{}", class, function_name),
+            None => function_name.to_string(),
+        };
+
+        self.scripts
+            .iter()
+            .any(|script| script.module == module_path && script.attribute == qualified_name)
+            || self.names.iter().any(|name| name.is_match(function_name))
+            || decorators
+                .iter()
+                .any(|decorator| self.decorators.iter().any(|d| d.is_match(decorator)))
+    }
+}
+
+pub struct PL003RequireE2ETest {
+    /// Set when PL003 only applies to entry points
+    entry_points: Option<EntryPoints>,
+}
 
 impl PL003RequireE2ETest {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(options: &RuleOptions, project_root: &Path) -> Self {
+        let entry_points = options
+            .get_bool("PL003", "entry_points_only")
+            .unwrap_or(false)
+            .then(|| EntryPoints::from_options(options, "PL003", project_root));
+        Self { entry_points }
     }
 }
 
@@ -25,16 +103,12 @@ impl LintRule for PL003RequireE2ETest {
         function_name: &str,
         file_path: &Path,
         line_number: usize,
-        line_content: &str,
+        _line_content: &str,
         class_name: Option<&str>,
         is_protocol: bool,
         context: &super::RuleContext,
     ) -> Option<LintViolation> {
-        // Skip if has noqa comment
-        let suppressed_rules = parse_noqa_rules(line_content);
-        if suppressed_rules.contains(self.rule_id()) {
-            return None;
-        }
+        // `noqa` comments are applied by the caller, which counts the hits they suppress
 
         // Skip protocol methods
         if is_protocol && class_name.is_some() {
@@ -46,7 +120,24 @@ impl LintRule for PL003RequireE2ETest {
             return None;
         }
 
-        // Look for corresponding e2e test using cache
+        // Skip functions below the configured size/complexity threshold
+        if context.is_below_size_threshold(self.rule_id()) {
+            return None;
+        }
+
+        // Only entry points need e2e tests when restricted
+        if let Some(entry_points) = &self.entry_points {
+            if !entry_points.contains(
+                function_name,
+                class_name,
+                context.module_path,
+                &context.function.decorators,
+            ) {
+                return None;
+            }
+        }
+
+        // Look for corresponding e2e test using cache, or one credited to it
         let test_found = context.test_cache.has_test_for_function_of_type(
             function_name,
             file_path,
@@ -54,62 +145,45 @@ impl LintRule for PL003RequireE2ETest {
             &crate::test_cache::TestType::E2E,
             context.module_path,
             context.project_root,
-        );
+        ) || context.credited_is_tested(&crate::test_cache::TestType::E2E, false);
 
         if !test_found {
-            // Get the single canonical test pattern
-            let test_name = context.test_cache.get_canonical_test_pattern(
-                function_name,
-                class_name,
-                &crate::test_cache::TestType::E2E,
-            );
-
-            // Get source file name
-            let source_file_name = file_path
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("module.py");
+            // The recommended test name, per the configured template
+            let test_name = context.test_names.render(function_name, class_name);
 
             // Get absolute path where test should be located
             let expected_test_file = context.test_cache.get_expected_test_file_path(
                 context.module_path,
-                source_file_name,
+                context.source_file_name,
                 &crate::test_cache::TestType::E2E,
                 context.project_root,
             );
 
-            let message = if let Some(class) = class_name {
-                format!(
-                    "[{}] Method '{}' of class '{}' has no e2e test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    class,
-                    test_name,
-                    expected_test_file.display()
-                )
-            } else {
-                format!(
-                    "[{}] Function '{}' has no e2e test found.\nExpected test function: {}\nIn test file: {}",
-                    self.rule_id(),
-                    function_name,
-                    test_name,
-                    expected_test_file.display()
-                )
-            };
-
-            Some(LintViolation {
+            let mut violation = LintViolation {
                 rule_name: format!("{}:{}", self.rule_id(), self.rule_name()),
-                file_path: file_path.to_string_lossy().to_string(),
+                file_path: context.file_path.clone(),
                 line_number,
                 function_name: function_name.to_string(),
-                message,
                 severity: "error".to_string(),
                 fix_type: None,
                 fix_content: None,
                 fix_line: None,
-            })
+                expected_test_file: Some(expected_test_file.to_string_lossy().to_string()),
+                expected_test_name: Some(test_name.clone()),
+                class_name: class_name.map(str::to_string),
+                ..Default::default()
+            };
+            context.attach_missing_test_fix(&mut violation, "e2e", &test_name, &expected_test_file);
+            Some(violation)
         } else {
-            None
+            context.satisfied(
+                format!("{}:{}", self.rule_id(), self.rule_name()),
+                function_name,
+                class_name,
+                line_number,
+                &crate::test_cache::TestType::E2E,
+                false,
+            )
         }
     }
 }
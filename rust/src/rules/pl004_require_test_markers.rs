@@ -1,43 +1,47 @@
+//! PL004: Require pytest markers on test functions
+//!
+//! This rule ensures that test functions have the appropriate pytest marker
+//! based on their location in the test hierarchy:
+//! - Tests in test/unit/ should have @pytest.mark.unit
+//! - Tests in test/integration/ should have @pytest.mark.integration
+//! - Tests in test/e2e/ should have @pytest.mark.e2e
+//!
+//! Markers used under an alias (`from pytest import mark as m`, `unit = pytest.mark.unit`
+//! in a helper module) are recognized through `aliases`.
+
 use pyo3::prelude::*;
 use rayon::prelude::*;
-use regex::Regex;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::file_discovery::find_python_files;
+use crate::aliases::Aliases;
+use crate::file_discovery::find_test_files;
 use crate::models::LintViolation;
 use crate::noqa::parse_noqa_rules;
+use crate::parser;
+use crate::patterns::PATTERNS;
 use crate::public_api;
 
-/// PL004: Require pytest markers on test functions
-///
-/// This rule ensures that test functions have the appropriate pytest marker
-/// based on their location in the test hierarchy:
-/// - Tests in test/unit/ should have @pytest.mark.unit
-/// - Tests in test/integration/ should have @pytest.mark.integration  
-/// - Tests in test/e2e/ should have @pytest.mark.e2e
-
 struct TestFunction {
     name: String,
     line_number: usize,
+    /// 1-based line of the first decorator (`line_number` when undecorated)
+    decorator_line: usize,
     decorators: Vec<String>,
 }
 
-/// Extract test functions from a Python file
-fn extract_test_functions(file_path: &Path) -> Result<Vec<TestFunction>, std::io::Error> {
-    let content = fs::read_to_string(file_path)?;
+/// Extract test functions from the content of a Python file
+fn extract_test_functions(content: &str) -> Vec<TestFunction> {
     let mut functions = Vec::new();
 
-    let func_regex = Regex::new(r"^(\s*)def\s+(test_\w+)\s*\(").unwrap();
-    let decorator_regex = Regex::new(r"^(\s*)@(.+)$").unwrap();
-
     let lines: Vec<&str> = content.lines().collect();
     let mut i = 0;
 
     while i < lines.len() {
-        if let Some(func_captures) = func_regex.captures(lines[i]) {
-            let func_name = func_captures.get(2).unwrap().as_str().to_string();
+        if let Some(func_captures) = PATTERNS.test_function.captures(lines[i]) {
+            let func_name = func_captures.get(1).unwrap().as_str().to_string();
             let func_line = i + 1;
 
             // Look back for decorators
@@ -50,7 +54,7 @@ fn extract_test_functions(file_path: &Path) -> Result<Vec<TestFunction>, std::io
                 if !lines[line_idx].trim().starts_with('@') {
                     break;
                 }
-                if let Some(dec_captures) = decorator_regex.captures(lines[line_idx]) {
+                if let Some(dec_captures) = PATTERNS.decorator.captures(lines[line_idx]) {
                     let decorator_raw = dec_captures.get(2).unwrap().as_str();
                     // Remove inline comments
                     let decorator = if let Some(comment_pos) = decorator_raw.find('#') {
@@ -68,18 +72,18 @@ fn extract_test_functions(file_path: &Path) -> Result<Vec<TestFunction>, std::io
             functions.push(TestFunction {
                 name: func_name,
                 line_number: func_line,
+                decorator_line: parser::decorator_start_line(&lines, func_line),
                 decorators,
             });
         }
         i += 1;
     }
 
-    Ok(functions)
+    functions
 }
 
-/// Extract all noqa rules from a file
-fn extract_file_noqa_rules(file_path: &Path) -> Result<HashSet<String>, std::io::Error> {
-    let content = fs::read_to_string(file_path)?;
+/// Extract all noqa rules from the content of a file
+fn extract_file_noqa_rules(content: &str) -> HashSet<String> {
     let mut all_rules = HashSet::new();
 
     // Check for file-level noqa at the beginning
@@ -95,7 +99,7 @@ fn extract_file_noqa_rules(file_path: &Path) -> Result<HashSet<String>, std::io:
             break; // Stop at first code line
         }
         let rules = parse_noqa_rules(line);
-        if rules.contains(&"PL004".to_string()) && i < 3 {
+        if rules.contains("PL004") && i < 3 {
             // Consider it file-level if in first 3 lines
             file_level_noqa = true;
             all_rules.insert("PL004".to_string());
@@ -113,13 +117,20 @@ fn extract_file_noqa_rules(file_path: &Path) -> Result<HashSet<String>, std::io:
         }
     }
 
-    Ok(all_rules)
+    all_rules
 }
 
 /// Check a single test file for missing pytest markers
-fn check_file(file_path: &Path, source_module_path: Option<&Path>) -> Vec<LintViolation> {
+fn check_file(
+    file_path: &Path,
+    source_module_path: Option<&Path>,
+    project_root: &Path,
+) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
     // Extract noqa rules for this file
-    let noqa_rules = extract_file_noqa_rules(file_path).unwrap_or_default();
+    let noqa_rules = extract_file_noqa_rules(&content);
 
     // Skip if PL004 is suppressed for this file
     if noqa_rules.contains("PL004") {
@@ -133,24 +144,24 @@ fn check_file(file_path: &Path, source_module_path: Option<&Path>) -> Vec<LintVi
     };
 
     // Extract test functions from the file
-    let test_functions = match extract_test_functions(file_path) {
-        Ok(funcs) => funcs,
-        Err(_) => return vec![],
-    };
+    let test_functions = extract_test_functions(&content);
 
-    // Extract public API from source module if available
-    let public_api = if let Some(source_path) = source_module_path {
-        public_api::extract_module_all(source_path).unwrap_or(public_api::PublicApi::default())
-    } else {
-        public_api::PublicApi::default()
-    };
+    let aliases = Aliases::of_file(file_path, project_root);
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+
+    // Extract the public API and classes of the source module if available
+    let source = source_module_path
+        .and_then(|source_path| fs::read_to_string(source_path).ok())
+        .unwrap_or_default();
+    let public_api = public_api::parse_module_all(&source);
+    let classes = class_names(&source);
 
     // Check each test function for the appropriate marker
-    test_functions
+    let mut violations: Vec<LintViolation> = test_functions
         .into_iter()
         .filter_map(|func| {
             // Try to infer what function this test is testing
-            let tested_func = infer_tested_function(&func.name);
+            let tested_func = infer_tested_function(&func.name, &classes);
 
             // Skip if testing a private function
             if let Some(tested) = &tested_func {
@@ -159,15 +170,18 @@ fn check_file(file_path: &Path, source_module_path: Option<&Path>) -> Vec<LintVi
                 }
             }
 
-            // Skip if the line has noqa
-            let line_noqa = noqa_rules.contains(&format!("{}:PL004", func.line_number));
-            if line_noqa || has_pytest_marker(&func, &expected_marker) {
+            // Skip if the `def` line or one of its decorator lines has noqa
+            let line_noqa = (func.decorator_line..=func.line_number)
+                .any(|line| noqa_rules.contains(&format!("{}:PL004", line)));
+            if line_noqa || has_pytest_marker(&func, &expected_marker, &aliases) {
                 None
             } else {
-                Some(create_violation(file_path, &func, &expected_marker))
+                Some(create_violation(&shared_path, &func, &expected_marker))
             }
         })
-        .collect()
+        .collect();
+    crate::fixes::record_content_hash(&mut violations, &content);
+    violations
 }
 
 /// Determine test type from file path
@@ -186,22 +200,26 @@ fn get_test_type_from_path(file_path: &Path) -> Option<String> {
 }
 
 /// Check if a function has the required pytest marker
-fn has_pytest_marker(func: &TestFunction, expected_marker: &str) -> bool {
+fn has_pytest_marker(func: &TestFunction, expected_marker: &str, aliases: &Aliases) -> bool {
     // Check if any decorator matches pytest.mark.{expected_marker}
     func.decorators.iter().any(|decorator| {
         // Handle various forms: pytest.mark.unit, mark.unit, unit
-        let dec = decorator.trim();
-        dec == &format!("pytest.mark.{}", expected_marker) ||
-        dec == &format!("mark.{}", expected_marker) ||
+        let dec = aliases.resolve(decorator);
+        dec == format!("pytest.mark.{}", expected_marker) ||
+        dec == format!("mark.{}", expected_marker) ||
         dec == expected_marker ||
         // Handle parentheses: pytest.mark.unit(), mark.unit()
-        dec == &format!("pytest.mark.{}()", expected_marker) ||
-        dec == &format!("mark.{}()", expected_marker)
+        dec == format!("pytest.mark.{}()", expected_marker) ||
+        dec == format!("mark.{}()", expected_marker)
     })
 }
 
 /// Create a violation for a missing pytest marker
-fn create_violation(file_path: &Path, func: &TestFunction, expected_marker: &str) -> LintViolation {
+fn create_violation(
+    file_path: &Arc<str>,
+    func: &TestFunction,
+    expected_marker: &str,
+) -> LintViolation {
     // The fix is to add the decorator on the line before the function
     let fix_line = if func.line_number > 1 {
         func.line_number - 1
@@ -211,50 +229,85 @@ fn create_violation(file_path: &Path, func: &TestFunction, expected_marker: &str
 
     LintViolation {
         rule_name: "PL004:require-test-markers".to_string(),
-        file_path: file_path.to_str().unwrap_or("").to_string(),
+        file_path: file_path.clone(),
         line_number: func.line_number,
+        decorator_start_line: Some(func.decorator_line),
         function_name: func.name.clone(),
         message: format!(
             "[PL004] Test function '{}' is missing required pytest marker.\nExpected: @pytest.mark.{}\nLocation: {}\n\nTip: Use --fix flag to automatically add missing markers",
             func.name,
-            expected_marker,
-            file_path.display()
+            expected_marker, file_path
         ),
         severity: "error".to_string(),
         fix_type: Some("add_decorator".to_string()),
         fix_content: Some(format!("@pytest.mark.{}", expected_marker)),
         fix_line: Some(fix_line),
+        ..Default::default()
     }
 }
 
-/// Infer the function being tested from the test function name
-fn infer_tested_function(test_name: &str) -> Option<String> {
+/// Names of the classes defined in `source`
+fn class_names(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| PATTERNS.class.captures(line))
+        .map(|captures| captures[2].to_string())
+        .collect()
+}
+
+/// `ClassName` as `class_name`
+fn snake_case(class: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in class.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Infer the function being tested from the test function name, as `Class.method` for
+/// the methods of `classes` (the classes of the source module)
+fn infer_tested_function(test_name: &str, classes: &[String]) -> Option<String> {
     // Common patterns:
     // test_function_name -> function_name
-    // test_method_name -> method_name
     // test_ClassName_method -> ClassName.method
+    // test_classname_method, test_class_name_method -> ClassName.method
+    let without_prefix = test_name.strip_prefix("test_")?;
+
+    // Check for class method pattern (test_ClassName_method)
+    if let Some((potential_class, method)) = without_prefix.split_once('_') {
+        // Check if first letter is uppercase (likely a class name)
+        if potential_class
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_uppercase())
+            && !method.is_empty()
+        {
+            return Some(format!("{}.{}", potential_class, method));
+        }
+    }
 
-    if test_name.starts_with("test_") {
-        let without_prefix = &test_name[5..];
-
-        // Check for class method pattern (test_ClassName_method)
-        if let Some(underscore_pos) = without_prefix.find('_') {
-            let potential_class = &without_prefix[..underscore_pos];
-            // Check if first letter is uppercase (likely a class name)
-            if potential_class
-                .chars()
-                .next()
-                .map_or(false, |c| c.is_uppercase())
-            {
-                return Some(without_prefix.to_string());
+    // Lowercase forms of the classes of the source module, longest class first
+    let mut candidates: Vec<(&String, String)> = classes
+        .iter()
+        .flat_map(|class| [(class, class.to_lowercase()), (class, snake_case(class))])
+        .collect();
+    candidates.sort_by_key(|(_, form)| std::cmp::Reverse(form.len()));
+    for (class, form) in candidates {
+        if let Some(method) = without_prefix
+            .strip_prefix(form.as_str())
+            .and_then(|rest| rest.strip_prefix('_'))
+        {
+            if !method.is_empty() {
+                return Some(format!("{}.{}", class, method));
             }
         }
-
-        // Regular function pattern
-        Some(without_prefix.to_string())
-    } else {
-        None
     }
+
+    // Regular function pattern
+    Some(without_prefix.to_string())
 }
 
 /// Check if we should check a test based on what it's testing
@@ -286,13 +339,15 @@ fn find_source_module_for_test(test_path: &Path, project_root: &Path) -> Option<
     let test_file_name = test_path.file_name()?.to_str()?;
 
     // Remove test_ prefix or _test suffix to get source file name
-    let source_file_name = if test_file_name.starts_with("test_") && test_file_name.ends_with(".py")
+    let source_file_name = if let Some(stem) = test_file_name
+        .strip_prefix("test_")
+        .and_then(|rest| rest.strip_suffix(".py"))
     {
         // test_module.py -> module.py
-        format!("{}.py", &test_file_name[5..test_file_name.len() - 3])
-    } else if test_file_name.ends_with("_test.py") {
+        format!("{}.py", stem)
+    } else if let Some(stem) = test_file_name.strip_suffix("_test.py") {
         // module_test.py -> module.py
-        format!("{}.py", &test_file_name[..test_file_name.len() - 8])
+        format!("{}.py", stem)
     } else {
         return None;
     };
@@ -309,7 +364,7 @@ fn find_source_module_for_test(test_path: &Path, project_root: &Path) -> Option<
                     if path.is_file()
                         && path
                             .file_name()
-                            .map_or(false, |n| n == source_file_name.as_str())
+                            .is_some_and(|n| n == source_file_name.as_str())
                     {
                         return Some(path);
                     }
@@ -334,31 +389,15 @@ fn find_source_module_for_test(test_path: &Path, project_root: &Path) -> Option<
 pub fn check_test_markers(
     project_root: PathBuf,
     test_directories: Vec<String>,
+    test_patterns: Vec<String>,
     exclude_patterns: Vec<String>,
 ) -> PyResult<Vec<LintViolation>> {
-    // Find all test files in the test directories
-    let test_files: Vec<PathBuf> = test_directories
-        .par_iter()
-        .flat_map(|test_dir| {
-            let test_path = project_root.join(test_dir);
-            if test_path.exists() {
-                find_python_files(&test_path, &exclude_patterns)
-                    .into_iter()
-                    .filter(|path| {
-                        // Only check files that start with test_ or end with _test.py
-                        if let Some(file_name) = path.file_name() {
-                            let name = file_name.to_string_lossy();
-                            name.starts_with("test_") || name.ends_with("_test.py")
-                        } else {
-                            false
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            } else {
-                vec![]
-            }
-        })
-        .collect();
+    let test_files = find_test_files(
+        &project_root,
+        &test_directories,
+        &test_patterns,
+        &exclude_patterns,
+    );
 
     // Check each test file for violations
     let violations: Vec<LintViolation> = test_files
@@ -368,7 +407,7 @@ pub fn check_test_markers(
             let source_module_path = find_source_module_for_test(file_path, &project_root);
 
             // Check the file for violations
-            check_file(file_path, source_module_path.as_deref())
+            check_file(file_path, source_module_path.as_deref(), &project_root)
         })
         .collect();
 
@@ -381,26 +420,56 @@ mod tests {
 
     #[test]
     fn test_infer_tested_function() {
+        let classes = class_names("class MyClass:\n    def method(self):\n        pass\n");
+        assert_eq!(classes, vec!["MyClass".to_string()]);
+
         // Test regular function pattern
         assert_eq!(
-            infer_tested_function("test_my_function"),
+            infer_tested_function("test_my_function", &classes),
             Some("my_function".to_string())
         );
 
         // Test class method pattern
         assert_eq!(
-            infer_tested_function("test_MyClass_method"),
-            Some("MyClass_method".to_string())
+            infer_tested_function("test_MyClass_method", &classes),
+            Some("MyClass.method".to_string())
+        );
+
+        // Test lowercase and snake_case class method patterns
+        assert_eq!(
+            infer_tested_function("test_myclass_method", &classes),
+            Some("MyClass.method".to_string())
+        );
+        assert_eq!(
+            infer_tested_function("test_my_class__private", &classes),
+            Some("MyClass._private".to_string())
         );
 
         // Test underscore in function name
         assert_eq!(
-            infer_tested_function("test_function_with_underscores"),
+            infer_tested_function("test_function_with_underscores", &classes),
             Some("function_with_underscores".to_string())
         );
 
         // Test non-test function
-        assert_eq!(infer_tested_function("not_a_test"), None);
+        assert_eq!(infer_tested_function("not_a_test", &classes), None);
+    }
+
+    #[test]
+    fn test_inferred_methods_follow_public_api() {
+        let public_api = public_api::parse_module_all("__all__ = ['Client']\n");
+        let classes = vec!["Client".to_string(), "Pool".to_string()];
+        let checked = |test_name: &str| {
+            should_check_test_for_function(
+                &infer_tested_function(test_name, &classes).unwrap(),
+                &public_api,
+            )
+        };
+        assert!(checked("test_Client_send"));
+        assert!(checked("test_client_send"));
+        assert!(!checked("test_client__retry"));
+        assert!(!checked("test_pool_acquire"));
+        assert!(!checked("test_Pool_acquire"));
     }
 
     #[test]
@@ -411,6 +480,7 @@ mod tests {
 
         let public_api = public_api::PublicApi {
             all_names: Some(names),
+            reexports: None,
         };
 
         // Test public function
@@ -464,4 +534,22 @@ mod tests {
         let other_path = PathBuf::from("/project/test/other/test_example.py");
         assert_eq!(get_test_type_from_path(&other_path), None);
     }
+
+    #[test]
+    fn test_has_pytest_marker_through_alias() {
+        let aliases = Aliases::from_source(
+            Path::new("test_m.py"),
+            "from pytest import mark as m\nunit = m.unit\n",
+            Path::new(""),
+        );
+        let function = |decorator: &str| TestFunction {
+            name: "test_a".to_string(),
+            line_number: 1,
+            decorator_line: 1,
+            decorators: vec![decorator.to_string()],
+        };
+        assert!(has_pytest_marker(&function("unit"), "unit", &aliases));
+        assert!(has_pytest_marker(&function("m.unit()"), "unit", &aliases));
+        assert!(!has_pytest_marker(&function("m.e2e"), "unit", &aliases));
+    }
 }
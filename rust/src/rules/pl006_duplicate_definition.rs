@@ -0,0 +1,123 @@
+//! PL006: Duplicate function definitions in a source module
+//!
+//! A second `def` of the same name in the same scope silently replaces the first one,
+//! which is then dead code, often still exported and untested. Top-level functions of a
+//! module and methods of a class are compared; definitions nested in functions or under
+//! `if`/`try` blocks are alternatives rather than redefinitions and are left alone, as are
+//! `@overload` signatures, property accessors (`@name.setter`) and functions named `_`
+//! (`@f.register` implementations of a `singledispatch` function).
+//!
+//! Each shadowed definition is reported at its `def` line.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa;
+use crate::patterns::Patterns;
+use crate::source_index::{DefinedFunction, SourceFile};
+
+pub const RULE_ID: &str = "PL006";
+const RULE_NAME: &str = "PL006:duplicate-definition";
+
+/// Whether `function` may legitimately share its name with another definition
+fn may_repeat(function: &DefinedFunction, lines: &[&str]) -> bool {
+    function.name == "_"
+        || function.metrics(lines).decorators.iter().any(|decorator| {
+            decorator.rsplit('.').next() == Some("overload")
+                || decorator.starts_with(&format!("{}.", function.name))
+        })
+}
+
+/// The violations of the redefined functions of `source`, the module at `file_path`
+pub fn check_duplicates(
+    source: &SourceFile,
+    lines: &[&str],
+    file_path: &Path,
+    shared_path: &Arc<str>,
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    // Indentation of the definitions in each scope: none at the top level, that of the
+    // first method in a class (deeper ones are nested in methods)
+    let mut scope_indents: HashMap<Option<&str>, &str> = HashMap::new();
+    let mut definitions: HashMap<(Option<&str>, &str), Vec<&DefinedFunction>> = HashMap::new();
+    for function in &source.functions {
+        let line = lines[function.line_number - 1];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let class_name = function.class_name.as_deref();
+        let scope_indent = *scope_indents
+            .entry(class_name)
+            .or_insert(if class_name.is_some() { indent } else { "" });
+        if indent != scope_indent || may_repeat(function, lines) {
+            continue;
+        }
+        definitions
+            .entry((class_name, function.name.as_str()))
+            .or_default()
+            .push(function);
+    }
+
+    let mut violations: Vec<LintViolation> = definitions
+        .values()
+        .flat_map(|functions| functions.windows(2))
+        .filter_map(|pair| {
+            let (shadowed, redefinition) = (pair[0], pair[1]);
+            if noqa::function_noqa_rules(patterns, lines, shadowed.line_number).contains(RULE_ID) {
+                return None;
+            }
+            let qualified_name = match &shadowed.class_name {
+                Some(class_name) => format!("{}.{}", class_name, shadowed.name),
+                None => shadowed.name.clone(),
+            };
+            Some(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: shadowed.line_number,
+                function_name: shadowed.name.clone(),
+                class_name: shadowed.class_name.clone(),
+                message: format!(
+                    "[{}] Function '{}' is defined again at line {}, which replaces this definition\nLocation: {}:{}\n\nTip: Remove or rename one of the definitions; the first one is dead code",
+                    RULE_ID,
+                    qualified_name,
+                    redefinition.line_number,
+                    file_path.display(),
+                    shadowed.line_number
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            })
+        })
+        .collect();
+    violations.sort_by_key(|violation| violation.line_number);
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_check_duplicates() {
+        let content = "def load(path):\n    pass\n\ndef load(path, mode):\n    def inner():\n        pass\n    pass\n\ndef save():\n    def inner():\n        pass\n\nif FAST:\n    def parse():\n        pass\nelse:\n    def parse():\n        pass\n\nclass Store:\n    @property\n    def size(self):\n        pass\n\n    @size.setter\n    def size(self, value):\n        pass\n\n    def get(self):\n        pass\n\n    def get(self):  # noqa: PL006\n        pass\n\n    def get(self, key):\n        pass\n\n@overload\ndef fetch(x: int) -> int: ...\n\ndef fetch(x):\n    pass\n";
+        let source = SourceFile::parse(content.to_string(), &PATTERNS);
+        let lines = source.lines();
+        let path = Path::new("pkg/store.py");
+        let shared_path: Arc<str> = "pkg/store.py".into();
+        let violations = check_duplicates(&source, &lines, path, &shared_path, &PATTERNS);
+
+        let found: Vec<(Option<&str>, &str, usize)> = violations
+            .iter()
+            .map(|v| {
+                (
+                    v.class_name.as_deref(),
+                    v.function_name.as_str(),
+                    v.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(found, vec![(None, "load", 1), (Some("Store"), "get", 29)]);
+        assert!(violations[0].message.contains("defined again at line 4"));
+    }
+}
@@ -0,0 +1,167 @@
+//! PL007: Private helpers nothing refers to
+//!
+//! A private function or method (`_helper`) is only meant to be used from inside the
+//! project, so one whose name appears nowhere in the project's sources or tests, other
+//! than in its own `def`, is likely dead code. Like the call graph, references are
+//! matched by name only: any `_helper` token counts for every function of that name,
+//! including mentions in strings (`mock.patch("pkg.mod._helper")`, `getattr`). That
+//! misses dead helpers sharing a name with a used one, but flags no helper that is used.
+//!
+//! Dunder methods are called by Python itself, and decorated functions are assumed to be
+//! registered by their decorator (`@app.route`, `@receiver`); both are left alone, except
+//! for decorators that merely change how the function is bound or cached.
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa;
+use crate::patterns::{Patterns, PATTERNS};
+use crate::source_index::SourceFile;
+
+pub const RULE_ID: &str = "PL007";
+const RULE_NAME: &str = "PL007:dead-private-helper";
+
+/// Decorators that keep a function as reachable as it was undecorated
+const PASSIVE_DECORATORS: &[&str] = &[
+    "staticmethod",
+    "classmethod",
+    "property",
+    "cached_property",
+    "cache",
+    "lru_cache",
+    "abstractmethod",
+];
+
+/// References to private names in `content`, not counting their definitions
+fn count_references(content: &str, counts: &mut HashMap<String, usize>) {
+    for captures in PATTERNS.private_name.captures_iter(content) {
+        if captures.get(1).is_none() {
+            *counts.entry(captures[2].to_string()).or_default() += 1;
+        }
+    }
+}
+
+/// Whether `name` is private, rather than public or a dunder
+fn is_private(name: &str) -> bool {
+    name.starts_with('_') && !(name.starts_with("__") && name.ends_with("__"))
+}
+
+/// The private helpers of `checked_files` that none of the `project_files` (sources and
+/// tests alike) refer to
+pub fn check_dead_helpers(
+    checked_files: &[PathBuf],
+    project_files: &[PathBuf],
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    let references = project_files
+        .par_iter()
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .fold(HashMap::new, |mut counts, content| {
+            count_references(&content, &mut counts);
+            counts
+        })
+        .reduce(HashMap::new, |mut total, counts| {
+            for (name, count) in counts {
+                *total.entry(name).or_default() += count;
+            }
+            total
+        });
+
+    let mut violations: Vec<LintViolation> = checked_files
+        .par_iter()
+        .flat_map_iter(|file_path| {
+            let content = fs::read_to_string(file_path).unwrap_or_default();
+            let source = SourceFile::parse(content, patterns);
+            let lines = source.lines();
+            let shared_path: Arc<str> = file_path.to_string_lossy().into();
+            source
+                .functions
+                .iter()
+                .filter(|function| {
+                    is_private(&function.name) && !references.contains_key(&function.name)
+                })
+                .filter(|function| {
+                    function.metrics(&lines).decorators.iter().all(|decorator| {
+                        let name = decorator.split('(').next().unwrap_or(decorator);
+                        PASSIVE_DECORATORS.contains(&name.rsplit('.').next().unwrap_or(name))
+                    }) && !noqa::function_noqa_rules(patterns, &lines, function.line_number)
+                        .contains(RULE_ID)
+                })
+                .map(|function| {
+                    let qualified_name = match &function.class_name {
+                        Some(class_name) => format!("{}.{}", class_name, function.name),
+                        None => function.name.clone(),
+                    };
+                    LintViolation {
+                        rule_name: RULE_NAME.to_string(),
+                        file_path: shared_path.clone(),
+                        line_number: function.line_number,
+                        function_name: function.name.clone(),
+                        class_name: function.class_name.clone(),
+                        message: format!(
+                            "[{}] Private function '{}' is not referenced anywhere in the project and is likely dead code\nLocation: {}:{}\n\nTip: Remove it, or add '# noqa: {}' if it is reached in a way the linter cannot see",
+                            RULE_ID,
+                            qualified_name,
+                            file_path.display(),
+                            function.line_number,
+                            RULE_ID
+                        ),
+                        severity: "warning".to_string(),
+                        ..Default::default()
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    violations.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_dead_helpers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        fs::create_dir_all(root.join("test")).unwrap();
+        let module = root.join("pkg/mod.py");
+        fs::write(
+            &module,
+            "def run():\n    return _used()\n\ndef _used():\n    pass\n\ndef _dead():\n    pass\n\ndef _patched():\n    pass\n\ndef _kept():  # noqa: PL007\n    pass\n\n@app.route(\"/\")\ndef _index():\n    pass\n\nclass Job:\n    def __call__(self):\n        return self._step\n\n    @property\n    def _step(self):\n        pass\n\n    @staticmethod\n    def _unused():\n        pass\n",
+        )
+        .unwrap();
+        let test_file = root.join("test/test_mod.py");
+        fs::write(
+            &test_file,
+            "def test_run(mocker):\n    mocker.patch(\"pkg.mod._patched\")\n",
+        )
+        .unwrap();
+
+        let violations = check_dead_helpers(
+            std::slice::from_ref(&module),
+            &[module.clone(), test_file],
+            &PATTERNS,
+        );
+        let found: Vec<(Option<&str>, &str, usize)> = violations
+            .iter()
+            .map(|v| {
+                (
+                    v.class_name.as_deref(),
+                    v.function_name.as_str(),
+                    v.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![(None, "_dead", 7), (Some("Job"), "_unused", 29)]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use super::LintRule;
+use crate::models::LintViolation;
+use crate::test_cache::TestType;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Flags functions whose matching tests are all marked `skip` or non-strict `xfail`,
+/// which PL001–PL003 still count as coverage
+pub struct PL022SkippedTestOnly {}
+
+impl PL022SkippedTestOnly {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Describe the violation, listing the skipped tests
+    fn message(
+        &self,
+        function_name: &str,
+        class_name: Option<&str>,
+        tests: &BTreeSet<(&Path, &str)>,
+        project_root: &Path,
+    ) -> String {
+        let skipped_tests = tests
+            .iter()
+            .map(|(test_file, test_name)| {
+                let test_file = test_file.strip_prefix(project_root).unwrap_or(test_file);
+                format!("{}::{}", test_file.display(), test_name)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let subject = match class_name {
+            Some(class) => format!("Method '{}' of class '{}'", function_name, class),
+            None => format!("Function '{}'", function_name),
+        };
+        format!(
+            "[{}] {} is only covered by skipped or xfail tests.\nSkipped tests: {}\n\nTip: Un-skip a test, or make the xfail strict (strict=True)",
+            self.rule_id(),
+            subject,
+            skipped_tests
+        )
+    }
+}
+
+impl LintRule for PL022SkippedTestOnly {
+    fn rule_id(&self) -> &'static str {
+        "PL022"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "skipped-test-only"
+    }
+
+    fn check_function(
+        &self,
+        function_name: &str,
+        file_path: &Path,
+        line_number: usize,
+        line_content: &str,
+        class_name: Option<&str>,
+        is_protocol: bool,
+        context: &super::RuleContext,
+    ) -> Option<LintViolation> {
+        let suppressed_rules = context.noqa_rules(line_content);
+        if suppressed_rules.contains(self.rule_id()) {
+            return None;
+        }
+
+        if (is_protocol && class_name.is_some()) || function_name == "__init__" {
+            return None;
+        }
+
+        if context.is_below_size_threshold(self.rule_id()) {
+            return None;
+        }
+
+        let tests: BTreeSet<(&Path, &str)> = [TestType::Unit, TestType::Integration, TestType::E2E]
+            .iter()
+            .flat_map(|test_type| {
+                context.test_cache.find_tests_for_function_of_type(
+                    function_name,
+                    file_path,
+                    class_name,
+                    test_type,
+                    context.module_path,
+                    context.project_root,
+                )
+            })
+            .collect();
+        if tests.is_empty()
+            || !tests
+                .iter()
+                .all(|(test_file, test_name)| context.test_cache.is_skipped(test_file, test_name))
+        {
+            return None;
+        }
+
+        let message = if context.build_messages {
+            self.message(function_name, class_name, &tests, context.project_root)
+        } else {
+            String::new()
+        };
+
+        Some(LintViolation {
+            rule_name: format!("{}:{}", self.rule_id(), self.rule_name()),
+            file_path: context.file_path.clone(),
+            line_number,
+            function_name: function_name.to_string(),
+            message,
+            severity: "warning".to_string(),
+            ..Default::default()
+        })
+    }
+}
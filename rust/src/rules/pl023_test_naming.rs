@@ -0,0 +1,229 @@
+//! PL023: Enforce a naming convention on test functions
+//!
+//! Every test function (`def test...`) in the test files must:
+//! - match the `pattern` option (default `^test_[A-Za-z0-9_]+$`)
+//! - not use camelCase, unless `forbid_camel_case = false`; segments starting with an
+//!   uppercase letter are class names (`test_ClassName_method`) and are allowed
+//! - contain `_when_` followed by `_then_`, if `require_when_then = true`
+//!
+//! Violations suggest a name that follows the convention.
+
+use rayon::prelude::*;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::function_noqa_rules;
+use crate::parser;
+use crate::patterns::Patterns;
+use crate::rules::options::RuleOptions;
+use crate::rules::test_functions;
+
+const RULE_ID: &str = "PL023";
+const RULE_NAME: &str = "PL023:test-naming";
+const DEFAULT_PATTERN: &str = "^test_[A-Za-z0-9_]+$";
+
+/// The naming convention, from the `PL023` rule options
+#[derive(Debug)]
+pub struct NamingConvention {
+    pattern: Regex,
+    forbid_camel_case: bool,
+    require_when_then: bool,
+}
+
+impl NamingConvention {
+    pub fn from_options(options: &RuleOptions) -> Result<Self, String> {
+        let pattern = options
+            .get_strings(RULE_ID, "pattern")
+            .and_then(|patterns| patterns.into_iter().next())
+            .unwrap_or_else(|| DEFAULT_PATTERN.to_string());
+        Ok(Self {
+            pattern: Regex::new(&pattern)
+                .map_err(|e| format!("invalid PL023 pattern '{}': {}", pattern, e))?,
+            forbid_camel_case: options
+                .get_bool(RULE_ID, "forbid_camel_case")
+                .unwrap_or(true),
+            require_when_then: options
+                .get_bool(RULE_ID, "require_when_then")
+                .unwrap_or(false),
+        })
+    }
+
+    /// Why `name` breaks the convention, if it does
+    fn problems(&self, name: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.pattern.is_match(name) {
+            problems.push(format!("does not match '{}'", self.pattern.as_str()));
+        }
+        if self.forbid_camel_case && name.split('_').any(is_camel_case) {
+            problems.push("uses camelCase".to_string());
+        }
+        if self.require_when_then && !has_when_then(name) {
+            problems.push("does not describe the scenario as _when_..._then_...".to_string());
+        }
+        problems
+    }
+
+    /// A name following the convention, derived from `name`
+    fn suggest(&self, name: &str) -> String {
+        let mut suggestion = to_snake_case(name);
+        if !suggestion.starts_with("test_") {
+            let rest = suggestion
+                .trim_start_matches("test")
+                .trim_start_matches('_');
+            suggestion = format!("test_{}", rest);
+        }
+        if self.require_when_then && !has_when_then(&suggestion) {
+            suggestion.push_str("_when_<condition>_then_<outcome>");
+        }
+        suggestion
+    }
+}
+
+/// A lowercase-first segment with an uppercase letter later on, e.g. `parseValue`
+fn is_camel_case(segment: &str) -> bool {
+    segment.starts_with(|c: char| c.is_ascii_lowercase())
+        && segment.contains(|c: char| c.is_ascii_uppercase())
+}
+
+fn has_when_then(name: &str) -> bool {
+    name.find("_when_")
+        .is_some_and(|when| name[when..].contains("_then_"))
+}
+
+/// Convert camelCase segments to snake_case, keeping class-name segments as they are
+fn to_snake_case(name: &str) -> String {
+    name.split('_')
+        .map(|segment| {
+            if !is_camel_case(segment) {
+                return segment.to_string();
+            }
+            let mut snake = String::new();
+            let mut previous_lower = false;
+            for c in segment.chars() {
+                if c.is_ascii_uppercase() && previous_lower {
+                    snake.push('_');
+                }
+                previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+                snake.push(c.to_ascii_lowercase());
+            }
+            snake
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Check the test function names in one test file
+fn check_file(
+    file_path: &Path,
+    convention: &NamingConvention,
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+
+    test_functions(&lines)
+        .filter_map(|(idx, name)| {
+            if function_noqa_rules(patterns, &lines, idx + 1).contains(RULE_ID) {
+                return None;
+            }
+            let problems = convention.problems(name);
+            if problems.is_empty() {
+                return None;
+            }
+            Some(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: idx + 1,
+                decorator_start_line: Some(parser::decorator_start_line(&lines, idx + 1)),
+                function_name: name.to_string(),
+                message: format!(
+                    "[{}] Test function '{}' {}.\nSuggested name: {}\nLocation: {}",
+                    RULE_ID,
+                    name,
+                    problems.join(" and "),
+                    convention.suggest(name),
+                    file_path.display()
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Check every test function name in `test_files`
+pub fn check_test_naming(
+    test_files: &[PathBuf],
+    convention: &NamingConvention,
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(|file_path| check_file(file_path, convention, patterns))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::options::OptionValue;
+    use std::collections::HashMap;
+
+    fn convention(options: &[(&str, OptionValue)]) -> NamingConvention {
+        let options = options
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+        NamingConvention::from_options(&RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            options,
+        )])))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_default_convention() {
+        let convention = convention(&[]);
+        assert!(convention.problems("test_parse_value").is_empty());
+        assert!(convention.problems("test_Parser_parse").is_empty());
+
+        assert_eq!(
+            convention.problems("test_parseValue"),
+            vec!["uses camelCase"]
+        );
+        assert_eq!(convention.suggest("test_parseValue"), "test_parse_value");
+        assert_eq!(convention.problems("testParse").len(), 2);
+        assert_eq!(convention.suggest("testParse"), "test_parse");
+    }
+
+    #[test]
+    fn test_configured_convention() {
+        let convention = convention(&[
+            ("pattern", OptionValue::Str("^test_[a-z0-9_]+$".to_string())),
+            ("forbid_camel_case", OptionValue::Bool(false)),
+            ("require_when_then", OptionValue::Bool(true)),
+        ]);
+        assert!(convention
+            .problems("test_parse_when_empty_then_none")
+            .is_empty());
+        assert_eq!(convention.problems("test_then_when").len(), 1);
+        assert_eq!(
+            convention.suggest("test_parse"),
+            "test_parse_when_<condition>_then_<outcome>"
+        );
+        assert!(
+            NamingConvention::from_options(&RuleOptions::from(HashMap::from([(
+                RULE_ID.to_string(),
+                HashMap::from([("pattern".to_string(), OptionValue::Str("(".to_string()))]),
+            )])))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! PL024: Require a docstring on test functions
+//!
+//! Our testing guidelines ask every test to describe the behavior under test in a one-line
+//! docstring. Test functions without one (or still carrying the template inserted by the
+//! fix) are reported; the `add_docstring` fix inserts `DOCSTRING_TEMPLATE` as the first
+//! statement of the body.
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::function_noqa_rules;
+use crate::parser;
+use crate::patterns::Patterns;
+use crate::rules::test_functions;
+
+const RULE_ID: &str = "PL024";
+const RULE_NAME: &str = "PL024:require-test-docstring";
+
+/// Marker identifying docstrings inserted by the fix
+const TEMPLATE_MARKER: &str = "TODO(proboscis):";
+/// Docstring inserted by the `add_docstring` fix
+pub const DOCSTRING_TEMPLATE: &str =
+    "\"\"\"TODO(proboscis): describe the behavior under test.\"\"\"";
+
+/// Check the test functions in one test file
+fn check_file(file_path: &Path, patterns: &Patterns) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+
+    let mut violations: Vec<LintViolation> = test_functions(&lines)
+        .filter_map(|(idx, name)| {
+            if function_noqa_rules(patterns, &lines, idx + 1).contains(RULE_ID) {
+                return None;
+            }
+            let function = parser::parse_function(&lines, idx + 1);
+            let first_body_line = lines[function.signature_end..]
+                .iter()
+                .find(|line| !line.trim().is_empty())
+                .copied()
+                .unwrap_or("");
+
+            let (problem, fix) = if !function.has_docstring {
+                // `def test_x(): pass` has no line to put a docstring on
+                let inline_body = function.end_line == function.signature_end;
+                ("has no docstring", !inline_body)
+            } else if first_body_line.contains(TEMPLATE_MARKER) {
+                ("still has the TODO docstring template", false)
+            } else {
+                return None;
+            };
+
+            let mut violation = LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: idx + 1,
+                decorator_start_line: Some(function.decorator_line),
+                function_name: name.to_string(),
+                message: format!(
+                    "[{}] Test function '{}' {}.\nLocation: {}\n\nTip: Add a one-line docstring describing the behavior under test",
+                    RULE_ID,
+                    name,
+                    problem,
+                    file_path.display()
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            };
+            if fix {
+                violation.fix_type = Some("add_docstring".to_string());
+                violation.fix_content = Some(DOCSTRING_TEMPLATE.to_string());
+                violation.fix_line = Some(function.signature_end + 1);
+            }
+            Some(violation)
+        })
+        .collect();
+    crate::fixes::record_content_hash(&mut violations, &content);
+    violations
+}
+
+/// Check every test function in `test_files` for a docstring
+pub fn check_test_docstrings(test_files: &[PathBuf], patterns: &Patterns) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(|file_path| check_file(file_path, patterns))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_check_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let path = root.join("test_m.py");
+        fs::write(
+            &path,
+            "def test_documented():\n    \"\"\"Adds two numbers.\"\"\"\n    assert add(1, 2) == 3\n\ndef test_missing(\n    tmp_path,\n):\n    assert tmp_path\n\ndef test_inline(): pass\n\ndef test_template():\n    \"\"\"TODO(proboscis): describe the behavior under test.\"\"\"\n\ndef test_skipped():  # noqa: PL024\n    pass\n",
+        )
+        .unwrap();
+
+        let violations = check_file(&path, &PATTERNS);
+        let found: Vec<(&str, Option<usize>)> = violations
+            .iter()
+            .map(|v| (v.function_name.as_str(), v.fix_line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("test_missing", Some(8)),
+                ("test_inline", None),
+                ("test_template", None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! PL025: Limit the length and assertion count of test functions
+//!
+//! Long tests with many assertions usually check several behaviors at once; when one
+//! fails, the rest go unchecked. Test functions with more body lines than `max_lines`
+//! (default 50) or more assertions than `max_assertions` (default 10) are reported.
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::function_noqa_rules;
+use crate::parser;
+use crate::patterns::Patterns;
+use crate::rules::options::RuleOptions;
+use crate::rules::test_functions;
+
+const RULE_ID: &str = "PL025";
+const RULE_NAME: &str = "PL025:test-too-long";
+
+/// Size limits of a test function, from the `PL025` rule options
+#[derive(Debug, Clone, Copy)]
+pub struct TestLimits {
+    max_lines: usize,
+    max_assertions: usize,
+}
+
+impl TestLimits {
+    pub fn from_options(options: &RuleOptions) -> Self {
+        Self {
+            max_lines: options.get_usize(RULE_ID, "max_lines").unwrap_or(50),
+            max_assertions: options.get_usize(RULE_ID, "max_assertions").unwrap_or(10),
+        }
+    }
+}
+
+/// Check the test functions in one test file
+fn check_file(file_path: &Path, limits: TestLimits, patterns: &Patterns) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+
+    test_functions(&lines)
+        .filter_map(|(idx, name)| {
+            if function_noqa_rules(patterns, &lines, idx + 1).contains(RULE_ID) {
+                return None;
+            }
+            let function = parser::parse_function(&lines, idx + 1);
+            let mut problems = Vec::new();
+            let body_lines = function.body_line_count();
+            if body_lines > limits.max_lines {
+                problems.push(format!(
+                    "{} lines (max {})",
+                    body_lines, limits.max_lines
+                ));
+            }
+            let assertions = function.assertion_count();
+            if assertions > limits.max_assertions {
+                problems.push(format!(
+                    "{} assertions (max {})",
+                    assertions, limits.max_assertions
+                ));
+            }
+            if problems.is_empty() {
+                return None;
+            }
+
+            Some(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: idx + 1,
+                decorator_start_line: Some(function.decorator_line),
+                function_name: name.to_string(),
+                message: format!(
+                    "[{}] Test function '{}' has {}.\nLocation: {}\n\nTip: Split it into focused tests that each check one behavior",
+                    RULE_ID,
+                    name,
+                    problems.join(" and "),
+                    file_path.display()
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Check every test function in `test_files` against `limits`
+pub fn check_test_length(
+    test_files: &[PathBuf],
+    limits: TestLimits,
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(|file_path| check_file(file_path, limits, patterns))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_check_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let path = root.join("test_m.py");
+        fs::write(
+            &path,
+            "def test_long():\n    x = 1\n    y = 2\n    assert x\n\ndef test_asserts():\n    assert a; assert b\n    with pytest.raises(ValueError):\n        c()\n\ndef test_short():  # noqa: PL025\n    x = 1\n    y = 2\n    z = 3\n",
+        )
+        .unwrap();
+
+        let limits = TestLimits {
+            max_lines: 2,
+            max_assertions: 2,
+        };
+        let violations = check_file(&path, limits, &PATTERNS);
+        let found: Vec<&str> = violations
+            .iter()
+            .map(|v| v.function_name.as_str())
+            .collect();
+        assert_eq!(found, vec!["test_long", "test_asserts"]);
+        assert!(violations[0].message.contains("3 lines (max 2)"));
+        assert!(violations[1].message.contains("3 assertions (max 2)"));
+    }
+}
@@ -0,0 +1,153 @@
+//! PL026: Forbid sleep-based waits in tests
+//!
+//! `time.sleep(...)` in a test either slows the suite down or, more often, waits for
+//! something that may take longer on a loaded CI machine, making the test flaky. Calls inside
+//! test functions in the test types listed by the `test_types` option (default unit and
+//! integration tests) are reported at the line of the call.
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::{function_noqa_rules, parse_noqa_rules_with};
+use crate::parser;
+use crate::patterns::{Patterns, PATTERNS};
+use crate::rules::options::RuleOptions;
+use crate::rules::test_functions;
+use crate::test_cache::TestType;
+
+const RULE_ID: &str = "PL026";
+const RULE_NAME: &str = "PL026:no-sleep-in-tests";
+
+/// Test types whose tests are checked, from the `PL026` rule options
+#[derive(Debug, Clone)]
+pub struct SleepCheck {
+    test_types: Vec<TestType>,
+}
+
+impl SleepCheck {
+    pub fn from_options(options: &RuleOptions) -> Result<Self, String> {
+        let names = options
+            .get_strings(RULE_ID, "test_types")
+            .unwrap_or_else(|| vec!["unit".to_string(), "integration".to_string()]);
+        let test_types = names
+            .iter()
+            .map(|name| match name.as_str() {
+                "unit" => Ok(TestType::Unit),
+                "integration" => Ok(TestType::Integration),
+                "e2e" => Ok(TestType::E2E),
+                _ => Err(format!(
+                    "invalid PL026 test type '{}', expected unit, integration or e2e",
+                    name
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { test_types })
+    }
+}
+
+/// Check the test functions in one test file for sleep calls
+fn check_file(file_path: &Path, patterns: &Patterns) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+    let sleep_imported = lines
+        .iter()
+        .any(|line| PATTERNS.sleep_import.is_match(line));
+
+    let mut violations = Vec::new();
+    for (idx, name) in test_functions(&lines) {
+        if function_noqa_rules(patterns, &lines, idx + 1).contains(RULE_ID) {
+            continue;
+        }
+        let function = parser::parse_function(&lines, idx + 1);
+        for (line_idx, line) in lines
+            .iter()
+            .enumerate()
+            .take(function.end_line)
+            .skip(idx + 1)
+        {
+            let code = line.split('#').next().unwrap_or(line);
+            let is_sleep = PATTERNS.time_sleep.is_match(code)
+                || (sleep_imported && PATTERNS.bare_sleep.is_match(code));
+            if !is_sleep || parse_noqa_rules_with(patterns, line).contains(RULE_ID) {
+                continue;
+            }
+            violations.push(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: line_idx + 1,
+                function_name: name.to_string(),
+                message: format!(
+                    "[{}] Test function '{}' waits with a sleep call: {}\nLocation: {}:{}\n\nTip: Wait for the condition itself (polling with a timeout), or fake the clock",
+                    RULE_ID,
+                    name,
+                    line.trim(),
+                    file_path.display(),
+                    line_idx + 1
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    violations
+}
+
+/// Check the tests in `test_files` of the test types selected by `check`
+pub fn check_sleep_calls(
+    test_files: &[PathBuf],
+    check: &SleepCheck,
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .filter(|file_path| check.test_types.contains(&TestType::from_path(file_path)))
+        .flat_map(|file_path| check_file(file_path, patterns))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::options::OptionValue;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_check_sleep_calls() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test").join("unit");
+        let e2e = root.join("test").join("e2e");
+        fs::create_dir_all(&unit).unwrap();
+        fs::create_dir_all(&e2e).unwrap();
+        let content = "import time\nfrom time import sleep\n\ndef helper():\n    time.sleep(1)\n\ndef test_wait():\n    start()\n    time.sleep(0.5)\n    sleep(1)  # noqa: PL026\n    asyncio.sleep(1)\n    # time.sleep(2)\n\ndef test_poll():\n    sleep(0.1)\n\n@pytest.mark.slow  # noqa: PL026\ndef test_retry():\n    sleep(5)\n";
+        fs::write(unit.join("test_m.py"), content).unwrap();
+        fs::write(e2e.join("test_m.py"), content).unwrap();
+        let files = vec![unit.join("test_m.py"), e2e.join("test_m.py")];
+
+        let check = SleepCheck::from_options(&RuleOptions::default()).unwrap();
+        let violations = check_sleep_calls(&files, &check, &PATTERNS);
+        let found: Vec<(&str, usize)> = violations
+            .iter()
+            .map(|v| (v.function_name.as_str(), v.line_number))
+            .collect();
+        assert_eq!(found, vec![("test_wait", 9), ("test_poll", 15)]);
+
+        let options = RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            HashMap::from([(
+                "test_types".to_string(),
+                OptionValue::Str("e2e".to_string()),
+            )]),
+        )]));
+        let check = SleepCheck::from_options(&options).unwrap();
+        let violations = check_sleep_calls(&files, &check, &PATTERNS);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].file_path.contains("e2e"));
+    }
+}
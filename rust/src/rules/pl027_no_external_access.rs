@@ -0,0 +1,243 @@
+//! PL027: Forbid network and file-system access in unit tests
+//!
+//! Unit tests should not reach the network or the real file system. Test files of each test
+//! type are checked against that type's deny list of modules, given by the
+//! `<type>_deny` options (`unit_deny`, `integration_deny`, `e2e_deny`), minus the
+//! `<type>_allow` entries:
+//! - importing a denied module (or a submodule of one) is reported at the import
+//! - the pseudo-module `open` denies calls to the builtin `open()` whose path does not
+//!   look temporary (`tmp_path`, `tmpdir`, `tempfile`, `/tmp/...`)
+//!
+//! Unit tests deny `DEFAULT_UNIT_DENY` by default; other test types are not checked unless
+//! configured.
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::parse_noqa_rules;
+use crate::parser;
+use crate::patterns::PATTERNS;
+use crate::rules::options::RuleOptions;
+use crate::rules::{imported_modules, test_functions};
+use crate::test_cache::TestType;
+
+const RULE_ID: &str = "PL027";
+const RULE_NAME: &str = "PL027:no-external-access";
+
+/// Modules denied in unit tests unless configured with `unit_deny`
+pub const DEFAULT_UNIT_DENY: &[&str] = &[
+    "aiohttp",
+    "boto3",
+    "ftplib",
+    "http.client",
+    "httpx",
+    "open",
+    "paramiko",
+    "requests",
+    "smtplib",
+    "socket",
+    "urllib.request",
+    "urllib3",
+];
+
+/// Denied and allowed modules of one test type
+#[derive(Debug, Clone, Default)]
+struct ModuleList {
+    deny: Vec<String>,
+    allow: Vec<String>,
+}
+
+/// Whether `module` is `entry` or one of its submodules
+fn covers(entry: &str, module: &str) -> bool {
+    module
+        .strip_prefix(entry)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+impl ModuleList {
+    fn is_denied(&self, module: &str) -> bool {
+        self.deny.iter().any(|entry| covers(entry, module))
+            && !self.allow.iter().any(|entry| covers(entry, module))
+    }
+}
+
+/// The modules denied per test type, from the `PL027` rule options
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    lists: HashMap<TestType, ModuleList>,
+}
+
+impl AccessPolicy {
+    pub fn from_options(options: &RuleOptions) -> Self {
+        let mut lists = HashMap::new();
+        for (name, test_type) in [
+            ("unit", TestType::Unit),
+            ("integration", TestType::Integration),
+            ("e2e", TestType::E2E),
+        ] {
+            let default_deny = match test_type {
+                TestType::Unit => DEFAULT_UNIT_DENY.iter().map(|m| m.to_string()).collect(),
+                _ => Vec::new(),
+            };
+            let list = ModuleList {
+                deny: options
+                    .get_strings(RULE_ID, &format!("{}_deny", name))
+                    .unwrap_or(default_deny),
+                allow: options
+                    .get_strings(RULE_ID, &format!("{}_allow", name))
+                    .unwrap_or_default(),
+            };
+            if !list.deny.is_empty() {
+                lists.insert(test_type, list);
+            }
+        }
+        Self { lists }
+    }
+}
+
+/// Whether the path passed to `open()` looks like a temporary file
+fn is_temporary_path(argument: &str) -> bool {
+    let argument = argument.to_lowercase();
+    argument.contains("tmp") || argument.contains("temp")
+}
+
+/// Check one test file against the modules denied for its test type
+fn check_file(file_path: &Path, list: &ModuleList) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+    // (first line, last line, name) of each test function, 1-based
+    let spans: Vec<(usize, usize, &str)> = test_functions(&lines)
+        .map(|(idx, name)| {
+            let end_line = parser::parse_function(&lines, idx + 1).end_line;
+            (idx + 1, end_line, name)
+        })
+        .collect();
+
+    let mut violations = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if parse_noqa_rules(line).contains(RULE_ID) {
+            continue;
+        }
+        let code = line.split('#').next().unwrap_or(line);
+        let mut found: Vec<String> = imported_modules(code)
+            .into_iter()
+            .filter(|module| list.is_denied(module))
+            .map(|module| format!("imports '{}'", module))
+            .collect();
+        if list.is_denied("open") {
+            if let Some(argument) = PATTERNS.open_call.captures(code).and_then(|c| c.get(1)) {
+                let argument = argument.as_str().trim();
+                if !argument.is_empty() && !is_temporary_path(argument) {
+                    found.push(format!("opens {} outside a temporary directory", argument));
+                }
+            }
+        }
+
+        let line_number = idx + 1;
+        let function_name = spans
+            .iter()
+            .find(|(start, end, _)| (*start..=*end).contains(&line_number))
+            .map(|(_, _, name)| name.to_string())
+            .unwrap_or_default();
+        for access in found {
+            violations.push(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number,
+                function_name: function_name.clone(),
+                message: format!(
+                    "[{}] Test code {}, which reaches outside the test.\nLocation: {}:{}\n\nTip: Use a fake or mock, or tmp_path for files; allow the module with the PL027 <type>_allow option if intended",
+                    RULE_ID,
+                    access,
+                    file_path.display(),
+                    line_number
+                ),
+                severity: "error".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    violations
+}
+
+/// Check `test_files` against the modules denied for their test type
+pub fn check_external_access(test_files: &[PathBuf], policy: &AccessPolicy) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(
+            |file_path| match policy.lists.get(&TestType::from_path(file_path)) {
+                Some(list) => check_file(file_path, list),
+                None => vec![],
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::options::OptionValue;
+
+    #[test]
+    fn test_imported_modules() {
+        assert_eq!(
+            imported_modules("import os, socket as s"),
+            vec!["os", "socket"]
+        );
+        assert_eq!(
+            imported_modules("    from urllib.request import urlopen"),
+            vec!["urllib.request"]
+        );
+        assert!(imported_modules("from . import helpers").is_empty());
+        assert!(imported_modules("x = important").is_empty());
+    }
+
+    #[test]
+    fn test_check_external_access() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test").join("unit");
+        let integration = root.join("test").join("integration");
+        fs::create_dir_all(&unit).unwrap();
+        fs::create_dir_all(&integration).unwrap();
+        let content = "import requests\nimport socket  # noqa: PL027\nfrom urllib.request import urlopen\nimport urllib.parse\n\ndef test_read(tmp_path):\n    open(tmp_path / \"a.txt\").read()\n    with open(\"data/config.json\") as f:\n        f.read()\n    os.open(path)\n";
+        fs::write(unit.join("test_m.py"), content).unwrap();
+        fs::write(integration.join("test_m.py"), content).unwrap();
+        let files = vec![unit.join("test_m.py"), integration.join("test_m.py")];
+
+        let violations =
+            check_external_access(&files, &AccessPolicy::from_options(&RuleOptions::default()));
+        let found: Vec<(usize, &str)> = violations
+            .iter()
+            .map(|v| (v.line_number, v.function_name.as_str()))
+            .collect();
+        assert_eq!(found, vec![(1, ""), (3, ""), (8, "test_read")]);
+
+        let options = RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            HashMap::from([
+                (
+                    "unit_allow".to_string(),
+                    OptionValue::List(vec!["urllib".to_string(), "open".to_string()]),
+                ),
+                (
+                    "integration_deny".to_string(),
+                    OptionValue::Str("socket".to_string()),
+                ),
+            ]),
+        )]));
+        let violations = check_external_access(&files, &AccessPolicy::from_options(&options));
+        let found: Vec<(usize, bool)> = violations
+            .iter()
+            .map(|v| (v.line_number, v.file_path.contains("integration")))
+            .collect();
+        assert_eq!(found, vec![(1, false)]);
+    }
+}
@@ -0,0 +1,222 @@
+//! PL028: Enforce conftest.py placement
+//!
+//! Reports:
+//! - `conftest.py` files outside the configured test directories (a `conftest.py` at the
+//!   project root is allowed unless `allow_root_conftest = false`)
+//! - fixtures defined again in the conftest.py of a subdirectory (overriding the parent's
+//!   fixture for part of the suite) or of a sibling directory (duplicated code), which
+//!   makes it surprising which fixture a test gets
+
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::file_discovery::{find_python_files, TestDirectories};
+use crate::fixtures::{self, Fixture};
+use crate::models::LintViolation;
+use crate::noqa::parse_noqa_rules;
+use crate::rules::options::RuleOptions;
+
+const RULE_ID: &str = "PL028";
+const RULE_NAME: &str = "PL028:conftest-placement";
+
+/// A fixture defined in a conftest.py
+struct ConftestFixture {
+    conftest: PathBuf,
+    fixture: Fixture,
+    suppressed: bool,
+}
+
+impl ConftestFixture {
+    fn directory(&self) -> &Path {
+        self.conftest.parent().unwrap_or(Path::new(""))
+    }
+}
+
+/// How a later definition of a fixture relates to an earlier one, if they clash
+fn clash(earlier: &ConftestFixture, later: &ConftestFixture) -> Option<&'static str> {
+    let (earlier_dir, later_dir) = (earlier.directory(), later.directory());
+    if earlier.conftest == later.conftest {
+        None
+    } else if later_dir.starts_with(earlier_dir) {
+        Some("overrides")
+    } else if earlier_dir.parent() == later_dir.parent() {
+        Some("duplicates")
+    } else {
+        None
+    }
+}
+
+fn violation(
+    path: &Path,
+    line_number: usize,
+    function_name: &str,
+    message: String,
+) -> LintViolation {
+    LintViolation {
+        rule_name: RULE_NAME.to_string(),
+        file_path: path.to_string_lossy().into(),
+        line_number,
+        function_name: function_name.to_string(),
+        message,
+        severity: "warning".to_string(),
+        ..Default::default()
+    }
+}
+
+/// Check the placement of every conftest.py under `project_root`, and the fixtures they define
+pub fn check_conftests(
+    project_root: &Path,
+    test_directories: &[String],
+    exclude_patterns: &[String],
+    options: &RuleOptions,
+) -> Vec<LintViolation> {
+    let allow_root = options
+        .get_bool(RULE_ID, "allow_root_conftest")
+        .unwrap_or(true);
+    let test_dirs = TestDirectories::new(test_directories);
+    let mut conftests: Vec<PathBuf> =
+        find_python_files(project_root, exclude_patterns, &TestDirectories::default())
+            .into_iter()
+            .filter(|path| path.file_name().is_some_and(|name| name == "conftest.py"))
+            .collect();
+    conftests.sort();
+
+    let mut violations: Vec<LintViolation> = conftests
+        .iter()
+        .filter_map(|conftest| {
+            let relative = conftest.strip_prefix(project_root).unwrap_or(conftest);
+            let at_root = relative.parent() == Some(Path::new(""));
+            if test_dirs.contains(relative) || (at_root && allow_root) {
+                return None;
+            }
+            Some(violation(
+                conftest,
+                1,
+                "",
+                format!(
+                    "[{}] conftest.py is outside the test directories ({}), so its fixtures apply to code pytest may not treat as tests.\nLocation: {}\n\nTip: Move it into a test directory",
+                    RULE_ID,
+                    test_directories.join(", "),
+                    conftest.display()
+                ),
+            ))
+        })
+        .collect();
+
+    // Fixtures by name, shallowest conftest first
+    let definitions: Vec<ConftestFixture> = conftests
+        .par_iter()
+        .flat_map(|conftest| {
+            let content = fs::read_to_string(conftest).unwrap_or_default();
+            let lines: Vec<&str> = content.lines().collect();
+            fixtures::parse_fixtures(conftest, &content)
+                .into_iter()
+                .map(|fixture| ConftestFixture {
+                    conftest: conftest.clone(),
+                    suppressed: lines
+                        .get(fixture.line_number - 1)
+                        .is_some_and(|line| parse_noqa_rules(line).contains(RULE_ID)),
+                    fixture,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let mut by_name: BTreeMap<&str, Vec<&ConftestFixture>> = BTreeMap::new();
+    for definition in &definitions {
+        by_name
+            .entry(definition.fixture.name.as_str())
+            .or_default()
+            .push(definition);
+    }
+
+    for (name, mut group) in by_name {
+        group.sort_by_key(|d| (d.directory().components().count(), d.conftest.clone()));
+        for (idx, later) in group.iter().enumerate() {
+            if later.suppressed {
+                continue;
+            }
+            let Some((earlier, relation)) = group[..idx]
+                .iter()
+                .find_map(|earlier| Some((earlier, clash(earlier, later)?)))
+            else {
+                continue;
+            };
+            let hint = if relation == "overrides" {
+                "Rename one of them, or make the override explicit by requesting the parent fixture"
+            } else {
+                "Move the fixture to the conftest.py of the common parent directory"
+            };
+            violations.push(violation(
+                &later.conftest,
+                later.fixture.line_number,
+                name,
+                format!(
+                    "[{}] Fixture '{}' {} the fixture defined in {}:{}.\nLocation: {}:{}\n\nTip: {}",
+                    RULE_ID,
+                    name,
+                    relation,
+                    earlier.conftest.display(),
+                    earlier.fixture.line_number,
+                    later.conftest.display(),
+                    later.fixture.line_number,
+                    hint
+                ),
+            ));
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_conftests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for dir in ["src/pkg", "test/unit/a", "test/unit/b", "test/e2e"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        let fixture = |name: &str| {
+            format!(
+                "import pytest\n\n@pytest.fixture\ndef {}():\n    return 1\n",
+                name
+            )
+        };
+        fs::write(root.join("conftest.py"), fixture("root")).unwrap();
+        fs::write(root.join("src/pkg/conftest.py"), fixture("misplaced")).unwrap();
+        fs::write(root.join("test/conftest.py"), fixture("client")).unwrap();
+        fs::write(
+            root.join("test/unit/a/conftest.py"),
+            fixture("client") + &fixture("data"),
+        )
+        .unwrap();
+        fs::write(root.join("test/unit/b/conftest.py"), fixture("data")).unwrap();
+        fs::write(root.join("test/e2e/conftest.py"), fixture("data")).unwrap();
+
+        let test_directories = vec!["test".to_string()];
+        let violations = check_conftests(root, &test_directories, &[], &RuleOptions::default());
+        let found: Vec<(String, &str, bool)> = violations
+            .iter()
+            .map(|v| {
+                let path = Path::new(&*v.file_path).strip_prefix(root).unwrap();
+                (
+                    path.display().to_string(),
+                    v.function_name.as_str(),
+                    v.message.contains("overrides"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("src/pkg/conftest.py".to_string(), "", false),
+                ("test/unit/a/conftest.py".to_string(), "client", true),
+                ("test/unit/b/conftest.py".to_string(), "data", false),
+            ]
+        );
+    }
+}
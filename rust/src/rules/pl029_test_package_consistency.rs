@@ -0,0 +1,143 @@
+//! PL029: Require consistent `__init__.py` use across sibling test directories
+//!
+//! pytest imports test files differently depending on whether their directory is a package.
+//! When some test directories have an `__init__.py` and their siblings don't, which one is
+//! wrong depends on the import mode (`[tool.pytest.ini_options] addopts`, or the
+//! `import_mode` option):
+//! - `prepend` (the default) and `append` put the directory of a package-less test file on
+//!   `sys.path` under its basename, so `test_utils.py` in two such directories collide; the
+//!   directories without `__init__.py` are reported, with a fix creating it
+//! - `importlib` needs no `__init__.py`; the directories that have one are reported
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+use crate::rules::options::RuleOptions;
+
+const RULE_ID: &str = "PL029";
+const RULE_NAME: &str = "PL029:test-package-consistency";
+
+/// pytest's import mode, from the `PL029` options or the project's pytest configuration
+pub fn import_mode(options: &RuleOptions, project_root: &Path) -> Result<String, String> {
+    let mode = options
+        .get_strings(RULE_ID, "import_mode")
+        .and_then(|modes| modes.into_iter().next())
+        .or_else(|| crate::pyproject::pytest_import_mode(&crate::pyproject::load(project_root)?))
+        .unwrap_or_else(|| "prepend".to_string());
+    match mode.as_str() {
+        "prepend" | "append" | "importlib" => Ok(mode),
+        _ => Err(format!(
+            "invalid PL029 import_mode '{}', expected prepend, append or importlib",
+            mode
+        )),
+    }
+}
+
+/// Check that sibling directories containing `test_files` agree on having `__init__.py`
+pub fn check_test_packages(test_files: &[PathBuf], import_mode: &str) -> Vec<LintViolation> {
+    // Directories holding test files, by parent directory
+    let mut siblings: BTreeMap<&Path, BTreeSet<&Path>> = BTreeMap::new();
+    for test_file in test_files {
+        if let Some(dir) = test_file.parent() {
+            siblings
+                .entry(dir.parent().unwrap_or(Path::new("")))
+                .or_default()
+                .insert(dir);
+        }
+    }
+
+    let importlib = import_mode == "importlib";
+    let mut violations = Vec::new();
+    for dirs in siblings.values() {
+        let (packages, plain): (Vec<&Path>, Vec<&Path>) = dirs
+            .iter()
+            .partition(|dir| dir.join("__init__.py").is_file());
+        if packages.is_empty() || plain.is_empty() {
+            continue;
+        }
+
+        if importlib {
+            for dir in packages {
+                let init_file = dir.join("__init__.py");
+                violations.push(LintViolation {
+                    rule_name: RULE_NAME.to_string(),
+                    file_path: init_file.to_string_lossy().into(),
+                    line_number: 1,
+                    message: format!(
+                        "[{}] Test directory '{}' has an __init__.py but its sibling '{}' does not.\nLocation: {}\n\nTip: With --import-mode=importlib test directories need no __init__.py; remove it",
+                        RULE_ID,
+                        dir.display(),
+                        plain[0].display(),
+                        init_file.display()
+                    ),
+                    severity: "warning".to_string(),
+                    ..Default::default()
+                });
+            }
+        } else {
+            for dir in plain {
+                let init_file = dir.join("__init__.py");
+                // Anchor the violation at the first test file of the directory
+                let Some(test_file) = test_files.iter().filter(|f| f.parent() == Some(dir)).min()
+                else {
+                    continue;
+                };
+                violations.push(LintViolation {
+                    rule_name: RULE_NAME.to_string(),
+                    file_path: test_file.to_string_lossy().into(),
+                    line_number: 1,
+                    message: format!(
+                        "[{}] Test directory '{}' has no __init__.py but its sibling '{}' does.\nLocation: {}\n\nTip: With --import-mode={} test files with the same name in package-less directories collide; add an __init__.py",
+                        RULE_ID,
+                        dir.display(),
+                        packages[0].display(),
+                        dir.display(),
+                        import_mode
+                    ),
+                    severity: "warning".to_string(),
+                    fix_type: Some("create_test_skeleton".to_string()),
+                    fix_content: Some(init_file.to_string_lossy().to_string()),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_check_test_packages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for dir in ["unit/a", "unit/b", "unit/c", "e2e"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("test_m.py"), "").unwrap();
+        }
+        fs::write(root.join("unit/a/__init__.py"), "").unwrap();
+        fs::write(root.join("unit/b/__init__.py"), "").unwrap();
+        let test_files: Vec<PathBuf> = ["unit/a", "unit/b", "unit/c", "e2e"]
+            .iter()
+            .map(|dir| root.join(dir).join("test_m.py"))
+            .collect();
+
+        let violations = check_test_packages(&test_files, "prepend");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(&*violations[0].file_path, test_files[2].to_string_lossy());
+        assert_eq!(
+            violations[0].fix_content.as_deref(),
+            Some(root.join("unit/c/__init__.py").to_string_lossy().as_ref())
+        );
+
+        let violations = check_test_packages(&test_files, "importlib");
+        assert_eq!(violations.len(), 2);
+        assert!(violations
+            .iter()
+            .all(|v| v.file_path.ends_with("__init__.py")));
+    }
+}
@@ -0,0 +1,205 @@
+//! PL030: Tests must import from the public API, not from internals
+//!
+//! A test importing `pkg._internal.parser` exercises an implementation detail: it keeps
+//! passing while the public `pkg` entry point it should cover breaks, and it breaks when
+//! the internals are reorganized without any change visible to users. Imports of private
+//! modules in test files are reported at the import, naming the public surface to import
+//! from instead.
+//!
+//! A module is private when a component after the first starts with a single underscore
+//! (`pkg._impl`, `pkg.sub._helpers.io`), so top-level modules such as `_pytest` are not
+//! flagged. The `private_patterns` option replaces that rule with dotted module patterns
+//! (`pkg.internal.*`), and the `allow` option exempts modules from it.
+
+use rayon::prelude::*;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::file_discovery::module_pattern_to_regex;
+use crate::models::LintViolation;
+use crate::noqa::parse_noqa_rules;
+use crate::parser;
+use crate::rules::options::RuleOptions;
+use crate::rules::{imported_modules, test_functions};
+
+const RULE_ID: &str = "PL030";
+const RULE_NAME: &str = "PL030:public-api-imports";
+
+/// Which modules are internal, from the `PL030` rule options
+#[derive(Debug, Clone)]
+pub struct PrivateModules {
+    /// `private_patterns`; None for the underscore convention
+    patterns: Option<Vec<Regex>>,
+    allow: Vec<Regex>,
+}
+
+fn compile(options: &RuleOptions, key: &str) -> Result<Option<Vec<Regex>>, String> {
+    options
+        .get_strings(RULE_ID, key)
+        .map(|patterns| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    module_pattern_to_regex(pattern)
+                        .ok_or_else(|| format!("invalid PL030 {} pattern '{}'", key, pattern))
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+impl PrivateModules {
+    pub fn from_options(options: &RuleOptions) -> Result<Self, String> {
+        Ok(Self {
+            patterns: compile(options, "private_patterns")?,
+            allow: compile(options, "allow")?.unwrap_or_default(),
+        })
+    }
+
+    /// The public part of `module` when it is internal: the components before the first
+    /// private one, or with `private_patterns` its longest parent matching none of them
+    fn public_surface<'a>(&self, module: &'a str) -> Option<&'a str> {
+        if self.allow.iter().any(|pattern| pattern.is_match(module)) {
+            return None;
+        }
+        if let Some(patterns) = &self.patterns {
+            let is_private = |module: &str| patterns.iter().any(|pattern| pattern.is_match(module));
+            if !is_private(module) {
+                return None;
+            }
+            let mut surface = module;
+            while let Some((parent, _)) = surface.rsplit_once('.') {
+                surface = parent;
+                if !is_private(surface) {
+                    break;
+                }
+            }
+            return Some(surface);
+        }
+        let mut end = module.find('.')?;
+        for component in module[end + 1..].split('.') {
+            if component.starts_with('_') && !component.starts_with("__") {
+                return Some(&module[..end]);
+            }
+            end += 1 + component.len();
+        }
+        None
+    }
+}
+
+/// Check the imports of one test file
+fn check_file(file_path: &Path, private: &PrivateModules) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+    // (first line, last line, name) of each test function, 1-based
+    let spans: Vec<(usize, usize, &str)> = test_functions(&lines)
+        .map(|(idx, name)| {
+            let end_line = parser::parse_function(&lines, idx + 1).end_line;
+            (idx + 1, end_line, name)
+        })
+        .collect();
+
+    let mut violations = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if parse_noqa_rules(line).contains(RULE_ID) {
+            continue;
+        }
+        let code = line.split('#').next().unwrap_or(line);
+        let line_number = idx + 1;
+        for module in imported_modules(code) {
+            let Some(surface) = private.public_surface(module) else {
+                continue;
+            };
+            let function_name = spans
+                .iter()
+                .find(|(start, end, _)| (*start..=*end).contains(&line_number))
+                .map(|(_, _, name)| name.to_string())
+                .unwrap_or_default();
+            violations.push(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number,
+                function_name,
+                message: format!(
+                    "[{}] Test code imports the internal module '{}'\nLocation: {}:{}\n\nTip: Import what the test exercises from the public '{}' API, or allow the module with the PL030 allow option",
+                    RULE_ID,
+                    module,
+                    file_path.display(),
+                    line_number,
+                    surface
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    violations
+}
+
+/// Check the imports of `test_files` for internal modules
+pub fn check_public_imports(
+    test_files: &[PathBuf],
+    private: &PrivateModules,
+) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(|file_path| check_file(file_path, private))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::options::OptionValue;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_check_public_imports() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test").join("unit");
+        fs::create_dir_all(&unit).unwrap();
+        let content = "from pkg._internal.parser import parse\nimport pkg.api, pkg._impl as impl\nfrom _pytest.fixtures import fixture\nfrom pkg.__main__ import main\nfrom pkg.sub import _helper\nimport pkg._legacy  # noqa: PL030\n\ndef test_parse():\n    from pkg.internal.io import read\n    assert parse(read())\n";
+        fs::write(unit.join("test_m.py"), content).unwrap();
+        let files = vec![unit.join("test_m.py")];
+
+        let private = PrivateModules::from_options(&RuleOptions::default()).unwrap();
+        let violations = check_public_imports(&files, &private);
+        let found: Vec<(usize, &str)> = violations
+            .iter()
+            .map(|v| (v.line_number, v.function_name.as_str()))
+            .collect();
+        assert_eq!(found, vec![(1, ""), (2, "")]);
+        assert!(violations[0].message().contains("public 'pkg' API"));
+
+        let options = RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            HashMap::from([
+                (
+                    "private_patterns".to_string(),
+                    OptionValue::List(vec!["pkg.internal.*".to_string(), "pkg._*".to_string()]),
+                ),
+                (
+                    "allow".to_string(),
+                    OptionValue::Str("pkg._internal.*".to_string()),
+                ),
+            ]),
+        )]));
+        let private = PrivateModules::from_options(&options).unwrap();
+        let violations = check_public_imports(&files, &private);
+        let found: Vec<(usize, &str)> = violations
+            .iter()
+            .map(|v| (v.line_number, v.function_name.as_str()))
+            .collect();
+        // `pkg._*` covers `pkg.__main__` too
+        assert_eq!(found, vec![(2, ""), (4, ""), (9, "test_parse")]);
+        assert!(violations[2]
+            .message()
+            .contains("public 'pkg.internal' API"));
+    }
+}
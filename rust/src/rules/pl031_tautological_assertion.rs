@@ -0,0 +1,310 @@
+//! PL031: Forbid assertions that cannot fail
+//!
+//! A tautological assertion passes whatever the code under test does, so the test counts
+//! towards coverage without checking anything. Assertions in test functions are reported
+//! when they:
+//! - compare a value to itself (`assert x == x`, `self.assertEqual(x, x)`)
+//! - assert a constant (`assert True`, `self.assertTrue(1)`, `self.assertFalse(None)`)
+//! - check the return value configured on a mock against the mock itself
+//!   (`m.return_value = 5` then `assert m() == 5` or `assert m.return_value == 5`)
+//!
+//! Assertions are read one physical line at a time; those spanning lines are checked on
+//! their first line only.
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa::{function_noqa_rules, parse_noqa_rules_with};
+use crate::parser;
+use crate::patterns::{Patterns, PATTERNS};
+use crate::rules::test_functions;
+
+const RULE_ID: &str = "PL031";
+const RULE_NAME: &str = "PL031:tautological-assertion";
+
+/// unittest assertions comparing their first two arguments
+const COMPARING_ASSERTIONS: &[&str] = &[
+    "assertEqual",
+    "assertEquals",
+    "assertIs",
+    "assertAlmostEqual",
+    "assertCountEqual",
+    "assertListEqual",
+    "assertDictEqual",
+    "assertSetEqual",
+    "assertTupleEqual",
+    "assertSequenceEqual",
+    "assertGreaterEqual",
+    "assertLessEqual",
+];
+
+/// Comparisons that hold between a value and itself
+const REFLEXIVE_OPERATORS: &[&str] = &[" == ", " is ", " >= ", " <= "];
+
+/// The ASCII characters of `code` outside string literals, as (byte offset, character,
+/// bracket depth before it)
+fn structure(code: &str) -> Vec<(usize, u8, usize)> {
+    let bytes = code.as_bytes();
+    let mut found = Vec::new();
+    let (mut depth, mut quote) = (0usize, None::<u8>);
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(_) if b == b'\\' => i += 1,
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b.is_ascii() => {
+                found.push((i, b, depth));
+                match b {
+                    b'"' | b'\'' => quote = Some(b),
+                    b'(' | b'[' | b'{' => depth += 1,
+                    b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    found
+}
+
+/// `line` without its comment
+fn strip_comment(line: &str) -> &str {
+    structure(line)
+        .into_iter()
+        .find(|(_, b, _)| *b == b'#')
+        .map_or(line, |(i, _, _)| &line[..i])
+}
+
+/// `code` split around the first occurrence of `separator` outside strings and brackets
+fn split_top_level<'a>(code: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    structure(code)
+        .into_iter()
+        .find(|(i, _, depth)| *depth == 0 && code[*i..].starts_with(separator))
+        .map(|(i, _, _)| (code[..i].trim(), code[i + separator.len()..].trim()))
+}
+
+/// The arguments of the call whose arguments start `rest` (just after the `(`)
+fn call_arguments(rest: &str) -> Vec<&str> {
+    let structure = structure(rest);
+    let Some(&(end, _, _)) = structure
+        .iter()
+        .find(|(_, b, depth)| *b == b')' && *depth == 0)
+    else {
+        return Vec::new();
+    };
+    let mut arguments = Vec::new();
+    let mut start = 0;
+    for &(i, b, depth) in &structure {
+        if i < end && b == b',' && depth == 0 {
+            arguments.push(rest[start..i].trim());
+            start = i + 1;
+        }
+    }
+    arguments.push(rest[start..end].trim());
+    arguments
+}
+
+/// Whether `expr` is a single string literal (whose opening quote is all `structure` sees)
+fn is_string_literal(expr: &str) -> bool {
+    expr.starts_with(['"', '\'']) && expr.len() >= 2 && structure(expr).len() == 1
+}
+
+fn is_truthy_constant(expr: &str) -> bool {
+    expr == "True"
+        || (is_string_literal(expr) && expr.len() > 2)
+        || expr.parse::<f64>().is_ok_and(|number| number != 0.0)
+}
+
+fn is_falsy_constant(expr: &str) -> bool {
+    matches!(expr, "False" | "None" | "0" | "\"\"" | "''")
+}
+
+/// Why comparing `left` to `right` always holds, given the values configured on the
+/// test's mocks (`mocks`, by mock)
+fn tautological_comparison(left: &str, right: &str, mocks: &HashMap<&str, &str>) -> Option<String> {
+    if left.is_empty() || right.is_empty() {
+        return None;
+    }
+    if left == right {
+        return Some(format!("compares {} to itself", left));
+    }
+    for (side, other) in [(left, right), (right, left)] {
+        for (mock, value) in mocks {
+            let on_mock = side.strip_prefix(mock).is_some_and(|rest| {
+                rest == ".return_value" || rest.starts_with('(') && rest.ends_with(')')
+            });
+            if on_mock && other == *value {
+                return Some(format!(
+                    "checks the return value configured on the mock {} against the mock itself",
+                    mock
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// Why the assertion on the code `code` cannot fail, if it cannot
+fn tautology(code: &str, mocks: &HashMap<&str, &str>) -> Option<String> {
+    if let Some(rest) = code.strip_prefix("assert ") {
+        let condition = split_top_level(rest, ",").map_or(rest.trim(), |(condition, _)| condition);
+        if is_truthy_constant(condition) {
+            return Some(format!("asserts the constant {}", condition));
+        }
+        if split_top_level(condition, " is not ").is_some() {
+            return None;
+        }
+        return REFLEXIVE_OPERATORS.iter().find_map(|operator| {
+            let (left, right) = split_top_level(condition, operator)?;
+            tautological_comparison(left, right, mocks)
+        });
+    }
+    let captures = PATTERNS.unittest_assert.captures(code)?;
+    let method = captures.get(1)?.as_str();
+    let arguments = call_arguments(&code[captures.get(0)?.end()..]);
+    match (method, arguments.as_slice()) {
+        ("assertTrue", [value, ..]) if is_truthy_constant(value) => {
+            Some(format!("asserts the constant {}", value))
+        }
+        ("assertFalse", [value, ..]) if is_falsy_constant(value) => {
+            Some(format!("asserts the constant {} is false", value))
+        }
+        (method, [left, right, ..]) if COMPARING_ASSERTIONS.contains(&method) => {
+            tautological_comparison(left, right, mocks)
+        }
+        _ => None,
+    }
+}
+
+/// Check the test functions in one test file for assertions that cannot fail
+fn check_file(file_path: &Path, patterns: &Patterns) -> Vec<LintViolation> {
+    let Ok(content) = fs::read_to_string(file_path) else {
+        return vec![];
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let shared_path: Arc<str> = file_path.to_string_lossy().into();
+
+    let mut violations = Vec::new();
+    for (idx, name) in test_functions(&lines) {
+        if function_noqa_rules(patterns, &lines, idx + 1).contains(RULE_ID) {
+            continue;
+        }
+        let function = parser::parse_function(&lines, idx + 1);
+        let body: Vec<(usize, &str)> = lines
+            .iter()
+            .enumerate()
+            .take(function.end_line)
+            .skip(idx + 1)
+            .map(|(line_idx, line)| (line_idx, strip_comment(line).trim()))
+            .collect();
+        let mocks: HashMap<&str, &str> = body
+            .iter()
+            .filter_map(|(_, code)| {
+                let captures = PATTERNS.mock_return_value.captures(code)?;
+                Some((captures.get(1)?.as_str(), captures.get(2)?.as_str().trim()))
+            })
+            .collect();
+
+        for &(line_idx, code) in &body {
+            let Some(reason) = tautology(code, &mocks) else {
+                continue;
+            };
+            if parse_noqa_rules_with(patterns, lines[line_idx]).contains(RULE_ID) {
+                continue;
+            }
+            violations.push(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: shared_path.clone(),
+                line_number: line_idx + 1,
+                function_name: name.to_string(),
+                message: format!(
+                    "[{}] Test function '{}' has an assertion that cannot fail: it {}\nLocation: {}:{}\n\nTip: Assert on what the code under test returns or does",
+                    RULE_ID,
+                    name,
+                    reason,
+                    file_path.display(),
+                    line_idx + 1
+                ),
+                severity: "warning".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+    violations
+}
+
+/// Check the tests in `test_files` for assertions that cannot fail
+pub fn check_tautological_assertions(
+    test_files: &[PathBuf],
+    patterns: &Patterns,
+) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .flat_map(|file_path| check_file(file_path, patterns))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_tautological_assertions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test").join("unit");
+        fs::create_dir_all(&unit).unwrap();
+        let content = r#"def test_values():
+    x = compute()
+    assert x == x
+    assert x == y, "x == x"
+    assert "a" == "b"
+    assert True
+    assert x is not x
+    assert f(a, b) == f(a, b)  # noqa: PL031
+    assert f(a, b) == f(a, c)  # x == x
+
+def test_mock(m):
+    m.return_value = 5
+    assert m() == 5
+    assert m.return_value == 5
+    assert service(m) == 5
+
+class TestCase(unittest.TestCase):
+    def test_unittest(self):
+        self.assertTrue(True)
+        self.assertTrue(x)
+        self.assertFalse(None)
+        self.assertEqual(self.a, self.a)
+        self.assertEqual(compute(1, 2), 3)
+"#;
+        fs::write(unit.join("test_m.py"), content).unwrap();
+
+        let violations = check_tautological_assertions(&[unit.join("test_m.py")], &PATTERNS);
+        let found: Vec<(&str, usize)> = violations
+            .iter()
+            .map(|v| (v.function_name.as_str(), v.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("test_values", 3),
+                ("test_values", 6),
+                ("test_mock", 13),
+                ("test_mock", 14),
+                ("test_unittest", 19),
+                ("test_unittest", 21),
+                ("test_unittest", 22),
+            ]
+        );
+        assert!(violations[0].message().contains("compares x to itself"));
+        assert!(violations[2].message().contains("mock m"));
+    }
+}
@@ -0,0 +1,95 @@
+//! PL032: Test files must contain tests
+//!
+//! A file matching the test file patterns that pytest collects no test from, such as one
+//! holding only helpers, looks like a test module to readers and to the coverage matching
+//! alike. Such files are reported once, on their first line. The test cache leaves them
+//! out, so their functions never count as tests of the code under test (see
+//! `rules::collects_tests`).
+
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+use crate::noqa::parse_noqa_rules;
+use crate::rules::collects_tests;
+
+const RULE_ID: &str = "PL032";
+const RULE_NAME: &str = "PL032:empty-test-file";
+
+/// Check that one test file has tests
+fn check_file(file_path: &Path) -> Option<LintViolation> {
+    let content = fs::read_to_string(file_path).ok()?;
+    let lines: Vec<&str> = content.lines().collect();
+    if collects_tests(&lines) {
+        return None;
+    }
+    if lines
+        .first()
+        .is_some_and(|line| parse_noqa_rules(line).contains(RULE_ID))
+    {
+        return None;
+    }
+    let file_name = file_path.file_name()?.to_string_lossy();
+    Some(LintViolation {
+        rule_name: RULE_NAME.to_string(),
+        file_path: file_path.to_string_lossy().into(),
+        line_number: 1,
+        function_name: String::new(),
+        message: format!(
+            "[{}] Test file '{}' has no tests pytest collects\nLocation: {}:1\n\nTip: Add tests, or rename the file so it no longer matches the test file patterns (e.g. helpers.py)",
+            RULE_ID,
+            file_name,
+            file_path.display()
+        ),
+        severity: "warning".to_string(),
+        ..Default::default()
+    })
+}
+
+/// Check that `test_files` have tests
+pub fn check_test_files_have_tests(test_files: &[PathBuf]) -> Vec<LintViolation> {
+    test_files
+        .par_iter()
+        .filter_map(|file_path| check_file(file_path))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_test_files_have_tests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test").join("unit");
+        fs::create_dir_all(&unit).unwrap();
+        let files = [
+            ("test_module.py", "def test_load():\n    pass\n"),
+            (
+                "test_class.py",
+                "class TestLoad:\n    def test_load(self):\n        pass\n",
+            ),
+            (
+                "test_helpers.py",
+                "def make_user():\n    pass\n\nclass Helper:\n    def test_like(self):\n        pass\n",
+            ),
+            ("test_empty.py", ""),
+            ("test_ignored.py", "# noqa: PL032\nimport pytest\n"),
+        ];
+        for (name, content) in files {
+            fs::write(unit.join(name), content).unwrap();
+        }
+        let test_files: Vec<PathBuf> = files.iter().map(|(name, _)| unit.join(name)).collect();
+
+        let violations = check_test_files_have_tests(&test_files);
+        let mut found: Vec<&str> = violations
+            .iter()
+            .map(|v| v.file_path.rsplit('/').next().unwrap())
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["test_empty.py", "test_helpers.py"]);
+        assert!(violations[0].message().contains("no tests pytest collects"));
+    }
+}
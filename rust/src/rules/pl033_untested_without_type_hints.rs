@@ -0,0 +1,107 @@
+use super::LintRule;
+use crate::models::LintViolation;
+use crate::test_cache::TestType;
+use std::path::Path;
+
+/// Flags public functions that have no unit test and are not fully annotated either, so
+/// that static type checking covers what tests don't (opt-in)
+pub struct PL033UntestedWithoutTypeHints {}
+
+impl PL033UntestedWithoutTypeHints {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Describe the violation, listing what lacks a type hint
+    fn message(&self, function_name: &str, class_name: Option<&str>, missing: &[String]) -> String {
+        let subject = match class_name {
+            Some(class) => format!("Method '{}' of class '{}'", function_name, class),
+            None => format!("Function '{}'", function_name),
+        };
+        format!(
+            "[{}] {} has no unit test and no type hints for: {}\n\nTip: Add a unit test, or annotate the function fully so type checking covers it",
+            self.rule_id(),
+            subject,
+            missing.join(", ")
+        )
+    }
+}
+
+impl LintRule for PL033UntestedWithoutTypeHints {
+    fn rule_id(&self) -> &'static str {
+        "PL033"
+    }
+
+    fn rule_name(&self) -> &'static str {
+        "untested-without-type-hints"
+    }
+
+    fn check_function(
+        &self,
+        function_name: &str,
+        file_path: &Path,
+        line_number: usize,
+        _line_content: &str,
+        class_name: Option<&str>,
+        is_protocol: bool,
+        context: &super::RuleContext,
+    ) -> Option<LintViolation> {
+        // `noqa` comments are applied by the caller
+
+        if (is_protocol && class_name.is_some()) || function_name == "__init__" {
+            return None;
+        }
+
+        if context.is_below_size_threshold(self.rule_id()) {
+            return None;
+        }
+
+        // The parameters bound to the instance or class go unannotated
+        let function = context.function;
+        let skip = usize::from(
+            class_name.is_some() && !function.decorators.iter().any(|d| d == "staticmethod"),
+        );
+        let mut missing: Vec<String> = function
+            .parameters()
+            .into_iter()
+            .skip(skip)
+            .filter(|parameter| !parameter.annotated)
+            .map(|parameter| parameter.name)
+            .collect();
+        if function.return_annotation().is_none() {
+            missing.push("return type".to_string());
+        }
+        if missing.is_empty() {
+            return None;
+        }
+
+        let tested = context.test_cache.has_test_for_function_of_type(
+            function_name,
+            file_path,
+            class_name,
+            &TestType::Unit,
+            context.module_path,
+            context.project_root,
+        ) || context.credited_is_tested(&TestType::Unit, false);
+        if tested {
+            return None;
+        }
+
+        let message = if context.build_messages {
+            self.message(function_name, class_name, &missing)
+        } else {
+            String::new()
+        };
+
+        Some(LintViolation {
+            rule_name: format!("{}:{}", self.rule_id(), self.rule_name()),
+            file_path: context.file_path.clone(),
+            line_number,
+            function_name: function_name.to_string(),
+            class_name: class_name.map(str::to_string),
+            message,
+            severity: "warning".to_string(),
+            ..Default::default()
+        })
+    }
+}
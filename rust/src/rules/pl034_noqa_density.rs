@@ -0,0 +1,116 @@
+//! PL034: Limit the share of functions in a source module that suppress rules
+//!
+//! A `noqa` on a function or two documents an exception; one on most functions of a module
+//! means the linter has effectively been turned off there, without anything in the
+//! configuration saying so. A module is reported once, on its first line, when more than
+//! `max_percent` percent (default 50) of its functions carry a `noqa` naming a rule. Modules
+//! with fewer than `min_functions` functions (default 4) are left alone, as a single
+//! suppression already makes up a large share of them.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::noqa;
+use crate::patterns::Patterns;
+use crate::rules::options::RuleOptions;
+use crate::source_index::SourceFile;
+
+pub const RULE_ID: &str = "PL034";
+const RULE_NAME: &str = "PL034:noqa-density";
+
+const DEFAULT_MAX_PERCENT: usize = 50;
+const DEFAULT_MIN_FUNCTIONS: usize = 4;
+
+/// The violation of `source`, the module at `file_path`, if too many of its functions
+/// suppress rules
+pub fn check_noqa_density(
+    source: &SourceFile,
+    lines: &[&str],
+    file_path: &Path,
+    shared_path: &Arc<str>,
+    options: &RuleOptions,
+    patterns: &Patterns,
+) -> Option<LintViolation> {
+    let max_percent = options
+        .get_usize(RULE_ID, "max_percent")
+        .unwrap_or(DEFAULT_MAX_PERCENT);
+    let min_functions = options
+        .get_usize(RULE_ID, "min_functions")
+        .unwrap_or(DEFAULT_MIN_FUNCTIONS);
+    let total = source.functions.len();
+    if total == 0 || total < min_functions {
+        return None;
+    }
+    let suppressing = source
+        .functions
+        .iter()
+        .filter(|function| {
+            !noqa::function_noqa_rules(patterns, lines, function.line_number).is_empty()
+        })
+        .count();
+    if suppressing * 100 <= max_percent * total {
+        return None;
+    }
+    if lines
+        .first()
+        .is_some_and(|line| noqa::parse_noqa_rules_with(patterns, line).contains(RULE_ID))
+    {
+        return None;
+    }
+    Some(LintViolation {
+        rule_name: RULE_NAME.to_string(),
+        file_path: shared_path.clone(),
+        line_number: 1,
+        function_name: String::new(),
+        message: format!(
+            "[{}] {} of {} functions ({}%) suppress rules with noqa, more than {}%\nLocation: {}:1\n\nTip: Add the missing tests, or exclude the module in the configuration so the exception is visible",
+            RULE_ID,
+            suppressing,
+            total,
+            suppressing * 100 / total,
+            max_percent,
+            file_path.display()
+        ),
+        severity: "warning".to_string(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+    use crate::rules::options::OptionValue;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_check_noqa_density() {
+        let content = "def a():  # noqa: PL001\n    pass\n\n# noqa: PL002\ndef b():\n    pass\n\n@cached  # noqa: PL001, PL002\ndef c():\n    pass\n\ndef d():\n    pass\n";
+        let source = SourceFile::parse(content.to_string(), &PATTERNS);
+        let lines = source.lines();
+        let path = Path::new("pkg/legacy.py");
+        let shared_path: Arc<str> = "pkg/legacy.py".into();
+        let check = |options: &RuleOptions| {
+            check_noqa_density(&source, &lines, path, &shared_path, options, &PATTERNS)
+        };
+
+        // a and c suppress rules (a comment above the `def` does not count): 2 of 4
+        assert!(check(&RuleOptions::default()).is_none());
+        let strict = RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            HashMap::from([("max_percent".to_string(), OptionValue::Int(25))]),
+        )]));
+        let violation = check(&strict).unwrap();
+        assert_eq!(violation.line_number, 1);
+        assert!(violation.message.contains("2 of 4 functions (50%)"));
+        let lenient = RuleOptions::from(HashMap::from([(
+            RULE_ID.to_string(),
+            HashMap::from([
+                ("max_percent".to_string(), OptionValue::Int(25)),
+                ("min_functions".to_string(), OptionValue::Int(5)),
+            ]),
+        )]));
+        assert!(check(&lenient).is_none());
+    }
+}
@@ -0,0 +1,262 @@
+//! Versioned rule behavior, so upgrades do not silently change which code is flagged
+//!
+//! A rule whose matching semantics change gets a new version rather than changing in
+//! place. Each run uses a rule's default version unless `rule_versions` pins another, and
+//! the default only moves in a release that says so. A version due to be replaced is
+//! deprecated first: runs using it without pinning it get a `PL000:deprecated-rule-version`
+//! warning naming the versions to choose from.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::models::LintViolation;
+
+/// One version of a rule's behavior
+#[derive(Debug, Clone, Copy)]
+pub struct RuleVersion {
+    pub rule_id: &'static str,
+    pub version: u32,
+    /// What the version checks, for deprecation warnings and `rules.all()`
+    pub summary: &'static str,
+    /// Whether runs use this version unless `rule_versions` pins another
+    pub default: bool,
+    pub deprecated: bool,
+}
+
+/// Versions of the rules that have more than one; every other rule only has version 1
+const VERSIONS: &[RuleVersion] = &[
+    RuleVersion {
+        rule_id: "PL001",
+        version: 1,
+        summary: "any `test_*` file under the unit test directory may hold the test",
+        default: true,
+        deprecated: false,
+    },
+    RuleVersion {
+        rule_id: "PL001",
+        version: 2,
+        summary: "the test must live in the directory mirroring the module, or in a test file named after it",
+        default: false,
+        deprecated: false,
+    },
+];
+
+/// The versions of `rule_id`, oldest first
+pub fn versions_of(rule_id: &str) -> Vec<u32> {
+    versions_in(VERSIONS, rule_id)
+}
+
+fn versions_in(table: &[RuleVersion], rule_id: &str) -> Vec<u32> {
+    let versions: Vec<u32> = table
+        .iter()
+        .filter(|v| v.rule_id == rule_id)
+        .map(|v| v.version)
+        .collect();
+    if versions.is_empty() {
+        vec![1]
+    } else {
+        versions
+    }
+}
+
+/// The version of `rule_id` runs use unless pinned
+pub fn default_version(rule_id: &str) -> u32 {
+    default_version_in(VERSIONS, rule_id)
+}
+
+fn default_version_in(table: &[RuleVersion], rule_id: &str) -> u32 {
+    table
+        .iter()
+        .find(|v| v.rule_id == rule_id && v.default)
+        .map_or(1, |v| v.version)
+}
+
+/// The rule versions a run uses: the defaults, with those pinned by `rule_versions`
+#[derive(Debug, Clone)]
+pub struct RuleVersions {
+    pinned: HashMap<String, u32>,
+    table: &'static [RuleVersion],
+}
+
+impl Default for RuleVersions {
+    fn default() -> Self {
+        Self {
+            pinned: HashMap::new(),
+            table: VERSIONS,
+        }
+    }
+}
+
+impl RuleVersions {
+    /// Validate the pins of `rule_versions` (rule id to version)
+    pub fn new(pinned: HashMap<String, u32>) -> Result<Self, String> {
+        Self::in_table(VERSIONS, pinned)
+    }
+
+    fn in_table(
+        table: &'static [RuleVersion],
+        pinned: HashMap<String, u32>,
+    ) -> Result<Self, String> {
+        let mut rule_ids: Vec<&String> = pinned.keys().collect();
+        rule_ids.sort();
+        for rule_id in rule_ids {
+            let version = pinned[rule_id];
+            let versions = versions_in(table, rule_id);
+            if !versions.contains(&version) {
+                let versions: Vec<String> = versions.iter().map(u32::to_string).collect();
+                return Err(format!(
+                    "{} has no version {}; rule_versions.{} must be one of: {}",
+                    rule_id,
+                    version,
+                    rule_id,
+                    versions.join(", ")
+                ));
+            }
+        }
+        Ok(Self { pinned, table })
+    }
+
+    /// The version of `rule_id` in use
+    pub fn get(&self, rule_id: &str) -> u32 {
+        self.pinned
+            .get(rule_id)
+            .copied()
+            .unwrap_or_else(|| default_version_in(self.table, rule_id))
+    }
+
+    /// This run's versions with those of `other` pinned on top
+    pub fn merged(&self, other: HashMap<String, u32>) -> Result<Self, String> {
+        let other = Self::in_table(self.table, other)?;
+        let mut pinned = self.pinned.clone();
+        pinned.extend(other.pinned);
+        Ok(Self {
+            pinned,
+            table: self.table,
+        })
+    }
+
+    /// The pins, as `PL001 = 2` entries in rule id order
+    pub fn describe(&self) -> Vec<String> {
+        let mut pins: Vec<String> = self
+            .pinned
+            .iter()
+            .map(|(rule_id, version)| format!("{} = {}", rule_id, version))
+            .collect();
+        pins.sort();
+        pins
+    }
+
+    /// Warnings for the rules among `running` that use a deprecated version without
+    /// pinning it, reported against the pyproject.toml of `project_root`
+    pub fn deprecation_warnings<'a>(
+        &self,
+        running: impl IntoIterator<Item = &'a str>,
+        project_root: &Path,
+    ) -> Vec<LintViolation> {
+        let config_file = project_root.join("pyproject.toml");
+        running
+            .into_iter()
+            .filter(|rule_id| !self.pinned.contains_key(*rule_id))
+            .filter_map(|rule_id| {
+                let version = self.get(rule_id);
+                self.table
+                    .iter()
+                    .find(|v| v.rule_id == rule_id && v.version == version)
+            })
+            .filter(|current| current.deprecated)
+            .map(|current| {
+                let newer: Vec<&RuleVersion> = self
+                    .table
+                    .iter()
+                    .filter(|v| v.rule_id == current.rule_id && !v.deprecated)
+                    .collect();
+                let descriptions: Vec<String> = newer
+                    .iter()
+                    .map(|v| format!("{} ({})", v.version, v.summary))
+                    .collect();
+                let latest = newer.last().map_or(current.version, |v| v.version);
+                LintViolation {
+                    rule_name: "PL000:deprecated-rule-version".to_string(),
+                    file_path: config_file.to_string_lossy().into(),
+                    line_number: 1,
+                    function_name: String::new(),
+                    message: format!(
+                        "[PL000] {} runs at deprecated version {} ({}); a future release makes a newer version the default, changing which code it flags.\nNewer versions: {}\n\nTip: Adopt the newer behavior with rule_versions = {{{} = {}}} in [tool.proboscis], or pin {} = {} to keep the current one",
+                        current.rule_id,
+                        current.version,
+                        current.summary,
+                        descriptions.join(", "),
+                        current.rule_id,
+                        latest,
+                        current.rule_id,
+                        current.version
+                    ),
+                    severity: "warning".to_string(),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_versions() {
+        assert_eq!(versions_of("PL001"), vec![1, 2]);
+        assert_eq!(versions_of("PL004"), vec![1]);
+        assert_eq!(RuleVersions::default().get("PL001"), 1);
+
+        let pinned = RuleVersions::new(HashMap::from([("PL001".to_string(), 2)])).unwrap();
+        assert_eq!(pinned.get("PL001"), 2);
+        assert_eq!(pinned.describe(), vec!["PL001 = 2".to_string()]);
+        let error = RuleVersions::new(HashMap::from([("PL002".to_string(), 2)])).unwrap_err();
+        assert!(error.contains("must be one of: 1"));
+        let merged = pinned
+            .merged(HashMap::from([("PL001".to_string(), 1)]))
+            .unwrap();
+        assert_eq!(merged.get("PL001"), 1);
+    }
+
+    const DEPRECATING: &[RuleVersion] = &[
+        RuleVersion {
+            rule_id: "PL001",
+            version: 1,
+            summary: "old matching",
+            default: true,
+            deprecated: true,
+        },
+        RuleVersion {
+            rule_id: "PL001",
+            version: 2,
+            summary: "new matching",
+            default: false,
+            deprecated: false,
+        },
+    ];
+
+    #[test]
+    fn test_deprecation_warnings() {
+        let root = Path::new("/repo");
+        assert!(RuleVersions::default()
+            .deprecation_warnings(["PL001"], root)
+            .is_empty());
+
+        let unpinned = RuleVersions::in_table(DEPRECATING, HashMap::new()).unwrap();
+        let warnings = unpinned.deprecation_warnings(["PL001", "PL002"], root);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].rule_name, "PL000:deprecated-rule-version");
+        assert_eq!(warnings[0].severity, "warning");
+        assert!(warnings[0]
+            .message
+            .contains("Newer versions: 2 (new matching)"));
+        assert!(warnings[0].message.contains("rule_versions = {PL001 = 2}"));
+
+        // Pinning the deprecated version acknowledges it
+        let pinned =
+            RuleVersions::in_table(DEPRECATING, HashMap::from([("PL001".to_string(), 1)])).unwrap();
+        assert!(pinned.deprecation_warnings(["PL001"], root).is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! What a run was made with (`RunMetadata`), for reproducibility audits
+//!
+//! Two reports of the same code only compare when they were produced by the same linter
+//! version, configuration and rule versions; the metadata records those along with the
+//! project, the commit checked out and when the run happened. The configuration is
+//! recorded as a hash of the settings `doctor` reports, so that two runs with the same
+//! effective settings share it however they were configured.
+//!
+//! `RustLinter.lint_project_run` returns the metadata with the violations. SARIF reports
+//! carry it in the run's `properties`; formats with a fixed schema (CSV, NDJSON, Code
+//! Climate, Checkstyle) do not, and `lint_project_to_file` writes it next to them.
+
+use pyo3::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::expiry::Date;
+use crate::models::LintViolation;
+
+/// Suffix of the file `lint_project_to_file` writes the metadata of its report to
+pub const SIDECAR_SUFFIX: &str = ".meta.json";
+
+/// What a run was made with
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct RunMetadata {
+    /// Version of the linter
+    #[pyo3(get)]
+    pub version: String,
+    /// Hash of the effective settings (see `RustLinter.doctor`)
+    #[pyo3(get)]
+    pub config_hash: String,
+    /// Behavior version of each rule that ran, by rule id
+    #[pyo3(get)]
+    pub rule_versions: HashMap<String, u32>,
+    #[pyo3(get)]
+    pub project_root: String,
+    /// Hex id of the commit checked out, when the project is in a git repository
+    #[pyo3(get)]
+    pub git_sha: Option<String>,
+    /// When the run started, in UTC (`2024-01-31T09:30:00Z`)
+    #[pyo3(get)]
+    pub timestamp: String,
+    #[pyo3(get)]
+    pub duration_seconds: f64,
+}
+
+impl RunMetadata {
+    /// The metadata as a JSON object, keyed as the attributes
+    pub fn to_value(&self) -> Value {
+        json!({
+            "version": self.version,
+            "config_hash": self.config_hash,
+            "rule_versions": self.rule_versions,
+            "project_root": self.project_root,
+            "git_sha": self.git_sha,
+            "timestamp": self.timestamp,
+            "duration_seconds": self.duration_seconds,
+        })
+    }
+}
+
+#[pymethods]
+impl RunMetadata {
+    /// The metadata as a JSON object
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_value()).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RunMetadata(version='{}', config_hash='{}', git_sha={}, timestamp='{}')",
+            self.version,
+            self.config_hash,
+            self.git_sha
+                .as_deref()
+                .map_or("None".to_string(), |sha| format!("'{}'", sha)),
+            self.timestamp
+        )
+    }
+}
+
+/// The violations of a run with its metadata (`RustLinter.lint_project_run`)
+#[pyclass]
+pub struct LintRun {
+    #[pyo3(get)]
+    pub violations: Vec<LintViolation>,
+    #[pyo3(get)]
+    pub metadata: RunMetadata,
+    /// Code the run exits with, per the `exit_codes` of the linter
+    #[pyo3(get)]
+    pub exit_code: i32,
+}
+
+/// `time` in UTC, as `YYYY-MM-DDTHH:MM:SSZ`
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() as i64);
+    let (days, of_day) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        Date::from_days(days),
+        of_day / 3600,
+        of_day % 3600 / 60,
+        of_day % 60
+    )
+}
+
+/// Where `lint_project_to_file` writes the metadata of the report at `path`
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(SIDECAR_SUFFIX);
+    name.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timestamp_and_json() {
+        let started = UNIX_EPOCH + Duration::from_secs(19_723 * 86_400 + 9 * 3600 + 30 * 60 + 5);
+        assert_eq!(timestamp(started), "2024-01-01T09:30:05Z");
+
+        let metadata = RunMetadata {
+            version: "1.0.0".to_string(),
+            config_hash: "00ff".to_string(),
+            rule_versions: HashMap::from([("PL001".to_string(), 2)]),
+            project_root: "/repo".to_string(),
+            git_sha: None,
+            timestamp: timestamp(started),
+            duration_seconds: 1.5,
+        };
+        let value = metadata.to_value();
+        assert_eq!(value["rule_versions"]["PL001"], 2);
+        assert_eq!(value["git_sha"], Value::Null);
+        assert_eq!(value["timestamp"], "2024-01-01T09:30:05Z");
+        assert_eq!(
+            sidecar_path(Path::new("out/report.csv")),
+            Path::new("out/report.csv.meta.json")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! One-off scripts, whose top-level functions are not held to the testing rules
+//!
+//! A file is a script when it lies in one of the `script_directories` (matched like
+//! `test_directories`: `scripts` and `bin` by default) or starts with a shebang line.
+//! Depending on `script_mode`, the top-level functions of scripts are skipped, reported as
+//! warnings, or checked like any other code; methods are always checked.
+
+use std::path::Path;
+
+use crate::file_discovery::TestDirectories;
+
+/// How the top-level functions of scripts are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptMode {
+    /// Not checked at all
+    Exempt,
+    /// Checked, with violations reported as warnings
+    Downgrade,
+    /// Checked like any other function
+    Check,
+}
+
+impl ScriptMode {
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "exempt" => Ok(Self::Exempt),
+            "downgrade" => Ok(Self::Downgrade),
+            "check" => Ok(Self::Check),
+            other => Err(format!(
+                "script_mode must be 'exempt', 'downgrade' or 'check', got '{}'",
+                other
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Exempt => "exempt",
+            Self::Downgrade => "downgrade",
+            Self::Check => "check",
+        }
+    }
+}
+
+/// Script detection for a lint run
+#[derive(Debug, Clone)]
+pub struct Scripts {
+    /// The `script_directories` setting
+    pub directories: Vec<String>,
+    matcher: TestDirectories,
+    pub mode: ScriptMode,
+}
+
+impl Scripts {
+    pub fn new(directories: &[String], mode: ScriptMode) -> Self {
+        Self {
+            directories: directories.to_vec(),
+            matcher: TestDirectories::new(directories),
+            mode,
+        }
+    }
+
+    /// Whether the file at `relative_path` (relative to the project root), with `content`,
+    /// is a script
+    pub fn is_script(&self, relative_path: &Path, content: &str) -> bool {
+        content.starts_with("#!") || self.matcher.contains(relative_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_script() {
+        let scripts = Scripts::new(
+            &["scripts".to_string(), "bin".to_string()],
+            ScriptMode::Exempt,
+        );
+        assert!(scripts.is_script(Path::new("scripts/migrate.py"), "def main(): pass\n"));
+        assert!(scripts.is_script(Path::new("tools/bin/run.py"), ""));
+        assert!(scripts.is_script(Path::new("src/pkg/cli.py"), "#!/usr/bin/env python\n"));
+        assert!(!scripts.is_script(Path::new("src/pkg/scripts.py"), "def main(): pass\n"));
+        assert!(ScriptMode::parse("skip").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Internal consistency checks over a project (`RustLinter.self_check`)
+//!
+//! The linter's verdicts rest on a few invariants that unit tests only exercise on toy
+//! layouts: the tests credited to a function exist, the test file expected for a module
+//! maps back to that module, and the (possibly stored, see `test_cache_store`) test cache
+//! agrees with the test files on disk. Running the checks nightly on a real repository
+//! catches matching regressions those layouts miss. Each broken invariant is reported as a
+//! `Discrepancy`; a consistent project has none.
+
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+use crate::patterns::PATTERNS;
+use crate::test_cache::{TestCache, TestType};
+
+/// A broken invariant, found by `RustLinter.self_check`
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// "matched-test", "expected-path" or "test-cache"
+    #[pyo3(get)]
+    pub check: &'static str,
+    /// File the discrepancy is about, relative to the project root
+    #[pyo3(get)]
+    pub file_path: String,
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl Discrepancy {
+    fn __repr__(&self) -> String {
+        format!(
+            "Discrepancy(check='{}', file_path='{}', detail='{}')",
+            self.check, self.file_path, self.detail
+        )
+    }
+}
+
+impl Discrepancy {
+    fn new(check: &'static str, file_path: &Path, project_root: &Path, detail: String) -> Self {
+        Self {
+            check,
+            file_path: crate::report::relative_path(&file_path.to_string_lossy(), project_root),
+            detail,
+        }
+    }
+}
+
+/// The tests matched to satisfied functions (`include_satisfied` records, linted from
+/// `lint_root`) that are not defined on disk
+pub fn missing_matched_tests(
+    satisfied: &[LintViolation],
+    lint_root: &Path,
+    project_root: &Path,
+) -> Vec<Discrepancy> {
+    // Test functions of each matched test file, None for files missing from disk
+    let mut defined: HashMap<PathBuf, Option<HashSet<String>>> = HashMap::new();
+    let mut discrepancies = Vec::new();
+    for violation in satisfied {
+        for matched in &violation.matched_tests {
+            let Some((test_file, test_name)) = matched.split_once("::") else {
+                continue;
+            };
+            let test_path = lint_root.join(test_file);
+            let names = defined.entry(test_path.clone()).or_insert_with(|| {
+                let content = fs::read_to_string(&test_path).ok()?;
+                Some(
+                    content
+                        .lines()
+                        .filter_map(|line| PATTERNS.any_def.captures(line))
+                        .map(|captures| captures[1].to_string())
+                        .collect(),
+                )
+            });
+            // Tests of test classes are matched as `TestClass::test_method`
+            let function = test_name.rsplit("::").next().unwrap_or(test_name);
+            let detail = match names {
+                None => "is missing from disk",
+                Some(names) if !names.contains(function) => "defines no such test",
+                Some(_) => continue,
+            };
+            discrepancies.push(Discrepancy::new(
+                "matched-test",
+                Path::new(&*violation.file_path),
+                project_root,
+                format!(
+                    "{} of {} satisfies {} but {} {}",
+                    matched,
+                    violation.function_name,
+                    violation.rule_id(),
+                    test_file,
+                    detail
+                ),
+            ));
+        }
+    }
+    discrepancies
+}
+
+/// Whether the unit test file expected for the source file `file` (of module
+/// `module_path`) is one the tests of the module are looked up in and, for modules other
+/// than packages, maps back to the module; `module_of` computes the module of a path
+pub fn expected_path_round_trip(
+    file: &Path,
+    module_path: &str,
+    test_cache: &TestCache,
+    project_root: &Path,
+    module_of: impl Fn(&Path) -> String,
+) -> Option<Discrepancy> {
+    let file_name = file.file_name()?.to_str()?;
+    let expected = test_cache.get_expected_test_file_path(
+        module_path,
+        file_name,
+        &TestType::Unit,
+        project_root,
+    );
+    let detail = if !test_cache.is_mirrored_test_file(&expected, module_path, project_root) {
+        format!(
+            "expected test file {} is not where tests of {} are looked up",
+            expected.display(),
+            module_path
+        )
+    } else {
+        // Packages (`__init__.py`) are tested in `test___init__.py` next to their parent's
+        // tests, which does not name them
+        if file_name == "__init__.py" {
+            return None;
+        }
+        let unit_dir =
+            project_root.join(test_cache.get_expected_test_path("", &TestType::Unit, project_root));
+        let mirrored = expected.strip_prefix(&unit_dir).ok()?;
+        let source_name = mirrored.file_name()?.to_str()?.strip_prefix("test_")?;
+        let round_trip = module_of(&project_root.join(mirrored.with_file_name(source_name)));
+        if round_trip == module_path {
+            return None;
+        }
+        format!(
+            "expected test file {} maps back to module {} instead of {}",
+            expected.display(),
+            round_trip,
+            module_path
+        )
+    };
+    Some(Discrepancy::new(
+        "expected-path",
+        file,
+        project_root,
+        detail,
+    ))
+}
+
+/// How the test cache the linter uses differs from one built fresh from disk
+pub fn test_cache_differences(
+    cached: &TestCache,
+    fresh: &TestCache,
+    project_root: &Path,
+) -> Vec<Discrepancy> {
+    cached
+        .differences(fresh)
+        .into_iter()
+        .map(|(path, detail)| Discrepancy::new("test-cache", &path, project_root, detail))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test/unit/pkg");
+        fs::create_dir_all(&unit).unwrap();
+        fs::write(unit.join("test_mod.py"), "def test_load():\n    pass\n").unwrap();
+        let cache = TestCache::build_from_directories(root, &["test".to_string()], false, None);
+
+        let satisfied = |matched: &str| LintViolation {
+            rule_name: "PL001:require-unit-test".to_string(),
+            file_path: root.join("src/pkg/mod.py").to_string_lossy().into(),
+            function_name: "load".to_string(),
+            satisfied: true,
+            matched_tests: vec![matched.to_string()],
+            ..Default::default()
+        };
+        let found = missing_matched_tests(
+            &[
+                satisfied("test/unit/pkg/test_mod.py::test_load"),
+                satisfied("test/unit/pkg/test_mod.py::TestMod::test_save"),
+                satisfied("test/unit/pkg/test_gone.py::test_load"),
+            ],
+            root,
+            root,
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].file_path, "src/pkg/mod.py");
+        assert!(found[1].detail.ends_with("is missing from disk"));
+
+        let module_of = |path: &Path| {
+            let relative = path.strip_prefix(root).unwrap().with_extension("");
+            relative.to_string_lossy().replace('/', ".")
+        };
+        let source = root.join("pkg/mod.py");
+        assert_eq!(
+            expected_path_round_trip(&source, "pkg.mod", &cache, root, module_of),
+            None
+        );
+        let broken = expected_path_round_trip(&source, "pkg.other", &cache, root, module_of);
+        assert_eq!(broken.unwrap().check, "expected-path");
+
+        fs::write(
+            unit.join("test_mod.py"),
+            "def test_load():\n    pass\n\ndef test_save():\n    pass\n",
+        )
+        .unwrap();
+        let fresh = TestCache::build_from_directories(root, &["test".to_string()], false, None);
+        let differences = test_cache_differences(&cache, &fresh, root);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].file_path, "test/unit/pkg/test_mod.py");
+        assert!(test_cache_differences(&fresh, &fresh, root).is_empty());
+    }
+}
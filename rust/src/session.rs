@@ -0,0 +1,168 @@
+//! Long-lived linting of one project (`open_session`)
+//!
+//! Every `RustLinter` entry point rebuilds what linting needs (the test cache, the moved
+//! aliases, the rules, the owners and call graph) before linting anything, which dominates
+//! the cost of linting one file. Editor plugins and scripts that lint the same project over
+//! and over open a `LintSession` instead: it builds that state once, and `lint_file`,
+//! `lint_changed` and `apply_fixes` all reuse it until the session is closed. The state is
+//! not refreshed, so tests added while the session is open are only seen by a new one.
+//!
+//! A session lints every file with the settings of the project; the settings of
+//! subprojects (see `subprojects`) are not applied.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::file_discovery::TestDirectories;
+use crate::fixes;
+use crate::git;
+use crate::models::LintViolation;
+use crate::stream::BatchState;
+use crate::{Annotations, RustLinter};
+
+struct WarmState {
+    batch: BatchState,
+    annotations: Annotations,
+}
+
+/// A project opened for repeated linting, with its state built once
+#[pyclass]
+pub struct LintSession {
+    linter: RustLinter,
+    project_root: PathBuf,
+    /// None once the session is closed
+    state: Option<WarmState>,
+}
+
+impl LintSession {
+    pub(crate) fn open(linter: RustLinter, project_root: PathBuf) -> Self {
+        let state = WarmState {
+            batch: BatchState::build(&linter, &project_root),
+            annotations: linter.annotations(&project_root),
+        };
+        Self {
+            linter,
+            project_root,
+            state: Some(state),
+        }
+    }
+
+    fn state(&self) -> PyResult<&WarmState> {
+        self.state
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("The lint session is closed"))
+    }
+
+    /// Lint `files` with the warm state, with the post-processing of `RustLinter`
+    fn lint(&self, state: &WarmState, files: &[PathBuf]) -> Vec<LintViolation> {
+        let violations = state.batch.lint(&self.linter, &self.project_root, files);
+        let mut violations = self.linter.add_advisories(violations, &self.project_root);
+        self.linter.annotate(&mut violations, &state.annotations);
+        violations
+    }
+
+    /// The source files changed in git, as `RustLinter.lint_changed_files` finds them
+    fn changed_files(&self, base: Option<&str>) -> Vec<PathBuf> {
+        if !git::is_git_repository(&self.project_root) {
+            return Vec::new();
+        }
+        let changed_files = match base {
+            Some(base) => git::get_changed_files_since(&self.project_root, base),
+            None => git::get_changed_files(&self.project_root),
+        };
+        let test_directories = TestDirectories::new(&self.linter.test_directories);
+        changed_files
+            .into_iter()
+            .filter(|file| {
+                file.strip_prefix(&self.project_root)
+                    .is_ok_and(|relative| !test_directories.contains(relative))
+            })
+            .collect()
+    }
+}
+
+#[pymethods]
+impl LintSession {
+    /// Root of the project the session lints
+    #[getter]
+    fn project_root(&self) -> String {
+        self.project_root.to_string_lossy().into_owned()
+    }
+
+    /// Whether `close` was called; a closed session raises `RuntimeError` when used
+    #[getter]
+    fn closed(&self) -> bool {
+        self.state.is_none()
+    }
+
+    /// Lint one source file of the project
+    fn lint_file(&self, py: Python<'_>, file_path: &str) -> PyResult<Vec<LintViolation>> {
+        let state = self.state()?;
+        let path = PathBuf::from(file_path);
+        // Raise for a missing file, which linting would skip
+        fs::metadata(&path)?;
+        Ok(py.allow_threads(|| self.lint(state, &[path])))
+    }
+
+    /// Lint the source files with uncommitted changes, plus (when `base` is given) every
+    /// one changed on the current branch since it diverged from `base`
+    #[pyo3(signature = (base=None))]
+    fn lint_changed(&self, py: Python<'_>, base: Option<&str>) -> PyResult<Vec<LintViolation>> {
+        let state = self.state()?;
+        Ok(py.allow_threads(|| {
+            let files = self.changed_files(base);
+            if files.is_empty() {
+                return Vec::new();
+            }
+            self.lint(state, &files)
+        }))
+    }
+
+    /// Apply the fixes attached to `violations`, or to those of the whole project when
+    /// none are given, returning a `FixReport` (see `apply_fixes`)
+    #[pyo3(signature = (violations=None, safety="safe"))]
+    fn apply_fixes(
+        &self,
+        py: Python<'_>,
+        violations: Option<Vec<LintViolation>>,
+        safety: &str,
+    ) -> PyResult<fixes::FixReport> {
+        let state = self.state()?;
+        let max_safety = fixes::FixSafety::parse(safety).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Unknown fix safety '{}' (expected 'safe' or 'unsafe')",
+                safety
+            ))
+        })?;
+        let violations = match violations {
+            Some(violations) => violations,
+            None => py.allow_threads(|| {
+                let files = self.linter.find_source_files(&self.project_root);
+                self.lint(state, &files)
+            }),
+        };
+        Ok(fixes::apply_fixes(&violations, max_safety)?)
+    }
+
+    /// Release the warm state; closing a closed session does nothing
+    fn close(&mut self) {
+        self.state = None;
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> bool {
+        self.close();
+        false
+    }
+}
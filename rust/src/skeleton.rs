@@ -0,0 +1,241 @@
+//! PL005: advisory check that expected test files have somewhere to go
+//!
+//! PL001–PL003 point at test files such as `test/unit/pkg/test_mod.py`. When the
+//! directories leading there do not exist yet (or lack the `__init__.py` files the test
+//! tree otherwise uses), a `PL005` warning is reported once per missing directory, with a
+//! `create_test_skeleton` fix that creates them.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::models::LintViolation;
+
+pub const RULE_NAME: &str = "PL005:missing-test-package";
+
+/// Directories and `__init__.py` files needed before a test file can be created
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Skeleton {
+    pub directories: Vec<PathBuf>,
+    pub init_files: Vec<PathBuf>,
+}
+
+impl Skeleton {
+    /// What is missing for `test_file`, relative to `project_root`; whether each test root
+    /// uses `__init__.py` files is looked up once in `init_file_roots`
+    pub fn for_test_file(
+        test_file: &Path,
+        project_root: &Path,
+        init_file_roots: &mut HashMap<PathBuf, bool>,
+    ) -> Self {
+        let mut skeleton = Skeleton::default();
+        let Ok(relative) = test_file.strip_prefix(project_root) else {
+            return skeleton;
+        };
+        let Some(parent) = relative.parent() else {
+            return skeleton;
+        };
+        let Some(test_root) = parent.components().next() else {
+            return skeleton;
+        };
+        let test_root = project_root.join(test_root);
+        let uses_init_files = *init_file_roots
+            .entry(test_root)
+            .or_insert_with_key(|test_root| uses_init_files(test_root));
+
+        let mut dir = project_root.to_path_buf();
+        for component in parent.components() {
+            dir.push(component);
+            if !dir.is_dir() {
+                skeleton.directories.push(dir.clone());
+            }
+            if uses_init_files && !dir.join("__init__.py").exists() {
+                skeleton.init_files.push(dir.join("__init__.py"));
+            }
+        }
+        skeleton
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directories.is_empty() && self.init_files.is_empty()
+    }
+
+    /// The `fix_content` of a `create_test_skeleton` fix: one path per line, directories
+    /// with a trailing `/`
+    pub fn fix_content(&self) -> String {
+        let directories = self
+            .directories
+            .iter()
+            .map(|dir| format!("{}/", dir.display()));
+        let files = self
+            .init_files
+            .iter()
+            .map(|file| file.display().to_string());
+        directories.chain(files).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Whether the test tree rooted at `test_root` uses `__init__.py` files
+fn uses_init_files(test_root: &Path) -> bool {
+    walkdir::WalkDir::new(test_root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .any(|entry| entry.file_name() == "__init__.py")
+}
+
+/// Advisory violations for expected test files whose directories are missing
+pub fn check(violations: &[LintViolation], project_root: &Path) -> Vec<LintViolation> {
+    // One advisory per missing directory, anchored at the first function pointing there
+    let mut by_directory: BTreeMap<PathBuf, &LintViolation> = BTreeMap::new();
+    for violation in violations {
+        let Some(test_file) = violation.expected_test_file.as_deref().map(Path::new) else {
+            continue;
+        };
+        if test_file.exists() {
+            continue;
+        }
+        let Some(directory) = test_file.parent() else {
+            continue;
+        };
+        by_directory
+            .entry(directory.to_path_buf())
+            .and_modify(|first| {
+                if (&violation.file_path, violation.line_number)
+                    < (&first.file_path, first.line_number)
+                {
+                    *first = violation;
+                }
+            })
+            .or_insert(violation);
+    }
+
+    let mut init_file_roots = HashMap::new();
+    by_directory
+        .into_iter()
+        .filter_map(|(directory, first)| {
+            let test_file = Path::new(first.expected_test_file.as_deref()?);
+            let skeleton = Skeleton::for_test_file(test_file, project_root, &mut init_file_roots);
+            if skeleton.is_empty() {
+                return None;
+            }
+            let relative = directory.strip_prefix(project_root).unwrap_or(&directory);
+            Some(LintViolation {
+                rule_name: RULE_NAME.to_string(),
+                file_path: first.file_path.clone(),
+                line_number: first.line_number,
+                function_name: first.function_name.clone(),
+                message: format!(
+                    "[PL005] Test directory '{}' for the expected test file is not set up.\nExpected test file: {}\n\nTip: Apply the create_test_skeleton fix to create it",
+                    relative.display(),
+                    test_file.display()
+                ),
+                severity: "warning".to_string(),
+                fix_type: Some("create_test_skeleton".to_string()),
+                fix_content: Some(skeleton.fix_content()),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Create the directories and empty files listed in a `create_test_skeleton` fix,
+/// returning how many were created
+pub fn create(fix_content: &str) -> io::Result<usize> {
+    let mut created = 0;
+    for entry in fix_content.lines().filter(|line| !line.is_empty()) {
+        match entry.strip_suffix('/') {
+            Some(dir) => {
+                if !Path::new(dir).is_dir() {
+                    fs::create_dir_all(dir)?;
+                    created += 1;
+                }
+            }
+            None => {
+                let file = Path::new(entry);
+                if !file.exists() {
+                    if let Some(parent) = file.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(file, "")?;
+                    created += 1;
+                }
+            }
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("test").join("unit")).unwrap();
+        root
+    }
+
+    fn missing_test(
+        root: &Path,
+        source: &str,
+        line_number: usize,
+        test_file: &str,
+    ) -> LintViolation {
+        LintViolation {
+            rule_name: "PL001:require-unit-test".to_string(),
+            file_path: root.join(source).to_string_lossy().into(),
+            line_number,
+            expected_test_file: Some(root.join(test_file).to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_skeleton_with_init_files() {
+        let tmp = project();
+        let root = tmp.path();
+        fs::write(root.join("test").join("__init__.py"), "").unwrap();
+
+        let test_file = root.join("test/unit/pkg/sub/test_mod.py");
+        let skeleton = Skeleton::for_test_file(&test_file, root, &mut HashMap::new());
+        assert_eq!(
+            skeleton.directories,
+            vec![root.join("test/unit/pkg"), root.join("test/unit/pkg/sub")]
+        );
+        assert_eq!(
+            skeleton.init_files,
+            vec![
+                root.join("test/unit/__init__.py"),
+                root.join("test/unit/pkg/__init__.py"),
+                root.join("test/unit/pkg/sub/__init__.py"),
+            ]
+        );
+
+        assert_eq!(create(&skeleton.fix_content()).unwrap(), 5);
+        assert!(root.join("test/unit/pkg/sub/__init__.py").is_file());
+        assert!(Skeleton::for_test_file(&test_file, root, &mut HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_check_reports_each_missing_directory_once() {
+        let tmp = project();
+        let root = tmp.path();
+        let violations = vec![
+            missing_test(root, "src/pkg/b.py", 3, "test/unit/pkg/test_b.py"),
+            missing_test(root, "src/pkg/a.py", 9, "test/unit/pkg/test_a.py"),
+            missing_test(root, "src/top.py", 1, "test/unit/test_top.py"),
+            LintViolation::default(),
+        ];
+
+        let advisories = check(&violations, root);
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].rule_name, RULE_NAME);
+        assert!(advisories[0].file_path.ends_with("a.py"));
+        // No `__init__.py` anywhere under test/, so only the directory is created
+        assert_eq!(
+            advisories[0].fix_content.as_deref(),
+            Some(format!("{}/", root.join("test/unit/pkg").display()).as_str())
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Source context attached to violations (`context_lines`)
+//!
+//! Report formats and code-review bots showing a violation want the code it is about, and
+//! re-reading the files is wasteful, or impossible once the results are archived. With
+//! `context_lines=N`, each violation carries the source from N lines above the offending
+//! definition (its decorators included) to N lines below the reported line, as `context`,
+//! with the number of its first line as `context_start_line`. Each file is read once;
+//! violations of files that cannot be read, or of lines past their end, get none.
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::models::LintViolation;
+
+/// Attach `lines` lines of context around each of `violations`
+pub fn attach(violations: &mut [LintViolation], lines: usize) {
+    let mut by_file: HashMap<String, Vec<&mut LintViolation>> = HashMap::new();
+    for violation in violations.iter_mut() {
+        by_file
+            .entry(violation.file_path.to_string())
+            .or_default()
+            .push(violation);
+    }
+    for (file, file_violations) in by_file {
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let source_lines: Vec<&str> = content.lines().collect();
+        for violation in file_violations {
+            let line = violation.line_number;
+            if line == 0 || line > source_lines.len() {
+                continue;
+            }
+            let first = violation
+                .decorator_start_line
+                .map_or(line, |start| start.min(line))
+                .saturating_sub(lines)
+                .max(1);
+            let last = (line + lines).min(source_lines.len());
+            violation.context = Some(source_lines[first - 1..last].join("\n"));
+            violation.context_start_line = Some(first);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let file = dir.join("mod.py");
+        fs::write(
+            &file,
+            "import functools\n\n@functools.cache\n@trace\ndef load(path):\n    return path\n",
+        )
+        .unwrap();
+        let violation = |line_number: usize, decorator_start_line: Option<usize>| LintViolation {
+            file_path: file.to_string_lossy().into(),
+            line_number,
+            decorator_start_line,
+            ..Default::default()
+        };
+        let mut violations = vec![
+            violation(5, Some(3)),
+            violation(1, None),
+            violation(40, None),
+            LintViolation {
+                file_path: dir.join("missing.py").to_string_lossy().into(),
+                line_number: 1,
+                ..Default::default()
+            },
+        ];
+        attach(&mut violations, 1);
+
+        assert_eq!(
+            violations[0].context.as_deref(),
+            Some("\n@functools.cache\n@trace\ndef load(path):\n    return path")
+        );
+        assert_eq!(violations[0].context_start_line, Some(2));
+        assert_eq!(violations[1].context.as_deref(), Some("import functools\n"));
+        assert_eq!(violations[1].context_start_line, Some(1));
+        assert!(violations[2].context.is_none() && violations[3].context.is_none());
+    }
+}
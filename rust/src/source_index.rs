@@ -0,0 +1,334 @@
+//! Parsed source files, shared between paths with identical content
+//!
+//! Vendored copies, copied templates and symlinked duplicates are parsed once; rules
+//! still run for each path, since the expected test locations depend on it. Files are
+//! grouped by a hash of their content up front and only read whole when their group is
+//! linted, so a run does not hold every source in memory at once.
+
+use rayon::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::budget::FileBudget;
+use crate::parser::{self, FunctionDef};
+use crate::patterns::Patterns;
+use crate::public_api::{self, PublicApi};
+
+/// A function or method definition in a source file
+#[derive(Debug)]
+pub struct DefinedFunction {
+    pub name: String,
+    /// 1-based line of the `def`
+    pub line_number: usize,
+    pub class_name: Option<String>,
+    pub is_protocol_method: bool,
+    /// Whether the function is a method of an abstract base class (`ABC`, `ABCMeta`)
+    pub is_abstract_class_method: bool,
+    /// Body metrics, parsed on first use
+    metrics: OnceLock<FunctionDef>,
+}
+
+/// One parsed source file
+#[derive(Debug)]
+pub struct SourceFile {
+    pub content: String,
+    /// `__all__` of the module, without re-exports (those depend on the path)
+    pub public_api: PublicApi,
+    pub functions: Vec<DefinedFunction>,
+    /// Time spent reading and parsing the file, counted against its time limit
+    pub spent: Duration,
+    /// Whether parsing stopped at the time limit, leaving `functions` incomplete
+    pub timed_out: bool,
+}
+
+impl SourceFile {
+    pub fn parse(content: String, patterns: &Patterns) -> Self {
+        Self::parse_within(content, patterns, FileBudget::start(None))
+    }
+
+    /// Parse `content`, giving up once `budget` is exceeded
+    pub fn parse_within(content: String, patterns: &Patterns, budget: FileBudget) -> Self {
+        let public_api = if budget.is_exceeded() {
+            PublicApi::default()
+        } else {
+            public_api::parse_module_all_within(&content, &budget)
+        };
+        let functions = scan_functions(&content, patterns, &budget);
+        Self {
+            content,
+            public_api,
+            functions,
+            spent: budget.elapsed(),
+            timed_out: budget.is_exceeded(),
+        }
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.content.lines().collect()
+    }
+}
+
+impl DefinedFunction {
+    /// Body metrics, given the `lines` of the file defining the function
+    pub fn metrics(&self, lines: &[&str]) -> &FunctionDef {
+        self.metrics
+            .get_or_init(|| parser::parse_function(lines, self.line_number))
+    }
+
+    /// Whether the function only declares an interface: an `@overload` signature, or an
+    /// abstract method, whose body is one of the `stub_bodies` forms
+    pub fn is_stub(&self, lines: &[&str], stub_bodies: &[String]) -> bool {
+        let metrics = self.metrics(lines);
+        let declaration = self.is_abstract_class_method
+            || metrics.decorators.iter().any(|decorator| {
+                let name = decorator.rsplit('.').next().unwrap_or(decorator);
+                name == "overload" || name == "abstractmethod"
+            });
+        declaration && metrics.is_stub_body(stub_bodies)
+    }
+}
+
+/// Find every function definition, tracking the enclosing class and whether it is a
+/// `Protocol`; stops early once `budget` is exceeded
+fn scan_functions(content: &str, patterns: &Patterns, budget: &FileBudget) -> Vec<DefinedFunction> {
+    let mut functions = Vec::new();
+    let mut current_class = None;
+    let mut in_protocol = false;
+    let mut in_abstract_class = false;
+
+    for (line_num, line) in content.lines().enumerate() {
+        if budget.is_exceeded() {
+            break;
+        }
+
+        // Check for class definitions
+        if let Some(captures) = patterns.class.captures(line) {
+            let class_name = captures.get(2).unwrap().as_str();
+            current_class = Some(class_name.to_string());
+            in_protocol = line.contains("Protocol");
+            in_abstract_class = patterns.abstract_class.is_match(line);
+            continue;
+        }
+
+        // Check for function definitions
+        let definition = patterns.function_definition(line);
+        if let Some((indent, name)) = definition {
+            let is_method = current_class.is_some() && !indent.is_empty();
+            functions.push(DefinedFunction {
+                name: name.to_string(),
+                line_number: line_num + 1,
+                class_name: if is_method {
+                    current_class.clone()
+                } else {
+                    None
+                },
+                is_protocol_method: in_protocol && is_method,
+                is_abstract_class_method: in_abstract_class && is_method,
+                metrics: OnceLock::new(),
+            });
+        }
+
+        // Reset class context on dedent (non-blank line with no indentation)
+        // But skip if it's a class or function definition
+        if current_class.is_some()
+            && !line.trim().is_empty()
+            && !line.starts_with(' ')
+            && !line.starts_with('\t')
+        {
+            // Don't reset if this line is defining a new class or function at module level
+            if !patterns.class.is_match(line) && definition.is_none() {
+                current_class = None;
+                in_protocol = false;
+                in_abstract_class = false;
+            }
+        }
+    }
+
+    functions
+}
+
+/// The source files of a run, grouped by content
+///
+/// Building the index only hashes the files, reading each in chunks; the sources are read
+/// again and parsed group by group while linting, so only the groups being linted are
+/// held in memory.
+pub struct SourceIndex<'a> {
+    paths: &'a [PathBuf],
+    patterns: &'a Patterns,
+    limit: Option<Duration>,
+    /// Indices into `paths` of the readable files, grouped by content hash, each group in
+    /// the order given
+    groups: Vec<Vec<usize>>,
+}
+
+impl<'a> SourceIndex<'a> {
+    /// Hash `paths`, skipping unreadable files; parsing a file stops once reading and
+    /// parsing it took `limit` (see `SourceFile::timed_out`)
+    pub fn build(paths: &'a [PathBuf], patterns: &'a Patterns, limit: Option<Duration>) -> Self {
+        let hashes: Vec<Option<u64>> = paths.par_iter().map(|path| hash_file(path).ok()).collect();
+        let mut by_hash: HashMap<u64, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for (idx, hash) in hashes.into_iter().enumerate() {
+            let Some(hash) = hash else {
+                continue;
+            };
+            let group = *by_hash.entry(hash).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(idx);
+        }
+        Self {
+            paths,
+            patterns,
+            limit,
+            groups,
+        }
+    }
+
+    /// Apply `map` to every readable path with its parsed source, in the order given
+    ///
+    /// Paths with identical content share one parse; contents are compared in full, so a
+    /// hash collision only costs a second parse.
+    pub fn flat_map<T, I>(&self, map: impl Fn(&Path, &SourceFile) -> I + Sync) -> Vec<T>
+    where
+        T: Send,
+        I: IntoIterator<Item = T>,
+    {
+        let mut mapped: Vec<(usize, Vec<T>)> = self
+            .groups
+            .par_iter()
+            .flat_map_iter(|group| {
+                let mut source: Option<SourceFile> = None;
+                group
+                    .iter()
+                    .filter_map(|&idx| {
+                        let path = self.paths[idx].as_path();
+                        let budget = FileBudget::start(None);
+                        let content = fs::read_to_string(path).ok()?;
+                        if source
+                            .as_ref()
+                            .is_none_or(|source| source.content != content)
+                        {
+                            let budget = FileBudget::resume(self.limit, budget.elapsed());
+                            source = Some(SourceFile::parse_within(content, self.patterns, budget));
+                        }
+                        let mapped = map(path, source.as_ref()?).into_iter().collect();
+                        Some((idx, mapped))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        mapped.sort_unstable_by_key(|(idx, _)| *idx);
+        mapped.into_iter().flat_map(|(_, mapped)| mapped).collect()
+    }
+}
+
+/// Hash of the content of `path`, read in chunks rather than whole
+fn hash_file(path: &Path) -> io::Result<u64> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => return Ok(hasher.finish()),
+            Ok(read) => hasher.write(&buffer[..read]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_scan_functions() {
+        let source = SourceFile::parse(
+            "def top():\n    pass\n\nclass Reader(Protocol):\n    def read(self): ...\n\nclass A:\n    def method(self):\n        pass\nx = 1\ndef after():\n    return x\n"
+                .to_string(),
+            &PATTERNS,
+        );
+        let found: Vec<(&str, Option<&str>, bool)> = source
+            .functions
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.class_name.as_deref(),
+                    f.is_protocol_method,
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("top", None, false),
+                ("read", Some("Reader"), true),
+                ("method", Some("A"), false),
+                ("after", None, false),
+            ]
+        );
+        assert_eq!(source.functions[3].metrics(&source.lines()).statements, 1);
+
+        let content = "def top():\n    pass\n".to_string();
+        let source =
+            SourceFile::parse_within(content, &PATTERNS, FileBudget::start(Some(Duration::ZERO)));
+        assert!(source.timed_out && source.functions.is_empty());
+    }
+
+    #[test]
+    fn test_interface_stubs() {
+        let source = SourceFile::parse(
+            "from abc import ABC, abstractmethod\nfrom typing import overload\n\n@overload\ndef get(x: int) -> int: ...\ndef get(x):\n    return x\n\nclass Base(ABC):\n    def run(self):\n        raise NotImplementedError\n    def helper(self):\n        return 1\n"
+                .to_string(),
+            &PATTERNS,
+        );
+        let lines = source.lines();
+        let forms = vec!["...".to_string(), "raise NotImplementedError".to_string()];
+        let stubs: Vec<bool> = source
+            .functions
+            .iter()
+            .map(|f| f.is_stub(&lines, &forms))
+            .collect();
+        assert_eq!(stubs, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn test_identical_files_are_parsed_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join("a.py"), "def foo(): pass\n").unwrap();
+        fs::write(root.join("vendor").join("a.py"), "def foo(): pass\n").unwrap();
+        fs::write(root.join("b.py"), "def bar(): pass\n").unwrap();
+
+        let paths = vec![
+            root.join("a.py"),
+            root.join("b.py"),
+            root.join("vendor").join("a.py"),
+            root.join("missing.py"),
+        ];
+        let index = SourceIndex::build(&paths, &PATTERNS, None);
+        assert_eq!(index.groups, vec![vec![0, 2], vec![1]]);
+
+        let found = index
+            .flat_map(|path, source| Some((path.to_path_buf(), source.functions[0].name.clone())));
+        assert_eq!(
+            found,
+            vec![
+                (paths[0].clone(), "foo".to_string()),
+                (paths[1].clone(), "bar".to_string()),
+                (paths[2].clone(), "foo".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! SQLite results store (`RustLinter.lint_project_to_store`, `query_store`)
+//!
+//! Each run appends a row to `runs` and its violations to `violations`, whose columns are
+//! `run_id` followed by the export schema (`report::EXPORT_COLUMNS`), indexed by file, rule
+//! and function. The database can be queried with any SQLite client, or through
+//! `query_store`, which takes a `WHERE` expression over those columns and reads the file
+//! read-only:
+//!
+//! ```sql
+//! SELECT rule_id, COUNT(*) FROM violations WHERE run_id = 3 GROUP BY rule_id;
+//! ```
+
+use pyo3::prelude::*;
+use std::path::Path;
+
+use crate::models::LintViolation;
+
+/// One run recorded in a results store
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct StoredRun {
+    #[pyo3(get)]
+    pub run_id: i64,
+    /// Project root the run linted
+    #[pyo3(get)]
+    pub project: String,
+    /// Unix timestamp (seconds) of the run
+    #[pyo3(get)]
+    pub created_at: i64,
+    #[pyo3(get)]
+    pub violation_count: usize,
+}
+
+#[pymethods]
+impl StoredRun {
+    fn __repr__(&self) -> String {
+        format!(
+            "StoredRun({} {} violations={})",
+            self.run_id, self.project, self.violation_count
+        )
+    }
+}
+
+#[cfg(feature = "store")]
+mod sqlite {
+    use rusqlite::types::Value;
+    use rusqlite::{params_from_iter, Connection, OpenFlags};
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::StoredRun;
+    use crate::models::LintViolation;
+    use crate::report::{export_row, EXPORT_COLUMNS};
+
+    /// Columns of `violations` indexed for lookups
+    const INDEXED: &[&str] = &["file", "rule_id", "function"];
+
+    fn store_error(database: &Path, error: rusqlite::Error) -> String {
+        format!("results store {}: {}", database.display(), error)
+    }
+
+    fn column_definition(column: &str) -> String {
+        match column {
+            "line" | "fix_available" | "context_start_line" => format!("{} INTEGER", column),
+            _ => format!("{} TEXT", column),
+        }
+    }
+
+    fn create_schema(connection: &Connection) -> rusqlite::Result<()> {
+        let columns: Vec<String> = EXPORT_COLUMNS
+            .iter()
+            .map(|column| column_definition(column))
+            .collect();
+        let mut schema = format!(
+            "CREATE TABLE IF NOT EXISTS runs (id INTEGER PRIMARY KEY, project TEXT NOT NULL, created_at INTEGER NOT NULL, violation_count INTEGER NOT NULL);\n\
+             CREATE TABLE IF NOT EXISTS violations (run_id INTEGER NOT NULL REFERENCES runs(id), {});\n\
+             CREATE INDEX IF NOT EXISTS violations_run_id ON violations (run_id);\n",
+            columns.join(", ")
+        );
+        for column in INDEXED {
+            schema.push_str(&format!(
+                "CREATE INDEX IF NOT EXISTS violations_{0} ON violations ({0});\n",
+                column
+            ));
+        }
+        connection.execute_batch(&schema)?;
+
+        // Stores written before the export schema gained columns get them, empty for the
+        // runs already recorded
+        let existing: Vec<String> = connection
+            .prepare("SELECT name FROM pragma_table_info('violations')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for column in EXPORT_COLUMNS {
+            if !existing.iter().any(|name| name == column) {
+                connection.execute_batch(&format!(
+                    "ALTER TABLE violations ADD COLUMN {};",
+                    column_definition(column)
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write(
+        database: &Path,
+        project_root: &Path,
+        violations: &[LintViolation],
+    ) -> Result<i64, String> {
+        let error = |e| store_error(database, e);
+        let mut connection = Connection::open(database).map_err(error)?;
+        create_schema(&connection).map_err(error)?;
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs()) as i64;
+
+        let transaction = connection.transaction().map_err(error)?;
+        transaction
+            .execute(
+                "INSERT INTO runs (project, created_at, violation_count) VALUES (?1, ?2, ?3)",
+                (
+                    project_root.to_string_lossy(),
+                    created_at,
+                    violations.len() as i64,
+                ),
+            )
+            .map_err(error)?;
+        let run_id = transaction.last_insert_rowid();
+        {
+            let placeholders = vec!["?"; EXPORT_COLUMNS.len() + 1].join(", ");
+            let mut insert = transaction
+                .prepare(&format!(
+                    "INSERT INTO violations (run_id, {}) VALUES ({})",
+                    EXPORT_COLUMNS.join(", "),
+                    placeholders
+                ))
+                .map_err(error)?;
+            for violation in violations {
+                let values =
+                    export_row(violation, project_root)
+                        .into_iter()
+                        .map(|value| match value {
+                            serde_json::Value::Null => Value::Null,
+                            serde_json::Value::Number(number) => {
+                                Value::Integer(number.as_i64().unwrap_or_default())
+                            }
+                            serde_json::Value::Bool(flag) => Value::Integer(flag.into()),
+                            serde_json::Value::String(text) => Value::Text(text),
+                            other => Value::Text(other.to_string()),
+                        });
+                insert
+                    .execute(params_from_iter(
+                        std::iter::once(Value::Integer(run_id)).chain(values),
+                    ))
+                    .map_err(error)?;
+            }
+        }
+        transaction.commit().map_err(error)?;
+        Ok(run_id)
+    }
+
+    fn open_read_only(database: &Path) -> Result<Connection, String> {
+        Connection::open_with_flags(database, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| store_error(database, e))
+    }
+
+    pub fn query(
+        database: &Path,
+        sql_filter: Option<&str>,
+        run_id: Option<i64>,
+    ) -> Result<Vec<LintViolation>, String> {
+        let error = |e| store_error(database, e);
+        let connection = open_read_only(database)?;
+        let sql = format!(
+            "SELECT runs.project, {} FROM violations JOIN runs ON runs.id = violations.run_id \
+             WHERE violations.run_id = COALESCE(?1, (SELECT MAX(id) FROM runs)) AND ({}) \
+             ORDER BY violations.rowid",
+            EXPORT_COLUMNS.join(", "),
+            sql_filter.unwrap_or("1")
+        );
+        let mut statement = connection.prepare(&sql).map_err(error)?;
+        let rows = statement
+            .query_map([run_id], |row| {
+                let project: String = row.get("project")?;
+                let absolute =
+                    |path: String| Path::new(&project).join(path).to_string_lossy().to_string();
+                Ok(LintViolation {
+                    module_path: row.get("module")?,
+                    file_path: absolute(row.get("file")?).into(),
+                    line_number: row.get::<_, i64>("line")? as usize,
+                    rule_name: row.get("rule")?,
+                    severity: row.get("severity")?,
+                    function_name: row
+                        .get::<_, Option<String>>("function")?
+                        .unwrap_or_default(),
+                    class_name: row.get("class")?,
+                    expected_test_file: row
+                        .get::<_, Option<String>>("expected_test_file")?
+                        .map(absolute),
+                    expected_test_name: row.get("expected_test_name")?,
+                    message: row.get::<_, Option<String>>("message")?.unwrap_or_default(),
+                    docs_url: row.get("docs_url")?,
+                    context_start_line: row
+                        .get::<_, Option<i64>>("context_start_line")?
+                        .map(|line| line as usize),
+                    context: row.get("context")?,
+                    ..Default::default()
+                })
+            })
+            .map_err(error)?;
+        rows.collect::<Result<_, _>>().map_err(error)
+    }
+
+    pub fn runs(database: &Path) -> Result<Vec<StoredRun>, String> {
+        let error = |e| store_error(database, e);
+        let connection = open_read_only(database)?;
+        let mut statement = connection
+            .prepare("SELECT id, project, created_at, violation_count FROM runs ORDER BY id")
+            .map_err(error)?;
+        let runs = statement
+            .query_map([], |row| {
+                Ok(StoredRun {
+                    run_id: row.get(0)?,
+                    project: row.get(1)?,
+                    created_at: row.get(2)?,
+                    violation_count: row.get::<_, i64>(3)? as usize,
+                })
+            })
+            .map_err(error)?;
+        runs.collect::<Result<_, _>>().map_err(error)
+    }
+}
+
+#[cfg(not(feature = "store"))]
+mod sqlite {
+    use std::path::Path;
+
+    use super::StoredRun;
+    use crate::models::LintViolation;
+
+    const UNAVAILABLE: &str = "SQLite support is not compiled in (enable the `store` feature)";
+
+    pub fn write(_: &Path, _: &Path, _: &[LintViolation]) -> Result<i64, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn query(_: &Path, _: Option<&str>, _: Option<i64>) -> Result<Vec<LintViolation>, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn runs(_: &Path) -> Result<Vec<StoredRun>, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+}
+
+/// Record the violations of a run of `project_root` in `database`, creating it if needed;
+/// returns the id of the new run
+pub fn write(
+    database: &Path,
+    project_root: &Path,
+    violations: &[LintViolation],
+) -> Result<i64, String> {
+    sqlite::write(database, project_root, violations)
+}
+
+/// Violations of run `run_id` (the latest run when None) matching `sql_filter`, a SQL
+/// `WHERE` expression over the violation columns, in the order they were written
+pub fn query(
+    database: &Path,
+    sql_filter: Option<&str>,
+    run_id: Option<i64>,
+) -> Result<Vec<LintViolation>, String> {
+    sqlite::query(database, sql_filter, run_id)
+}
+
+/// The runs recorded in `database`, oldest first
+pub fn runs(database: &Path) -> Result<Vec<StoredRun>, String> {
+    sqlite::runs(database)
+}
+
+#[cfg(all(test, feature = "store"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_query() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let database = dir.join("results.db");
+        let root = Path::new("/repo");
+        let violation = |rule: &str, file: &str, function: &str| LintViolation {
+            rule_name: rule.to_string(),
+            file_path: format!("/repo/{}", file).into(),
+            line_number: 3,
+            function_name: function.to_string(),
+            module_path: Some("pkg.m".to_string()),
+            severity: "error".to_string(),
+            message: "missing test".to_string(),
+            ..Default::default()
+        };
+
+        let first = write(
+            &database,
+            root,
+            &[violation("PL001:require-unit-test", "src/pkg/m.py", "load")],
+        )
+        .unwrap();
+        let second = write(
+            &database,
+            root,
+            &[
+                violation("PL001:require-unit-test", "src/pkg/m.py", "load"),
+                violation("PL002:require-integration-test", "src/pkg/m.py", "save"),
+            ],
+        )
+        .unwrap();
+        assert!(second > first);
+
+        let latest = query(&database, None, None).unwrap();
+        assert_eq!(latest.len(), 2);
+        assert_eq!(&*latest[1].file_path, "/repo/src/pkg/m.py");
+        assert_eq!(latest[1].module_path.as_deref(), Some("pkg.m"));
+        let filtered = query(&database, Some("rule_id = 'PL002'"), None).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].function_name, "save");
+        assert_eq!(query(&database, None, Some(first)).unwrap().len(), 1);
+        assert!(query(&database, Some("no_such_column = 1"), None).is_err());
+
+        let runs = runs(&database).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].violation_count, 2);
+
+        // A store from before `fix_available` and `docs_url` gains the columns
+        let legacy = dir.join("legacy.db");
+        let connection = rusqlite::Connection::open(&legacy).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE violations (run_id INTEGER NOT NULL, module TEXT, file TEXT, \
+                 line INTEGER, rule_id TEXT, rule TEXT, severity TEXT, function TEXT, \
+                 class TEXT, expected_test_file TEXT, expected_test_name TEXT, message TEXT);",
+            )
+            .unwrap();
+        let documented = LintViolation {
+            docs_url: Some("https://wiki.example.com/PL001".to_string()),
+            ..violation("PL001:require-unit-test", "src/pkg/m.py", "load")
+        };
+        write(&legacy, root, &[documented]).unwrap();
+        let stored = query(&legacy, Some("fix_available = 0"), None).unwrap();
+        assert_eq!(
+            stored[0].docs_url.as_deref(),
+            Some("https://wiki.example.com/PL001")
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Chunked retrieval of a project's violations (`RustLinter.lint_project_iter`)
+//!
+//! `lint_project` builds one list of every violation, which for very large projects spikes
+//! memory on both sides of the FFI boundary. A `ViolationStream` lints the source files a
+//! chunk at a time instead, yielding the violations of up to `chunk_size` files per step,
+//! so consumers can write them to disk or a database as they come. Each chunk gets the same
+//! post-processing as `lint_project` (PL005 advisories, once per directory; merging;
+//! fixes; owners; fan-in, which orders each chunk rather than the whole run), and the
+//! warnings about the configuration come last.
+
+use pyo3::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::models::LintViolation;
+use crate::moved_aliases::MovedAliases;
+use crate::rules::LintRule;
+use crate::source_index::SourceIndex;
+use crate::test_cache::TestCache;
+use crate::{Annotations, RustLinter};
+
+/// Source files linted with one linter, from one root (the project or a subproject)
+struct Batch {
+    linter: RustLinter,
+    root: PathBuf,
+    files: VecDeque<PathBuf>,
+    /// Built when the first chunk of the batch is linted
+    state: Option<BatchState>,
+}
+
+/// What linting a file of a project needs, built once and reused for many files (also
+/// the warm state of a `LintSession`)
+pub(crate) struct BatchState {
+    test_cache: Arc<TestCache>,
+    moved_aliases: MovedAliases,
+    source_roots: Vec<PathBuf>,
+    rules: Vec<Box<dyn LintRule + Send + Sync>>,
+}
+
+impl BatchState {
+    pub(crate) fn build(linter: &RustLinter, root: &Path) -> Self {
+        let source_roots = linter.source_roots(root);
+        Self {
+            test_cache: linter.test_cache(root),
+            moved_aliases: linter.moved_aliases(root, &source_roots),
+            source_roots,
+            rules: linter.rules(root),
+        }
+    }
+
+    /// The per-function violations of `files`, linted in parallel
+    pub(crate) fn lint(
+        &self,
+        linter: &RustLinter,
+        root: &Path,
+        files: &[PathBuf],
+    ) -> Vec<LintViolation> {
+        let index = SourceIndex::build(files, &linter.patterns, linter.file_time_limit);
+        index.flat_map(|file, source| {
+            linter.lint_file_internal_with_cache(
+                file,
+                source,
+                &self.rules,
+                &self.test_cache,
+                &self.moved_aliases,
+                root,
+                &self.source_roots,
+            )
+        })
+    }
+}
+
+/// Iterator over the violations of a project, as lists of at most `chunk_size` files' worth
+#[pyclass]
+pub struct ViolationStream {
+    /// The linter the run was started with, which post-processes every chunk
+    linter: RustLinter,
+    project_root: PathBuf,
+    batches: VecDeque<Batch>,
+    chunk_size: usize,
+    annotations: Annotations,
+    /// `fix_content` of the PL005 advisories already yielded, one per directory
+    skeletons: HashSet<String>,
+    finished: bool,
+}
+
+impl ViolationStream {
+    pub(crate) fn new(
+        linter: &RustLinter,
+        project_root: PathBuf,
+        batches: Vec<(RustLinter, PathBuf, Vec<PathBuf>)>,
+        chunk_size: usize,
+    ) -> Self {
+        Self {
+            annotations: linter.annotations(&project_root),
+            linter: linter.clone(),
+            project_root,
+            batches: batches
+                .into_iter()
+                .map(|(linter, root, files)| Batch {
+                    linter,
+                    root,
+                    files: files.into(),
+                    state: None,
+                })
+                .collect(),
+            chunk_size: chunk_size.max(1),
+            skeletons: HashSet::new(),
+            finished: false,
+        }
+    }
+
+    /// The next non-empty chunk of violations, or None when the run is over
+    pub(crate) fn next_chunk(&mut self) -> Option<Vec<LintViolation>> {
+        while let Some(batch) = self.batches.front_mut() {
+            if batch.files.is_empty() {
+                self.batches.pop_front();
+                continue;
+            }
+            let take = self.chunk_size.min(batch.files.len());
+            let files: Vec<PathBuf> = batch.files.drain(..take).collect();
+            let Batch {
+                linter,
+                root,
+                state,
+                ..
+            } = batch;
+            let state = state.get_or_insert_with(|| BatchState::build(linter, root));
+            let violations = state.lint(linter, root, &files);
+
+            let mut violations = self
+                .linter
+                .check_missing_tests(violations, &self.project_root);
+            violations.retain(|violation| {
+                violation.rule_name != crate::skeleton::RULE_NAME
+                    || violation
+                        .fix_content
+                        .as_ref()
+                        .is_none_or(|content| self.skeletons.insert(content.clone()))
+            });
+            if !violations.is_empty() {
+                self.linter.annotate(&mut violations, &self.annotations);
+                return Some(violations);
+            }
+        }
+
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        let mut warnings = self.linter.config_warnings(&self.project_root);
+        self.linter.annotate(&mut warnings, &self.annotations);
+        (!warnings.is_empty()).then_some(warnings)
+    }
+}
+
+#[pymethods]
+impl ViolationStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// The violations of the next files linted; the GIL is released while linting
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> Option<Vec<LintViolation>> {
+        let stream: &mut Self = &mut slf;
+        py.allow_threads(|| stream.next_chunk())
+    }
+}
@@ -0,0 +1,27 @@
+//! Check that the shipped type stub matches the one generated by `build.rs`
+
+const GENERATED: &str = include_str!(concat!(env!("OUT_DIR"), "/proboscis_linter_rust.pyi"));
+
+#[test]
+#[cfg_attr(
+    not(all(
+        feature = "gitoxide",
+        feature = "webhook",
+        feature = "asyncio",
+        feature = "store"
+    )),
+    ignore = "the shipped stub is generated with the default features"
+)]
+fn test_shipped_stub_is_up_to_date() {
+    let shipped = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../src/proboscis_linter/proboscis_linter_rust.pyi");
+    if std::env::var_os("UPDATE_STUBS").is_some() {
+        std::fs::write(&shipped, GENERATED).unwrap();
+    }
+    let current = std::fs::read_to_string(&shipped).unwrap_or_default();
+    assert!(
+        current == GENERATED,
+        "{} is out of date; run `UPDATE_STUBS=1 cargo test` to regenerate it",
+        shipped.display()
+    );
+}
@@ -0,0 +1,207 @@
+//! Subprojects with their own `[tool.proboscis]` section
+//!
+//! In a repository where `services/api/pyproject.toml` configures the linter differently
+//! from the root (other test directories, say), each file is linted with the settings of
+//! the nearest `pyproject.toml` above it that has a `[tool.proboscis]` section. Settings a
+//! subproject leaves out keep the values of the run, and its paths (test directories,
+//! source roots) are relative to the subproject directory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::file_discovery::is_virtualenv;
+
+/// A directory below the project root with its own `[tool.proboscis]` settings
+#[derive(Debug, Clone)]
+pub struct Subproject {
+    pub root: PathBuf,
+    /// The file the settings come from: the `pyproject.toml` of the subproject, or the
+    /// configuration file of the project itself (see `config`)
+    pub file: PathBuf,
+    /// The `[tool.proboscis]` table, parsed once per run
+    pub settings: toml::Table,
+}
+
+/// Every subproject below `project_root` (the root's own `pyproject.toml` excluded)
+pub fn discover(project_root: &Path) -> Vec<Subproject> {
+    WalkDir::new(project_root)
+        .min_depth(2) // depth 1 holds the root's own pyproject.toml
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !(entry.file_type().is_dir()
+                && (name.starts_with('.')
+                    || matches!(name.as_ref(), "__pycache__" | "node_modules")
+                    || is_virtualenv(entry.path())))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == "pyproject.toml")
+        .filter_map(|entry| {
+            let root = entry.path().parent()?.to_path_buf();
+            let pyproject: toml::Table = fs::read_to_string(entry.path()).ok()?.parse().ok()?;
+            let settings = pyproject.get("tool")?.get("proboscis")?.as_table()?.clone();
+            Some(Subproject {
+                root,
+                file: entry.path().to_path_buf(),
+                settings,
+            })
+        })
+        .collect()
+}
+
+/// The subproject whose settings apply to `file`: the one nearest above it, if any
+pub fn nearest<'a>(subprojects: &'a [Subproject], file: &Path) -> Option<&'a Subproject> {
+    subprojects
+        .iter()
+        .filter(|subproject| file.starts_with(&subproject.root))
+        .max_by_key(|subproject| subproject.root.components().count())
+}
+
+/// Whether `file` is linted with the settings of `subproject` (None: the top-level project)
+pub fn owns(subprojects: &[Subproject], subproject: Option<&Subproject>, file: &Path) -> bool {
+    nearest(subprojects, file).map(|s| &s.root) == subproject.map(|s| &s.root)
+}
+
+impl Subproject {
+    fn invalid(&self, key: &str, expected: &str) -> String {
+        // A proboscis.toml holds the settings at its top level
+        let section = if self.file.ends_with(crate::config::PYPROJECT) {
+            "tool.proboscis."
+        } else {
+            ""
+        };
+        format!(
+            "{}: {}{} must be {}",
+            self.file.display(),
+            section,
+            key,
+            expected
+        )
+    }
+
+    /// A list of strings setting, if present
+    pub fn strings(&self, key: &str) -> Result<Option<Vec<String>>, String> {
+        let Some(value) = self.settings.get(key) else {
+            return Ok(None);
+        };
+        value
+            .as_array()
+            .and_then(|items| {
+                items
+                    .iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .map(Some)
+            .ok_or_else(|| self.invalid(key, "a list of strings"))
+    }
+
+    /// A string setting, if present
+    pub fn string(&self, key: &str) -> Result<Option<&str>, String> {
+        match self.settings.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_str()
+                .map(Some)
+                .ok_or_else(|| self.invalid(key, "a string")),
+        }
+    }
+
+    /// A boolean setting, if present
+    pub fn bool(&self, key: &str) -> Result<Option<bool>, String> {
+        match self.settings.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_bool()
+                .map(Some)
+                .ok_or_else(|| self.invalid(key, "true or false")),
+        }
+    }
+
+    /// The `rule_versions` table (rule id to version), if present
+    pub fn rule_versions(&self) -> Result<Option<HashMap<String, u32>>, String> {
+        let Some(value) = self.settings.get("rule_versions") else {
+            return Ok(None);
+        };
+        value
+            .as_table()
+            .and_then(|versions| {
+                versions
+                    .iter()
+                    .map(|(rule_id, version)| {
+                        let version = u32::try_from(version.as_integer()?).ok()?;
+                        Some((rule_id.clone(), version))
+                    })
+                    .collect::<Option<HashMap<_, _>>>()
+            })
+            .map(Some)
+            .ok_or_else(|| self.invalid("rule_versions", "a table of rule ids to versions"))
+    }
+
+    /// Rules switched on (`true`, or `enabled = true`) and off under
+    /// `[tool.proboscis.rules]`
+    pub fn rule_switches(&self) -> HashMap<String, bool> {
+        let mut switches = HashMap::new();
+        let Some(rules) = self.settings.get("rules").and_then(|r| r.as_table()) else {
+            return switches;
+        };
+        for (rule_id, value) in rules {
+            let is_enabled = match value {
+                toml::Value::Boolean(flag) => *flag,
+                toml::Value::Table(rule) => rule
+                    .get("enabled")
+                    .and_then(|e| e.as_bool())
+                    .unwrap_or(true),
+                _ => continue,
+            };
+            switches.insert(rule_id.clone(), is_enabled);
+        }
+        switches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_nearest_subproject() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let api = root.join("services/api");
+        let worker = root.join("services/worker");
+        fs::create_dir_all(api.join("src")).unwrap();
+        fs::create_dir_all(&worker).unwrap();
+        fs::write(root.join("pyproject.toml"), "[tool.proboscis]\n").unwrap();
+        fs::write(
+            api.join("pyproject.toml"),
+            "[tool.proboscis]\ntest_directories = [\"spec\"]\nrule_versions = { PL001 = 2 }\n\n[tool.proboscis.rules]\nPL002 = false\n",
+        )
+        .unwrap();
+        // A pyproject.toml without a [tool.proboscis] section is not a subproject
+        fs::write(
+            worker.join("pyproject.toml"),
+            "[project]\nname = \"worker\"\n",
+        )
+        .unwrap();
+
+        let subprojects = discover(root);
+        assert_eq!(subprojects.len(), 1);
+        let api_project = nearest(&subprojects, &api.join("src/app.py")).unwrap();
+        assert_eq!(api_project.root, api);
+        assert_eq!(
+            api_project.strings("test_directories").unwrap(),
+            Some(vec!["spec".to_string()])
+        );
+        assert_eq!(api_project.rule_switches().get("PL002"), Some(&false));
+        assert_eq!(
+            api_project.rule_versions().unwrap(),
+            Some(HashMap::from([("PL001".to_string(), 2)]))
+        );
+        assert!(api_project.bool("test_directories").is_err());
+        assert!(owns(&subprojects, None, &worker.join("main.py")));
+        assert!(!owns(&subprojects, None, &api.join("src/app.py")));
+    }
+}
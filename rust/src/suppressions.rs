@@ -0,0 +1,107 @@
+//! Inventory of the `noqa` comments of a project (`RustLinter.suppression_report`)
+//!
+//! Lists every `noqa` comment naming rules, in source and test files, with its reason and
+//! expiry, so reviewers can audit what is hidden and why.
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::expiry::{self, Date};
+use crate::noqa::parse_noqa_with;
+use crate::patterns::Patterns;
+
+/// One `noqa` comment
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Suppression {
+    #[pyo3(get)]
+    pub file_path: String,
+    #[pyo3(get)]
+    pub line_number: usize,
+    /// Rule ids the comment names, sorted
+    #[pyo3(get)]
+    pub rules: Vec<String>,
+    /// The text after `--`
+    #[pyo3(get)]
+    pub reason: Option<String>,
+    /// The text after `until=`
+    #[pyo3(get)]
+    pub until: Option<String>,
+    /// Whether the expiry date has passed, so the comment no longer suppresses
+    #[pyo3(get)]
+    pub expired: bool,
+}
+
+#[pymethods]
+impl Suppression {
+    fn __repr__(&self) -> String {
+        format!(
+            "Suppression({}:{} {})",
+            self.file_path,
+            self.line_number,
+            self.rules.join(", ")
+        )
+    }
+}
+
+/// The `noqa` comments of `files` that name rules, by file and line
+pub fn scan(patterns: &Patterns, files: &[PathBuf], today: Date) -> Vec<Suppression> {
+    let mut suppressions: Vec<Suppression> = files
+        .par_iter()
+        .filter_map(|file| Some((file, fs::read_to_string(file).ok()?)))
+        .flat_map_iter(|(file, content)| {
+            content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains("noqa"))
+                .filter_map(|(idx, line)| {
+                    let noqa = parse_noqa_with(patterns, line)?;
+                    if noqa.rules.is_empty() {
+                        return None;
+                    }
+                    let mut rules: Vec<String> = noqa.rules.into_iter().collect();
+                    rules.sort();
+                    Some(Suppression {
+                        file_path: file.to_string_lossy().to_string(),
+                        line_number: idx + 1,
+                        rules,
+                        reason: noqa.reason.map(str::to_string),
+                        until: noqa.until.map(str::to_string),
+                        expired: !expiry::is_active(noqa.until, today),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    suppressions.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+    suppressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::PATTERNS;
+
+    #[test]
+    fn test_scan() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let file = dir.join("m.py");
+        fs::write(
+            &file,
+            "import os  # noqa\n\ndef load():  # noqa: PL002, PL001 -- generated\n    pass\n\ndef save():  # noqa: PL001 until=2025-01-01\n    pass\n",
+        )
+        .unwrap();
+
+        let suppressions = scan(&PATTERNS, &[file], Date::parse("2025-06-30").unwrap());
+        assert_eq!(suppressions.len(), 2);
+        assert_eq!(suppressions[0].line_number, 3);
+        assert_eq!(suppressions[0].rules, ["PL001", "PL002"]);
+        assert_eq!(suppressions[0].reason.as_deref(), Some("generated"));
+        assert!(!suppressions[0].expired);
+        assert_eq!(suppressions[1].until.as_deref(), Some("2025-01-01"));
+        assert!(suppressions[1].expired && suppressions[1].reason.is_none());
+    }
+}
@@ -0,0 +1,134 @@
+//! Test types required of modules by the size of their public API (`surface_test_types`)
+//!
+//! A utility module exposing one trivial function does not need unit, integration and
+//! end-to-end tests alike. The setting maps a number of public symbols (top-level functions
+//! and classes, per `__all__` or the underscore convention) to the test types the
+//! functions of modules with at least that many need. Each module takes the largest
+//! threshold it reaches, and PL001–PL003 only check the types listed there; modules below
+//! every threshold need none:
+//!
+//! ```toml
+//! [tool.proboscis.surface_test_types]
+//! 1 = ["unit"]
+//! 5 = ["unit", "integration"]
+//! 20 = ["unit", "integration", "e2e"]
+//! ```
+//!
+//! A function matching a `decorator_test_types` pattern needs the test types of its
+//! decorators instead, whatever the size of its module.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::decorator_policies::rule_of_test_type;
+use crate::patterns::PATTERNS;
+use crate::public_api::PublicApi;
+use crate::source_index::SourceFile;
+
+#[derive(Debug, Clone, Default)]
+pub struct SurfacePolicies {
+    /// Ids of the missing-test rules that apply from each number of public symbols on, by
+    /// increasing number
+    thresholds: Vec<(usize, HashSet<&'static str>)>,
+}
+
+impl SurfacePolicies {
+    pub fn new(test_types: HashMap<usize, Vec<String>>) -> Result<Self, String> {
+        let mut thresholds = test_types
+            .into_iter()
+            .map(|(symbols, types)| {
+                let rules = types
+                    .iter()
+                    .map(|name| {
+                        rule_of_test_type(name).ok_or_else(|| {
+                            format!(
+                                "invalid test type '{}' for {} public symbols, expected unit, integration or e2e",
+                                name, symbols
+                            )
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((symbols, rules))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        thresholds.sort_by_key(|(symbols, _)| *symbols);
+        Ok(Self { thresholds })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.thresholds.is_empty()
+    }
+
+    /// Ids of the missing-test rules that apply to the functions of a module with
+    /// `symbols` public symbols, or None when no threshold is configured and every rule
+    /// applies
+    pub fn required_rules(&self, symbols: usize) -> Option<HashSet<&'static str>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(
+            self.thresholds
+                .iter()
+                .rev()
+                .find(|(threshold, _)| *threshold <= symbols)
+                .map_or_else(HashSet::new, |(_, rules)| rules.clone()),
+        )
+    }
+}
+
+/// Number of public symbols of `source`: its top-level functions and classes that
+/// `public_api` makes public
+pub fn public_symbols(source: &SourceFile, lines: &[&str], public_api: &PublicApi) -> usize {
+    let functions = source
+        .functions
+        .iter()
+        .filter(|function| {
+            function.class_name.is_none()
+                && !lines[function.line_number - 1].starts_with([' ', '\t'])
+        })
+        .map(|function| function.name.as_str());
+    let classes = lines.iter().filter_map(|line| {
+        let captures = PATTERNS.class.captures(line)?;
+        let name = captures.get(2)?.as_str();
+        captures[1].is_empty().then_some(name)
+    });
+    functions
+        .chain(classes)
+        .filter(|name| public_api.is_public(name))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_rules() {
+        let policies = SurfacePolicies::new(HashMap::from([
+            (1, vec!["unit".to_string()]),
+            (5, vec!["unit".to_string(), "integration".to_string()]),
+        ]))
+        .unwrap();
+        let required = |symbols| {
+            policies.required_rules(symbols).map(|rules| {
+                let mut rules: Vec<&str> = rules.into_iter().collect();
+                rules.sort();
+                rules
+            })
+        };
+        assert_eq!(required(0), Some(vec![]));
+        assert_eq!(required(4), Some(vec!["PL001"]));
+        assert_eq!(required(12), Some(vec!["PL001", "PL002"]));
+        assert_eq!(SurfacePolicies::default().required_rules(3), None);
+        assert!(
+            SurfacePolicies::new(HashMap::from([(3, vec!["smoke".to_string()])]))
+                .unwrap_err()
+                .contains("'smoke'")
+        );
+
+        let content = "def load():\n    def inner():\n        pass\n\ndef _helper():\n    pass\n\nclass Store:\n    def get(self):\n        pass\n\nclass Empty:\n    pass\n\nclass _Private:\n    pass\n";
+        let source = SourceFile::parse(content.to_string(), &PATTERNS);
+        let lines = source.lines();
+        assert_eq!(public_symbols(&source, &lines, &PublicApi::default()), 3);
+    }
+}
@@ -1,11 +1,16 @@
 use rayon::prelude::*;
-use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use walkdir::WalkDir;
 
+use crate::aliases::Aliases;
+use crate::fixtures::{self, Fixture};
+use crate::parser;
+use crate::patterns::PATTERNS;
+use crate::test_cache_store::{self, Entries, Extracted, Stamp};
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TestType {
     Unit,
@@ -15,6 +20,16 @@ pub enum TestType {
 }
 
 impl TestType {
+    /// Lowercase name, as used for markers and test directories
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestType::Unit => "unit",
+            TestType::Integration => "integration",
+            TestType::E2E => "e2e",
+            TestType::General => "general",
+        }
+    }
+
     pub fn from_path(path: &Path) -> Self {
         let path_str = path.to_string_lossy();
         if path_str.contains("/e2e/") || path_str.contains("\\e2e\\") {
@@ -27,44 +42,50 @@ impl TestType {
             TestType::General
         }
     }
-
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            TestType::Unit => "unit",
-            TestType::Integration => "integration",
-            TestType::E2E => "e2e",
-            TestType::General => "general",
-        }
-    }
 }
 
 /// Information about a test file
 #[derive(Debug)]
 struct TestFileInfo {
-    path: PathBuf,
     test_type: TestType,
     functions: HashSet<String>,
+    /// Tests marked skip or non-strict xfail, which pass whatever the code does
+    skipped: HashSet<String>,
 }
 
 /// Cache for test file contents and patterns
 pub struct TestCache {
     /// Map from test file path to test file info
     test_files: HashMap<PathBuf, TestFileInfo>,
-    /// Compiled regex for finding function definitions
-    function_regex: Regex,
+    /// Fixtures defined in conftest.py files, by file and line
+    fixtures: Vec<Fixture>,
+    /// Accept only tests named after the class for methods, without the `test_<method>`
+    /// fallbacks
+    qualified_names: bool,
 }
 
 impl TestCache {
     pub fn new() -> Self {
         Self {
             test_files: HashMap::new(),
-            function_regex: Regex::new(r"^\s*def\s+(\w+)\s*\(").unwrap(),
+            fixtures: Vec::new(),
+            qualified_names: false,
         }
     }
 
-    /// Build cache from test directories
-    pub fn build_from_directories(project_root: &Path, test_directories: &[String]) -> Arc<Self> {
+    /// Build cache from test directories; with `qualified_names`, tests of methods must
+    /// name the class
+    ///
+    /// With a `cache_dir`, only the test files changed since the last build are parsed
+    /// (see `test_cache_store`).
+    pub fn build_from_directories(
+        project_root: &Path,
+        test_directories: &[String],
+        qualified_names: bool,
+        cache_dir: Option<&Path>,
+    ) -> Arc<Self> {
         let mut cache = Self::new();
+        cache.qualified_names = qualified_names;
 
         // Find all test files in parallel
         let test_files: Vec<PathBuf> = test_directories
@@ -84,83 +105,167 @@ impl TestCache {
             })
             .collect();
 
-        // Parse test files in parallel
-        let file_infos: Vec<TestFileInfo> = test_files
-            .par_iter()
-            .filter_map(|path| {
-                if let Ok(content) = fs::read_to_string(path) {
-                    let functions = cache.extract_functions(&content);
-                    if !functions.is_empty() {
-                        let test_type = TestType::from_path(path);
-                        return Some(TestFileInfo {
-                            path: path.clone(),
-                            test_type,
-                            functions,
-                        });
-                    }
+        // Reuse what is stored for the files whose stamp did not change, parse the others
+        let mut stored = cache_dir
+            .map(|dir| test_cache_store::load(dir, project_root))
+            .unwrap_or_default();
+        let stamped: Vec<(PathBuf, Option<Stamp>)> = test_files
+            .into_par_iter()
+            .map(|path| {
+                let stamp = cache_dir.and_then(|_| Stamp::read(&path));
+                (path, stamp)
+            })
+            .collect();
+        let stored_count = stored.len();
+        let mut entries = Entries::new();
+        let mut changed = Vec::new();
+        // Test files share their helper modules, each is stamped once
+        let mut module_stamps: HashMap<PathBuf, Option<Stamp>> = HashMap::new();
+        let mut unchanged = |(stamp, extracted): &(Stamp, Extracted), current: Option<Stamp>| {
+            Some(*stamp) == current
+                && extracted.modules.iter().all(|(module, stamp)| {
+                    *module_stamps
+                        .entry(module.clone())
+                        .or_insert_with(|| Stamp::read(module))
+                        == Some(*stamp)
+                })
+        };
+        for (path, stamp) in stamped {
+            match stored.remove_entry(&path) {
+                Some((path, entry)) if unchanged(&entry, stamp) => {
+                    entries.insert(path, entry);
                 }
-                None
+                _ => changed.push((path, stamp)),
+            }
+        }
+        let reused = entries.len();
+
+        // Parse test files in parallel
+        let parsed: Vec<(PathBuf, Option<Stamp>, Extracted)> = changed
+            .into_par_iter()
+            .filter_map(|(path, stamp)| {
+                let content = fs::read_to_string(&path).ok()?;
+                let extracted = cache.extract(&path, &content, project_root);
+                Some((path, stamp, extracted))
             })
             .collect();
+        let mut unstamped = Vec::new();
+        for (path, stamp, extracted) in parsed {
+            match stamp {
+                Some(stamp) => {
+                    entries.insert(path, (stamp, extracted));
+                }
+                None => unstamped.push((path, extracted)),
+            }
+        }
+        if let Some(dir) = cache_dir {
+            if entries.len() != reused || reused != stored_count {
+                // A store that cannot be written only costs the next build its speed
+                let _ = test_cache_store::save(dir, project_root, &entries);
+            }
+        }
 
-        // Build the cache
-        for info in file_infos {
-            cache.test_files.insert(info.path.clone(), info);
+        // Build the cache, with the fixtures shared through conftest.py files
+        let mut fixtures = Vec::new();
+        let extracted = entries
+            .into_iter()
+            .map(|(path, (_, extracted))| (path, extracted))
+            .chain(unstamped);
+        for (path, extracted) in extracted {
+            fixtures.extend(extracted.fixtures);
+            // Files pytest collects no tests from (helpers, conftest.py) credit no function
+            if extracted.collects_tests {
+                let test_type = TestType::from_path(&path);
+                cache.test_files.insert(
+                    path,
+                    TestFileInfo {
+                        test_type,
+                        functions: extracted.functions,
+                        skipped: extracted.skipped,
+                    },
+                );
+            }
         }
+        fixtures.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+        cache.fixtures = fixtures;
 
         Arc::new(cache)
     }
 
-    /// Extract function names from file content
-    fn extract_functions(&self, content: &str) -> HashSet<String> {
-        let mut functions = HashSet::new();
-
-        for line in content.lines() {
-            if let Some(captures) = self.function_regex.captures(line) {
-                if let Some(func_name) = captures.get(1) {
-                    functions.insert(func_name.as_str().to_string());
-                }
-            }
+    /// What the cache takes from the test file at `path`, whose source is `content`
+    fn extract(&self, path: &Path, content: &str, project_root: &Path) -> Extracted {
+        let aliases = Aliases::from_source(path, content, project_root);
+        let (functions, skipped) = self.extract_functions(content, &aliases);
+        let fixtures = if path.file_name().is_some_and(|name| name == "conftest.py") {
+            fixtures::parse_fixtures(path, content)
+        } else {
+            Vec::new()
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let modules = aliases
+            .modules()
+            .iter()
+            .filter_map(|module| Some((module.clone(), Stamp::read(module)?)))
+            .collect();
+        Extracted {
+            functions,
+            skipped,
+            fixtures,
+            collects_tests: crate::rules::collects_tests(&lines),
+            modules,
         }
-
-        functions
     }
 
-    /// Check if a test exists for the given function
-    pub fn has_test_for_function(
+    /// Extract function names from file content, along with those of skipped tests
+    ///
+    /// Decorators are resolved through `aliases`, so `@skip` after
+    /// `from pytest.mark import skip` counts as well.
+    fn extract_functions(
         &self,
-        function_name: &str,
-        source_path: &Path,
-        class_name: Option<&str>,
-    ) -> bool {
-        // Get module name for file matching
-        let module_name = source_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("");
-
-        // Check cached test files
-        for (_, info) in &self.test_files {
-            // Check if this test file might be for our module
-            let file_name = info.path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-
-            if !file_name.contains(module_name) && !file_name.starts_with("test_") {
-                continue;
+        content: &str,
+        aliases: &Aliases,
+    ) -> (HashSet<String>, HashSet<String>) {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut functions = HashSet::new();
+        let mut skipped = HashSet::new();
+
+        // Top-level test class being read, whose methods are also indexed as
+        // `TestClass::test_method`
+        let mut class: Option<&str> = None;
+        for (idx, line) in lines.iter().enumerate() {
+            if !line.starts_with([' ', '\t', '@', '#']) && !line.trim().is_empty() {
+                class = PATTERNS
+                    .class
+                    .captures(line)
+                    .and_then(|captures| captures.get(2))
+                    .map(|name| name.as_str())
+                    .filter(|name| name.starts_with("Test"));
             }
-
-            // Generate test patterns based on test type
-            let test_patterns =
-                self.generate_test_patterns(function_name, class_name, &info.test_type);
-
-            // Check if any test pattern exists in this file
-            for pattern in &test_patterns {
-                if info.functions.contains(pattern) {
-                    return true;
+            if let Some(captures) = PATTERNS.def.captures(line) {
+                if let Some(func_name) = captures.get(2) {
+                    let mut names = vec![func_name.as_str().to_string()];
+                    if let Some(class) = class.filter(|_| line.starts_with([' ', '\t'])) {
+                        names.push(format!("{}::{}", class, func_name.as_str()));
+                    }
+                    if parser::decorator_calls(&lines, idx + 1)
+                        .iter()
+                        .any(|call| is_skip_decorator(&aliases.resolve(call)))
+                    {
+                        skipped.extend(names.iter().cloned());
+                    }
+                    functions.extend(names);
                 }
             }
         }
 
-        false
+        (functions, skipped)
+    }
+
+    /// Whether the test `test_name` in `test_file` is marked skip or non-strict xfail
+    pub fn is_skipped(&self, test_file: &Path, test_name: &str) -> bool {
+        self.test_files
+            .get(test_file)
+            .is_some_and(|info| info.skipped.contains(test_name))
     }
 
     /// Check if a test of a specific type exists for the given function
@@ -173,70 +278,205 @@ impl TestCache {
         module_path: &str,
         project_root: &Path,
     ) -> bool {
+        self.find_tests_for_function_of_type(
+            function_name,
+            source_path,
+            class_name,
+            test_type,
+            module_path,
+            project_root,
+        )
+        .next()
+        .is_some()
+    }
+
+    /// Find the tests of a specific type for the given function, as (test file, test name)
+    pub fn find_tests_for_function_of_type<'a>(
+        &'a self,
+        function_name: &str,
+        source_path: &Path,
+        class_name: Option<&str>,
+        test_type: &'a TestType,
+        module_path: &'a str,
+        project_root: &'a Path,
+    ) -> impl Iterator<Item = (&'a Path, &'a str)> + 'a {
         // Get module name for file matching
         let module_name = source_path
             .file_stem()
             .and_then(|s| s.to_str())
-            .unwrap_or("");
-
-        // Check cached test files of the specific type
-        for (test_path, info) in &self.test_files {
-            // Skip if not the right test type
-            if &info.test_type != test_type && info.test_type != TestType::General {
-                continue;
-            }
+            .unwrap_or("")
+            .to_string();
 
-            // Check if this test file is in the right directory structure
-            // For pkg.mod1.submod, we expect tests in test/unit/pkg/mod1/test_submod.py
-            if !module_path.is_empty() {
-                let expected_test_dir =
-                    self.get_expected_test_path(module_path, &info.test_type, project_root);
-                let test_dir = test_path.parent().unwrap_or(Path::new(""));
+        // Generate test patterns based on test type
+        let test_patterns = self.generate_test_patterns(function_name, class_name, test_type);
 
-                // Check if the test file is in the expected directory
-                if !test_dir.ends_with(&expected_test_dir) {
-                    // Also check if it's in the parent directory with the right name
-                    let file_name = test_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        // Check cached test files of the specific type
+        self.test_files
+            .iter()
+            .filter(move |(test_path, info)| {
+                // Skip if not the right test type
+                if &info.test_type != test_type && info.test_type != TestType::General {
+                    return false;
+                }
 
-                    if !file_name.contains(module_name) && !file_name.starts_with("test_") {
-                        continue;
+                // Check if this test file is in the right directory structure
+                // For pkg.mod1.submod, we expect tests in test/unit/pkg/mod1/test_submod.py
+                if !module_path.is_empty() {
+                    let expected_test_dir =
+                        self.get_expected_test_path(module_path, &info.test_type, project_root);
+                    let test_dir = test_path.parent().unwrap_or(Path::new(""));
+
+                    // Check if the test file is in the expected directory
+                    if !test_dir.ends_with(&expected_test_dir) {
+                        // Also check if it's in the parent directory with the right name
+                        let file_name =
+                            test_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+
+                        if !file_name.contains(module_name.as_str())
+                            && !file_name.starts_with("test_")
+                        {
+                            return false;
+                        }
                     }
                 }
-            }
+                true
+            })
+            .flat_map(move |(test_path, info)| {
+                // Check if any test pattern exists in this file
+                test_patterns
+                    .clone()
+                    .into_iter()
+                    .filter_map(move |pattern| {
+                        info.functions
+                            .get(&pattern)
+                            .map(|name| (test_path.as_path(), name.as_str()))
+                    })
+            })
+    }
 
-            // Generate test patterns based on test type
-            let test_patterns = self.generate_test_patterns(function_name, class_name, test_type);
+    /// Whether `test_file` is where the tests of `module_path` belong: in the directory
+    /// mirroring the module, or in a test file named after it
+    ///
+    /// `find_tests_for_function_of_type` also accepts any other `test_*` file; version 2
+    /// of PL001 applies this stricter check on top.
+    pub fn is_mirrored_test_file(
+        &self,
+        test_file: &Path,
+        module_path: &str,
+        project_root: &Path,
+    ) -> bool {
+        if module_path.is_empty() {
+            return true;
+        }
+        let expected_test_dir =
+            self.get_expected_test_path(module_path, &TestType::from_path(test_file), project_root);
+        let module_name = module_path.rsplit('.').next().unwrap_or(module_path);
+        test_file
+            .parent()
+            .is_some_and(|dir| dir.ends_with(&expected_test_dir))
+            || test_file
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| {
+                    stem.strip_prefix("test_")
+                        .or_else(|| stem.strip_suffix("_test"))
+                        == Some(module_name)
+                })
+    }
 
-            // Check if any test pattern exists in this file
-            for pattern in &test_patterns {
-                if info.functions.contains(pattern) {
-                    return true;
-                }
-            }
+    /// Number of cached test files of each type
+    pub fn count_by_type(&self) -> HashMap<&'static str, usize> {
+        let mut counts = HashMap::new();
+        for info in self.test_files.values() {
+            *counts.entry(info.test_type.name()).or_default() += 1;
         }
+        counts
+    }
 
-        false
+    /// Paths of all cached test files
+    pub fn test_file_paths(&self) -> impl Iterator<Item = &Path> {
+        self.test_files.keys().map(PathBuf::as_path)
     }
 
-    /// Get the single canonical test pattern for a function
-    pub fn get_canonical_test_pattern(
-        &self,
-        function_name: &str,
-        class_name: Option<&str>,
-        test_type: &TestType,
-    ) -> String {
-        // Single deterministic pattern for each case
-        if let Some(class) = class_name {
-            match test_type {
-                TestType::Unit => format!("test_{}_{}", class, function_name),
-                TestType::Integration => format!("test_{}_{}", class, function_name),
-                TestType::E2E => format!("test_{}_{}", class, function_name),
-                TestType::General => format!("test_{}_{}", class, function_name),
+    /// How this cache differs from `fresh`, one built from the test files as they are
+    /// now: the test files either one lacks or whose tests or skipped tests differ, and
+    /// the conftest.py files whose fixtures differ
+    pub fn differences(&self, fresh: &TestCache) -> Vec<(PathBuf, String)> {
+        let mut differences = Vec::new();
+        let paths: HashSet<&PathBuf> = self
+            .test_files
+            .keys()
+            .chain(fresh.test_files.keys())
+            .collect();
+        for path in paths {
+            let difference = match (self.test_files.get(path), fresh.test_files.get(path)) {
+                (Some(_), None) => "indexed test file no longer has tests on disk".to_string(),
+                (None, Some(_)) => "test file on disk is missing from the index".to_string(),
+                (Some(cached), Some(fresh)) if cached.functions != fresh.functions => {
+                    let mut names: Vec<&String> = cached
+                        .functions
+                        .symmetric_difference(&fresh.functions)
+                        .collect();
+                    names.sort();
+                    format!("indexed tests differ from those on disk: {:?}", names)
+                }
+                (Some(cached), Some(fresh)) if cached.skipped != fresh.skipped => {
+                    "indexed skipped tests differ from those on disk".to_string()
+                }
+                _ => continue,
+            };
+            differences.push((path.clone(), difference));
+        }
+        if self.fixtures != fresh.fixtures {
+            let files: HashSet<&str> = self
+                .fixtures
+                .iter()
+                .chain(&fresh.fixtures)
+                .map(|fixture| fixture.file_path.as_str())
+                .collect();
+            for file in files {
+                let of = |fixtures: &[Fixture]| -> Vec<Fixture> {
+                    fixtures
+                        .iter()
+                        .filter(|fixture| fixture.file_path == file)
+                        .cloned()
+                        .collect()
+                };
+                if of(&self.fixtures) != of(&fresh.fixtures) {
+                    differences.push((
+                        PathBuf::from(file),
+                        "indexed fixtures differ from those on disk".to_string(),
+                    ));
+                }
             }
-        } else {
-            // For standalone functions
-            format!("test_{}", function_name)
         }
+        differences.sort();
+        differences
+    }
+
+    /// Fixtures defined in the conftest.py files of the test directories
+    pub fn fixtures(&self) -> &[Fixture] {
+        &self.fixtures
+    }
+
+    /// Fixtures available to `test_file` through the conftest.py files of its directory and
+    /// its parents, nearest conftest first, so the first fixture of a name is the one pytest uses
+    pub fn fixtures_visible_from(&self, test_file: &Path) -> Vec<&Fixture> {
+        let mut visible: Vec<(usize, &Fixture)> = self
+            .fixtures
+            .iter()
+            .filter_map(|fixture| {
+                let conftest_dir = Path::new(&fixture.file_path).parent()?;
+                let depth = test_file
+                    .strip_prefix(conftest_dir)
+                    .ok()?
+                    .components()
+                    .count();
+                Some((depth, fixture))
+            })
+            .collect();
+        visible.sort_by_key(|(depth, _)| *depth);
+        visible.into_iter().map(|(_, fixture)| fixture).collect()
     }
 
     /// Generate test patterns based on function name, class, and test type
@@ -254,33 +494,45 @@ impl TestCache {
                 TestType::Unit => {
                     // Primary pattern: test_ClassName_method_name
                     patterns.push(format!("test_{}_{}", class, function_name));
+                    patterns.push(format!("Test{}::test_{}", class, function_name));
                     patterns.push(format!("test_{}_{}", class.to_lowercase(), function_name));
                     patterns.push(format!("test_unit_{}_{}", class, function_name));
                     // Fallback patterns
-                    patterns.push(format!("test_{}", function_name));
+                    if !self.qualified_names {
+                        patterns.push(format!("test_{}", function_name));
+                    }
                 }
                 TestType::Integration => {
                     patterns.push(format!("test_integration_{}_{}", class, function_name));
                     patterns.push(format!("test_int_{}_{}", class, function_name));
                     patterns.push(format!("test_{}_{}", class, function_name));
+                    patterns.push(format!("Test{}::test_{}", class, function_name));
                     // Fallback
-                    patterns.push(format!("test_integration_{}", function_name));
+                    if !self.qualified_names {
+                        patterns.push(format!("test_integration_{}", function_name));
+                    }
                 }
                 TestType::E2E => {
                     patterns.push(format!("test_e2e_{}_{}", class, function_name));
                     patterns.push(format!("test_end_to_end_{}_{}", class, function_name));
                     patterns.push(format!("test_{}_{}", class, function_name));
+                    patterns.push(format!("Test{}::test_{}", class, function_name));
                     // Fallback
-                    patterns.push(format!("test_e2e_{}", function_name));
+                    if !self.qualified_names {
+                        patterns.push(format!("test_e2e_{}", function_name));
+                    }
                 }
                 TestType::General => {
                     patterns.push(format!("test_{}_{}", class, function_name));
+                    patterns.push(format!("Test{}::test_{}", class, function_name));
                     patterns.push(format!("test_{}_{}", class.to_lowercase(), function_name));
                     patterns.push(format!("test_unit_{}_{}", class, function_name));
                     patterns.push(format!("test_integration_{}_{}", class, function_name));
                     patterns.push(format!("test_e2e_{}_{}", class, function_name));
                     // Fallback
-                    patterns.push(format!("test_{}", function_name));
+                    if !self.qualified_names {
+                        patterns.push(format!("test_{}", function_name));
+                    }
                 }
             }
         } else {
@@ -317,7 +569,7 @@ impl TestCache {
         &self,
         module_path: &str,
         test_type: &TestType,
-        project_root: &Path,
+        _project_root: &Path,
     ) -> PathBuf {
         // Split module path into components
         let components: Vec<&str> = module_path.split('.').collect();
@@ -362,32 +614,222 @@ impl TestCache {
         // Return absolute path
         project_root.join(test_dir).join(test_file_name)
     }
+}
 
-    /// Get information about where tests are found (for error messages)
-    pub fn get_test_locations(&self) -> HashMap<TestType, Vec<String>> {
-        let mut locations: HashMap<TestType, Vec<String>> = HashMap::new();
-
-        for (_, info) in &self.test_files {
-            let dir = info
-                .path
-                .parent()
-                .and_then(|p| p.file_name())
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown")
-                .to_string();
-
-            locations
-                .entry(info.test_type.clone())
-                .or_insert_with(Vec::new)
-                .push(dir);
-        }
+/// Whether a decorator call (as returned by `parser::decorator_calls`) makes a test pass
+/// regardless of the code under test: `skip`, or `xfail` without `strict=True`
+fn is_skip_decorator(call: &str) -> bool {
+    let call = call
+        .strip_prefix("pytest.")
+        .or_else(|| call.strip_prefix("unittest."))
+        .unwrap_or(call);
+    let call = call.strip_prefix("mark.").unwrap_or(call);
+    let (name, args) = call.split_once('(').unwrap_or((call, ""));
+    match name {
+        "skip" => true,
+        "xfail" => !args.contains("strict=True"),
+        _ => false,
+    }
+}
 
-        // Deduplicate
-        for (_, dirs) in locations.iter_mut() {
-            dirs.sort();
-            dirs.dedup();
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_skipped_functions() {
+        let content = "import pytest\nfrom pytest.mark import skip as ignore\n\n@ignore(reason=\"flaky\")\ndef test_a():\n    pass\n\n@pytest.mark.xfail(\n    strict=True,\n)\ndef test_b():\n    pass\n\n@pytest.mark.xfail\ndef test_c():\n    pass\n\n@pytest.mark.skipif(True, reason=\"\")\ndef test_d():\n    pass\n";
+        let aliases = Aliases::from_source(Path::new("test_m.py"), content, Path::new(""));
+        let (functions, skipped) = TestCache::new().extract_functions(content, &aliases);
+        assert_eq!(functions.len(), 4);
+        let mut skipped: Vec<&str> = skipped.iter().map(String::as_str).collect();
+        skipped.sort();
+        assert_eq!(skipped, vec!["test_a", "test_c"]);
+    }
+
+    #[test]
+    fn test_extract_class_based_tests() {
+        let content = "class TestCache:\n    @pytest.mark.skip\n    def test_get(self):\n        pass\n\n\ndef test_put():\n    pass\n\n\nclass Helper:\n    def test_like(self):\n        pass\n";
+        let aliases = Aliases::from_source(Path::new("test_m.py"), content, Path::new(""));
+        let (functions, skipped) = TestCache::new().extract_functions(content, &aliases);
+        assert!(functions.contains("TestCache::test_get"));
+        assert!(skipped.contains("TestCache::test_get"));
+        assert!(functions.contains("test_put"));
+        assert!(!functions.contains("TestCache::test_put"));
+        assert!(!functions.contains("Helper::test_like"));
+    }
+
+    #[test]
+    fn test_conftest_fixture_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let unit = root.join("test/unit");
+        fs::create_dir_all(&unit).unwrap();
+        fs::write(
+            root.join("test/conftest.py"),
+            "import pytest\n\n@pytest.fixture(scope=\"session\")\ndef db():\n    pass\n\n@pytest.fixture(autouse=True)\ndef env():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            unit.join("conftest.py"),
+            "import pytest\n\n@pytest.fixture\ndef db():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(root.join("test/test_top.py"), "def test_a(db):\n    pass\n").unwrap();
+
+        let cache = TestCache::build_from_directories(root, &["test".to_string()], false, None);
+        assert_eq!(cache.fixtures().len(), 3);
+        // conftest.py files hold no tests, so only their fixtures are indexed
+        let indexed: Vec<&Path> = cache.test_file_paths().collect();
+        assert_eq!(indexed, vec![root.join("test/test_top.py")]);
+
+        let visible = |test_file: &Path| -> Vec<(String, String)> {
+            cache
+                .fixtures_visible_from(test_file)
+                .iter()
+                .map(|f| (f.name.clone(), f.scope.clone()))
+                .collect()
+        };
+        assert_eq!(
+            visible(&unit.join("test_m.py")),
+            vec![
+                ("db".to_string(), "function".to_string()),
+                ("db".to_string(), "session".to_string()),
+                ("env".to_string(), "function".to_string()),
+            ]
+        );
+        assert_eq!(visible(&root.join("test/test_top.py")).len(), 2);
+        assert!(visible(&root.join("src/m.py")).is_empty());
+    }
+
+    #[test]
+    fn test_build_reuses_stored_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let cache_dir = root.join(".proboscis_cache");
+        fs::create_dir_all(root.join("test")).unwrap();
+        let test_file = root.join("test/test_mod.py");
+        fs::write(&test_file, "def test_a():\n    pass\n").unwrap();
+        let build = || {
+            TestCache::build_from_directories(root, &["test".to_string()], false, Some(&cache_dir))
+        };
+        let functions = |cache: &TestCache| {
+            let mut names: Vec<String> = cache.test_files[&test_file]
+                .functions
+                .iter()
+                .cloned()
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(functions(&build()), vec!["test_a"]);
+        assert!(cache_dir.join(test_cache_store::FILE_NAME).is_file());
+
+        // Same size and modification time: the stored functions are used, unread
+        let modified = fs::metadata(&test_file).unwrap().modified().unwrap();
+        fs::write(&test_file, "def test_b():\n    pass\n").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&test_file)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(functions(&build()), vec!["test_a"]);
+
+        fs::write(
+            &test_file,
+            "def test_c():\n    pass\n\ndef test_d():\n    pass\n",
+        )
+        .unwrap();
+        assert_eq!(functions(&build()), vec!["test_c", "test_d"]);
+    }
+
+    #[test]
+    fn test_build_rereads_files_whose_helper_modules_changed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let cache_dir = root.join(".proboscis_cache");
+        fs::create_dir_all(root.join("test")).unwrap();
+        let helpers = root.join("test/helpers.py");
+        fs::write(
+            &helpers,
+            "import pytest
+
+flaky = pytest.mark.skip
+",
+        )
+        .unwrap();
+        let test_file = root.join("test/test_mod.py");
+        fs::write(
+            &test_file,
+            "from test.helpers import flaky
+
+@flaky
+def test_a():
+    pass
+",
+        )
+        .unwrap();
+        let skipped = || {
+            TestCache::build_from_directories(root, &["test".to_string()], false, Some(&cache_dir))
+                .is_skipped(&test_file, "test_a")
+        };
+        assert!(skipped());
+        assert!(skipped());
+
+        // The test file is unchanged, but the marker its alias stands for is not a skip
+        fs::write(
+            &helpers,
+            "import pytest
+
+flaky = pytest.mark.flaky(reruns=2)
+",
+        )
+        .unwrap();
+        assert!(!skipped());
+    }
+
+    #[test]
+    fn test_qualified_test_names() {
+        let mut cache = TestCache::new();
+        let patterns = cache.generate_test_patterns("run", Some("Job"), &TestType::Unit);
+        assert!(patterns.contains(&"test_run".to_string()));
+        cache.qualified_names = true;
+        let patterns = cache.generate_test_patterns("run", Some("Job"), &TestType::Unit);
+        assert!(patterns.contains(&"test_Job_run".to_string()));
+        assert!(patterns.contains(&"TestJob::test_run".to_string()));
+        assert!(!patterns.contains(&"test_run".to_string()));
+        // Functions outside a class have nothing to qualify their tests with
+        assert_eq!(
+            cache.generate_test_patterns("run", None, &TestType::Unit),
+            vec!["test_run".to_string(), "test_unit_run".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_is_mirrored_test_file() {
+        let cache = TestCache::new();
+        let root = Path::new("/repo");
+        let mirrored = |test_file: &str| {
+            cache.is_mirrored_test_file(&root.join(test_file), "pkg.io.reader", root)
+        };
+        assert!(mirrored("test/unit/pkg/io/test_anything.py"));
+        assert!(mirrored("test/unit/test_reader.py"));
+        assert!(mirrored("test/unit/reader_test.py"));
+        assert!(!mirrored("test/unit/pkg/test_other.py"));
+        assert!(!mirrored("test/unit/test_reader_extra.py"));
+        assert!(cache.is_mirrored_test_file(&root.join("test/unit/test_x.py"), "", root));
+    }
 
-        locations
+    #[test]
+    fn test_is_skip_decorator() {
+        assert!(is_skip_decorator("pytest.mark.skip"));
+        assert!(is_skip_decorator("unittest.skip(\"\")"));
+        assert!(is_skip_decorator("mark.xfail(strict=False)"));
+        assert!(!is_skip_decorator(
+            "pytest.mark.xfail(raises=ValueError,strict=True)"
+        ));
+        assert!(!is_skip_decorator("pytest.mark.skipif(sys.platform==\"\")"));
+        assert!(!is_skip_decorator("pytest.mark.unit"));
     }
 }
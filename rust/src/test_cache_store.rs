@@ -0,0 +1,291 @@
+//! On-disk layer of the test cache (`cache_dir`)
+//!
+//! Building the `TestCache` reads and parses every test file, which dominates the start of
+//! each run on large suites. With a `cache_dir`, what was extracted from each test file is
+//! stored along with the file's modification time and size, and those of the project
+//! modules its decorator aliases were resolved from (see `aliases`), since a marker bound
+//! in a helper module changes what the file skips. The next build only stats the files and
+//! parses those whose stamps changed or that are new; a suite that did not change is
+//! loaded without reading a single test file. As with ruff's cache, an edit that keeps
+//! the size of a file and lands within the timestamp resolution of the file system is
+//! missed.
+//!
+//! The store is one JSON file, rewritten when anything changed. A store that cannot be
+//! read, that was written for another project root, or in another `FORMAT_VERSION`, is
+//! ignored and replaced by the next save.
+//!
+//! Pre-commit hooks, CI jobs and editors may share the directory and run at the same time.
+//! The store is written to a temporary file renamed over it, so readers never see half of
+//! it, and runs coordinate through an advisory lock on `LOCK_FILE_NAME`: loading takes it
+//! shared, saving exclusive. A run that finds another one saving does not wait for it and
+//! leaves the store as the other one writes it.
+
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::fixtures::Fixture;
+
+/// Name of the store in the cache directory
+pub const FILE_NAME: &str = "test_cache.json";
+
+/// Name of the file locked while the store is read or written
+pub const LOCK_FILE_NAME: &str = "test_cache.lock";
+
+/// Version of the store's format, bumped whenever what is stored changes
+pub const FORMAT_VERSION: u64 = 3;
+
+/// Modification time and size of a file, which tell whether it changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stamp {
+    modified_ns: u64,
+    size: u64,
+}
+
+impl Stamp {
+    /// The stamp of the file at `path`, if it can be read
+    pub fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            modified_ns: modified.as_nanos() as u64,
+            size: metadata.len(),
+        })
+    }
+}
+
+/// What the test cache takes from one test file
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    pub functions: HashSet<String>,
+    /// Tests marked skip or non-strict xfail
+    pub skipped: HashSet<String>,
+    /// Fixtures, for conftest.py files
+    pub fixtures: Vec<Fixture>,
+    /// Whether pytest collects tests from the file (see `rules::collects_tests`)
+    pub collects_tests: bool,
+    /// The project modules decorator aliases were resolved from, with their stamps
+    pub modules: Vec<(PathBuf, Stamp)>,
+}
+
+/// The extracted test files of a store, by path
+pub type Entries = HashMap<PathBuf, (Stamp, Extracted)>;
+
+fn strings(value: &Value) -> HashSet<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| Some(item.as_str()?.to_string()))
+        .collect()
+}
+
+/// `names` in order, so that the store does not change when they do not
+fn sorted(names: &HashSet<String>) -> Vec<&String> {
+    let mut names: Vec<&String> = names.iter().collect();
+    names.sort();
+    names
+}
+
+fn fixture(value: &Value, file_path: &str) -> Option<Fixture> {
+    Some(Fixture {
+        name: value["name"].as_str()?.to_string(),
+        scope: value["scope"].as_str()?.to_string(),
+        autouse: value["autouse"].as_bool()?,
+        file_path: file_path.to_string(),
+        line_number: value["line_number"].as_u64()? as usize,
+    })
+}
+
+fn stamp_of(value: &Value) -> Option<Stamp> {
+    Some(Stamp {
+        modified_ns: value["modified_ns"].as_u64()?,
+        size: value["size"].as_u64()?,
+    })
+}
+
+fn entry(value: &Value, file_path: &str) -> Option<(Stamp, Extracted)> {
+    let stamp = stamp_of(value)?;
+    let fixtures = value["fixtures"]
+        .as_array()?
+        .iter()
+        .map(|item| fixture(item, file_path))
+        .collect::<Option<Vec<_>>>()?;
+    let modules = value["modules"]
+        .as_array()?
+        .iter()
+        .map(|item| Some((PathBuf::from(item["path"].as_str()?), stamp_of(item)?)))
+        .collect::<Option<Vec<_>>>()?;
+    Some((
+        stamp,
+        Extracted {
+            functions: strings(&value["functions"]),
+            skipped: strings(&value["skipped"]),
+            fixtures,
+            collects_tests: value["collects_tests"].as_bool()?,
+            modules,
+        },
+    ))
+}
+
+/// The lock file of the store in `cache_dir`, created if needed
+fn lock_file(cache_dir: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(cache_dir.join(LOCK_FILE_NAME))
+}
+
+/// The entries stored in `cache_dir` for the project at `project_root`; none when there
+/// is no usable store
+pub fn load(cache_dir: &Path, project_root: &Path) -> Entries {
+    let store_path = cache_dir.join(FILE_NAME);
+    if !store_path.is_file() {
+        return Entries::new();
+    }
+    // Read-only cache directories are read without the lock; renames keep them whole
+    let _lock = lock_file(cache_dir).and_then(|lock| lock.lock_shared().map(|()| lock));
+    let Ok(content) = fs::read_to_string(store_path) else {
+        return Entries::new();
+    };
+    let Ok(store) = serde_json::from_str::<Value>(&content) else {
+        return Entries::new();
+    };
+    if store["version"].as_u64() != Some(FORMAT_VERSION)
+        || store["project_root"].as_str() != Some(&*project_root.to_string_lossy())
+    {
+        return Entries::new();
+    }
+    store["files"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(path, value)| Some((PathBuf::from(path), entry(value, path)?)))
+        .collect()
+}
+
+/// Store `entries` in `cache_dir` for the project at `project_root`
+pub fn save(cache_dir: &Path, project_root: &Path, entries: &Entries) -> io::Result<()> {
+    let files: Map<String, Value> = entries
+        .iter()
+        .map(|(path, (stamp, extracted))| {
+            let fixtures: Vec<Value> = extracted
+                .fixtures
+                .iter()
+                .map(|fixture| {
+                    json!({
+                        "name": fixture.name,
+                        "scope": fixture.scope,
+                        "autouse": fixture.autouse,
+                        "line_number": fixture.line_number,
+                    })
+                })
+                .collect();
+            let modules: Vec<Value> = extracted
+                .modules
+                .iter()
+                .map(|(path, stamp)| {
+                    json!({
+                        "path": path.to_string_lossy(),
+                        "modified_ns": stamp.modified_ns,
+                        "size": stamp.size,
+                    })
+                })
+                .collect();
+            (
+                path.to_string_lossy().into_owned(),
+                json!({
+                    "modified_ns": stamp.modified_ns,
+                    "size": stamp.size,
+                    "functions": sorted(&extracted.functions),
+                    "skipped": sorted(&extracted.skipped),
+                    "fixtures": fixtures,
+                    "collects_tests": extracted.collects_tests,
+                    "modules": modules,
+                }),
+            )
+        })
+        .collect();
+    let store = json!({
+        "version": FORMAT_VERSION,
+        "project_root": project_root.to_string_lossy(),
+        "files": files,
+    });
+    fs::create_dir_all(cache_dir)?;
+    let lock = lock_file(cache_dir)?;
+    match lock.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => return Ok(()),
+        Err(TryLockError::Error(e)) => return Err(e),
+    }
+    let temporary = cache_dir.join(format!("{}.{}.tmp", FILE_NAME, std::process::id()));
+    fs::write(&temporary, store.to_string())
+        .and_then(|()| fs::rename(&temporary, cache_dir.join(FILE_NAME)))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&temporary);
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let root = Path::new("/repo");
+        let conftest = root.join("test/conftest.py");
+        let stamp = Stamp {
+            modified_ns: 1_700_000_000_123_456_789,
+            size: 42,
+        };
+        let extracted = Extracted {
+            functions: HashSet::from(["db".to_string(), "test_a".to_string()]),
+            skipped: HashSet::from(["test_a".to_string()]),
+            fixtures: vec![Fixture {
+                name: "db".to_string(),
+                scope: "session".to_string(),
+                autouse: false,
+                file_path: conftest.to_string_lossy().into_owned(),
+                line_number: 4,
+            }],
+            collects_tests: false,
+            modules: vec![(root.join("test/helpers.py"), stamp)],
+        };
+        let entries = Entries::from([(conftest.clone(), (stamp, extracted))]);
+        save(dir, root, &entries).unwrap();
+
+        assert_eq!(load(dir, root), entries);
+        // A store written for another root, in another format or unreadable, holds nothing
+        assert!(load(dir, Path::new("/other")).is_empty());
+        let store = fs::read_to_string(dir.join(FILE_NAME)).unwrap();
+        let old_format = store.replace(&format!("\"version\":{}", FORMAT_VERSION), "\"version\":1");
+        assert_ne!(old_format, store);
+        fs::write(dir.join(FILE_NAME), old_format).unwrap();
+        assert!(load(dir, root).is_empty());
+        fs::write(dir.join(FILE_NAME), "{not json").unwrap();
+        assert!(load(dir, root).is_empty());
+
+        // While another run saves, saving leaves the store alone
+        let other_run = lock_file(dir).unwrap();
+        other_run.lock().unwrap();
+        save(dir, root, &entries).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join(FILE_NAME)).unwrap(),
+            "{not json"
+        );
+        other_run.unlock().unwrap();
+        save(dir, root, &entries).unwrap();
+        assert_eq!(load(dir, root), entries);
+        let names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert!(names.iter().all(|name| !name.ends_with(".tmp")));
+    }
+}
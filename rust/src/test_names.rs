@@ -0,0 +1,119 @@
+//! The test names recommended in missing-test violations
+//!
+//! Matching accepts many spellings of a test (see `TestCache::generate_test_patterns`);
+//! violations recommend one, rendered from `test_name_template` for functions and
+//! `method_test_name_template` for methods, so the guidance follows the team's convention
+//! (e.g. `Test{class}::test_{function}`) without narrowing what counts as a test. The
+//! `test_suggestion_style` "class" makes that class-based form the method default, matching
+//! suites that group the tests of a class in a `class Test<Class>:`.
+
+/// Templates of the recommended test names, with `{function}` and `{class}` placeholders
+#[derive(Debug, Clone)]
+pub struct TestNameTemplates {
+    pub function: String,
+    pub method: String,
+}
+
+impl Default for TestNameTemplates {
+    fn default() -> Self {
+        Self {
+            function: "test_{function}".to_string(),
+            method: "test_{class}_{function}".to_string(),
+        }
+    }
+}
+
+/// Method template of the `test_suggestion_style` "class"
+const CLASS_METHOD_TEMPLATE: &str = "Test{class}::test_{function}";
+
+impl TestNameTemplates {
+    /// Validate the templates: both name the function, the method template the class,
+    /// and neither uses other placeholders; `style` ("function" or "class") picks the
+    /// default method template
+    pub fn new(
+        function: Option<&str>,
+        method: Option<&str>,
+        style: Option<&str>,
+    ) -> Result<Self, String> {
+        let defaults = Self::default();
+        let default_method = match style.unwrap_or("function") {
+            "function" => defaults.method,
+            "class" => CLASS_METHOD_TEMPLATE.to_string(),
+            other => {
+                return Err(format!(
+                    "test_suggestion_style must be 'function' or 'class', got '{}'",
+                    other
+                ))
+            }
+        };
+        let templates = Self {
+            function: function.map_or(defaults.function, str::to_string),
+            method: method.map_or(default_method, str::to_string),
+        };
+        check("test_name_template", &templates.function, &["{function}"])?;
+        check(
+            "method_test_name_template",
+            &templates.method,
+            &["{function}", "{class}"],
+        )?;
+        Ok(templates)
+    }
+
+    /// The recommended name of the test of `function_name`, a method of `class_name` if any
+    pub fn render(&self, function_name: &str, class_name: Option<&str>) -> String {
+        match class_name {
+            Some(class) => self
+                .method
+                .replace("{class}", class)
+                .replace("{function}", function_name),
+            None => self.function.replace("{function}", function_name),
+        }
+    }
+}
+
+fn check(setting: &str, template: &str, required: &[&str]) -> Result<(), String> {
+    if let Some(missing) = required.iter().find(|p| !template.contains(*p)) {
+        return Err(format!(
+            "{} must contain {}, got '{}'",
+            setting, missing, template
+        ));
+    }
+    let unknown = template
+        .replace("{function}", "")
+        .replace("{class}", "")
+        .contains(['{', '}']);
+    if unknown {
+        return Err(format!(
+            "{} only supports the {{function}} and {{class}} placeholders, got '{}'",
+            setting, template
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let defaults = TestNameTemplates::default();
+        assert_eq!(defaults.render("load", None), "test_load");
+        assert_eq!(defaults.render("get", Some("Cache")), "test_Cache_get");
+
+        let classes = TestNameTemplates::new(None, None, Some("class")).unwrap();
+        assert_eq!(classes.render("get", Some("Cache")), "TestCache::test_get");
+        assert_eq!(classes.render("load", None), "test_load");
+
+        assert!(TestNameTemplates::new(Some("test_it"), None, None)
+            .unwrap_err()
+            .contains("must contain {function}"));
+        assert!(TestNameTemplates::new(None, Some("test_{function}"), None).is_err());
+        assert!(TestNameTemplates::new(Some("test_{type}_{function}"), None, None).is_err());
+        assert!(TestNameTemplates::new(None, None, Some("nested")).is_err());
+        // An explicit method template wins over the style
+        let explicit =
+            TestNameTemplates::new(None, Some("test_{class}__{function}"), Some("class")).unwrap();
+        assert_eq!(explicit.render("get", Some("Cache")), "test_Cache__get");
+    }
+}
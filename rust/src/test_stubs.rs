@@ -0,0 +1,138 @@
+//! Test stubs for missing tests (`add_test_stub` fixes)
+//!
+//! With `test_stubs=True`, PL001–PL003 violations carry the skeleton of the test they
+//! recommend. A plain name such as `test_Cache_get` becomes a module-level function; a
+//! class-based name such as `TestCache::test_get` becomes a method of `class TestCache:`,
+//! added to that class when the test file already has it. Stubs are marked for their test
+//! type and skipped, so they keep the gap visible instead of passing silently.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The stub of the test `test_name` (`test_x` or `TestClass::test_x`), marked with the
+/// `kind` of test ("unit", "integration" or "e2e")
+pub fn render(test_name: &str, kind: &str) -> String {
+    match test_name.split_once("::") {
+        Some((class, method)) => format!(
+            "class {}:\n{}",
+            class,
+            function(method, kind, "    ", "self")
+        ),
+        None => function(test_name, kind, "", ""),
+    }
+}
+
+fn function(name: &str, kind: &str, indent: &str, params: &str) -> String {
+    format!(
+        "{indent}@pytest.mark.{kind}\n{indent}def {name}({params}):\n{indent}    pytest.skip(\"not implemented yet\")\n"
+    )
+}
+
+/// Add the stub of `test_name` to `test_file`, creating the file (and its directories) if
+/// needed; returns whether anything was written, which is not the case when the test
+/// already exists
+pub fn add(test_file: &Path, test_name: &str, stub: &str) -> io::Result<bool> {
+    let content = fs::read_to_string(test_file).unwrap_or_default();
+    let (class, method) = match test_name.split_once("::") {
+        Some((class, method)) => (Some(class), method),
+        None => (None, test_name),
+    };
+    let mut lines: Vec<String> = content.split_inclusive('\n').map(str::to_string).collect();
+    if let Some(last) = lines.last_mut() {
+        if !last.ends_with('\n') {
+            last.push('\n');
+        }
+    }
+
+    let class_line = class.and_then(|class| {
+        lines.iter().position(|line| {
+            line.strip_prefix("class ")
+                .and_then(|rest| rest.strip_prefix(class))
+                .is_some_and(|rest| rest.starts_with([':', '(']))
+        })
+    });
+    let defines = |line: &str, indent: &str| {
+        line.strip_prefix(indent)
+            .and_then(|line| line.strip_prefix("def "))
+            .and_then(|rest| rest.strip_prefix(method))
+            .is_some_and(|rest| rest.starts_with('('))
+    };
+
+    match (class_line, class) {
+        (Some(start), _) => {
+            // Insert the method at the end of the existing class
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| !line.trim().is_empty() && !line.starts_with([' ', '\t']))
+                .map_or(lines.len(), |offset| start + 1 + offset);
+            if lines[start..end].iter().any(|line| defines(line, "    ")) {
+                return Ok(false);
+            }
+            let mut at = end;
+            while at > start + 1 && lines[at - 1].trim().is_empty() {
+                at -= 1;
+            }
+            let method_stub = stub.split_once('\n').map_or("", |(_, rest)| rest);
+            lines.insert(at, format!("\n{}", method_stub));
+        }
+        (None, None) if lines.iter().any(|line| defines(line, "")) => return Ok(false),
+        _ => {
+            if lines.iter().any(|line| !line.trim().is_empty()) {
+                lines.push("\n\n".to_string());
+            }
+            lines.push(stub.to_string());
+        }
+    }
+
+    if !lines.iter().any(|line| line.trim() == "import pytest") {
+        // After the leading imports (keeping `from __future__` first), or at the top
+        let at = lines
+            .iter()
+            .position(|line| !line.starts_with("import ") && !line.starts_with("from "))
+            .unwrap_or(0);
+        let blank = if at == 0 { "\n\n" } else { "" };
+        lines.insert(at, format!("import pytest\n{}", blank));
+    }
+
+    if let Some(parent) = test_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(test_file, lines.concat())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render("test_load", "unit"),
+            "@pytest.mark.unit\ndef test_load():\n    pytest.skip(\"not implemented yet\")\n"
+        );
+        assert_eq!(
+            render("TestCache::test_get", "e2e"),
+            "class TestCache:\n    @pytest.mark.e2e\n    def test_get(self):\n        pytest.skip(\"not implemented yet\")\n"
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let test_file = dir.join("unit/test_cache.py");
+
+        let get = "TestCache::test_get";
+        assert!(add(&test_file, get, &render(get, "unit")).unwrap());
+        assert!(!add(&test_file, get, &render(get, "unit")).unwrap());
+        let put = "TestCache::test_put";
+        assert!(add(&test_file, put, &render(put, "unit")).unwrap());
+        assert!(add(&test_file, "test_load", &render("test_load", "unit")).unwrap());
+        assert_eq!(
+            fs::read_to_string(&test_file).unwrap(),
+            "import pytest\n\n\nclass TestCache:\n    @pytest.mark.unit\n    def test_get(self):\n        pytest.skip(\"not implemented yet\")\n\n    @pytest.mark.unit\n    def test_put(self):\n        pytest.skip(\"not implemented yet\")\n\n\n@pytest.mark.unit\ndef test_load():\n    pytest.skip(\"not implemented yet\")\n"
+        );
+    }
+}
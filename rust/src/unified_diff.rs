@@ -0,0 +1,155 @@
+//! Unified diffs of the changes the fixer makes (`FixReport.diffs`)
+//!
+//! Lines are compared with Myers' algorithm, whose cost grows with the number of changed
+//! lines rather than the size of the file, which suits fixes inserting a few lines into
+//! large modules. Hunks carry three lines of context, as `diff -u` and `git diff` do.
+
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Unified diff turning `old` into `new`, the contents of `path` before and after; an
+/// `old` of None marks a created file. Empty when the contents are the same.
+pub fn unified(path: &str, old: Option<&str>, new: &str) -> String {
+    let old_lines: Vec<&str> = old.unwrap_or_default().split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let script = edit_script(&old_lines, &new_lines);
+    let changes: Vec<usize> = script
+        .iter()
+        .enumerate()
+        .filter(|(_, (edit, _, _))| *edit != Edit::Equal)
+        .map(|(idx, _)| idx)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let mut diff = format!(
+        "--- {}\n+++ {}\n",
+        if old.is_some() { path } else { "/dev/null" },
+        path
+    );
+    // Changes at most twice the context apart share a hunk
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changes {
+        match groups.last_mut() {
+            Some((_, last)) if idx - *last <= 2 * CONTEXT => *last = idx,
+            _ => groups.push((idx, idx)),
+        }
+    }
+    for (first, last) in groups {
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(script.len());
+        let hunk = &script[start..end];
+        let count = |side: Edit| hunk.iter().filter(|(edit, _, _)| *edit != side).count();
+        let (old_len, new_len) = (count(Edit::Insert), count(Edit::Delete));
+        let (_, old_start, new_start) = hunk[0];
+        // An empty side is numbered after the line it follows, as `diff -u` does
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + usize::from(old_len > 0),
+            old_len,
+            new_start + usize::from(new_len > 0),
+            new_len
+        ));
+        for &(edit, old_idx, new_idx) in hunk {
+            let (marker, line) = match edit {
+                Edit::Equal => (' ', old_lines[old_idx]),
+                Edit::Delete => ('-', old_lines[old_idx]),
+                Edit::Insert => ('+', new_lines[new_idx]),
+            };
+            diff.push(marker);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+    diff
+}
+
+/// Shortest edit script turning `a` into `b`, as (edit, index in `a`, index in `b`) with
+/// the indices of the lines before which each edit happens
+fn edit_script(a: &[&str], b: &[&str]) -> Vec<(Edit, usize, usize)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m + 1;
+    let mut v = vec![0isize; (2 * offset + 1) as usize];
+    let at = |k: isize| (k + offset) as usize;
+
+    // The furthest-reaching path of each diagonal, before each round
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    'search: for d in 0..=(n + m) {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+                v[at(k + 1)]
+            } else {
+                v[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut script = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[at(k - 1)] < v[at(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[at(prev_k)];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            script.push((Edit::Equal, x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                script.push((Edit::Insert, x as usize, prev_y as usize));
+            } else {
+                script.push((Edit::Delete, prev_x as usize, y as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    script.reverse();
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let new = "@x\na\nb\nc\nd\ne\nf\ng\nh\ni\nJ\nk";
+        assert_eq!(
+            unified("m.py", Some(old), new),
+            "--- m.py\n+++ m.py\n@@ -1,3 +1,4 @@\n+@x\n a\n b\n c\n@@ -7,5 +8,5 @@\n g\n h\n i\n-j\n-k\n+J\n+k\n\\ No newline at end of file\n"
+        );
+        assert_eq!(unified("m.py", Some(old), old), "");
+        assert_eq!(
+            unified("t.py", None, "import pytest\n"),
+            "--- /dev/null\n+++ t.py\n@@ -0,0 +1,1 @@\n+import pytest\n"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Local usage statistics (`stats_file`)
+//!
+//! Teams rolling the linter out want to know how often it runs and how long it takes, and
+//! whether the violations go down, without anything leaving the machine. With a
+//! `stats_file`, every run over a whole project appends one JSON line to it: when the run
+//! started, how long it took and how many violations it reported, in total and per rule.
+//! Nothing is uploaded; `RustLinter.stats` summarizes the runs of a project, and other
+//! tooling can aggregate the file line by line.
+//!
+//! Lines are appended with a single write, so concurrent runs do not interleave them, and
+//! lines that cannot be parsed (e.g. cut short by a crash) are skipped when reading.
+
+use pyo3::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::models::LintViolation;
+
+/// One run, as recorded in the stats file
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    /// Version of the linter
+    #[pyo3(get)]
+    pub version: String,
+    #[pyo3(get)]
+    pub project_root: String,
+    /// When the run started, in UTC (`2024-01-31T09:30:00Z`)
+    #[pyo3(get)]
+    pub timestamp: String,
+    #[pyo3(get)]
+    pub duration_seconds: f64,
+    #[pyo3(get)]
+    pub violations: usize,
+    /// Violations per rule id
+    #[pyo3(get)]
+    pub by_rule: HashMap<String, usize>,
+}
+
+impl RunRecord {
+    fn to_value(&self) -> Value {
+        json!({
+            "version": self.version,
+            "project_root": self.project_root,
+            "timestamp": self.timestamp,
+            "duration_seconds": self.duration_seconds,
+            "violations": self.violations,
+            "by_rule": self.by_rule,
+        })
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        let by_rule = value["by_rule"]
+            .as_object()?
+            .iter()
+            .map(|(rule_id, count)| Some((rule_id.clone(), count.as_u64()? as usize)))
+            .collect::<Option<_>>()?;
+        Some(Self {
+            version: value["version"].as_str()?.to_string(),
+            project_root: value["project_root"].as_str()?.to_string(),
+            timestamp: value["timestamp"].as_str()?.to_string(),
+            duration_seconds: value["duration_seconds"].as_f64()?,
+            violations: value["violations"].as_u64()? as usize,
+            by_rule,
+        })
+    }
+}
+
+#[pymethods]
+impl RunRecord {
+    fn __repr__(&self) -> String {
+        format!(
+            "RunRecord(timestamp='{}', duration_seconds={:.3}, violations={})",
+            self.timestamp, self.duration_seconds, self.violations
+        )
+    }
+}
+
+/// The runs of a project recorded in the stats file (`RustLinter.stats`)
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct UsageStats {
+    /// The runs, oldest first
+    #[pyo3(get)]
+    pub history: Vec<RunRecord>,
+}
+
+#[pymethods]
+impl UsageStats {
+    #[getter]
+    fn runs(&self) -> usize {
+        self.history.len()
+    }
+
+    #[getter]
+    fn total_duration_seconds(&self) -> f64 {
+        self.history.iter().map(|run| run.duration_seconds).sum()
+    }
+
+    /// Mean duration of a run; 0 without runs
+    #[getter]
+    fn mean_duration_seconds(&self) -> f64 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        self.total_duration_seconds() / self.history.len() as f64
+    }
+
+    #[getter]
+    fn first_run(&self) -> Option<String> {
+        self.history.first().map(|run| run.timestamp.clone())
+    }
+
+    #[getter]
+    fn last_run(&self) -> Option<String> {
+        self.history.last().map(|run| run.timestamp.clone())
+    }
+
+    /// Violations reported by each run, as (timestamp, count), oldest first
+    #[getter]
+    fn violation_trend(&self) -> Vec<(String, usize)> {
+        self.history
+            .iter()
+            .map(|run| (run.timestamp.clone(), run.violations))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "UsageStats(runs={}, mean_duration_seconds={:.3}, last_run={})",
+            self.history.len(),
+            self.mean_duration_seconds(),
+            self.history
+                .last()
+                .map_or("None".to_string(), |run| format!("'{}'", run.timestamp))
+        )
+    }
+}
+
+/// Violations per rule id, leaving out suppressed ones and the records of satisfied rules
+pub fn count_by_rule(violations: &[LintViolation], by_rule: &mut HashMap<String, usize>) {
+    for violation in violations {
+        if !violation.suppressed && !violation.satisfied {
+            *by_rule.entry(violation.rule_id().to_string()).or_default() += 1;
+        }
+    }
+}
+
+/// Append `record` to the stats file at `path`, creating it (and its directory) if needed
+pub fn append(path: &Path, record: &RunRecord) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format!("{}\n", record.to_value()).as_bytes())
+}
+
+/// The runs of the project at `project_root` recorded in the stats file at `path`; none
+/// when there is no file yet
+pub fn load(path: &Path, project_root: &Path) -> io::Result<UsageStats> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(UsageStats::default()),
+        Err(e) => return Err(e),
+    };
+    let project_root = project_root.to_string_lossy();
+    let history = content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|value| RunRecord::from_value(&value))
+        .filter(|run| run.project_root == project_root)
+        .collect();
+    Ok(UsageStats { history })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_load() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("stats/runs.ndjson");
+        let run = |project_root: &str, timestamp: &str, violations: usize| RunRecord {
+            version: "1.0.0".to_string(),
+            project_root: project_root.to_string(),
+            timestamp: timestamp.to_string(),
+            duration_seconds: 1.5,
+            violations,
+            by_rule: HashMap::from([("PL001".to_string(), violations)]),
+        };
+        append(&path, &run("/repo", "2024-01-01T09:00:00Z", 12)).unwrap();
+        append(&path, &run("/other", "2024-01-01T09:30:00Z", 3)).unwrap();
+        // A line cut short by a crash
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"version\": \"1.0\n")
+            .unwrap();
+        append(&path, &run("/repo", "2024-01-02T09:00:00Z", 8)).unwrap();
+
+        let stats = load(&path, Path::new("/repo")).unwrap();
+        assert_eq!(
+            stats.history,
+            vec![
+                run("/repo", "2024-01-01T09:00:00Z", 12),
+                run("/repo", "2024-01-02T09:00:00Z", 8)
+            ]
+        );
+        assert_eq!(stats.mean_duration_seconds(), 1.5);
+        assert_eq!(
+            stats.violation_trend()[1],
+            ("2024-01-02T09:00:00Z".to_string(), 8)
+        );
+        assert!(load(&dir.join("missing.ndjson"), Path::new("/repo"))
+            .unwrap()
+            .history
+            .is_empty());
+    }
+}
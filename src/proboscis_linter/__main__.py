@@ -1,8 +1,14 @@
-from .cli import cli
+import sys
+
+from .cli import cli, init
 
 
 def main():
-    cli()
+    # `proboscis-lint init [PATH]` scaffolds the configuration; anything else lints
+    if sys.argv[1:2] == ["init"]:
+        init(sys.argv[2:])
+    else:
+        cli()
 
 
 if __name__ == "__main__":
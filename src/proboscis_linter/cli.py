@@ -1,12 +1,12 @@
 import sys
+import time
 from pathlib import Path
 import click
 from loguru import logger
 
 from .linter import ProboscisLinter
-from .report_generator import TextReportGenerator, JsonReportGenerator
-from .config import ProboscisConfig, ConfigLoader
-from .auto_fix import AutoFixer
+from .report_generator import JsonReportGenerator
+from .config import CONFIG_FILES, ProboscisConfig, ConfigLoader, read_proboscis_section
 
 # Version info
 __version__ = "0.1.0"
@@ -41,6 +41,9 @@ EXAMPLES:
   
   # Verbose output for debugging
   proboscis-linter . -v
+  
+  # Write a [tool.proboscis] section tailored to the project
+  proboscis-linter init .
 
 \b
 RULES:
@@ -55,6 +58,45 @@ RULES:
     
   PL004: require-test-markers
     Ensures test functions have appropriate pytest markers (@pytest.mark.unit/integration/e2e)
+    
+  PL022: skipped-test-only
+    Warns when every test of a function is marked skip or non-strict xfail
+    
+  PL023: test-naming (opt-in)
+    Ensures test function names follow the configured naming convention
+    
+  PL024: require-test-docstring (opt-in)
+    Ensures test functions have a one-line docstring describing the behavior under test
+    
+  PL025: test-too-long (opt-in)
+    Flags test functions with too many body lines or assertions
+    
+  PL026: no-sleep-in-tests
+    Flags time.sleep() calls in unit and integration tests
+    
+  PL027: no-external-access (opt-in)
+    Flags network imports and non-temporary open() calls in unit tests
+    
+  PL028: conftest-placement
+    Flags conftest.py files outside test directories and clashing conftest fixtures
+    
+  PL029: test-package-consistency
+    Flags sibling test directories that disagree on having an __init__.py
+    
+  PL030: public-api-imports (opt-in)
+    Flags tests importing private modules (pkg._internal) instead of the public API
+    
+  PL031: tautological-assertion
+    Flags assertions that cannot fail (x == x, constants, a mock's own return value)
+    
+  PL032: empty-test-file (opt-in)
+    Flags test files pytest collects no tests from (e.g. only helpers)
+    
+  PL033: untested-without-type-hints (opt-in)
+    Flags public functions with neither a unit test nor full type annotations
+    
+  PL034: noqa-density
+    Flags modules where most functions suppress rules with noqa
 
 \b
 TEST NAMING CONVENTIONS:
@@ -64,7 +106,8 @@ TEST NAMING CONVENTIONS:
   Expected tests: test_process_data, test_process_data_*
   
   For a method: class DataProcessor: def process(self):
-  Expected tests: test_process, test_DataProcessor_process, test_process_*
+  Expected tests: test_process, test_DataProcessor_process, test_process_*,
+  or test_process in a class TestDataProcessor
   
   Test files should mirror the source structure:
   src/module.py → test/unit/test_module.py
@@ -84,7 +127,8 @@ SUPPRESSING VIOLATIONS:
 
 \b
 CONFIGURATION:
-  Create a pyproject.toml file with [tool.proboscis] section:
+  Run `proboscis-linter init` to scaffold one, or create a pyproject.toml file
+  with [tool.proboscis] section:
   
   [tool.proboscis]
   test_directories = ["test", "tests"]
@@ -95,6 +139,15 @@ CONFIGURATION:
   PL002 = false  # Disable integration test requirement
   PL003 = true
 
+\b
+EXIT CODES:
+  0  no violations (or --no-fail-on-error)
+  1  violations found, errors or only warnings
+  2  invalid configuration
+  3  linting failed
+  Change them under [tool.proboscis.exit_codes], e.g. warnings = 0 to pass
+  runs with only warnings
+
 \b
 For more information, visit: https://github.com/proboscis/proboscis-linter
 """
@@ -112,10 +165,16 @@ For more information, visit: https://github.com/proboscis/proboscis-linter
     help="Output format for violations report. 'text' for human-readable output, 'json' for machine-parseable output.",
     show_default=True
 )
+@click.option(
+    "--color",
+    type=click.Choice(["auto", "always", "never"], case_sensitive=False),
+    default=None,
+    help="Color the text output. 'auto' colors when writing to a terminal and NO_COLOR is not set."
+)
 @click.option(
     "--fail-on-error/--no-fail-on-error",
     default=None,
-    help="Exit with non-zero status code (1) if any violations are found. Useful for CI/CD pipelines."
+    help="Exit with non-zero status code (1, or the code configured in exit_codes) if any violations are found. Useful for CI/CD pipelines."
 )
 @click.option(
     "--exclude", "-e",
@@ -136,7 +195,12 @@ For more information, visit: https://github.com/proboscis/proboscis-linter
 @click.option(
     "--fix",
     is_flag=True,
-    help="Automatically fix violations when possible. Currently supports adding missing pytest markers for PL004."
+    help="Automatically fix violations when possible. Applies the safe fixes: missing pytest markers for PL004, TODO comments and docstrings (e.g. PL024)."
+)
+@click.option(
+    "--unsafe-fixes",
+    is_flag=True,
+    help="With --fix, also apply the unsafe fixes, which create files and may need review (e.g. the missing test packages of PL005 and PL029)."
 )
 @click.version_option(
     __version__,
@@ -144,7 +208,7 @@ For more information, visit: https://github.com/proboscis/proboscis-linter
     message="%(prog)s version %(version)s",
     help="Show the version and exit."
 )
-def cli(path: Path, format: str, fail_on_error: bool, exclude: tuple, verbose: bool, changed_only: bool, fix: bool):
+def cli(path: Path, format: str, color: str, fail_on_error: bool, exclude: tuple, verbose: bool, changed_only: bool, fix: bool, unsafe_fixes: bool):
     """
     Proboscis Linter - A fast, Rust-powered linter that ensures all Python functions have corresponding tests.
     
@@ -161,25 +225,43 @@ def cli(path: Path, format: str, fail_on_error: bool, exclude: tuple, verbose: b
         logger.remove()
         logger.add(sys.stderr, level="DEBUG")
     
-    # Load configuration
     config_file = ConfigLoader.find_config_file(path)
-    if config_file:
-        config = ConfigLoader.load_from_file(config_file)
-    else:
-        config = ProboscisConfig()
-    
-    # Merge CLI options
-    config = ConfigLoader.merge_cli_options(
-        config,
-        format=format,
-        fail_on_error=fail_on_error,
-        exclude=list(exclude) if exclude else None
-    )
+    try:
+        # Load configuration, rejecting invalid settings rather than linting with defaults
+        config = ConfigLoader.load_from_file(config_file, strict=True) if config_file else ProboscisConfig()
+        
+        # Merge CLI options
+        config = ConfigLoader.merge_cli_options(
+            config,
+            format=format,
+            color=color,
+            fail_on_error=fail_on_error,
+            exclude=list(exclude) if exclude else None
+        )
+        
+        # Create linter with configuration (uses Rust implementation by default)
+        linter = ProboscisLinter(config)
+    except ValueError as e:
+        click.echo(f"Error: invalid configuration: {e}", err=True)
+        sys.exit(ConfigLoader.config_error_exit_code(config_file))
     
-    # Create linter with configuration (uses Rust implementation by default)
-    linter = ProboscisLinter(config)
+    try:
+        violations = _run(linter, config, path, changed_only, fix, unsafe_fixes)
+    except Exception as e:
+        logger.exception(f"Linting failed: {e}")
+        sys.exit(linter.exit_code_for("internal_error"))
     
+    # Exit with the code configured for the outcome
+    if config.fail_on_error:
+        exit_code = linter.exit_code(violations)
+        if exit_code:
+            sys.exit(exit_code)
+
+
+def _run(linter: ProboscisLinter, config: ProboscisConfig, path: Path, changed_only: bool, fix: bool, unsafe_fixes: bool) -> list:
+    """Lint `path`, apply fixes if requested and print the report, returning the violations."""
     # Lint the project
+    started = time.monotonic()
     if changed_only:
         logger.info(f"Linting changed files in {path}...")
         violations = linter.lint_changed_files(path)
@@ -190,11 +272,13 @@ def cli(path: Path, format: str, fail_on_error: bool, exclude: tuple, verbose: b
     # Apply fixes if requested
     if fix and violations:
         logger.info("Applying automatic fixes...")
-        fixer = AutoFixer()
-        fixes_applied = fixer.apply_fixes(violations)
+        fix_report = linter.apply_fixes(violations, "unsafe" if unsafe_fixes else "safe")
+        unsafe_skipped = sum(1 for skipped in fix_report.skipped if skipped.reason == "unsafe")
+        if unsafe_skipped:
+            logger.info(f"{unsafe_skipped} unsafe fix(es) not applied; rerun with --unsafe-fixes to apply them")
         
         # Re-lint to get updated violations after fixes
-        if fixes_applied:
+        if fix_report.fixes_applied:
             logger.info("Re-linting after applying fixes...")
             if changed_only:
                 violations = linter.lint_changed_files(path)
@@ -202,21 +286,56 @@ def cli(path: Path, format: str, fail_on_error: bool, exclude: tuple, verbose: b
                 violations = linter.lint_project(path)
             
             # Log summary of fixes
-            for file_path, count in fixes_applied.items():
+            for file_path, count in fix_report.applied.items():
                 logger.info(f"Fixed {count} violation(s) in {file_path}")
     
     # Generate report
     if config.output_format == "json":
-        generator = JsonReportGenerator()
+        metadata = linter.run_metadata(path, time.monotonic() - started)
+        report = JsonReportGenerator().generate_report(violations, metadata)
     else:
-        generator = TextReportGenerator()
+        # Paths are shown relative to the directory linted
+        report = linter.format_text(violations, path if path.is_dir() else path.parent)
+    # The renderer already left colors out where they do not belong
+    click.echo(report, color=True)
+    return violations
+
+
+@click.command(
+    name="proboscis-lint init",
+    context_settings=dict(help_option_names=["-h", "--help"]),
+)
+@click.argument(
+    "path",
+    type=click.Path(exists=True, file_okay=False, path_type=Path),
+    default=".",
+    metavar="[PATH]"
+)
+def init(path: Path):
+    """
+    Write a [tool.proboscis] section tailored to the project to its pyproject.toml.
+    
+    \b
+    The project is inspected for its test directories, the test types it has
+    (unit, integration, e2e), its package roots and the pytest markers its tests
+    use. Rules for test types without tests yet are switched off, and the markers
+    pytest lacks are registered under [tool.pytest.ini_options].
+    
+    PATH: Project root (defaults to current directory)
+    """
+    for name in CONFIG_FILES[1:]:
+        config_file = path / name
+        if config_file.exists() and read_proboscis_section(config_file) is not None:
+            raise click.ClickException(f"{config_file} already configures proboscis-linter")
     
-    report = generator.generate_report(violations)
-    click.echo(report)
+    linter = ProboscisLinter(ProboscisConfig())
+    try:
+        added = linter.init_config(path)
+    except FileExistsError as e:
+        raise click.ClickException(str(e))
     
-    # Exit with appropriate code
-    if config.fail_on_error and violations:
-        sys.exit(1)
+    click.echo(f"Added to {path / 'pyproject.toml'}:\n")
+    click.echo(added)
 
 
 if __name__ == "__main__":
@@ -1,6 +1,7 @@
 """Configuration management for proboscis-linter."""
+import configparser
 from pathlib import Path
-from typing import Dict, List, Optional, Any
+from typing import Dict, List, Literal, Optional, Any, get_args, get_origin
 import tomllib
 from pydantic import BaseModel, Field, field_validator
 from loguru import logger
@@ -10,11 +11,37 @@ class RuleConfig(BaseModel):
     """Configuration for individual rules."""
     enabled: bool = True
     options: Dict[str, Any] = Field(default_factory=dict)
+    
+    @field_validator("options")
+    @classmethod
+    def validate_option_values(cls, v: Dict[str, Any]) -> Dict[str, Any]:
+        """Rules read booleans, numbers, strings and lists of strings; reject anything else, such as tables."""
+        for key, value in v.items():
+            if isinstance(value, (bool, int, float, str)):
+                continue
+            if isinstance(value, list) and all(isinstance(item, str) for item in value):
+                continue
+            raise ValueError(
+                f"Invalid value of rule option {key!r}: {value!r}. "
+                "Must be a boolean, number, string or list of strings"
+            )
+        return v
 
 
 class ProboscisConfig(BaseModel):
     """Configuration model for proboscis-linter."""
     
+    # Strictness preset supplying the defaults of strict_mode, qualified_test_names and rules
+    profile: Literal["minimal", "standard", "strict"] = Field(
+        default="standard",
+        description="Preset: minimal (PL001 only, as warnings), standard, or strict (every rule)"
+    )
+    # Framework rule pack layered under the settings below
+    framework: Optional[Literal["airflow", "django", "fastapi", "flask"]] = Field(
+        default=None,
+        description="Rule pack adjusting exclusions, test locations and rule options to a framework"
+    )
+    
     # Test discovery configuration
     test_directories: List[str] = Field(
         default_factory=lambda: ["test", "tests"],
@@ -30,27 +57,207 @@ class ProboscisConfig(BaseModel):
         default_factory=list,
         description="Glob patterns for files/directories to exclude from linting"
     )
+    exclude_modules: List[str] = Field(
+        default_factory=list,
+        description="Dotted module patterns to exclude from linting, e.g. '*.migrations.*'"
+    )
+    declared_packages_only: bool = Field(
+        default=False,
+        description="Only lint the packages pyproject.toml declares (setuptools, poetry, hatch, flit or [project] name)"
+    )
+    
+    # Source layout
+    source_roots: Optional[List[str]] = Field(
+        default=None,
+        description="Directories containing top-level packages (default: setuptools config, else src)"
+    )
+    stub_bodies: List[str] = Field(
+        default_factory=lambda: ["...", "raise NotImplementedError"],
+        description="Bodies marking @overload and abstract methods as stubs that no rule checks"
+    )
+    function_patterns: List[str] = Field(
+        default_factory=list,
+        description="Extra function-definition regexes, each capturing the name as (?P<name>...)"
+    )
+    
+    # Scripts
+    script_directories: List[str] = Field(
+        default_factory=lambda: ["scripts", "bin"],
+        description="Directories of one-off scripts, matched like test_directories"
+    )
+    script_mode: Literal["exempt", "downgrade", "check"] = Field(
+        default="exempt",
+        description="How top-level functions of scripts (and files with a shebang) are checked"
+    )
+    example_directories: List[str] = Field(
+        default_factory=lambda: ["examples", "docs", "samples"],
+        description="Directories of example and documentation code, matched like test_directories"
+    )
+    example_mode: Literal["skip", "warn", "full"] = Field(
+        default="skip",
+        description="How example code is checked: skipped, reported as warnings, or fully"
+    )
+    experimental_decorators: List[str] = Field(
+        default_factory=lambda: ["experimental"],
+        description="Decorators marking functions as experimental, e.g. 'api.experimental'"
+    )
+    lifecycle_mode: Literal["exempt", "downgrade", "check"] = Field(
+        default="check",
+        description="How deprecated (@deprecated, warn(DeprecationWarning)) and experimental functions are checked"
+    )
+    
+    # Airflow DAGs: @dag/@task functions and top-level functions of DAG files
+    dag_directories: List[str] = Field(
+        default_factory=lambda: ["dags"],
+        description="Directories of Airflow DAG files, matched like test_directories"
+    )
+    dag_mode: Optional[Literal["exempt", "e2e", "downgrade", "check"]] = Field(
+        default=None,
+        description="How DAG functions are checked: e2e requires PL003 only (default: e2e with the airflow framework, else check)"
+    )
+    
+    # Test types required of decorated functions, e.g. {"shared_task": ["integration"]}
+    decorator_test_types: Dict[str, List[Literal["unit", "integration", "e2e"]]] = Field(
+        default_factory=dict,
+        description="Decorator patterns mapped to the test types their functions need; an empty list exempts them"
+    )
+    # Test types required of modules with at least so many public symbols, e.g. {5: ["unit"]}
+    surface_test_types: Dict[int, List[Literal["unit", "integration", "e2e"]]] = Field(
+        default_factory=dict,
+        description="Numbers of public symbols mapped to the test types the functions of modules with at least that many need"
+    )
+    credit_delegation: bool = Field(
+        default=False,
+        description="Credit functions that only return a call to another project function with that function's tests"
+    )
+    
+    # Suggested test names
+    test_name_template: str = Field(
+        default="test_{function}",
+        description="Test name violations recommend for functions, with a {function} placeholder"
+    )
+    method_test_name_template: Optional[str] = Field(
+        default=None,
+        description="Test name violations recommend for methods, e.g. 'test_{class}__{function}' (default: from test_suggestion_style)"
+    )
+    test_suggestion_style: Literal["function", "class"] = Field(
+        default="function",
+        description="Recommend tests of methods as test_Class_method functions, or as methods of a TestClass class"
+    )
+    
+    # Advisory checks
+    check_test_skeleton: bool = Field(
+        default=False,
+        description="Report missing test directories and __init__.py files as PL005"
+    )
+    aggregate_missing_tests: bool = Field(
+        default=False,
+        description="Report one PL00X:missing-tests violation per function instead of PL001-PL003"
+    )
+    
+    # Severity escalation
+    escalate_file_violations: Optional[int] = Field(
+        default=None,
+        description="Raise the severity of every violation in a file with more than this many violations"
+    )
+    escalate_untested_functions: bool = Field(
+        default=False,
+        description="Raise the severity of the violations of functions with no unit, integration or e2e test"
+    )
+    
+    # Central exemptions
+    allowlist: Dict[str, List[str]] = Field(
+        default_factory=dict,
+        description="Rule ids each fully qualified function (pkg.mod.func, pkg.mod.Class.method) is exempt from"
+    )
+    allowlist_file: Optional[str] = Field(
+        default=None,
+        description="File of allowlist entries, one 'pkg.mod.func PL001, PL002' per line, relative to the config file"
+    )
+    
+    require_noqa_reason: bool = Field(
+        default=False,
+        description="Report noqa comments without a reason, e.g. '# noqa: PL001 -- covered by contract tests'"
+    )
+    
+    # Performance
+    file_time_limit: float = Field(
+        default=10.0,
+        description="Seconds to spend linting one file before reporting it as PL000 (inf: unlimited)"
+    )
+    max_depth: int = Field(
+        default=32,
+        description="Directory levels below the project root that source discovery walks (0: unlimited)"
+    )
+    max_files: int = Field(
+        default=100000,
+        description="Python files source discovery finds before it stops (0: unlimited)"
+    )
+    cache_dir: Optional[str] = Field(
+        default=None,
+        description="Directory, relative to the project root, caching what was read from each test file between runs"
+    )
+    stats_file: Optional[str] = Field(
+        default=None,
+        description="File, relative to the project root, recording the duration and violation counts of each run (never uploaded)"
+    )
+    # Exit code of each outcome, e.g. {"warnings": 0} to pass builds with only warnings
+    exit_codes: Dict[Literal["success", "warnings", "violations", "config_error", "internal_error"], int] = Field(
+        default_factory=dict,
+        description="Exit code of each outcome: success, warnings, violations, config_error and internal_error (default: 0, 1, 1, 2, 3)"
+    )
     
     # Rule configuration
     rules: Dict[str, RuleConfig] = Field(
         default_factory=dict,
         description="Rule-specific configuration"
     )
+    # Precedence: disabled_rules and rules switched off, then enabled_rules, then rules
+    # switched on and the profile (the same as RustLinter's, see is_rule_enabled)
+    enabled_rules: Optional[List[str]] = Field(
+        default=None,
+        description="Run only these rules, whatever the profile enables; disabled rules still do not run (default: those of the profile and rules)"
+    )
+    disabled_rules: List[str] = Field(
+        default_factory=list,
+        description="Rules not to run, like PL002 = false under rules"
+    )
+    rule_versions: Dict[str, int] = Field(
+        default_factory=dict,
+        description="Behavior version to run each rule at, e.g. {PL001 = 2} (default: each rule's default version)"
+    )
     
     # Output configuration
     output_format: str = Field(
         default="text",
         description="Default output format (text or json)"
     )
+    color: Literal["auto", "always", "never"] = Field(
+        default="auto",
+        description="Color the text output: auto colors when writing to a terminal and NO_COLOR is not set"
+    )
     fail_on_error: bool = Field(
         default=False,
         description="Exit with non-zero code if violations are found"
     )
     
+    docs_url: Optional[str] = Field(
+        default=None,
+        description="Template of the rule documentation links attached to violations, with {rule_id}, {rule_name} and {anchor} placeholders"
+    )
+    context_lines: Optional[int] = Field(
+        default=None,
+        description="Attach the source from this many lines above each offending definition (decorators included) to as many below to violations (default: none)"
+    )
+    
     # Visibility configuration
-    strict_mode: bool = Field(
-        default=False,
-        description="Check private functions/methods (with _ prefix) in addition to public ones"
+    strict_mode: Optional[bool] = Field(
+        default=None,
+        description="Check private functions/methods (with _ prefix) in addition to public ones (default: from the profile)"
+    )
+    qualified_test_names: Optional[bool] = Field(
+        default=None,
+        description="Only accept tests of methods named after their class, e.g. test_Job_run (default: from the profile)"
     )
     
     @field_validator("output_format")
@@ -61,6 +268,40 @@ class ProboscisConfig(BaseModel):
             raise ValueError(f"Invalid output format: {v}. Must be 'text' or 'json'")
         return v
     
+    @field_validator("rules", "rule_versions", "enabled_rules", "disabled_rules")
+    @classmethod
+    def validate_rule_ids(cls, v: Any) -> Any:
+        """Reject rule ids the linter does not know, so typos do not pass silently."""
+        try:
+            from .proboscis_linter_rust import rules as registry
+        except ImportError:
+            # Without the extension there is no registry to check against
+            return v
+        known = [rule.rule_id for rule in registry.all()]
+        unknown = sorted(rule_id for rule_id in v or () if rule_id not in known)
+        if unknown:
+            raise ValueError(
+                f"Unknown rule id(s): {', '.join(unknown)}. Known rules: {', '.join(known)}"
+            )
+        return v
+    
+    @field_validator("exit_codes")
+    @classmethod
+    def validate_exit_codes(cls, v: Dict[str, int]) -> Dict[str, int]:
+        """Exit codes must fit in the byte a process exits with."""
+        invalid = sorted(outcome for outcome, code in v.items() if not 0 <= code <= 255)
+        if invalid:
+            raise ValueError(f"Exit codes must be between 0 and 255: {', '.join(invalid)}")
+        return v
+    
+    @field_validator("context_lines")
+    @classmethod
+    def validate_context_lines(cls, v: Optional[int]) -> Optional[int]:
+        """A negative number of context lines means nothing."""
+        if v is not None and v < 0:
+            raise ValueError(f"context_lines cannot be negative: {v}")
+        return v
+    
     @field_validator("test_directories", "test_patterns")
     @classmethod
     def validate_non_empty_list(cls, v: List[str]) -> List[str]:
@@ -69,11 +310,40 @@ class ProboscisConfig(BaseModel):
             raise ValueError("List cannot be empty")
         return v
     
+    def _rule_switch(self, rule_id: str) -> Optional[bool]:
+        """Whether a rule is listed in disabled_rules or switched on or off under rules;
+        None when it is left to enabled_rules and the profile."""
+        if rule_id in self.disabled_rules:
+            return False
+        if rule_id in self.rules:
+            return self.rules[rule_id].enabled
+        return None
+    
     def is_rule_enabled(self, rule_id: str) -> bool:
-        """Check if a rule is enabled."""
-        if rule_id not in self.rules:
-            return True  # Rules are enabled by default
-        return self.rules[rule_id].enabled
+        """Check if a rule is enabled: not disabled, and listed in enabled_rules when that
+        is set, else switched on or enabled by the profile."""
+        switched = self._rule_switch(rule_id)
+        if switched is False:
+            return False
+        if self.enabled_rules is not None:
+            # PL000 reports on the run itself
+            return rule_id in self.enabled_rules or rule_id == "PL000"
+        if switched is None:
+            # Rules are enabled by default, except outside PL001 in the minimal profile;
+            # PL000 reports on the run itself
+            return self.profile != "minimal" or rule_id in ("PL000", "PL001")
+        return switched
+    
+    def is_rule_opted_in(self, rule_id: str) -> bool:
+        """Check if an opt-in rule runs: enabled explicitly, or by the strict profile."""
+        switched = self._rule_switch(rule_id)
+        if switched is False:
+            return False
+        if self.enabled_rules is not None:
+            return rule_id in self.enabled_rules
+        if switched is None:
+            return self.profile == "strict"
+        return switched
     
     def get_rule_options(self, rule_id: str) -> Dict[str, Any]:
         """Get options for a specific rule."""
@@ -82,26 +352,143 @@ class ProboscisConfig(BaseModel):
         return self.rules[rule_id].options
 
 
+# Files read for configuration, in order of precedence within a directory
+CONFIG_FILES = ("pyproject.toml", "setup.cfg", "tox.ini")
+
+
+def _split_list(value: str) -> List[str]:
+    """Split an INI list value on newlines and commas."""
+    return [item.strip() for line in value.splitlines() for item in line.split(",") if item.strip()]
+
+
+def _is_list_field(name: str) -> bool:
+    """Whether a ProboscisConfig field holds a list (possibly Optional)."""
+    field = ProboscisConfig.model_fields.get(name)
+    if field is None:
+        return False
+    annotation = field.annotation
+    return get_origin(annotation) is list or any(
+        get_origin(arg) is list for arg in get_args(annotation)
+    )
+
+
+def _parse_ini_option(value: str) -> Any:
+    """Parse a rule option from an INI file: booleans, numbers, newline-separated lists."""
+    value = value.strip()
+    if value.lower() in ("true", "false"):
+        return value.lower() == "true"
+    for convert in (int, float):
+        try:
+            return convert(value)
+        except ValueError:
+            pass
+    if "\n" in value:
+        return [line.strip() for line in value.splitlines() if line.strip()]
+    return value
+
+
+def _read_ini_section(config_path: Path) -> Optional[Dict[str, Any]]:
+    """Read the [proboscis] sections of a setup.cfg or tox.ini file.
+    
+    `[proboscis]` holds the settings, with lists separated by newlines or commas;
+    `[proboscis.rules]` switches rules on and off (`PL002 = false`),
+    `[proboscis.rules.PL001]` sets `enabled` and the options of one rule, and
+    `[proboscis.rule_versions]` pins rule versions (`PL001 = 2`),
+    `[proboscis.allowlist]` exempts functions from rules (`pkg.mod.func = PL001, PL002`), and
+    `[proboscis.decorator_test_types]` sets the test types of decorated functions
+    (`shared_task = integration`), and `[proboscis.surface_test_types]` those of modules by
+    their number of public symbols (`5 = unit, integration`), and `[proboscis.exit_codes]`
+    the exit code of each outcome (`warnings = 0`).
+    """
+    parser = configparser.ConfigParser(interpolation=None)
+    parser.optionxform = str  # Keep rule ids and option names as written
+    parser.read(config_path, encoding="utf-8")
+    if not parser.has_section("proboscis"):
+        return None
+    
+    data: Dict[str, Any] = {}
+    for key, value in parser.items("proboscis"):
+        data[key] = _split_list(value) if _is_list_field(key) else value
+    
+    rules: Dict[str, Any] = {}
+    if parser.has_section("proboscis.rules"):
+        for rule_id, value in parser.items("proboscis.rules"):
+            rules[rule_id] = _parse_ini_option(value)
+    for section in parser.sections():
+        if section.startswith("proboscis.rules."):
+            rule_id = section[len("proboscis.rules."):]
+            options = {key: _parse_ini_option(value) for key, value in parser.items(section)}
+            enabled = options.pop("enabled", True)
+            rules[rule_id] = {"enabled": enabled, "options": options}
+    if rules:
+        data["rules"] = rules
+    if parser.has_section("proboscis.rule_versions"):
+        data["rule_versions"] = {
+            rule_id: int(version) for rule_id, version in parser.items("proboscis.rule_versions")
+        }
+    if parser.has_section("proboscis.allowlist"):
+        data["allowlist"] = {
+            name: _split_list(rules) for name, rules in parser.items("proboscis.allowlist")
+        }
+    if parser.has_section("proboscis.decorator_test_types"):
+        data["decorator_test_types"] = {
+            decorator: _split_list(types)
+            for decorator, types in parser.items("proboscis.decorator_test_types")
+        }
+    if parser.has_section("proboscis.surface_test_types"):
+        data["surface_test_types"] = {
+            int(symbols): _split_list(types)
+            for symbols, types in parser.items("proboscis.surface_test_types")
+        }
+    if parser.has_section("proboscis.exit_codes"):
+        data["exit_codes"] = {
+            outcome: int(code) for outcome, code in parser.items("proboscis.exit_codes")
+        }
+    return data
+
+
+def read_proboscis_section(config_path: Path) -> Optional[Dict[str, Any]]:
+    """Read the proboscis settings of a config file: `[tool.proboscis]` of a
+    pyproject.toml, or the `[proboscis]` sections of a setup.cfg or tox.ini file.
+    
+    Returns None when the file has no such section.
+    """
+    if config_path.suffix == ".toml":
+        with open(config_path, "rb") as f:
+            data = tomllib.load(f)
+        return data.get("tool", {}).get("proboscis")
+    return _read_ini_section(config_path)
+
+
+# Exit code of a rejected configuration when the configuration cannot say otherwise
+DEFAULT_CONFIG_ERROR_EXIT_CODE = 2
+
+
+class ConfigError(ValueError):
+    """A configuration file that cannot be read or holds invalid settings."""
+
+
 class ConfigLoader:
-    """Loads configuration from pyproject.toml."""
+    """Loads configuration from pyproject.toml, setup.cfg or tox.ini."""
     
     @staticmethod
-    def load_from_file(config_path: Path) -> ProboscisConfig:
-        """Load configuration from a pyproject.toml file."""
+    def load_from_file(config_path: Path, strict: bool = False) -> ProboscisConfig:
+        """Load configuration from a pyproject.toml, setup.cfg or tox.ini file.
+        
+        A file that cannot be loaded raises `ConfigError` with `strict`, and otherwise
+        leaves the defaults in place.
+        """
         with logger.contextualize(config_file=str(config_path)):
             if not config_path.exists():
-                logger.debug("No pyproject.toml found, using defaults")
+                logger.debug(f"No {config_path.name} found, using defaults")
                 return ProboscisConfig()
             
             try:
-                with open(config_path, "rb") as f:
-                    data = tomllib.load(f)
-                
                 # Extract proboscis configuration
-                proboscis_data = data.get("tool", {}).get("proboscis", {})
+                proboscis_data = read_proboscis_section(config_path)
                 
                 if not proboscis_data:
-                    logger.debug("No [tool.proboscis] section found, using defaults")
+                    logger.debug(f"No proboscis section found in {config_path.name}, using defaults")
                     return ProboscisConfig()
                 
                 # Convert rule configuration
@@ -118,28 +505,51 @@ class ConfigLoader:
                 
                 proboscis_data["rules"] = rules_config
                 
+                # The allowlist file is relative to the config file
+                if proboscis_data.get("allowlist_file"):
+                    proboscis_data["allowlist_file"] = str(
+                        config_path.parent / proboscis_data["allowlist_file"]
+                    )
+                
                 config = ProboscisConfig(**proboscis_data)
-                logger.info("Loaded configuration from pyproject.toml")
+                logger.info(f"Loaded configuration from {config_path.name}")
                 return config
                 
             except Exception as e:
+                if strict:
+                    raise ConfigError(f"{config_path}: {e}") from e
                 logger.error(f"Failed to load configuration: {e}")
                 logger.info("Using default configuration")
                 return ProboscisConfig()
     
+    @staticmethod
+    def config_error_exit_code(config_path: Optional[Path]) -> int:
+        """The exit code `config_path` sets for a rejected configuration, read without
+        validating the rest of it, since that is what may have been rejected."""
+        try:
+            data = read_proboscis_section(config_path) if config_path else None
+            code = (data or {}).get("exit_codes", {}).get("config_error")
+        except Exception:
+            code = None
+        if isinstance(code, int) and not isinstance(code, bool) and 0 <= code <= 255:
+            return code
+        return DEFAULT_CONFIG_ERROR_EXIT_CODE
+    
     @staticmethod
     def find_config_file(start_path: Path) -> Optional[Path]:
-        """Find pyproject.toml by traversing up the directory tree."""
+        """Find the nearest config file with a proboscis section by traversing up the
+        directory tree; within a directory pyproject.toml wins over setup.cfg, and
+        setup.cfg over tox.ini."""
         current = start_path.resolve()
         
         while current != current.parent:
-            config_file = current / "pyproject.toml"
-            if config_file.exists():
-                # Check if it has [tool.proboscis] section
+            for name in CONFIG_FILES:
+                config_file = current / name
+                if not config_file.exists():
+                    continue
+                # Check if it has a proboscis section
                 try:
-                    with open(config_file, "rb") as f:
-                        data = tomllib.load(f)
-                    if "tool" in data and "proboscis" in data["tool"]:
+                    if read_proboscis_section(config_file) is not None:
                         logger.debug(f"Found configuration at {config_file}")
                         return config_file
                 except Exception:
@@ -159,6 +569,9 @@ class ConfigLoader:
         if cli_options.get("format") is not None:
             merged_data["output_format"] = cli_options["format"]
         
+        if cli_options.get("color") is not None:
+            merged_data["color"] = cli_options["color"]
+        
         if cli_options.get("fail_on_error") is not None:
             merged_data["fail_on_error"] = cli_options["fail_on_error"]
         
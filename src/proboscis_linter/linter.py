@@ -1,5 +1,5 @@
 from pathlib import Path
-from typing import List, Optional
+from typing import Any, Dict, List, Optional
 from loguru import logger
 
 from .models import LintViolation
@@ -24,4 +24,28 @@ class ProboscisLinter:
     
     def lint_changed_files(self, project_root: Path) -> List[LintViolation]:
         """Lint only files with git changes (staged, unstaged, or untracked)."""
-        return self._rust_linter.lint_changed_files(project_root)
\ No newline at end of file
+        return self._rust_linter.lint_changed_files(project_root)
+    
+    def init_config(self, project_root: Path) -> str:
+        """Scaffold the [tool.proboscis] section and pytest markers of a project."""
+        return self._rust_linter.init_config(project_root)
+    
+    def run_metadata(self, project_root: Path, duration_seconds: float) -> Dict[str, Any]:
+        """Version, config hash, rule versions, git SHA and timing of a run, for audits."""
+        return self._rust_linter.run_metadata(project_root, duration_seconds)
+    
+    def format_text(self, violations: List[LintViolation], project_root: Path) -> str:
+        """Text report for a terminal: violations grouped by file, with source context and colored severities."""
+        return self._rust_linter.format_text(violations, project_root)
+    
+    def apply_fixes(self, violations: List[LintViolation], safety: str = "safe") -> Any:
+        """Apply the fixes of `violations`: only safe ones unless `safety` is "unsafe"; returns a FixReport."""
+        return self._rust_linter.apply_fixes(violations, safety)
+    
+    def exit_code(self, violations: List[LintViolation]) -> int:
+        """Exit code of a run reporting `violations`, per the configured `exit_codes`."""
+        return self._rust_linter.exit_code(violations)
+    
+    def exit_code_for(self, outcome: str) -> int:
+        """Exit code of a run with `outcome`: success, warnings, violations, config_error or internal_error."""
+        return self._rust_linter.exit_code_for(outcome)
@@ -1,6 +1,6 @@
 from pathlib import Path
-from typing import Literal, Optional
-from pydantic import BaseModel
+from typing import Any, Literal, Optional
+from pydantic import BaseModel, PrivateAttr
 
 
 class LintViolation(BaseModel):
@@ -9,7 +9,20 @@ class LintViolation(BaseModel):
     line_number: int
     function_name: str
     message: str
-    severity: Literal['error', 'warning']
+    severity: Literal['critical', 'error', 'warning']
     fix_type: Optional[str] = None
     fix_content: Optional[str] = None
-    fix_line: Optional[int] = None
\ No newline at end of file
+    fix_line: Optional[int] = None
+    decorator_start_line: Optional[int] = None
+    # "deprecated" or "experimental" for violations of such functions
+    lifecycle: Optional[str] = None
+    # Whether the violation carries a fix apply_fixes can apply
+    fix_available: bool = False
+    # Documentation of the rule, rendered from the docs_url setting
+    docs_url: Optional[str] = None
+    # Source around the offending definition, decorators included (with context_lines),
+    # and the number of its first line
+    context: Optional[str] = None
+    context_start_line: Optional[int] = None
+    # The Rust violation this one was converted from, whose fix apply_fixes applies
+    _rust_violation: Any = PrivateAttr(default=None)
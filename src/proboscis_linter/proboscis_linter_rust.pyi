@@ -0,0 +1,1016 @@
+# Generated by rust/build.rs from the pyo3 bindings; do not edit.
+# Refresh with `UPDATE_STUBS=1 cargo test` in rust/.
+
+from collections.abc import Awaitable
+from typing import Any, final
+
+__version__: str
+
+
+class RustLinter:
+    def __init__(
+        self,
+        test_directories: list[str] | None = None,
+        test_patterns: list[str] | None = None,
+        exclude_patterns: list[str] | None = None,
+        strict_mode: bool | None = None,
+        attribute_owners: bool | None = None,
+        attach_code_owners: bool | None = None,
+        todo_comments: bool | None = None,
+        rule_options: dict[str, dict[str, bool | int | float | str | list[str]]] | None = None,
+        fan_in_analysis: bool | None = None,
+        critical_fan_in: int | None = None,
+        source_roots: list[str] | None = None,
+        exclude_modules: list[str] | None = None,
+        file_time_limit: float | None = None,
+        check_test_skeleton: bool | None = None,
+        aggregate_missing_tests: bool | None = None,
+        disabled_rules: list[str] | None = None,
+        function_patterns: list[str] | None = None,
+        stub_bodies: list[str] | None = None,
+        script_directories: list[str] | None = None,
+        script_mode: str | None = None,
+        profile: str | None = None,
+        enabled_rules: list[str] | None = None,
+        qualified_test_names: bool | None = None,
+        rule_versions: dict[str, int] | None = None,
+        declared_packages_only: bool | None = None,
+        example_directories: list[str] | None = None,
+        example_mode: str | None = None,
+        experimental_decorators: list[str] | None = None,
+        lifecycle_mode: str | None = None,
+        test_name_template: str | None = None,
+        method_test_name_template: str | None = None,
+        test_suggestion_style: str | None = None,
+        test_stubs: bool | None = None,
+        escalate_file_violations: int | None = None,
+        escalate_untested_functions: bool | None = None,
+        allowlist: dict[str, list[str]] | None = None,
+        allowlist_file: str | None = None,
+        require_noqa_reason: bool | None = None,
+        include_satisfied: bool | None = None,
+        framework: str | None = None,
+        dag_directories: list[str] | None = None,
+        dag_mode: str | None = None,
+        decorator_test_types: dict[str, list[str]] | None = None,
+        surface_test_types: dict[int, list[str]] | None = None,
+        credit_delegation: bool | None = None,
+        max_depth: int | None = None,
+        max_files: int | None = None,
+        cache_dir: str | None = None,
+        stats_file: str | None = None,
+        exit_codes: dict[str, int] | None = None,
+        docs_url: str | None = None,
+        context_lines: int | None = None,
+        rule_switches: dict[str, bool] | None = None,
+        config_root: str | None = None,
+    ) -> None: ...
+
+    def lint_project(self, project_root: str) -> list[LintViolation]: ...
+
+    def lint_project_run(self, project_root: str) -> LintRun:
+        """Lint a project, returning its violations with the metadata of the run"""
+
+    def run_metadata(self, project_root: str, duration_seconds: float = 0.0) -> RunMetadata:
+        """Metadata of a run over the project at `project_root` with this configuration,
+        for runs linted another way (e.g. `lint_project_iter`) that took `duration_seconds`
+        """
+
+    def exit_code(self, violations: list[Any]) -> int:
+        """Code a run reporting `violations` (from this linter or the Python wrapper: anything
+        with a `severity`) exits with (see `exit_codes`); suppressed violations do not count
+        """
+
+    def exit_code_for(self, outcome: str) -> int:
+        """Code a run with the `outcome` (e.g. `"internal_error"`) exits with"""
+
+    def lint_project_iter(self, project_root: str, chunk_size: int = 200) -> ViolationStream:
+        """Lint a project a chunk at a time: an iterator of violation lists, each holding the
+        violations of up to `chunk_size` source files (see `stream`)
+        """
+
+    def lint_project_to_file(self, project_root: str, path: str, format: str = "sarif") -> int:
+        """Lint a project and write its violations to `path` in one of the report formats,
+        returning how many were written; nothing is handed to Python, and line-oriented
+        formats (csv, ndjson) are written a chunk at a time
+        """
+
+    def lint_project_to_store(self, project_root: str, database: str) -> int:
+        """Lint a project and record its violations as a new run in the SQLite database
+        `database` (created if needed; see `store`), returning the run id
+        """
+
+    def stats(self, project_root: str) -> UsageStats:
+        """The runs over the project at `project_root` recorded in the `stats_file`, oldest
+        first (see `usage_stats`)
+        """
+
+    def query_store(
+        self,
+        database: str,
+        sql_filter: str | None = None,
+        run_id: int | None = None,
+    ) -> list[LintViolation]:
+        """Violations of a stored run (the latest when `run_id` is None) matching `sql_filter`,
+        a SQL `WHERE` expression over the export columns, e.g. `"rule_id = 'PL001' AND file
+        LIKE 'src/api/%'"`; the database is opened read-only
+        """
+
+    def store_runs(self, database: str) -> list[StoredRun]:
+        """The runs recorded in a results store, oldest first"""
+
+    def prioritized_violations(self, project_root: str, days: int = 90) -> list[LintViolation]:
+        """The violations of `lint_project`, riskiest first: ranked by the commits touching
+        their file in the last `days` days (see `churn`); without git history every file
+        has a churn of 0 and the order is unchanged
+        """
+
+    def lint_project_counts(self, project_root: str) -> ViolationCounts:
+        """Count the violations `lint_project` reports, per rule id and per file, without
+        formatting their messages; also counts those `noqa` comments and the allowlist
+        suppress, per rule id. A counts query is not recorded in the stats file as a run
+        """
+
+    def lint_project_async(self, project_root: str) -> Awaitable[list[LintViolation]]:
+        """Awaitable `lint_project`: the linting runs on a worker thread, so the event loop
+        stays responsive
+        """
+
+    def lint_file(self, file_path: str) -> list[LintViolation]: ...
+
+    def lint_symbol(self, project_root: str, symbol: str) -> list[LintViolation]:
+        """Lint a single function, named by its qualified name (`pkg.mod.func` or
+        `pkg.mod.Class.method`), with the settings of the subproject holding it
+        """
+
+    def lint_changed_files(self, project_root: str, base: str | None = None) -> list[LintViolation]:
+        """Lint files with uncommitted changes, plus (when `base` is given) every file changed
+        on the current branch since it diverged from `base`
+        """
+
+    def check_test_markers(self, project_root: str) -> list[LintViolation]: ...
+
+    def check_test_naming(self, project_root: str) -> list[LintViolation]:
+        """Check test function names against the `PL023` naming convention"""
+
+    def check_test_docstrings(self, project_root: str) -> list[LintViolation]:
+        """Check that test functions have a docstring (`PL024`)"""
+
+    def check_test_length(self, project_root: str) -> list[LintViolation]:
+        """Check test functions against the `PL025` length and assertion limits"""
+
+    def check_sleep_calls(self, project_root: str) -> list[LintViolation]:
+        """Check tests for `time.sleep` calls (`PL026`)"""
+
+    def check_external_access(self, project_root: str) -> list[LintViolation]:
+        """Check tests for network and file-system access (`PL027`)"""
+
+    def check_public_imports(self, project_root: str) -> list[LintViolation]:
+        """Check that tests import from the public API rather than private modules (`PL030`)"""
+
+    def check_tautological_assertions(self, project_root: str) -> list[LintViolation]:
+        """Check tests for assertions that cannot fail (`PL031`)"""
+
+    def check_test_files_have_tests(self, project_root: str) -> list[LintViolation]:
+        """Check that test files contain tests pytest collects (`PL032`)"""
+
+    def check_conftests(self, project_root: str) -> list[LintViolation]:
+        """Check conftest.py placement and clashing conftest fixtures (`PL028`)"""
+
+    def check_test_packages(self, project_root: str) -> list[LintViolation]:
+        """Check that sibling test directories agree on having an `__init__.py` (`PL029`)"""
+
+    def check_dead_helpers(self, project_root: str) -> list[LintViolation]:
+        """Find private functions referenced nowhere in the project's sources or tests
+        (`PL007`)
+        """
+
+    def coverage_report(self, project_root: str) -> CoverageReport:
+        """Score how thoroughly each checked function is tested (see `quality`)"""
+
+    def module_report(self, project_root: str) -> list[ModuleSummary]:
+        """Roll functions, tests and violations up per Python module (see `report::modules`);
+        subprojects are included, with their own settings, like in `lint_project`
+        """
+
+    def export_test_map(self, project_root: str, path: str) -> int:
+        """Write the JSON test map of a project to `path`: every checked function, by qualified
+        name, with the tests satisfying each missing-test rule (see `report::test_map`);
+        returns the number of functions
+        """
+
+    def suppression_report(self, project_root: str) -> list[Suppression]:
+        """Every `noqa` comment naming rules in the source and test files of a project, with
+        its reason and expiry (see `suppressions`)
+        """
+
+    def fixtures(
+        self,
+        project_root: str,
+        name: str | None = None,
+        test_file: str | None = None,
+    ) -> list[Fixture]:
+        """Fixtures defined in the conftest.py files of the test directories
+
+        With `test_file`, only the fixtures available to that file are returned, nearest
+        conftest first, so the first fixture of a name is the one pytest uses. `name`
+        restricts the result to the fixtures of that name.
+        """
+
+    def doctor(self, project_root: str) -> DoctorReport:
+        """Diagnose the environment and the project at `project_root`: git availability, the
+        test directories and test files found, the settings in effect, and likely
+        misconfigurations
+        """
+
+    def self_check(self, project_root: str) -> list[Discrepancy]:
+        """Check the internal consistency of the linter on the project at `project_root`,
+        returning the discrepancies found (see `self_check`): tests matched to satisfied
+        functions that do not exist on disk, expected test paths that do not map back to
+        their module, and test cache contents that differ from a fresh scan
+        """
+
+    def init_config(self, project_root: str) -> str:
+        """Write a `[tool.proboscis]` section tailored to the project at `project_root` (its
+        test directories, test types, package roots and pytest markers) to its
+        pyproject.toml, registering the markers pytest lacks, and return the text appended
+
+        Raises `FileExistsError` if the project already has a `[tool.proboscis]` section.
+        """
+
+    def remove_stale_todo_comments(self, project_root: str) -> int:
+        """Remove `# TODO(proboscis):` comments whose test now exists, returning how many
+        comments were removed
+        """
+
+
+class LintViolation:
+    @property
+    def rule_name(self) -> str: ...
+
+    @property
+    def line_number(self) -> int: ...
+
+    @property
+    def function_name(self) -> str: ...
+
+    @property
+    def severity(self) -> str: ...
+
+    @property
+    def fix_type(self) -> str | None: ...
+
+    @property
+    def fix_content(self) -> str | None: ...
+
+    @property
+    def fix_line(self) -> int | None: ...
+
+    @property
+    def fix_safety(self) -> str | None:
+        """"safe" or "unsafe"; only safe fixes are applied by default"""
+
+    @property
+    def content_hash(self) -> str | None:
+        """Hash of the file's content when it was linted, on violations carrying a fix;
+        `apply_fixes` re-anchors the fix by its function when the file has changed since
+        """
+
+    @property
+    def docs_url(self) -> str | None:
+        """Documentation of the rule, rendered from the `docs_url` template (see `docs_url`)"""
+
+    @property
+    def author(self) -> str | None:
+        """Author of the most recent commit touching the offending function"""
+
+    @property
+    def author_email(self) -> str | None: ...
+
+    @property
+    def commit(self) -> str | None:
+        """Hex id of the most recent commit touching the offending function"""
+
+    @property
+    def code_owners(self) -> list[str]:
+        """Owners assigned to the file by CODEOWNERS"""
+
+    @property
+    def fan_in(self) -> int | None:
+        """Number of call sites of the function across the project (with `fan_in_analysis=True`)"""
+
+    @property
+    def churn(self) -> int | None:
+        """Commits touching the file in the window of `prioritized_violations`"""
+
+    @property
+    def expected_test_file(self) -> str | None:
+        """Test file a missing-test violation (PL001–PL003) expects the test in"""
+
+    @property
+    def decorator_start_line(self) -> int | None:
+        """First decorator line of the offending function (its `def` line when undecorated),
+        for violations reported against a function
+        """
+
+    @property
+    def class_name(self) -> str | None:
+        """Class of the offending method"""
+
+    @property
+    def expected_test_name(self) -> str | None:
+        """Test function a missing-test violation (PL001–PL003) expects"""
+
+    @property
+    def lifecycle(self) -> str | None:
+        """"deprecated" or "experimental" for violations of such functions (see `lifecycle`)"""
+
+    @property
+    def module_path(self) -> str | None:
+        """Dotted module of the offending source file (e.g. `pkg.sub.mod`), for violations
+        reported against a function
+        """
+
+    @property
+    def satisfied(self) -> bool:
+        """Set on the records of rules a function satisfies, listed with
+        `include_satisfied=True` (severity "info"); never set on violations
+        """
+
+    @property
+    def matched_tests(self) -> list[str]:
+        """Tests satisfying the rule, as `test_file::test_name` relative to the project root"""
+
+    @property
+    def context(self) -> str | None:
+        """Source around the offending definition, decorators included (with `context_lines`)"""
+
+    @property
+    def context_start_line(self) -> int | None:
+        """Line number of the first line of `context`"""
+
+    @property
+    def file_path(self) -> str:
+        """Path of the offending file, as an interned string shared by all violations of
+        the file
+        """
+
+    @property
+    def fix_available(self) -> bool:
+        """Whether the violation carries a fix `apply_fixes` can apply"""
+
+    @property
+    def message(self) -> str:
+        """Human-readable description of the violation"""
+
+
+class ViolationCounts:
+    """Violation counts, as returned by `RustLinter.lint_project_counts`"""
+
+    @property
+    def total(self) -> int: ...
+
+    @property
+    def by_rule(self) -> dict[str, int]:
+        """Violations per rule id (e.g. "PL001")"""
+
+    @property
+    def by_file(self) -> dict[str, int]:
+        """Violations per file path"""
+
+    @property
+    def by_lifecycle(self) -> dict[str, int]:
+        """Violations of deprecated and experimental functions, per lifecycle; they are
+        included in the counts above
+        """
+
+    @property
+    def suppressed_by_rule(self) -> dict[str, int]:
+        """Violations suppressed by `noqa` comments or the allowlist, per rule id; they are
+        not included in the counts above
+        """
+
+
+class ViolationStream:
+    """Iterator over the violations of a project, as lists of at most `chunk_size` files' worth"""
+
+    def __iter__(slf: Any) -> Any: ...
+
+    def __next__(slf: Any) -> list[LintViolation] | None:
+        """The violations of the next files linted; the GIL is released while linting"""
+
+
+class LintSession:
+    """A project opened for repeated linting, with its state built once"""
+
+    @property
+    def project_root(self) -> str:
+        """Root of the project the session lints"""
+
+    @property
+    def closed(self) -> bool:
+        """Whether `close` was called; a closed session raises `RuntimeError` when used"""
+
+    def lint_file(self, file_path: str) -> list[LintViolation]:
+        """Lint one source file of the project"""
+
+    def lint_changed(self, base: str | None = None) -> list[LintViolation]:
+        """Lint the source files with uncommitted changes, plus (when `base` is given) every
+        one changed on the current branch since it diverged from `base`
+        """
+
+    def apply_fixes(
+        self,
+        violations: list[LintViolation] | None = None,
+        safety: str = "safe",
+    ) -> FixReport:
+        """Apply the fixes attached to `violations`, or to those of the whole project when
+        none are given, returning a `FixReport` (see `apply_fixes`)
+        """
+
+    def close(self) -> None:
+        """Release the warm state; closing a closed session does nothing"""
+
+    def __enter__(slf: Any) -> Any: ...
+
+    def __exit__(
+        self,
+        _exc_type: Any | None = None,
+        _exc_value: Any | None = None,
+        _traceback: Any | None = None,
+    ) -> bool: ...
+
+
+class RunMetadata:
+    """What a run was made with"""
+
+    @property
+    def version(self) -> str:
+        """Version of the linter"""
+
+    @property
+    def config_hash(self) -> str:
+        """Hash of the effective settings (see `RustLinter.doctor`)"""
+
+    @property
+    def rule_versions(self) -> dict[str, int]:
+        """Behavior version of each rule that ran, by rule id"""
+
+    @property
+    def project_root(self) -> str: ...
+
+    @property
+    def git_sha(self) -> str | None:
+        """Hex id of the commit checked out, when the project is in a git repository"""
+
+    @property
+    def timestamp(self) -> str:
+        """When the run started, in UTC (`2024-01-31T09:30:00Z`)"""
+
+    @property
+    def duration_seconds(self) -> float: ...
+
+    def to_json(self) -> str:
+        """The metadata as a JSON object"""
+
+    def __repr__(self) -> str: ...
+
+
+class LintRun:
+    """The violations of a run with its metadata (`RustLinter.lint_project_run`)"""
+
+    @property
+    def violations(self) -> list[LintViolation]: ...
+
+    @property
+    def metadata(self) -> RunMetadata: ...
+
+    @property
+    def exit_code(self) -> int:
+        """Code the run exits with, per the `exit_codes` of the linter"""
+
+
+class OwnerSummary:
+    """Missing-test debt attributed to one CODEOWNERS owner"""
+
+    @property
+    def owner(self) -> str: ...
+
+    @property
+    def violation_count(self) -> int: ...
+
+    @property
+    def file_count(self) -> int: ...
+
+    @property
+    def rule_counts(self) -> dict[str, int]:
+        """Violation counts keyed by rule id (e.g. "PL001")"""
+
+
+class ModuleSummary:
+    """Functions, tests and violations of one Python module"""
+
+    @property
+    def module(self) -> str:
+        """Dotted module path, e.g. "pkg.sub.mod\""""
+
+    @property
+    def total_functions(self) -> int: ...
+
+    @property
+    def tested_functions(self) -> int:
+        """Functions with at least one test of any type"""
+
+    @property
+    def unit_tested(self) -> int: ...
+
+    @property
+    def integration_tested(self) -> int: ...
+
+    @property
+    def e2e_tested(self) -> int: ...
+
+    @property
+    def violation_count(self) -> int: ...
+
+    @property
+    def rule_counts(self) -> dict[str, int]:
+        """Violation counts keyed by rule id (e.g. "PL001")"""
+
+    @property
+    def coverage_percent(self) -> float:
+        """Share of the functions with a test, 0–100 (0 when the module has none)"""
+
+
+class Suppression:
+    """One `noqa` comment"""
+
+    @property
+    def file_path(self) -> str: ...
+
+    @property
+    def line_number(self) -> int: ...
+
+    @property
+    def rules(self) -> list[str]:
+        """Rule ids the comment names, sorted"""
+
+    @property
+    def reason(self) -> str | None:
+        """The text after `--`"""
+
+    @property
+    def until(self) -> str | None:
+        """The text after `until=`"""
+
+    @property
+    def expired(self) -> bool:
+        """Whether the expiry date has passed, so the comment no longer suppresses"""
+
+    def __repr__(self) -> str: ...
+
+
+class StoredRun:
+    """One run recorded in a results store"""
+
+    @property
+    def run_id(self) -> int: ...
+
+    @property
+    def project(self) -> str:
+        """Project root the run linted"""
+
+    @property
+    def created_at(self) -> int:
+        """Unix timestamp (seconds) of the run"""
+
+    @property
+    def violation_count(self) -> int: ...
+
+    def __repr__(self) -> str: ...
+
+
+class ResultDiff:
+    """Violations of the new result set that were not in the old one, those of the old one no
+    longer in the new one, and those in both
+    """
+
+    @property
+    def new(self) -> list[LintViolation]: ...
+
+    @property
+    def fixed(self) -> list[LintViolation]: ...
+
+    @property
+    def unchanged(self) -> list[LintViolation]:
+        """As found in the new result set"""
+
+    def __repr__(self) -> str: ...
+
+
+class FixReport:
+    """What `apply_fixes` did, per file"""
+
+    @property
+    def applied(self) -> dict[str, int]:
+        """Number of fixes applied per file"""
+
+    @property
+    def skipped(self) -> list[SkippedFix]: ...
+
+    @property
+    def diffs(self) -> dict[str, str]:
+        """Unified diff of each file edited in place; the empty files and directories created
+        by `create_test_skeleton` have none
+        """
+
+    @property
+    def files_modified(self) -> list[str]:
+        """Files the fixes changed or created, sorted"""
+
+    @property
+    def fixes_applied(self) -> int:
+        """Total number of fixes applied"""
+
+    def __repr__(self) -> str: ...
+
+
+class SkippedFix:
+    """A fix `apply_fixes` left unapplied, and why"""
+
+    @property
+    def violation(self) -> LintViolation:
+        """The violation carrying the fix"""
+
+    @property
+    def reason(self) -> str:
+        """Why the fix was skipped: "already_applied", "conflict" (another fix of the function
+        takes its place), "file_changed" (the file changed since it was linted and the
+        function cannot be found again), "encoding" (the file is not UTF-8), "unsafe" (above
+        the requested safety) or "unsupported" (unknown fix type)
+        """
+
+    @property
+    def detail(self) -> str: ...
+
+    def __repr__(self) -> str: ...
+
+
+class UsageStats:
+    """The runs of a project recorded in the stats file (`RustLinter.stats`)"""
+
+    @property
+    def history(self) -> list[RunRecord]:
+        """The runs, oldest first"""
+
+    @property
+    def runs(self) -> int: ...
+
+    @property
+    def total_duration_seconds(self) -> float: ...
+
+    @property
+    def mean_duration_seconds(self) -> float:
+        """Mean duration of a run; 0 without runs"""
+
+    @property
+    def first_run(self) -> str | None: ...
+
+    @property
+    def last_run(self) -> str | None: ...
+
+    @property
+    def violation_trend(self) -> list[Any]:
+        """Violations reported by each run, as (timestamp, count), oldest first"""
+
+    def __repr__(self) -> str: ...
+
+
+class RunRecord:
+    """One run, as recorded in the stats file"""
+
+    @property
+    def version(self) -> str:
+        """Version of the linter"""
+
+    @property
+    def project_root(self) -> str: ...
+
+    @property
+    def timestamp(self) -> str:
+        """When the run started, in UTC (`2024-01-31T09:30:00Z`)"""
+
+    @property
+    def duration_seconds(self) -> float: ...
+
+    @property
+    def violations(self) -> int: ...
+
+    @property
+    def by_rule(self) -> dict[str, int]:
+        """Violations per rule id"""
+
+    def __repr__(self) -> str: ...
+
+
+class CoverageReport:
+    """Test quality across a project, as returned by `RustLinter.coverage_report`"""
+
+    @property
+    def functions(self) -> list[FunctionQuality]: ...
+
+    @property
+    def total_functions(self) -> int: ...
+
+    @property
+    def tested_functions(self) -> int: ...
+
+    @property
+    def average_score(self) -> float:
+        """Mean score over all functions (0 when there are none)"""
+
+
+class FunctionQuality:
+    """Test quality of one source function"""
+
+    @property
+    def file_path(self) -> str: ...
+
+    @property
+    def line_number(self) -> int: ...
+
+    @property
+    def function_name(self) -> str: ...
+
+    @property
+    def class_name(self) -> str | None: ...
+
+    @property
+    def module_path(self) -> str:
+        """Dotted module of the function's file (e.g. `pkg.sub.mod`)"""
+
+    @property
+    def tests(self) -> list[str]:
+        """Matching tests as `test_file::test_name`"""
+
+    @property
+    def unit_tests(self) -> int: ...
+
+    @property
+    def integration_tests(self) -> int: ...
+
+    @property
+    def e2e_tests(self) -> int: ...
+
+    @property
+    def assertion_count(self) -> int:
+        """Total assertions across all matching tests"""
+
+    @property
+    def parametrized(self) -> bool: ...
+
+    @property
+    def has_markers(self) -> bool:
+        """Whether every matching test carries a pytest marker"""
+
+    @property
+    def score(self) -> int: ...
+
+
+class Fixture:
+    """A fixture definition"""
+
+    @property
+    def name(self) -> str:
+        """Name tests request the fixture by (the `name=` argument, or the function name)"""
+
+    @property
+    def scope(self) -> str:
+        """`function` unless the decorator sets `scope=`"""
+
+    @property
+    def autouse(self) -> bool: ...
+
+    @property
+    def file_path(self) -> str: ...
+
+    @property
+    def line_number(self) -> int:
+        """1-based line of the `def`"""
+
+    def __repr__(self) -> str: ...
+
+
+class DoctorReport:
+    """Diagnostics for a project, as returned by `RustLinter.doctor`; `str()` renders a report"""
+
+    @property
+    def git_backend(self) -> str:
+        """"gitoxide" (in-process) or "git command\""""
+
+    @property
+    def git_available(self) -> bool:
+        """Whether git operations can run (with the `git command` backend, whether `git` is on
+        PATH)
+        """
+
+    @property
+    def git_repository(self) -> bool: ...
+
+    @property
+    def test_directories_found(self) -> list[str]:
+        """Configured test directories present under the project root"""
+
+    @property
+    def test_directories_missing(self) -> list[str]:
+        """Configured test directories absent from the project root"""
+
+    @property
+    def test_files(self) -> dict[str, int]:
+        """Indexed test files per type ("unit", "integration", "e2e", "general")"""
+
+    @property
+    def source_files(self) -> int:
+        """Source files the per-function rules check"""
+
+    @property
+    def settings(self) -> list[Any]:
+        """Settings in effect, as (name, value) pairs"""
+
+    @property
+    def problems(self) -> list[str]:
+        """Likely misconfigurations, as sentences"""
+
+    def __str__(self) -> str: ...
+
+
+class Discrepancy:
+    """A broken invariant, found by `RustLinter.self_check`"""
+
+    @property
+    def check(self) -> str:
+        """"matched-test", "expected-path" or "test-cache\""""
+
+    @property
+    def file_path(self) -> str:
+        """File the discrepancy is about, relative to the project root"""
+
+    @property
+    def detail(self) -> str: ...
+
+    def __repr__(self) -> str: ...
+
+def violations_by_owner(violations: list[LintViolation]) -> dict[str, list[LintViolation]]:
+    """Group violations by the owner attributed via `attribute_owners=True`"""
+
+def summarize_by_owner(violations: list[LintViolation]) -> list[OwnerSummary]:
+    """Summarize violations per CODEOWNERS owner (requires `attach_code_owners=True`)"""
+
+def send_webhook(
+    url: str,
+    violations: list[LintViolation],
+    project_root: str = "",
+    template: str | None = None,
+) -> int:
+    """POST a JSON summary of `violations` to a webhook, returning the HTTP status code
+
+    `template` is a JSON payload with `{{placeholder}}` markers (see `report::webhook`);
+    the default is a Slack-compatible `{"text": ...}` message plus the summary object.
+    """
+
+def format_violations(
+    violations: list[LintViolation],
+    format: str,
+    project_root: str = ".",
+    metadata: RunMetadata | None = None,
+) -> str:
+    """Render violations in a serialized report format ("checkstyle", "codeclimate", "csv",
+    "ndjson" or "sarif"), embedding the `metadata` of their run where the format allows
+    """
+
+def format_text(violations: list[Any], project_root: str = ".", color: str = "auto") -> str:
+    """Render violations for a terminal: grouped by file with paths relative to
+    `project_root`, with the source lines around each, and colored per `color` ("auto",
+    "always" or "never"; see `report::text`). Violations of the Python wrapper are
+    accepted as well.
+    """
+
+def apply_fixes(violations: list[LintViolation], safety: str = "safe") -> FixReport:
+    """Apply the fixes attached to violations, returning a `FixReport` of the fixes applied and
+    skipped (with the reason) and of the diff of each modified file
+
+    Violations carry fixes when produced with e.g. `todo_comments=True`. `safety="safe"`
+    (the default) only applies fixes marked safe; `safety="unsafe"` applies every fix.
+    """
+
+def filter_violations(
+    violations: list[LintViolation],
+    rules: list[str] | None = None,
+    paths: list[str] | None = None,
+    severities: list[str] | None = None,
+    functions: list[str] | None = None,
+) -> list[LintViolation]:
+    """The violations matching every criterion given: `rules` (ids like "PL001" or full rule
+    names), `paths` (globs matched like `exclude_patterns`), `severities` and `functions`
+    (names, or "Class.method"); the same violation objects are returned, in order
+    """
+
+def diff_results(old: str | list[Any], new: str | list[Any], project_root: str = ".") -> ResultDiff:
+    """Classify violations as new, fixed or unchanged between two result sets, each a list
+    of violations or the path of an NDJSON export; paths are compared relative to
+    `project_root` (see `diff`)
+    """
+
+def lint(path: str, **config: Any) -> list[LintViolation]:
+    """Lint a project directory or a single file without managing a `RustLinter`
+
+    Keyword arguments are the `RustLinter` constructor options, e.g.
+    `lint("src", strict_mode=True, exclude_modules=["*.migrations.*"])`; the settings
+    configured in the directory linted (or the project of the file, as `lint_file` finds
+    it) fill in the others, unless `config_root` names another directory.
+    """
+
+def open_session(project_root: str, **config: Any) -> LintSession:
+    """Open a `LintSession` on the project at `project_root`, building its state once
+
+    Keyword arguments are the `RustLinter` constructor options, as for `lint`, and the
+    settings configured in the project fill in the others.
+    """
+
+def features() -> list[str]:
+    """Optional cargo features this extension was built with"""
+
+
+@final
+class rules:
+    """The `proboscis_linter_rust.rules` submodule"""
+
+    class RuleInfo:
+        """Description of a rule, as returned by `rules.all()` / `rules.get()`"""
+
+        @property
+        def rule_id(self) -> str:
+            """e.g. "PL001\""""
+
+        @property
+        def name(self) -> str:
+            """e.g. "require-unit-test\""""
+
+        @property
+        def description(self) -> str: ...
+
+        @property
+        def category(self) -> str:
+            """What the rule checks: "coverage" (functions have tests), "organization" (where tests
+            live and how they are marked) or "quality" (how tests are written)
+            """
+
+        @property
+        def tags(self) -> list[str]:
+            """Free-form labels for filtering, e.g. "markers"; rules not run unless enabled carry
+            "opt-in"
+            """
+
+        @property
+        def default_severity(self) -> str:
+            """Severity of the violations the rule reports ("error" or "warning")"""
+
+        @property
+        def fix_safety(self) -> str | None:
+            """Safety of the fixes the rule attaches ("safe", "unsafe"; see `fixes::FixSafety`), or
+            None when it attaches none
+            """
+
+        @property
+        def since(self) -> str:
+            """Version the rule first shipped in"""
+
+        @property
+        def test_type(self) -> str | None:
+            """Test type the rule requires ("unit", "integration", "e2e"), or None for rules without one"""
+
+        @property
+        def options(self) -> list[str]:
+            """Option names accepted under `[tool.proboscis.rules.<ID>] options`"""
+
+        @property
+        def versions(self) -> list[int]:
+            """Behavior versions of the rule, oldest first (see `rule_versions`)"""
+
+        @property
+        def default_version(self) -> int:
+            """Version runs use unless `rule_versions` pins another"""
+
+        def __repr__(self) -> str: ...
+
+    @staticmethod
+    def all() -> list[RuleInfo]:
+        """Metadata of every rule"""
+
+    @staticmethod
+    def get(rule_id: str) -> RuleInfo | None:
+        """Metadata of the rule with `rule_id` (e.g. "PL001"), or None if there is none"""
+
+    @staticmethod
+    def list_rules(category: str | None = None, tag: str | None = None) -> list[RuleInfo]:
+        """Metadata of the rules in `category` and carrying `tag`, in rule id order"""
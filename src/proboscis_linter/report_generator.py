@@ -1,5 +1,5 @@
 import json
-from typing import List, Protocol
+from typing import Any, Dict, List, Optional, Protocol
 
 from .models import LintViolation
 
@@ -12,6 +12,15 @@ class IReportGenerator(Protocol):
         ...
 
 
+def _count_lifecycles(violations: List[LintViolation]) -> Dict[str, int]:
+    """Count the violations of deprecated and experimental functions, per lifecycle."""
+    counts: Dict[str, int] = {}
+    for violation in violations:
+        if violation.lifecycle:
+            counts[violation.lifecycle] = counts.get(violation.lifecycle, 0) + 1
+    return dict(sorted(counts.items()))
+
+
 class TextReportGenerator:
     def generate_report(self, violations: List[LintViolation]) -> str:
         if not violations:
@@ -36,9 +45,13 @@ class TextReportGenerator:
 
 
 class JsonReportGenerator:
-    def generate_report(self, violations: List[LintViolation]) -> str:
-        report_data = {
+    def generate_report(
+        self, violations: List[LintViolation], metadata: Optional[Dict[str, Any]] = None
+    ) -> str:
+        """Render the report, with the `metadata` of the run (see `ProboscisLinter.run_metadata`)."""
+        report_data: Dict[str, Any] = {
             "total_violations": len(violations),
+            "lifecycle_violations": _count_lifecycles(violations),
             "violations": [
                 {
                     "rule": violation.rule_name,
@@ -46,11 +59,17 @@ class JsonReportGenerator:
                     "file": str(violation.file_path),
                     "line": violation.line_number,
                     "message": violation.message,
-                    "severity": violation.severity
+                    "severity": violation.severity,
+                    "lifecycle": violation.lifecycle,
+                    "fix_available": violation.fix_available,
+                    "docs_url": violation.docs_url,
+                    "context": violation.context
                 }
                 for violation in violations
             ]
         }
+        if metadata is not None:
+            report_data["metadata"] = metadata
         
         return json.dumps(report_data, indent=2)
     
@@ -1,6 +1,7 @@
 """Python wrapper for Rust linter implementation."""
+import json
 from pathlib import Path
-from typing import List, Optional
+from typing import Any, Dict, List, Optional
 from loguru import logger
 
 from .models import LintViolation
@@ -13,6 +14,43 @@ except ImportError:
     logger.warning("Rust extension not available, falling back to Python implementation")
     RUST_AVAILABLE = False
 
+# Rules that check test files: the RustLinter method running each, and whether it is opt-in
+TEST_FILE_CHECKS = {
+    "PL023": ("check_test_naming", True),
+    "PL024": ("check_test_docstrings", True),
+    "PL025": ("check_test_length", True),
+    "PL026": ("check_sleep_calls", False),
+    "PL027": ("check_external_access", True),
+    "PL028": ("check_conftests", False),
+    "PL029": ("check_test_packages", False),
+    "PL030": ("check_public_imports", True),
+    "PL031": ("check_tautological_assertions", False),
+    "PL032": ("check_test_files_have_tests", True),
+}
+
+
+def _from_rust(rv: Any) -> LintViolation:
+    """Convert a violation of the Rust linter to the Python model, keeping it for apply_fixes."""
+    violation = LintViolation(
+        rule_name=rv.rule_name,
+        file_path=Path(rv.file_path),
+        line_number=rv.line_number,
+        function_name=rv.function_name,
+        message=rv.message,
+        severity=rv.severity,
+        fix_type=rv.fix_type,
+        fix_content=rv.fix_content,
+        fix_line=rv.fix_line,
+        decorator_start_line=rv.decorator_start_line,
+        lifecycle=rv.lifecycle,
+        fix_available=rv.fix_available,
+        docs_url=rv.docs_url,
+        context=rv.context,
+        context_start_line=rv.context_start_line
+    )
+    violation._rust_violation = rv
+    return violation
+
 
 class RustLinterWrapper:
     """Wrapper for the Rust linter implementation."""
@@ -25,10 +63,65 @@ class RustLinterWrapper:
             test_directories=config.test_directories,
             test_patterns=config.test_patterns,
             exclude_patterns=config.exclude_patterns,
-            strict_mode=config.strict_mode
+            exclude_modules=config.exclude_modules,
+            declared_packages_only=config.declared_packages_only,
+            profile=config.profile,
+            framework=config.framework,
+            strict_mode=config.strict_mode,
+            qualified_test_names=config.qualified_test_names,
+            source_roots=config.source_roots,
+            function_patterns=config.function_patterns,
+            stub_bodies=config.stub_bodies,
+            script_directories=config.script_directories,
+            script_mode=config.script_mode,
+            example_directories=config.example_directories,
+            example_mode=config.example_mode,
+            experimental_decorators=config.experimental_decorators,
+            lifecycle_mode=config.lifecycle_mode,
+            dag_directories=config.dag_directories,
+            dag_mode=config.dag_mode,
+            decorator_test_types=config.decorator_test_types,
+            surface_test_types=config.surface_test_types,
+            credit_delegation=config.credit_delegation,
+            test_name_template=config.test_name_template,
+            method_test_name_template=config.method_test_name_template,
+            test_suggestion_style=config.test_suggestion_style,
+            file_time_limit=config.file_time_limit,
+            max_depth=config.max_depth,
+            max_files=config.max_files,
+            cache_dir=config.cache_dir,
+            stats_file=config.stats_file,
+            exit_codes=config.exit_codes,
+            docs_url=config.docs_url,
+            context_lines=config.context_lines,
+            check_test_skeleton=config.check_test_skeleton,
+            aggregate_missing_tests=config.aggregate_missing_tests,
+            escalate_file_violations=config.escalate_file_violations,
+            escalate_untested_functions=config.escalate_untested_functions,
+            allowlist=config.allowlist,
+            allowlist_file=config.allowlist_file,
+            require_noqa_reason=config.require_noqa_reason,
+            disabled_rules=config.disabled_rules,
+            enabled_rules=config.enabled_rules,
+            rule_switches={rule_id: rule.enabled for rule_id, rule in config.rules.items()},
+            rule_options={
+                rule_id: rule.options
+                for rule_id, rule in config.rules.items()
+                if rule.options
+            },
+            rule_versions=config.rule_versions,
         )
         self._config = config
     
+    def _check_test_files(self, project_root: Path) -> list:
+        """Run the enabled rules that check test files, returning Rust violations."""
+        violations = []
+        for rule_id, (check, opt_in) in TEST_FILE_CHECKS.items():
+            enabled = self._config.is_rule_opted_in(rule_id) if opt_in else self._config.is_rule_enabled(rule_id)
+            if enabled:
+                violations.extend(getattr(self._rust_linter, check)(str(project_root)))
+        return violations
+    
     def lint_project(self, project_root: Path) -> List[LintViolation]:
         """Lint a project using the Rust implementation."""
         with logger.contextualize(project_root=str(project_root)):
@@ -42,6 +135,13 @@ class RustLinterWrapper:
                 test_marker_violations = self._rust_linter.check_test_markers(str(project_root))
                 rust_violations.extend(test_marker_violations)
             
+            # Look for dead private helpers (PL007) if opted in
+            if self._config.is_rule_opted_in("PL007"):
+                rust_violations.extend(self._rust_linter.check_dead_helpers(str(project_root)))
+            
+            # Run the test file checks (PL023+)
+            rust_violations.extend(self._check_test_files(project_root))
+            
             # Convert Rust violations to Python models
             violations = []
             for rv in rust_violations:
@@ -50,18 +150,7 @@ class RustLinterWrapper:
                 if not self._config.is_rule_enabled(rule_id):
                     continue
                 
-                violation = LintViolation(
-                    rule_name=rv.rule_name,
-                    file_path=Path(rv.file_path),
-                    line_number=rv.line_number,
-                    function_name=rv.function_name,
-                    message=rv.message,
-                    severity=rv.severity,
-                    fix_type=rv.fix_type,
-                    fix_content=rv.fix_content,
-                    fix_line=rv.fix_line
-                )
-                violations.append(violation)
+                violations.append(_from_rust(rv))
             
             logger.info(f"Found {len(violations)} violations")
             return violations
@@ -76,18 +165,39 @@ class RustLinterWrapper:
             if not self._config.is_rule_enabled(rule_id):
                 continue
             
-            violation = LintViolation(
-                rule_name=rv.rule_name,
-                file_path=Path(rv.file_path),
-                line_number=rv.line_number,
-                function_name=rv.function_name,
-                message=rv.message,
-                severity=rv.severity
-            )
-            violations.append(violation)
+            violations.append(_from_rust(rv))
         
         return violations
     
+    def init_config(self, project_root: Path) -> str:
+        """Write a [tool.proboscis] section tailored to the project, returning the text added."""
+        return self._rust_linter.init_config(str(project_root))
+    
+    def run_metadata(self, project_root: Path, duration_seconds: float) -> Dict[str, Any]:
+        """What a run over the project that took `duration_seconds` was made with."""
+        metadata = self._rust_linter.run_metadata(str(project_root), duration_seconds)
+        return json.loads(metadata.to_json())
+    
+    def format_text(self, violations: List[LintViolation], project_root: Path) -> str:
+        """Render violations for a terminal, colored per the configured `color`."""
+        return proboscis_linter_rust.format_text(
+            violations, project_root=str(project_root), color=self._config.color
+        )
+    
+    def apply_fixes(self, violations: List[LintViolation], safety: str = "safe") -> Any:
+        """Apply the fixes `violations` carry, up to `safety` ("safe" or "unsafe"), returning the FixReport."""
+        return proboscis_linter_rust.apply_fixes(
+            [v._rust_violation for v in violations if v._rust_violation is not None], safety
+        )
+    
+    def exit_code(self, violations: List[LintViolation]) -> int:
+        """Code a run reporting `violations` exits with, per the configured exit codes."""
+        return self._rust_linter.exit_code(violations)
+    
+    def exit_code_for(self, outcome: str) -> int:
+        """Code a run with `outcome` (e.g. "internal_error") exits with."""
+        return self._rust_linter.exit_code_for(outcome)
+    
     def lint_changed_files(self, project_root: Path) -> List[LintViolation]:
         """Lint only files with git changes using the Rust implementation."""
         with logger.contextualize(project_root=str(project_root)):
@@ -102,6 +212,9 @@ class RustLinterWrapper:
                 test_marker_violations = self._rust_linter.check_test_markers(str(project_root))
                 rust_violations.extend(test_marker_violations)
             
+            # Run the test file checks (PL023+)
+            rust_violations.extend(self._check_test_files(project_root))
+            
             # Convert Rust violations to Python models
             violations = []
             for rv in rust_violations:
@@ -110,18 +223,7 @@ class RustLinterWrapper:
                 if not self._config.is_rule_enabled(rule_id):
                     continue
                 
-                violation = LintViolation(
-                    rule_name=rv.rule_name,
-                    file_path=Path(rv.file_path),
-                    line_number=rv.line_number,
-                    function_name=rv.function_name,
-                    message=rv.message,
-                    severity=rv.severity,
-                    fix_type=rv.fix_type,
-                    fix_content=rv.fix_content,
-                    fix_line=rv.fix_line
-                )
-                violations.append(violation)
+                violations.append(_from_rust(rv))
             
             logger.info(f"Found {len(violations)} violations in changed files")
             return violations
\ No newline at end of file
@@ -6,8 +6,8 @@ from textwrap import dedent
 
 
 @pytest.mark.e2e
-def test_AutoFixer_apply_fixes(tmp_path):
-    """End-to-end test for the apply_fixes method of AutoFixer class via CLI."""
+def test_RustLinter_apply_fixes(tmp_path):
+    """End-to-end test for the apply_fixes method of RustLinter via CLI."""
     # Create a test project
     src_dir = tmp_path / "src"
     src_dir.mkdir()
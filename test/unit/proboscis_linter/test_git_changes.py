@@ -203,4 +203,78 @@ def test_lint_changed_files_no_changes():
         violations = linter.lint_changed_files(tmpdir_path)
         
         # Should have no violations since no files were checked
-        assert len(violations) == 0
\ No newline at end of file
+        assert len(violations) == 0
+
+@pytest.mark.unit
+def test_changed_and_single_file_runs_keep_fix_and_docs_annotations():
+    """Test that --changed-only and single-file runs carry fix_available and docs_url."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmpdir_path = Path(tmpdir)
+        init_git_repo(tmpdir_path)
+        
+        source_file = tmpdir_path / "src" / "module.py"
+        source_file.parent.mkdir()
+        source_file.write_text(
+            "def load():\n"
+            "    return 42\n"
+        )
+        test_file = tmpdir_path / "test" / "unit" / "test_module.py"
+        test_file.parent.mkdir(parents=True)
+        test_file.write_text(
+            "def test_load():\n"
+            "    assert True\n"
+        )
+        
+        config = ProboscisConfig(docs_url="https://docs.example.com/{rule_id}")
+        linter = ProboscisLinter(config)
+        
+        changed = linter.lint_changed_files(tmpdir_path)
+        marker = next(v for v in changed if v.rule_name.startswith("PL004"))
+        assert marker.fix_available is True
+        assert marker.docs_url == "https://docs.example.com/PL004"
+        
+        single = linter.lint_file(source_file, [tmpdir_path / "test"])
+        assert single
+        for violation in single:
+            assert violation.docs_url == f"https://docs.example.com/{violation.rule_name[:5]}"
+            assert violation.fix_available is violation._rust_violation.fix_available
+
+
+@pytest.mark.unit
+def test_changed_and_single_file_runs_keep_context_and_fixes():
+    """Test that --changed-only and single-file runs carry source context and fixes."""
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmpdir_path = Path(tmpdir)
+        init_git_repo(tmpdir_path)
+        
+        source_file = tmpdir_path / "src" / "module.py"
+        source_file.parent.mkdir()
+        source_file.write_text(
+            "import os\n"
+            "def load():\n"
+            "    return 42\n"
+        )
+        test_file = tmpdir_path / "test" / "unit" / "test_module.py"
+        test_file.parent.mkdir(parents=True)
+        test_file.write_text(
+            "def test_save():\n"
+            "    assert True\n"
+        )
+        
+        linter = ProboscisLinter(ProboscisConfig(context_lines=1))
+        
+        changed = linter.lint_changed_files(tmpdir_path)
+        missing = next(v for v in changed if v.rule_name.startswith("PL001"))
+        assert missing.context == "import os\ndef load():\n    return 42"
+        assert missing.context_start_line == 1
+        marker = next(v for v in changed if v.rule_name.startswith("PL004"))
+        assert marker.fix_type == "add_decorator"
+        assert marker.fix_content and marker.fix_line
+        
+        single = linter.lint_file(source_file, [tmpdir_path / "test"])
+        missing = next(v for v in single if v.rule_name.startswith("PL001"))
+        assert missing.context == "import os\ndef load():\n    return 42"
+        assert missing.context_start_line == 1
+        for violation in single:
+            rv = violation._rust_violation
+            assert (violation.fix_type, violation.fix_content, violation.fix_line) == (rv.fix_type, rv.fix_content, rv.fix_line)
@@ -76,6 +76,17 @@ class TestMain:
                 assert exc_info.value.code == 0
                 mock_cli.assert_called_once()
 
+    
+    @pytest.mark.unit
+    def test_main_init_command(self):
+        """Test main() dispatches `init` to the init command."""
+        with patch('sys.argv', ['proboscis-linter', 'init', 'project']):
+            with patch('proboscis_linter.__main__.cli') as mock_cli, \
+                    patch('proboscis_linter.__main__.init') as mock_init:
+                main()
+                mock_init.assert_called_once_with(['project'])
+                mock_cli.assert_not_called()
+
 
 class TestMainModule:
     """Test the __main__ module execution."""
@@ -311,4 +311,74 @@ def test_cli_no_python_files(tmp_path):
     result = runner.invoke(cli, [str(tmp_path)])
     
     assert result.exit_code == 0
-    assert "No violations found" in result.output
\ No newline at end of file
+    assert "No violations found" in result.output
+
+@pytest.mark.unit
+def test_cli_exit_codes(tmp_path):
+    """Test the exit codes configured for each outcome."""
+    config_file = tmp_path / "pyproject.toml"
+    config_file.write_text("""
+[tool.proboscis]
+fail_on_error = true
+
+[tool.proboscis.exit_codes]
+violations = 5
+config_error = 4
+""")
+    src_file = tmp_path / "module.py"
+    src_file.write_text("""
+def untested_function():
+    pass
+""")
+    
+    runner = CliRunner()
+    result = runner.invoke(cli, [str(tmp_path)])
+    assert result.exit_code == 5
+    
+    # A setting the linter rejects is a configuration error
+    config_file.write_text(config_file.read_text().replace(
+        "fail_on_error = true", 'fail_on_error = true\ntest_name_template = "check"'
+    ))
+    result = runner.invoke(cli, [str(tmp_path)])
+    assert result.exit_code == 4
+    assert "invalid configuration" in result.output
+    
+    # So is a setting the configuration itself rejects, rather than linting with defaults
+    config_file.write_text(config_file.read_text().replace(
+        'test_name_template = "check"', 'enabled_rules = ["PL001", "PL999"]'
+    ))
+    result = runner.invoke(cli, [str(tmp_path)])
+    assert result.exit_code == 4
+    assert "PL999" in result.output
+
+
+@pytest.mark.unit
+def test_cli_fix_applies_unsafe_fixes_on_request(tmp_path):
+    """Test that --fix applies safe fixes, and unsafe ones only with --unsafe-fixes."""
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "module.py").write_text("""
+def compute():
+    pass
+""")
+    test_dir = tmp_path / "test" / "unit"
+    test_dir.mkdir(parents=True)
+    test_file = test_dir / "test_module.py"
+    test_file.write_text("""
+def test_compute():
+    pass
+""")
+    # Sibling test packages, only one with an __init__.py (PL029)
+    for package in ("alpha", "beta"):
+        (test_dir / package).mkdir()
+        (test_dir / package / f"test_{package}.py").write_text("")
+    (test_dir / "alpha" / "__init__.py").write_text("")
+    
+    runner = CliRunner()
+    runner.invoke(cli, [str(tmp_path), "--fix"])
+    # The missing PL004 marker is a safe fix
+    assert "@pytest.mark.unit" in test_file.read_text()
+    # Creating an __init__.py is unsafe
+    assert not (test_dir / "beta" / "__init__.py").exists()
+    
+    runner.invoke(cli, [str(tmp_path), "--fix", "--unsafe-fixes"])
+    assert (test_dir / "beta" / "__init__.py").exists()
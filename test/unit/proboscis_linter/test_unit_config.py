@@ -15,6 +15,11 @@ def test_default_config():
     assert config.exclude_patterns == []
     assert config.output_format == "text"
     assert config.fail_on_error is False
+    assert config.declared_packages_only is False
+    assert config.experimental_decorators == ["experimental"]
+    assert config.lifecycle_mode == "check"
+    assert config.method_test_name_template is None
+    assert config.test_suggestion_style == "function"
     assert config.rules == {}
 
 
@@ -56,6 +61,12 @@ def test_rule_config():
     assert config.get_rule_options("PL001") == {}
     assert config.get_rule_options("PL002") == {"severity": "warning"}
     assert config.get_rule_options("PL003") == {}
+    
+    # Values the rules cannot read are rejected up front
+    RuleConfig(options={"allowed_hosts": ["localhost"], "max_percent": 25, "ratio": 0.5})
+    for value in ({"nested": 1}, [1, 2]):
+        with pytest.raises(ValueError, match="Must be a boolean, number, string or list of strings"):
+            RuleConfig(options={"table": value})
 
 
 @pytest.mark.unit
@@ -68,6 +79,7 @@ test_patterns = ["test_*.py", "*_spec.py"]
 exclude_patterns = ["**/migrations/**"]
 output_format = "json"
 fail_on_error = true
+rule_versions = {PL001 = 2}
 
 [tool.proboscis.rules]
 PL001 = true
@@ -84,6 +96,7 @@ PL002 = false
     assert config.exclude_patterns == ["**/migrations/**"]
     assert config.output_format == "json"
     assert config.fail_on_error is True
+    assert config.rule_versions == {"PL001": 2}
     assert config.is_rule_enabled("PL001") is True
     assert config.is_rule_enabled("PL002") is False
 
@@ -113,6 +126,143 @@ enabled = false
     assert config.is_rule_enabled("PL002") is False
 
 
+@pytest.mark.unit
+def test_load_from_setup_cfg(tmp_path):
+    """Test loading configuration from the [proboscis] sections of setup.cfg."""
+    config_content = """
+[metadata]
+name = legacy
+
+[proboscis]
+test_directories =
+    tests
+    spec
+exclude_patterns = **/migrations/**, **/build/**
+fail_on_error = true
+
+[proboscis.rules]
+PL002 = false
+
+[proboscis.rules.PL001]
+min_statements = 2
+entry_point_names =
+    main
+    run
+
+[proboscis.rule_versions]
+PL001 = 2
+"""
+    
+    config_file = tmp_path / "setup.cfg"
+    config_file.write_text(config_content)
+    
+    config = ConfigLoader.load_from_file(config_file)
+    
+    assert config.test_directories == ["tests", "spec"]
+    assert config.exclude_patterns == ["**/migrations/**", "**/build/**"]
+    assert config.fail_on_error is True
+    assert config.is_rule_enabled("PL002") is False
+    assert config.is_rule_enabled("PL001") is True
+    assert config.get_rule_options("PL001") == {
+        "min_statements": 2,
+        "entry_point_names": ["main", "run"],
+    }
+    assert config.rule_versions == {"PL001": 2}
+
+
+@pytest.mark.unit
+def test_load_allowlist(tmp_path):
+    """Test loading the allowlist, with its file relative to the config file."""
+    config_dir = tmp_path / "config"
+    config_dir.mkdir()
+    config_file = config_dir / "pyproject.toml"
+    config_file.write_text("""
+[tool.proboscis]
+allowlist_file = "allowlist.txt"
+
+[tool.proboscis.allowlist]
+"pkg.api_pb2.Stub.call" = ["PL001", "PL002"]
+""")
+    
+    config = ConfigLoader.load_from_file(config_file)
+    
+    assert config.allowlist == {"pkg.api_pb2.Stub.call": ["PL001", "PL002"]}
+    assert config.allowlist_file == str(config_dir / "allowlist.txt")
+    
+    setup_cfg = tmp_path / "setup.cfg"
+    setup_cfg.write_text("[proboscis]\n\n[proboscis.allowlist]\npkg.mod.load = PL001, PL003\n")
+    assert ConfigLoader.load_from_file(setup_cfg).allowlist == {"pkg.mod.load": ["PL001", "PL003"]}
+
+
+@pytest.mark.unit
+def test_load_decorator_test_types(tmp_path):
+    """Test loading the test types of decorated functions from TOML and INI files."""
+    config_file = tmp_path / "pyproject.toml"
+    config_file.write_text("""
+[tool.proboscis.decorator_test_types]
+shared_task = ["integration"]
+receiver = []
+""")
+    
+    config = ConfigLoader.load_from_file(config_file)
+    
+    assert config.decorator_test_types == {"shared_task": ["integration"], "receiver": []}
+    
+    setup_cfg = tmp_path / "setup.cfg"
+    setup_cfg.write_text("[proboscis]\n\n[proboscis.decorator_test_types]\napp.task = integration, e2e\nreceiver =\n")
+    assert ConfigLoader.load_from_file(setup_cfg).decorator_test_types == {
+        "app.task": ["integration", "e2e"],
+        "receiver": [],
+    }
+
+
+@pytest.mark.unit
+def test_load_surface_test_types(tmp_path):
+    """Test loading the test types of modules by public API size from TOML and INI files."""
+    config_file = tmp_path / "pyproject.toml"
+    config_file.write_text("""
+[tool.proboscis.surface_test_types]
+1 = ["unit"]
+10 = ["unit", "integration"]
+""")
+    
+    config = ConfigLoader.load_from_file(config_file)
+    
+    assert config.surface_test_types == {1: ["unit"], 10: ["unit", "integration"]}
+    
+    setup_cfg = tmp_path / "setup.cfg"
+    setup_cfg.write_text("[proboscis]\n\n[proboscis.surface_test_types]\n3 = unit, e2e\n")
+    assert ConfigLoader.load_from_file(setup_cfg).surface_test_types == {3: ["unit", "e2e"]}
+
+@pytest.mark.unit
+def test_find_config_file_precedence(tmp_path):
+    """Test that pyproject.toml wins over setup.cfg, and setup.cfg over tox.ini."""
+    project_root = tmp_path / "project"
+    subdir = project_root / "src"
+    subdir.mkdir(parents=True)
+    
+    tox_ini = project_root / "tox.ini"
+    tox_ini.write_text("[proboscis]\ntest_directories = spec\n")
+    assert ConfigLoader.find_config_file(subdir) == tox_ini
+    
+    # A setup.cfg without a [proboscis] section is skipped
+    setup_cfg = project_root / "setup.cfg"
+    setup_cfg.write_text("[metadata]\nname = legacy\n")
+    assert ConfigLoader.find_config_file(subdir) == tox_ini
+    
+    setup_cfg.write_text("[proboscis]\ntest_directories = tests\n")
+    assert ConfigLoader.find_config_file(subdir) == setup_cfg
+    
+    pyproject = project_root / "pyproject.toml"
+    pyproject.write_text("[tool.proboscis]\n")
+    assert ConfigLoader.find_config_file(subdir) == pyproject
+    
+    # A nearer directory wins regardless of the file type
+    nearer = subdir / "tox.ini"
+    nearer.write_text("[proboscis]\n")
+    assert ConfigLoader.find_config_file(subdir) == nearer
+
+
 @pytest.mark.unit
 def test_load_missing_file():
     """Test loading from non-existent file."""
@@ -232,6 +382,58 @@ def test_is_rule_enabled():
     assert config.is_rule_enabled("PL999") is True
 
 
+@pytest.mark.unit
+def test_profile_rule_defaults():
+    """Test that the profile decides whether rules left unconfigured run."""
+    minimal = ProboscisConfig(profile="minimal", rules={"PL003": RuleConfig(enabled=True)})
+    assert minimal.is_rule_enabled("PL001") is True
+    assert minimal.is_rule_enabled("PL002") is False
+    assert minimal.is_rule_enabled("PL003") is True
+    assert minimal.is_rule_opted_in("PL023") is False
+    
+    strict = ProboscisConfig(profile="strict", rules={"PL024": RuleConfig(enabled=False)})
+    assert strict.is_rule_opted_in("PL023") is True
+    assert strict.is_rule_opted_in("PL024") is False
+    assert ProboscisConfig().is_rule_opted_in("PL023") is False
+    
+    with pytest.raises(ValueError):
+        ProboscisConfig(profile="lenient")
+
+
+@pytest.mark.unit
+def test_rule_lists():
+    """Test the precedence of disabled_rules and switches off, enabled_rules, then
+    switches on and the profile, as RustLinter applies it."""
+    only = ProboscisConfig(
+        profile="minimal",
+        enabled_rules=["PL001", "PL023"],
+        disabled_rules=["PL023"],
+        rules={"PL002": RuleConfig(enabled=True)},
+    )
+    assert only.is_rule_enabled("PL001") is True
+    assert only.is_rule_enabled("PL002") is False
+    assert only.is_rule_enabled("PL000") is True
+    assert only.is_rule_opted_in("PL023") is False
+    
+    disabled = ProboscisConfig(disabled_rules=["PL002"], rules={"PL002": RuleConfig(enabled=True)})
+    assert disabled.is_rule_enabled("PL002") is False
+    assert disabled.is_rule_enabled("PL003") is True
+    switched_off = ProboscisConfig(enabled_rules=["PL003"], rules={"PL003": RuleConfig(enabled=False)})
+    assert switched_off.is_rule_enabled("PL003") is False
+
+
+@pytest.mark.unit
+def test_unknown_rule_ids_rejected():
+    """Test that rule ids missing from the extension's registry are rejected."""
+    pytest.importorskip("proboscis_linter.proboscis_linter_rust")
+    
+    with pytest.raises(ValueError, match="Unknown rule id"):
+        ProboscisConfig(rules={"PL0001": RuleConfig(enabled=False)})
+    assert ProboscisConfig(rules={"PL026": RuleConfig(enabled=False)}).is_rule_enabled("PL026") is False
+    with pytest.raises(ValueError, match="PL999"):
+        ProboscisConfig(enabled_rules=["PL001", "PL999"])
+
+
 @pytest.mark.unit
 def test_get_rule_options():
     """Test get_rule_options method."""
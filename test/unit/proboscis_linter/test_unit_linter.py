@@ -230,6 +230,71 @@ def untested_function():
     assert rule_names == {"PL002", "PL003"}
 
 
+@pytest.mark.unit
+def test_linter_file_time_limit(tmp_path):
+    """A pathological file is reported as PL000 instead of stalling the run."""
+    src_dir = tmp_path / "src"
+    src_dir.mkdir()
+    # The body of each nested function runs to the end of the file, so measuring them
+    # all takes far longer than the limit
+    depth = 1500
+    (src_dir / "generated.py").write_text(
+        "".join("    " * i + f"def level_{i}():\n" for i in range(depth))
+        + "    " * depth + "pass\n"
+    )
+    (src_dir / "module.py").write_text("""
+def untested_function():
+    pass
+""")
+    
+    config = ProboscisConfig(file_time_limit=1.0)
+    linter = ProboscisLinter(config)
+    violations = linter.lint_project(tmp_path)
+    
+    generated = [v.rule_name for v in violations if v.file_path.name == "generated.py"]
+    assert generated == ["PL000:file-time-limit"]
+    # The other files are still linted
+    module = {v.rule_name.split(":")[0] for v in violations if v.file_path.name == "module.py"}
+    assert module == {"PL001", "PL002", "PL003"}
+
+
+@pytest.mark.unit
+def test_stats_file_records_lint_runs_only(tmp_path):
+    """Counts queries and TODO cleanups are not recorded as runs in the stats file."""
+    rust = pytest.importorskip("proboscis_linter.proboscis_linter_rust")
+    src_dir = tmp_path / "src"
+    src_dir.mkdir()
+    (src_dir / "module.py").write_text("""
+def untested_function():
+    pass
+""")
+    
+    linter = rust.RustLinter(stats_file="stats.ndjson")
+    linter.lint_project(str(tmp_path))
+    linter.lint_project_counts(str(tmp_path))
+    linter.remove_stale_todo_comments(str(tmp_path))
+    
+    assert linter.stats(str(tmp_path)).runs == 1
+
+
+@pytest.mark.unit
+def test_discovery_limit_warning_is_not_repeated(tmp_path):
+    """A run within the discovery limits reports no warning left over from an earlier run."""
+    rust = pytest.importorskip("proboscis_linter.proboscis_linter_rust")
+    src_dir = tmp_path / "src"
+    src_dir.mkdir()
+    for name in ("a", "b", "c"):
+        (src_dir / f"{name}.py").write_text(f"def {name}():\n    pass\n")
+    
+    linter = rust.RustLinter(max_files=2, fan_in_analysis=True)
+    first = [v.rule_name for v in linter.lint_project(str(tmp_path))]
+    assert first.count("PL000:discovery-limit") == 1
+    
+    (src_dir / "c.py").unlink()
+    second = [v.rule_name for v in linter.lint_project(str(tmp_path))]
+    assert "PL000:discovery-limit" not in second
+
+
 @pytest.mark.unit
 def test_linter_error_handling():
     """Test error handling in linter methods."""
@@ -70,6 +70,60 @@ def test_json_report_generator():
     assert data["violations"][0]["severity"] == "error"
 
 
+@pytest.mark.unit
+def test_reports_count_lifecycle_violations():
+    violations = [
+        LintViolation(
+            rule_name="PL001:require-test",
+            file_path=Path("src/module.py"),
+            line_number=line,
+            function_name=name,
+            message=f"[PL001] Function '{name}' has no test found",
+            severity="error",
+            lifecycle=lifecycle
+        )
+        for line, name, lifecycle in [(10, "old", "deprecated"), (20, "new", "experimental"), (30, "func", None)]
+    ]
+    
+    rust = pytest.importorskip("proboscis_linter.proboscis_linter_rust")
+    text = rust.format_text(violations, color="never")
+    assert "Found 3 violations (3 errors) in 1 file" in text
+    assert "Of which in deprecated or experimental functions: 1 deprecated, 1 experimental" in text
+    
+    data = json.loads(JsonReportGenerator().generate_report(violations))
+    assert data["lifecycle_violations"] == {"deprecated": 1, "experimental": 1}
+    assert data["violations"][0]["lifecycle"] == "deprecated"
+    assert data["violations"][2]["lifecycle"] is None
+
+
+@pytest.mark.unit
+def test_json_report_links_rule_docs():
+    violation = LintViolation(
+        rule_name="PL001:require-unit-test",
+        file_path=Path("src/module.py"),
+        line_number=10,
+        function_name="func",
+        message="[PL001] Function 'func' has no unit test",
+        severity="error",
+        fix_type="add_todo_comment",
+        fix_available=True,
+        docs_url="https://wiki.example.com/lint/PL001"
+    )
+    
+    data = json.loads(JsonReportGenerator().generate_report([violation]))
+    assert data["violations"][0]["fix_available"] is True
+    assert data["violations"][0]["docs_url"] == "https://wiki.example.com/lint/PL001"
+
+
+@pytest.mark.unit
+def test_json_report_includes_run_metadata():
+    metadata = {"version": "0.1.0", "config_hash": "00ff", "git_sha": None}
+    
+    data = json.loads(JsonReportGenerator().generate_report([], metadata))
+    assert data["metadata"] == metadata
+    assert "metadata" not in json.loads(JsonReportGenerator().generate_report([]))
+
+
 @pytest.mark.unit
 def test_report_generator_names():
     text_gen = TextReportGenerator()